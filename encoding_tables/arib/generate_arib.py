@@ -0,0 +1,262 @@
+#!/usr/bin/env python3
+
+# The control codes and escape sequences that the generated test data uses.
+APR = b"\x0D"
+LS0 = b"\x0F"
+LS1 = b"\x0E"
+LS2R = b"\x1B\x7D"
+LS3R = b"\x1B\x7C"
+
+
+def code_to_index(code):
+    """ Map an additional symbol's 7-bit code to a pointer, counting from the
+        start of row 85.
+    """
+    return ((code >> 8) - 0x75) * 94 + (code & 0xFF) - 0x21
+
+
+def index_to_gl_bytes(index):
+    """ Map an additional symbol pointer to the two bytes of its 7-bit code.
+    """
+    return bytes([index // 94 + 0x75, index % 94 + 0x21])
+
+
+def jis_index_to_gl_bytes(index):
+    """ Map a JIS X 0208 pointer to the two bytes of its 7-bit code.
+    """
+    return bytes([index // 94 + 0x21, index % 94 + 0x21])
+
+
+def load_table_file(path):
+    """ Loads the additional symbols table file, returning a list of
+        `(index, codepoint)` pairs, sorted by index.
+    """
+    in_file = open(path)
+    table = []
+    for line in in_file:
+        parts = line.split()
+        if len(parts) >= 2 and not parts[0].startswith("#"):
+            index = code_to_index(int(parts[0], 16))
+            codepoint = int(parts[1], 16)
+            table += [(index, codepoint)]
+    table.sort()
+    return table
+
+
+def load_jis_table_file(path):
+    """ Loads the JIS X 0208 table file used by the `shiftjis` module,
+        returning a list of `(index, codepoint)` pairs, sorted by index.
+    """
+    in_file = open(path)
+    table = []
+    for line in in_file:
+        parts = line.split()
+        if len(parts) >= 2 and not parts[0].startswith("#"):
+            table += [(int(parts[0]), int(parts[1], 16))]
+    table.sort()
+    return table
+
+
+def encode_table(table, jis_table):
+    """ The `(codepoint, index)` pairs used for encoding, sorted by code
+        point.  Code points that are also in JIS X 0208 are left out, since
+        the encoder uses JIS X 0208 for those, and for the few symbols that
+        are in more than one cell, the first cell is used.
+    """
+    jis_codepoints = set(codepoint for (_, codepoint) in jis_table)
+    enc_table = {}
+    for (index, codepoint) in table:
+        if codepoint not in jis_codepoints and codepoint not in enc_table:
+            enc_table[codepoint] = index
+    return sorted(enc_table.items())
+
+
+def generate_arib_tables(in_path, jis_path, out_path):
+    table = load_table_file(in_path)
+    jis_table = load_jis_table_file(jis_path)
+
+    # Create the decode table
+    dec_table = []
+    i = 0
+    for (index, codepoint) in table:
+        while i < index:
+            dec_table += [None]
+            i += 1
+        dec_table += [codepoint]
+        i += 1
+
+    # Create the encode table
+    enc_table = encode_table(table, jis_table)
+
+    # Write file.
+    out_file = open(out_path, mode='w')
+    out_file.write(
+"""// This file is auto-generated.  Please see `encoding_tables/arib/`
+// from the root directory for the files that generate this.
+
+"""
+    )
+
+    # Write out decode table
+    out_file.write("static SYMBOL_DECODE_TABLE: [char; {}] = [".format(len(dec_table)))
+    for (i, c) in enumerate(dec_table):
+        if i % 8 == 0:
+            out_file.write("\n    ")
+        if c is None:
+            out_file.write("'�',")
+        else:
+            out_file.write("'\\u{{{:04X}}}',".format(c))
+    out_file.write("\n];\n\n")
+
+    # Write out encode table
+    out_file.write("static SYMBOL_ENCODE_TABLE: [(char, u16); {}] = [".format(len(enc_table)))
+    for (i, pair) in enumerate(enc_table):
+        if i % 4 == 0:
+            out_file.write("\n    ")
+        out_file.write("('\\u{{{:X}}}', {}), ".format(pair[0], pair[1]))
+    out_file.write("\n];\n")
+
+
+def alphanumeric_table():
+    """ The alphanumeric set, as `(byte, codepoint)` pairs.  This is the
+        JIS X 0201 Latin set, so 0x5C is the yen sign and 0x7E the overline.
+    """
+    table = []
+    for byte in range(0x21, 0x7F):
+        if byte == 0x5C:
+            table += [(byte, 0xA5)]
+        elif byte == 0x7E:
+            table += [(byte, 0x203E)]
+        else:
+            table += [(byte, byte)]
+    return table
+
+
+# The symbols at the end of both the hiragana and katakana sets.
+KANA_SYMBOLS = [0x30FC, 0x3002, 0x300C, 0x300D, 0x3001, 0x30FB]
+
+
+def hiragana_table():
+    """ The hiragana set, as `(byte, codepoint)` pairs.
+    """
+    table = [(byte, byte - 0x21 + 0x3041) for byte in range(0x21, 0x74)]
+    table += [(0x77, 0x309D), (0x78, 0x309E)]
+    table += [(0x79 + n, codepoint) for (n, codepoint) in enumerate(KANA_SYMBOLS)]
+    return table
+
+
+def katakana_table():
+    """ The katakana set, as `(byte, codepoint)` pairs.
+    """
+    table = [(byte, byte - 0x21 + 0x30A1) for byte in range(0x21, 0x77)]
+    table += [(0x77, 0x30FD), (0x78, 0x30FE)]
+    table += [(0x79 + n, codepoint) for (n, codepoint) in enumerate(KANA_SYMBOLS)]
+    return table
+
+
+def generate_arib_decode_test_data(in_path, jis_path, arib_path, utf8_path):
+    """ Generates ARIB STD-B24 decode test data, with every character of the
+        initially designated sets and the additional symbols on its own line.
+        Lines are ended with APR, which decodes to a line feed.
+    """
+    table = load_table_file(in_path)
+    jis_table = load_jis_table_file(jis_path)
+    arib_file = open(arib_path, "wb")
+    utf8_file = open(utf8_path, "wb")
+
+    def write(arib_bytes, codepoint):
+        arib_file.write(arib_bytes + APR)
+        utf8_file.write(chr(codepoint).encode('utf-8') + b"\n")
+
+    # Alphanumeric, invoked into GL from G1.
+    arib_file.write(LS1)
+    for (byte, codepoint) in alphanumeric_table():
+        write(bytes([byte]), codepoint)
+    arib_file.write(LS0)
+
+    # Hiragana, in GR from G2.
+    for (byte, codepoint) in hiragana_table():
+        write(bytes([byte | 0x80]), codepoint)
+
+    # Katakana, invoked into GR from G3.
+    arib_file.write(LS3R)
+    for (byte, codepoint) in katakana_table():
+        write(bytes([byte | 0x80]), codepoint)
+    arib_file.write(LS2R)
+
+    # Kanji and additional symbols, in GL from G0.
+    for (index, codepoint) in jis_table:
+        write(jis_index_to_gl_bytes(index), codepoint)
+    for (index, codepoint) in table:
+        write(index_to_gl_bytes(index), codepoint)
+
+
+def generate_arib_encode_test_data(in_path, jis_path, utf8_path, arib_path):
+    """ Generates ARIB STD-B24 encode test data, with every encodable
+        character on its own line, encoded the same way that the encoder
+        does it.
+    """
+    table = load_table_file(in_path)
+    jis_table = load_jis_table_file(jis_path)
+    utf8_file = open(utf8_path, "wb")
+    arib_file = open(arib_path, "wb")
+
+    # Which of G0-G3 is invoked into GL and GR.
+    invoked = {"gl": 0, "gr": 2}
+
+    def write(codepoint, g, arib_bytes):
+        if g in [0, 1] and invoked["gl"] != g:
+            arib_file.write(LS0 if g == 0 else LS1)
+            invoked["gl"] = g
+        elif g in [2, 3] and invoked["gr"] != g:
+            arib_file.write(LS2R if g == 2 else LS3R)
+            invoked["gr"] = g
+        arib_file.write(arib_bytes + APR)
+        utf8_file.write(chr(codepoint).encode('utf-8') + b"\n")
+
+    for (byte, codepoint) in alphanumeric_table():
+        write(codepoint, 1, bytes([byte]))
+    for (byte, codepoint) in hiragana_table():
+        if 0x3041 <= codepoint <= 0x3093:
+            write(codepoint, 2, bytes([byte | 0x80]))
+    for (byte, codepoint) in katakana_table():
+        if 0x30A1 <= codepoint <= 0x30F6:
+            write(codepoint, 3, bytes([byte | 0x80]))
+    for (index, codepoint) in jis_table:
+        if not (0x3041 <= codepoint <= 0x3093 or 0x30A1 <= codepoint <= 0x30F6):
+            write(codepoint, 0, jis_index_to_gl_bytes(index))
+    for (codepoint, index) in encode_table(table, jis_table):
+        write(codepoint, 0, index_to_gl_bytes(index))
+
+    # Return to the initial invocations at the end of the text.
+    if invoked["gl"] != 0:
+        arib_file.write(LS0)
+    if invoked["gr"] != 2:
+        arib_file.write(LS2R)
+
+
+if __name__ == "__main__":
+    table_root = "../../src/generated/arib"
+    test_root = "../../tests/test_data/arib"
+    jis_path = "../shiftjis/jis0208-1997.txt"
+
+    # Generate the table files.
+    generate_arib_tables(
+        "arib_additional_symbols.txt",
+        jis_path,
+        table_root + "/arib_tables.rs.inc",
+    )
+
+    # Generate the test data files.
+    generate_arib_decode_test_data(
+        "arib_additional_symbols.txt",
+        jis_path,
+        test_root + "/arib_b24_test_decode_in.txt",
+        test_root + "/arib_b24_test_decode_out.txt",
+    )
+    generate_arib_encode_test_data(
+        "arib_additional_symbols.txt",
+        jis_path,
+        test_root + "/arib_b24_test_encode_in.txt",
+        test_root + "/arib_b24_test_encode_out.txt",
+    )
@@ -0,0 +1,162 @@
+#!/usr/bin/env python3
+
+
+def index_to_bytes(i):
+    """ Map a BIG5 pointer back to the original BIG5 bytes.
+    """
+    lead = i // 157 + 0x81
+    trail = i % 157
+    offset = 0x40 if trail < 0x3f else 0x62
+    return (lead, trail + offset)
+
+
+def bytes_to_index(lead, tail):
+    """ Map a pair of BIG5 bytes to a BIG5 pointer.
+    """
+    offset = 0x40 if tail < 0x7F else 0x62
+    return (lead - 0x81) * 157 + (tail - offset)
+
+
+def load_table_file(path):
+    """ Loads the table file, returning a list of
+        `(index, [codepoints], is_decode_only)` tuples, sorted by index.
+    """
+    in_file = open(path)
+    table = []
+    for line in in_file:
+        parts = line.split()
+        if len(parts) >= 2 and not parts[0].startswith("#"):
+            code = int(parts[0], 16)
+            index = bytes_to_index(code >> 8, code & 0xFF)
+            codepoints = [int(c, 16) for c in parts[1].split("+")]
+            decode_only = len(parts) >= 3 and parts[2] == "decode-only"
+            table += [(index, codepoints, decode_only)]
+    table.sort()
+    return table
+
+
+def generate_big5_hkscs_tables(in_path, out_path):
+    table = load_table_file(in_path)
+    single = [(i, c[0], d) for (i, c, d) in table if len(c) == 1]
+    composed = [(i, c) for (i, c, d) in table if len(c) == 2]
+    assert len(single) + len(composed) == len(table)
+
+    # Create the decode table
+    dec_table = []
+    i = 0
+    for (index, codepoint, _) in single:
+        while i < index:
+            dec_table += [None]
+            i += 1
+        dec_table += [codepoint]
+        i += 1
+
+    # Create the encode table
+    enc_table = [(codepoint, index_to_bytes(index)) for (index, codepoint, decode_only) in single if not decode_only]
+    enc_table.sort()
+    for n in range(1, len(enc_table)):
+        if enc_table[n - 1][0] == enc_table[n][0]:
+            raise Exception("Duplicate encodable code point: {:04X}".format(enc_table[n][0]))
+
+    # Create the sequence table, sorted by sequence
+    seq_table = [("".join([chr(c) for c in codepoints]), index_to_bytes(index)) for (index, codepoints) in composed]
+    seq_table.sort()
+
+    # Write file.
+    out_file = open(out_path, mode='w')
+    out_file.write(
+"""// This file is auto-generated.  Please see `encoding_tables/big5_hkscs/`
+// from the root directory for the files that generate this.
+
+"""
+    )
+
+    # Write out decode table
+    out_file.write("static DECODE_TABLE: [char; {}] = [".format(len(dec_table)))
+    for (i, c) in enumerate(dec_table):
+        if i % 8 == 0:
+            out_file.write("\n    ")
+        if c is None:
+            out_file.write("'�',")
+        else:
+            out_file.write("'\\u{{{:04X}}}',".format(c))
+    out_file.write("\n];\n\n")
+
+    # Write out encode table
+    out_file.write("static ENCODE_TABLE: [(char, [u8; 2]); {}] = [".format(len(enc_table)))
+    for (i, pair) in enumerate(enc_table):
+        if i % 3 == 0:
+            out_file.write("\n    ")
+        out_file.write("('\\u{{{:X}}}', [0x{:X}, 0x{:X}]), ".format(pair[0], pair[1][0], pair[1][1]))
+    out_file.write("\n];\n\n")
+
+    # Write out sequence table
+    out_file.write("static SEQUENCES: [(&str, &[u8]); {}] = [".format(len(seq_table)))
+    for (seq, code) in seq_table:
+        out_file.write("\n    (\"{}\", &[0x{:X}, 0x{:X}]),".format(
+            "".join(["\\u{{{:04X}}}".format(ord(c)) for c in seq]), code[0], code[1]))
+    out_file.write("\n];\n")
+
+
+def generate_big5_hkscs_test_data(in_path, dec_in_path, dec_out_path, enc_in_path, enc_out_path):
+    table = load_table_file(in_path)
+
+    # Generate decoding test files.
+    decode_in_file = open(dec_in_path, "wb")
+    decode_out_file = open(dec_out_path, "wb")
+    for ascii_byte in range(1, 128):
+        # In
+        decode_in_file.write(bytes([ascii_byte]))
+        decode_in_file.write(bytes("\n", 'utf-8'))
+        # Out
+        decode_out_file.write(bytes([ascii_byte]))
+        decode_out_file.write(bytes("\n", 'utf-8'))
+    for (index, codepoints, _) in table:
+        # In
+        decode_in_file.write(bytes(index_to_bytes(index)))
+        decode_in_file.write(bytes("\n", 'utf-8'))
+        # Out
+        for codepoint in codepoints:
+            decode_out_file.write(chr(codepoint).encode('utf-8'))
+        decode_out_file.write(bytes("\n", 'utf-8'))
+
+    # Generate encoding test files.
+    encode_in_file = open(enc_in_path, "wb")
+    encode_out_file = open(enc_out_path, "wb")
+    for ascii_byte in range(1, 128):
+        # In
+        encode_in_file.write(bytes([ascii_byte]))
+        encode_in_file.write(bytes("\n", 'utf-8'))
+        # Out
+        encode_out_file.write(bytes([ascii_byte]))
+        encode_out_file.write(bytes("\n", 'utf-8'))
+    for (index, codepoints, decode_only) in table:
+        if decode_only:
+            continue
+        # In
+        for codepoint in codepoints:
+            encode_in_file.write(chr(codepoint).encode('utf-8'))
+        encode_in_file.write(bytes("\n", 'utf-8'))
+        # Out
+        encode_out_file.write(bytes(index_to_bytes(index)))
+        encode_out_file.write(bytes("\n", 'utf-8'))
+
+
+if __name__ == "__main__":
+    table_root = "../../src/generated/big5_hkscs"
+    test_root = "../../tests/test_data/big5_hkscs"
+
+    # Generate the table files.
+    generate_big5_hkscs_tables(
+        "big5_hkscs_2008.txt",
+        table_root + "/big5_hkscs_tables.rs.inc",
+    )
+
+    # Generate the test data files.
+    generate_big5_hkscs_test_data(
+        "big5_hkscs_2008.txt",
+        test_root + "/big5_hkscs_test_decode_in.txt",
+        test_root + "/big5_hkscs_test_decode_out.txt",
+        test_root + "/big5_hkscs_test_encode_in.txt",
+        test_root + "/big5_hkscs_test_encode_out.txt",
+    )
@@ -0,0 +1,166 @@
+#!/usr/bin/env python3
+
+
+def index_to_bytes(i):
+    """ Map a BIG5 pointer back to the original BIG5 bytes.
+    """
+    lead = i // 157 + 0x81
+    trail = i % 157
+    offset = 0x40 if trail < 0x3f else 0x62
+    return (lead, trail + offset)
+
+
+def bytes_to_index(lead, tail):
+    """ Map a pair of BIG5 bytes to a BIG5 pointer.
+    """
+    offset = 0x40 if tail < 0x7F else 0x62
+    return (lead - 0x81) * 157 + (tail - offset)
+
+
+def load_table_file(path):
+    """ Loads a UAO table file, returning a list of `(big5, codepoint)`
+        pairs in file order.  Entries mapping to U+FFFD are skipped.
+    """
+    in_file = open(path)
+    table = []
+    for line in in_file:
+        parts = line.split()
+        if len(parts) >= 2 and not parts[0].startswith("#"):
+            big5 = int(parts[0], 16)
+            unicode = int(parts[1], 16)
+            if big5 != 0xFFFD and unicode != 0xFFFD:
+                table += [(big5, unicode)]
+    return table
+
+
+def load_decode_table(path):
+    table = [(bytes_to_index(big5 >> 8, big5 & 0xFF), codepoint) for (big5, codepoint) in load_table_file(path)]
+    table.sort()
+    return table
+
+
+def load_encode_table(b2u_path, u2b_path):
+    """ Builds the encode table from the u2b file, keeping only the entries
+        that round-trip through the b2u file.  The u2b file also contains
+        "best fit" fallbacks (e.g. U+00A9 to the two bytes "c "), which we
+        don't want since they're lossy.
+    """
+    b2u = dict(load_table_file(b2u_path))
+    table = []
+    for (big5, codepoint) in load_table_file(u2b_path):
+        if big5 > 0xFF and b2u.get(big5) == codepoint:
+            table += [(codepoint, bytes_to_index(big5 >> 8, big5 & 0xFF))]
+    table.sort()
+
+    # Every decodable code point should be encodable.
+    encodable = set([c for (c, _) in table])
+    for codepoint in b2u.values():
+        if codepoint not in encodable:
+            raise Exception("Code point not encodable: {:04X}".format(codepoint))
+
+    return table
+
+
+def generate_big5_uao_tables(b2u_path, u2b_path, out_path):
+    table = load_decode_table(b2u_path)
+
+    # Create the decode table
+    dec_table = []
+    i = 0
+    for (index, codepoint) in table:
+        while i < index:
+            dec_table += [None]
+            i += 1
+        dec_table += [codepoint]
+        i += 1
+
+    # Create the encode table
+    enc_table = [(codepoint, index_to_bytes(index)) for (codepoint, index) in load_encode_table(b2u_path, u2b_path)]
+
+    # Write file.
+    out_file = open(out_path, mode='w')
+    out_file.write(
+"""// This file is auto-generated.  Please see `encoding_tables/big5_uao/`
+// from the root directory for the files that generate this.
+
+"""
+    )
+
+    # Write out decode table
+    out_file.write("static DECODE_TABLE: [char; {}] = [".format(len(dec_table)))
+    for (i, c) in enumerate(dec_table):
+        if i % 8 == 0:
+            out_file.write("\n    ")
+        if c is None:
+            out_file.write("'�',")
+        else:
+            out_file.write("'\\u{{{:04X}}}',".format(c))
+    out_file.write("\n];\n\n")
+
+    # Write out encode table
+    out_file.write("static ENCODE_TABLE: [(char, [u8; 2]); {}] = [".format(len(enc_table)))
+    for (i, pair) in enumerate(enc_table):
+        if i % 3 == 0:
+            out_file.write("\n    ")
+        out_file.write("('\\u{{{:X}}}', [0x{:X}, 0x{:X}]), ".format(pair[0], pair[1][0], pair[1][1]))
+    out_file.write("\n];\n")
+
+
+def generate_big5_uao_test_data(b2u_path, u2b_path, dec_in_path, dec_out_path, enc_in_path, enc_out_path):
+    # Generate decoding test files.
+    decode_in_file = open(dec_in_path, "wb")
+    decode_out_file = open(dec_out_path, "wb")
+    for ascii_byte in range(1, 128):
+        # In
+        decode_in_file.write(bytes([ascii_byte]))
+        decode_in_file.write(bytes("\n", 'utf-8'))
+        # Out
+        decode_out_file.write(bytes([ascii_byte]))
+        decode_out_file.write(bytes("\n", 'utf-8'))
+    for (index, codepoint) in load_decode_table(b2u_path):
+        # In
+        decode_in_file.write(bytes(index_to_bytes(index)))
+        decode_in_file.write(bytes("\n", 'utf-8'))
+        # Out
+        decode_out_file.write(chr(codepoint).encode('utf-8'))
+        decode_out_file.write(bytes("\n", 'utf-8'))
+
+    # Generate encoding test files.
+    encode_in_file = open(enc_in_path, "wb")
+    encode_out_file = open(enc_out_path, "wb")
+    for ascii_byte in range(1, 128):
+        # In
+        encode_in_file.write(bytes([ascii_byte]))
+        encode_in_file.write(bytes("\n", 'utf-8'))
+        # Out
+        encode_out_file.write(bytes([ascii_byte]))
+        encode_out_file.write(bytes("\n", 'utf-8'))
+    for (codepoint, index) in load_encode_table(b2u_path, u2b_path):
+        # In
+        encode_in_file.write(chr(codepoint).encode('utf-8'))
+        encode_in_file.write(bytes("\n", 'utf-8'))
+        # Out
+        encode_out_file.write(bytes(index_to_bytes(index)))
+        encode_out_file.write(bytes("\n", 'utf-8'))
+
+
+if __name__ == "__main__":
+    table_root = "../../src/generated/big5_uao"
+    test_root = "../../tests/test_data/big5_uao"
+
+    # Generate the table files.
+    generate_big5_uao_tables(
+        "uao250-b2u.txt",
+        "uao250-u2b.txt",
+        table_root + "/big5_uao_tables.rs.inc",
+    )
+
+    # Generate the test data files.
+    generate_big5_uao_test_data(
+        "uao250-b2u.txt",
+        "uao250-u2b.txt",
+        test_root + "/big5_uao_test_decode_in.txt",
+        test_root + "/big5_uao_test_decode_out.txt",
+        test_root + "/big5_uao_test_encode_in.txt",
+        test_root + "/big5_uao_test_encode_out.txt",
+    )
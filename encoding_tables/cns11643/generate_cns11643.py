@@ -0,0 +1,218 @@
+#!/usr/bin/env python3
+
+
+def code_to_index(code):
+    """ Map a CNS 11643 plane and code in its 7-bit form to a pointer.
+    """
+    plane = code >> 16
+    return (plane - 1) * 8836 + (((code >> 8) & 0xFF) - 0x21) * 94 + (code & 0xFF) - 0x21
+
+
+def load_table_file(path):
+    """ Loads the table file, returning a list of `(index, codepoint)`
+        pairs, sorted by index.
+    """
+    in_file = open(path)
+    table = []
+    for line in in_file:
+        parts = line.split()
+        if len(parts) >= 2 and not parts[0].startswith("#"):
+            index = code_to_index(int(parts[0], 16))
+            codepoint = int(parts[1], 16)
+            table += [(index, codepoint)]
+    table.sort()
+    return table
+
+
+def index_to_gl_bytes(index):
+    """ Map a pointer to the two bytes of its 7-bit form, within its plane.
+    """
+    index = index % 8836
+    return bytes([index // 94 + 0x21, index % 94 + 0x21])
+
+
+def index_to_euc_tw_bytes(index):
+    """ Map a pointer to its EUC-TW bytes, using the two-byte form for plane
+        1 and the four-byte SS2 form for the other planes.
+    """
+    plane = index // 8836 + 1
+    gl = index_to_gl_bytes(index)
+    if plane == 1:
+        return bytes([gl[0] | 0x80, gl[1] | 0x80])
+    else:
+        return bytes([0x8E, 0xA0 + plane, gl[0] | 0x80, gl[1] | 0x80])
+
+
+def encode_table(table):
+    """ Creates the `(codepoint, index)` encode table.  A code point that's
+        in more than one plane encodes to its lowest plane.
+    """
+    enc_table = []
+    for (codepoint, index) in sorted([(codepoint, index) for (index, codepoint) in table]):
+        if len(enc_table) > 0 and enc_table[-1][0] == codepoint:
+            if enc_table[-1][1] // 8836 == index // 8836:
+                raise Exception("Duplicate code point: {:04X}".format(codepoint))
+            continue
+        enc_table += [(codepoint, index)]
+    return enc_table
+
+
+def generate_cns11643_tables(in_path, out_path):
+    table = load_table_file(in_path)
+
+    # Create the decode table
+    dec_table = []
+    i = 0
+    for (index, codepoint) in table:
+        while i < index:
+            dec_table += [None]
+            i += 1
+        dec_table += [codepoint]
+        i += 1
+
+    # Create the encode table
+    enc_table = encode_table(table)
+
+    # Write file.
+    out_file = open(out_path, mode='w')
+    out_file.write(
+"""// This file is auto-generated.  Please see `encoding_tables/cns11643/`
+// from the root directory for the files that generate this.
+
+"""
+    )
+
+    # Write out decode table
+    out_file.write("static DECODE_TABLE: [char; {}] = [".format(len(dec_table)))
+    for (i, c) in enumerate(dec_table):
+        if i % 8 == 0:
+            out_file.write("\n    ")
+        if c is None:
+            out_file.write("'�',")
+        else:
+            out_file.write("'\\u{{{:04X}}}',".format(c))
+    out_file.write("\n];\n\n")
+
+    # Write out encode table
+    out_file.write("static ENCODE_TABLE: [(char, u16); {}] = [".format(len(enc_table)))
+    for (i, pair) in enumerate(enc_table):
+        if i % 4 == 0:
+            out_file.write("\n    ")
+        out_file.write("('\\u{{{:X}}}', {}), ".format(pair[0], pair[1]))
+    out_file.write("\n];\n")
+
+
+def generate_iso_2022_cn_test_data(in_path, gb2312_path, dec_in_path, dec_out_path, enc_in_path, enc_out_path):
+    """ Generates ISO-2022-CN test data, with every character on its own
+        line.  Designations only last until the end of a line, so every line
+        has its own.
+    """
+    # ISO-2022-CN only has planes 1 and 2.
+    table = [(index, codepoint) for (index, codepoint) in load_table_file(in_path) if index < 8836 * 2]
+    gb2312_table = []
+    for line in open(gb2312_path):
+        parts = line.split()
+        if len(parts) >= 2 and not parts[0].startswith("#"):
+            code = int(parts[0], 16)
+            index = ((code >> 8) - 0x21) * 94 + (code & 0xFF) - 0x21
+            gb2312_table += [(index, int(parts[1], 16))]
+    gb2312_table.sort()
+
+    def gb2312_line(index):
+        return b"\x1B$)A\x0E" + index_to_gl_bytes(index) + b"\x0F\n"
+
+    def cns11643_line(index):
+        if index < 8836:
+            return b"\x1B$)G\x0E" + index_to_gl_bytes(index) + b"\x0F\n"
+        else:
+            return b"\x1B$*H\x1BN" + index_to_gl_bytes(index) + b"\n"
+
+    # Decoding covers every code of every character set.  Encoding covers
+    # every character once, using GB 2312 in preference to CNS 11643.
+    ascii_bytes = [b for b in range(1, 128) if b not in [0x0E, 0x0F, 0x1B]]
+    decode_lines = [(bytes([b]) + b"\n", chr(b)) for b in ascii_bytes]
+    decode_lines += [(gb2312_line(i), chr(c)) for (i, c) in gb2312_table]
+    decode_lines += [(cns11643_line(i), chr(c)) for (i, c) in table]
+    encode_lines = []
+    seen = set()
+    for (line, c) in decode_lines:
+        if c not in seen:
+            seen.add(c)
+            encode_lines += [(line, c)]
+
+    for (lines, in_path, out_path) in [(decode_lines, dec_in_path, dec_out_path), (encode_lines, enc_out_path, enc_in_path)]:
+        cn_file = open(in_path, "wb")
+        utf8_file = open(out_path, "wb")
+        for (line, c) in lines:
+            # ISO-2022-CN
+            cn_file.write(line)
+            # Utf8
+            utf8_file.write(c.encode('utf-8'))
+            utf8_file.write(bytes("\n", 'utf-8'))
+
+
+def generate_euc_tw_test_data(in_path, dec_in_path, dec_out_path, enc_in_path, enc_out_path):
+    table = load_table_file(in_path)
+
+    # Generate decoding test files.
+    decode_in_file = open(dec_in_path, "wb")
+    decode_out_file = open(dec_out_path, "wb")
+    for ascii_byte in range(1, 128):
+        # In
+        decode_in_file.write(bytes([ascii_byte]))
+        decode_in_file.write(bytes("\n", 'utf-8'))
+        # Out
+        decode_out_file.write(bytes([ascii_byte]))
+        decode_out_file.write(bytes("\n", 'utf-8'))
+    for (index, codepoint) in table:
+        # In
+        decode_in_file.write(index_to_euc_tw_bytes(index))
+        decode_in_file.write(bytes("\n", 'utf-8'))
+        # Out
+        decode_out_file.write(chr(codepoint).encode('utf-8'))
+        decode_out_file.write(bytes("\n", 'utf-8'))
+
+    # Generate encoding test files.
+    encode_in_file = open(enc_in_path, "wb")
+    encode_out_file = open(enc_out_path, "wb")
+    for ascii_byte in range(1, 128):
+        # In
+        encode_in_file.write(bytes([ascii_byte]))
+        encode_in_file.write(bytes("\n", 'utf-8'))
+        # Out
+        encode_out_file.write(bytes([ascii_byte]))
+        encode_out_file.write(bytes("\n", 'utf-8'))
+    for (codepoint, index) in encode_table(table):
+        # In
+        encode_in_file.write(chr(codepoint).encode('utf-8'))
+        encode_in_file.write(bytes("\n", 'utf-8'))
+        # Out
+        encode_out_file.write(index_to_euc_tw_bytes(index))
+        encode_out_file.write(bytes("\n", 'utf-8'))
+
+
+if __name__ == "__main__":
+    table_root = "../../src/generated/cns11643"
+
+    # Generate the table files.
+    generate_cns11643_tables(
+        "cns11643.txt",
+        table_root + "/cns11643_tables.rs.inc",
+    )
+
+    # Generate the test data files.
+    generate_iso_2022_cn_test_data(
+        "cns11643.txt",
+        "../gb2312/gb2312.txt",
+        "../../tests/test_data/iso2022/iso_2022_cn_test_decode_in.txt",
+        "../../tests/test_data/iso2022/iso_2022_cn_test_decode_out.txt",
+        "../../tests/test_data/iso2022/iso_2022_cn_test_encode_in.txt",
+        "../../tests/test_data/iso2022/iso_2022_cn_test_encode_out.txt",
+    )
+    generate_euc_tw_test_data(
+        "cns11643.txt",
+        "../../tests/test_data/cns11643/euc_tw_test_decode_in.txt",
+        "../../tests/test_data/cns11643/euc_tw_test_decode_out.txt",
+        "../../tests/test_data/cns11643/euc_tw_test_encode_in.txt",
+        "../../tests/test_data/cns11643/euc_tw_test_encode_out.txt",
+    )
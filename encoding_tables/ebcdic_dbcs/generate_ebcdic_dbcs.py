@@ -0,0 +1,222 @@
+#!/usr/bin/env python3
+
+SO = 0x0E
+SI = 0x0F
+
+# The number of double-byte characters per line in the test data.
+TEST_LINE_LEN = 16
+
+
+def code_to_index(code):
+    """ Map a double-byte code to a pointer.
+    """
+    return ((code >> 8) - 0x40) * 191 + (code & 0xFF) - 0x40
+
+
+def load_table_file(path):
+    """ Loads the table file, returning a `(single, double)` tuple of lists
+        of `(code, codepoint)` pairs, sorted by code.
+    """
+    in_file = open(path)
+    single = []
+    double = []
+    for line in in_file:
+        parts = line.split()
+        if len(parts) >= 2 and not parts[0].startswith("#"):
+            code = int(parts[0], 16)
+            codepoint = int(parts[1], 16)
+            if len(parts[0]) == 4:
+                single += [(code, codepoint)]
+            else:
+                double += [(code, codepoint)]
+    single.sort()
+    double.sort()
+    return (single, double)
+
+
+def encode_table(table):
+    """ The `(codepoint, code)` pairs used for encoding, sorted by code point.
+        For characters with more than one code, the highest code is used.
+    """
+    enc_table = {}
+    for (code, codepoint) in table:
+        enc_table[codepoint] = max(code, enc_table.get(codepoint, code))
+    return sorted(enc_table.items())
+
+
+def write_char_table(out_file, name, dec_table):
+    out_file.write("static {}: [char; {}] = [".format(name, len(dec_table)))
+    for (i, c) in enumerate(dec_table):
+        if i % 8 == 0:
+            out_file.write("\n    ")
+        if c is None:
+            out_file.write("'�',")
+        else:
+            out_file.write("'\\u{{{:04X}}}',".format(c))
+    out_file.write("\n];\n\n")
+
+
+def write_pair_table(out_file, name, int_type, enc_table):
+    out_file.write("static {}: [(char, {}); {}] = [".format(name, int_type, len(enc_table)))
+    for (i, pair) in enumerate(enc_table):
+        if i % 4 == 0:
+            out_file.write("\n    ")
+        out_file.write("('\\u{{{:X}}}', {}), ".format(pair[0], pair[1]))
+    out_file.write("\n];\n")
+
+
+def generate_dbcs_tables(in_path, out_path, prefix):
+    """ Generates the tables for the double-byte half of a code page, which
+        may be shared by more than one code page.
+    """
+    (_, table) = load_table_file(in_path)
+
+    # Create the decode table
+    dec_table = []
+    i = 0
+    for (code, codepoint) in table:
+        index = code_to_index(code)
+        while i < index:
+            dec_table += [None]
+            i += 1
+        dec_table += [codepoint]
+        i += 1
+
+    # Write file.
+    out_file = open(out_path, mode='w')
+    out_file.write(
+"""// This file is auto-generated.  Please see `encoding_tables/ebcdic_dbcs/`
+// from the root directory for the files that generate this.
+
+"""
+    )
+    write_char_table(out_file, prefix + "_DBCS_DECODE_TABLE", dec_table)
+    write_pair_table(out_file, prefix + "_DBCS_ENCODE_TABLE", "u16", encode_table(table))
+
+
+def generate_sbcs_tables(in_path, out_path, dbcs_prefix):
+    """ Generates the module contents for a code page: its single-byte
+        tables, and the encode and decode functions.
+    """
+    (table, _) = load_table_file(in_path)
+
+    # Create the decode table
+    dec_table = [None] * 256
+    for (code, codepoint) in table:
+        dec_table[code] = codepoint
+
+    # Write file.
+    out_file = open(out_path, mode='w')
+    out_file.write(
+"""// This file is auto-generated.  Please see `encoding_tables/ebcdic_dbcs/`
+// from the root directory for the files that generate this.
+
+use {{DecodeResult, EncodeResult}};
+
+pub use super::State;
+
+pub fn decode_to_str<'a>(
+    input: &[u8],
+    out_buffer: &'a mut [u8],
+    state: &mut State,
+    is_end: bool,
+) -> DecodeResult<'a> {{
+    super::ebcdic_dbcs_decode_to_str(
+        &SBCS_DECODE_TABLE,
+        &super::{0}_DBCS_DECODE_TABLE,
+        input,
+        out_buffer,
+        state,
+        is_end,
+    )
+}}
+
+pub fn encode_from_str<'a>(
+    input: &str,
+    out_buffer: &'a mut [u8],
+    state: &mut State,
+    is_end: bool,
+) -> EncodeResult<'a> {{
+    super::ebcdic_dbcs_encode_from_str(
+        &SBCS_ENCODE_TABLE,
+        &super::{0}_DBCS_ENCODE_TABLE,
+        input,
+        out_buffer,
+        state,
+        is_end,
+    )
+}}
+
+""".format(dbcs_prefix)
+    )
+    write_char_table(out_file, "SBCS_DECODE_TABLE", dec_table)
+    write_pair_table(out_file, "SBCS_ENCODE_TABLE", "u8", encode_table(table))
+
+
+def generate_test_data(sbcs_path, dbcs_path, dec_in_path, dec_out_path, enc_in_path, enc_out_path):
+    """ Generates test data with every single-byte character on its own line,
+        followed by the double-byte characters in shifted runs of
+        `TEST_LINE_LEN` characters per line.  The decode data has every code,
+        and the encode data has every character.
+    """
+    (single, _) = load_table_file(sbcs_path)
+    (_, double) = load_table_file(dbcs_path)
+    line_feed = [code for (code, codepoint) in single if codepoint == 0x0A][0]
+
+    enc_double = sorted([(code, codepoint) for (codepoint, code) in encode_table(double)])
+    for (in_path, out_path, double_table) in [
+        (dec_in_path, dec_out_path, double),
+        (enc_out_path, enc_in_path, enc_double),
+    ]:
+        ebcdic_file = open(in_path, "wb")
+        utf8_file = open(out_path, "wb")
+        for (code, codepoint) in single:
+            ebcdic_file.write(bytes([code, line_feed]))
+            utf8_file.write(chr(codepoint).encode('utf-8') + b"\n")
+        for n in range(0, len(double_table), TEST_LINE_LEN):
+            line = double_table[n:(n + TEST_LINE_LEN)]
+            ebcdic_file.write(bytes([SO]))
+            for (code, codepoint) in line:
+                ebcdic_file.write(bytes([code >> 8, code & 0xFF]))
+                utf8_file.write(chr(codepoint).encode('utf-8'))
+            ebcdic_file.write(bytes([SI, line_feed]))
+            utf8_file.write(b"\n")
+
+
+if __name__ == "__main__":
+    table_root = "../../src/generated/ebcdic_dbcs"
+    test_root = "../../tests/test_data/ebcdic_dbcs"
+
+    # Each code page, and the code page whose double-byte half it uses.
+    code_pages = [
+        ("ibm930", "ibm930"),
+        ("ibm933", "ibm933"),
+        ("ibm935", "ibm935"),
+        ("ibm937", "ibm937"),
+        ("ibm939", "ibm930"),
+    ]
+
+    # Generate the table files.
+    for (name, dbcs_name) in code_pages:
+        if name == dbcs_name:
+            generate_dbcs_tables(
+                name + ".txt",
+                table_root + "/" + name + "_dbcs_tables.rs.inc",
+                name.upper(),
+            )
+        generate_sbcs_tables(
+            name + ".txt",
+            table_root + "/" + name + "_tables.rs.inc",
+            dbcs_name.upper(),
+        )
+
+    # Generate the test data files.
+    for (name, dbcs_name) in code_pages:
+        generate_test_data(
+            name + ".txt",
+            dbcs_name + ".txt",
+            test_root + "/" + name + "_test_decode_in.txt",
+            test_root + "/" + name + "_test_decode_out.txt",
+            test_root + "/" + name + "_test_encode_in.txt",
+            test_root + "/" + name + "_test_encode_out.txt",
+        )
@@ -0,0 +1,123 @@
+#!/usr/bin/env python3
+
+
+def code_to_index(code):
+    """ Map a GB 2312 code in its 7-bit form to a pointer.
+    """
+    return ((code >> 8) - 0x21) * 94 + (code & 0xFF) - 0x21
+
+
+def load_table_file(path):
+    """ Loads the table file, returning a list of `(index, codepoint)`
+        pairs, sorted by index.
+    """
+    in_file = open(path)
+    table = []
+    for line in in_file:
+        parts = line.split()
+        if len(parts) >= 2 and not parts[0].startswith("#"):
+            index = code_to_index(int(parts[0], 16))
+            codepoint = int(parts[1], 16)
+            table += [(index, codepoint)]
+    table.sort()
+    return table
+
+
+def index_to_gl_bytes(index):
+    """ Map a pointer to the two bytes of its 7-bit form.
+    """
+    return bytes([index // 94 + 0x21, index % 94 + 0x21])
+
+
+def generate_gb2312_tables(in_path, out_path):
+    table = load_table_file(in_path)
+
+    # Create the decode table
+    dec_table = []
+    i = 0
+    for (index, codepoint) in table:
+        while i < index:
+            dec_table += [None]
+            i += 1
+        dec_table += [codepoint]
+        i += 1
+
+    # Create the encode table
+    enc_table = [(codepoint, index) for (index, codepoint) in table]
+    enc_table.sort()
+    for n in range(1, len(enc_table)):
+        if enc_table[n - 1][0] == enc_table[n][0]:
+            raise Exception("Duplicate code point: {:04X}".format(enc_table[n][0]))
+
+    # Write file.
+    out_file = open(out_path, mode='w')
+    out_file.write(
+"""// This file is auto-generated.  Please see `encoding_tables/gb2312/`
+// from the root directory for the files that generate this.
+
+"""
+    )
+
+    # Write out decode table
+    out_file.write("static DECODE_TABLE: [char; {}] = [".format(len(dec_table)))
+    for (i, c) in enumerate(dec_table):
+        if i % 8 == 0:
+            out_file.write("\n    ")
+        if c is None:
+            out_file.write("'�',")
+        else:
+            out_file.write("'\\u{{{:04X}}}',".format(c))
+    out_file.write("\n];\n\n")
+
+    # Write out encode table
+    out_file.write("static ENCODE_TABLE: [(char, u16); {}] = [".format(len(enc_table)))
+    for (i, pair) in enumerate(enc_table):
+        if i % 4 == 0:
+            out_file.write("\n    ")
+        out_file.write("('\\u{{{:X}}}', {}), ".format(pair[0], pair[1]))
+    out_file.write("\n];\n")
+
+
+def generate_hz_test_data(in_path, dec_in_path, dec_out_path, enc_in_path, enc_out_path):
+    """ Generates HZ-GB-2312 test data, with every character on its own
+        line.  Since lines always end in ASCII mode, the same data is used
+        for both decoding and encoding.
+    """
+    table = load_table_file(in_path)
+    for (in_path, out_path) in [(dec_in_path, dec_out_path), (enc_out_path, enc_in_path)]:
+        hz_file = open(in_path, "wb")
+        utf8_file = open(out_path, "wb")
+        for ascii_byte in range(1, 128):
+            # HZ
+            hz_file.write(b"~~" if ascii_byte == ord("~") else bytes([ascii_byte]))
+            hz_file.write(bytes("\n", 'utf-8'))
+            # Utf8
+            utf8_file.write(bytes([ascii_byte]))
+            utf8_file.write(bytes("\n", 'utf-8'))
+        for (index, codepoint) in table:
+            # HZ
+            hz_file.write(b"~{" + index_to_gl_bytes(index) + b"~}")
+            hz_file.write(bytes("\n", 'utf-8'))
+            # Utf8
+            utf8_file.write(chr(codepoint).encode('utf-8'))
+            utf8_file.write(bytes("\n", 'utf-8'))
+
+
+if __name__ == "__main__":
+    table_root = "../../src/generated/gb2312"
+    test_root = "../../tests/test_data/iso2022"
+
+    # Generate the table files.
+    generate_gb2312_tables(
+        "gb2312.txt",
+        table_root + "/gb2312_tables.rs.inc",
+    )
+
+    # Generate the test data files.
+    generate_hz_test_data(
+        "gb2312.txt",
+        test_root + "/hz_gb_2312_test_decode_in.txt",
+        test_root + "/hz_gb_2312_test_decode_out.txt",
+        test_root + "/hz_gb_2312_test_encode_in.txt",
+        test_root + "/hz_gb_2312_test_encode_out.txt",
+    )
@@ -0,0 +1,94 @@
+#!/usr/bin/env python3
+
+# The table files, and the name of the tables generated from each.
+TABLES = [
+    ("default.txt", "DEFAULT"),
+    ("default_ext.txt", "DEFAULT_EXT"),
+    ("turkish.txt", "TURKISH"),
+    ("turkish_ext.txt", "TURKISH_EXT"),
+    ("spanish_ext.txt", "SPANISH_EXT"),
+    ("portuguese.txt", "PORTUGUESE"),
+    ("portuguese_ext.txt", "PORTUGUESE_EXT"),
+    ("bengali.txt", "BENGALI"),
+    ("bengali_ext.txt", "BENGALI_EXT"),
+    ("gujarati.txt", "GUJARATI"),
+    ("gujarati_ext.txt", "GUJARATI_EXT"),
+    ("hindi.txt", "HINDI"),
+    ("hindi_ext.txt", "HINDI_EXT"),
+    ("kannada.txt", "KANNADA"),
+    ("kannada_ext.txt", "KANNADA_EXT"),
+    ("malayalam.txt", "MALAYALAM"),
+    ("malayalam_ext.txt", "MALAYALAM_EXT"),
+    ("oriya.txt", "ORIYA"),
+    ("oriya_ext.txt", "ORIYA_EXT"),
+    ("punjabi.txt", "PUNJABI"),
+    ("punjabi_ext.txt", "PUNJABI_EXT"),
+    ("tamil.txt", "TAMIL"),
+    ("tamil_ext.txt", "TAMIL_EXT"),
+    ("telugu.txt", "TELUGU"),
+    ("telugu_ext.txt", "TELUGU_EXT"),
+    ("urdu.txt", "URDU"),
+    ("urdu_ext.txt", "URDU_EXT"),
+]
+
+
+def load_table_file(path):
+    """ Loads a table file, returning a list of `(septet, char, decode_only)`
+        tuples.
+    """
+    in_file = open(path)
+    table = []
+    for line in in_file:
+        parts = line.split()
+        if len(parts) >= 2 and not parts[0].startswith("#"):
+            septet = int(parts[0], 16)
+            if septet > 0x7F:
+                raise Exception("Not a septet: {}".format(parts[0]))
+            decode_only = len(parts) >= 3 and parts[2] == "decode-only"
+            table += [(septet, chr(int(parts[1], 16)), decode_only)]
+    table.sort()
+    return table
+
+
+def generate_gsm_0338_tables(out_path):
+    out_file = open(out_path, mode='w')
+    out_file.write(
+"""// This file is auto-generated.  Please see `encoding_tables/gsm_0338/`
+// from the root directory for the files that generate this.
+""")
+
+    for (in_path, name) in TABLES:
+        table = load_table_file(in_path)
+
+        # Create the decode table, and the encode table sorted by code
+        # point.
+        dec_table = [None] * 128
+        for (septet, c, _) in table:
+            dec_table[septet] = c
+        enc_table = sorted((c, septet) for (septet, c, decode_only) in table if not decode_only)
+        for i in range(1, len(enc_table)):
+            if enc_table[i][0] == enc_table[i - 1][0]:
+                raise Exception("Duplicate code point in {}: {:04X}".format(in_path, ord(enc_table[i][0])))
+
+        # Write out decode table
+        out_file.write("\nstatic {}_DECODE_TABLE: [char; 128] = [".format(name))
+        for (i, c) in enumerate(dec_table):
+            if i % 8 == 0:
+                out_file.write("\n    ")
+            if c is None:
+                out_file.write("'�', ")
+            else:
+                out_file.write("'\\u{{{:04X}}}', ".format(ord(c)))
+        out_file.write("\n];\n")
+
+        # Write out encode table
+        out_file.write("\nstatic {}_ENCODE_TABLE: [(char, u8); {}] = [".format(name, len(enc_table)))
+        for (i, (c, septet)) in enumerate(enc_table):
+            if i % 4 == 0:
+                out_file.write("\n    ")
+            out_file.write("('\\u{{{:04X}}}', 0x{:02X}), ".format(ord(c), septet))
+        out_file.write("\n];\n")
+
+
+if __name__ == "__main__":
+    generate_gsm_0338_tables("../../src/generated/gsm_0338/gsm_0338_tables.rs.inc")
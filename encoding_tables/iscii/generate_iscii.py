@@ -0,0 +1,111 @@
+#!/usr/bin/env python3
+
+# The number of scripts, i.e. columns in the table file.
+SCRIPT_COUNT = 10
+
+# The range of single bytes covered by the decode tables.
+FIRST_BYTE = 0xA0
+LAST_BYTE = 0xFA
+
+
+def load_table_file(path):
+    """ Loads the table file, returning a list of `(bytes, columns)` pairs,
+        where `bytes` is a tuple of one or two bytes and `columns` has a
+        string of code points for each script, or None.
+    """
+    in_file = open(path)
+    table = []
+    for line in in_file:
+        parts = line.split()
+        if len(parts) >= 2 and not parts[0].startswith("#"):
+            code = tuple(int(x, 16) for x in parts[0].split("+"))
+            if len(parts) != SCRIPT_COUNT + 1:
+                raise Exception("Wrong number of columns: {}".format(parts[0]))
+            columns = []
+            for part in parts[1:]:
+                if part == "-":
+                    columns += [None]
+                else:
+                    columns += ["".join(chr(int(x, 16)) for x in part.split("+"))]
+            table += [(code, columns)]
+    table.sort()
+    return table
+
+
+def rust_str(s):
+    return "".join("\\u{{{:04X}}}".format(ord(c)) for c in s)
+
+
+def generate_iscii_tables(in_path, out_path):
+    table = load_table_file(in_path)
+
+    # Create the decode tables for single bytes, one for each script.
+    dec_tables = [[None] * (LAST_BYTE - FIRST_BYTE + 1) for _ in range(SCRIPT_COUNT)]
+    for (code, columns) in table:
+        if len(code) == 1:
+            for (script, s) in enumerate(columns):
+                if s is not None:
+                    dec_tables[script][code[0] - FIRST_BYTE] = s
+
+    # Create the pair table, sorted by script and then bytes.
+    pair_table = []
+    for (code, columns) in table:
+        if len(code) == 2:
+            for (script, s) in enumerate(columns):
+                if s is not None:
+                    pair_table += [(script, code[0], code[1], s)]
+    pair_table.sort()
+
+    # Create the encode table, sorted by code point and then script, from
+    # everything that decodes to a single code point.
+    enc_table = []
+    for (code, columns) in table:
+        for (script, s) in enumerate(columns):
+            if s is not None and len(s) == 1:
+                enc_table += [(s, script, code)]
+    enc_table.sort()
+    for i in range(1, len(enc_table)):
+        if enc_table[i][:2] == enc_table[i - 1][:2]:
+            raise Exception("Duplicate code point: {:04X}".format(ord(enc_table[i][0])))
+
+    # Write file.
+    out_file = open(out_path, mode='w')
+    out_file.write(
+"""// This file is auto-generated.  Please see `encoding_tables/iscii/`
+// from the root directory for the files that generate this.
+
+""")
+
+    # Write out decode tables
+    out_file.write("static DECODE_TABLE: [[char; {}]; {}] = [".format(len(dec_tables[0]), SCRIPT_COUNT))
+    for dec_table in dec_tables:
+        out_file.write("\n    [")
+        for (i, s) in enumerate(dec_table):
+            if i % 8 == 0:
+                out_file.write("\n        ")
+            if s is None:
+                out_file.write("'�',")
+            else:
+                out_file.write("'{}',".format(rust_str(s)))
+        out_file.write("\n    ],")
+    out_file.write("\n];\n")
+
+    # Write out pair table
+    out_file.write("\nstatic PAIR_TABLE: [(u8, u8, u8, &str); {}] = [".format(len(pair_table)))
+    for (i, (script, byte_1, byte_2, s)) in enumerate(pair_table):
+        if i % 3 == 0:
+            out_file.write("\n    ")
+        out_file.write("({}, 0x{:02X}, 0x{:02X}, \"{}\"), ".format(script, byte_1, byte_2, rust_str(s)))
+    out_file.write("\n];\n")
+
+    # Write out encode table
+    out_file.write("\nstatic ENCODE_TABLE: [(char, u8, &[u8]); {}] = [".format(len(enc_table)))
+    for (i, (s, script, code)) in enumerate(enc_table):
+        if i % 3 == 0:
+            out_file.write("\n    ")
+        out_file.write("('{}', {}, &[{}]), ".format(rust_str(s), script, ", ".join("0x{:02X}".format(b) for b in code)))
+    out_file.write("\n];\n")
+
+
+if __name__ == "__main__":
+    generate_iscii_tables("iscii.txt", "../../src/generated/iscii/iscii_tables.rs.inc")
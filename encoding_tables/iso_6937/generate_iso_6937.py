@@ -0,0 +1,136 @@
+#!/usr/bin/env python3
+
+import unicodedata
+
+# The non-spacing diacritical marks, and their Unicode combining marks, as in
+# `MARK_TABLE` in `src/iso_6937.rs`.
+MARKS = {
+    0xC1: 0x0300, 0xC2: 0x0301, 0xC3: 0x0302, 0xC4: 0x0303, 0xC5: 0x0304,
+    0xC6: 0x0306, 0xC7: 0x0307, 0xC8: 0x0308, 0xCA: 0x030A, 0xCB: 0x0327,
+    0xCD: 0x030B, 0xCE: 0x0328, 0xCF: 0x030C,
+}
+
+def load_table_file(path):
+    """ Loads the table file, returning a list of `(bytes, char)` pairs,
+        where `bytes` is a tuple of one or two bytes.
+    """
+    in_file = open(path)
+    table = []
+    for line in in_file:
+        parts = line.split()
+        if len(parts) >= 2 and not parts[0].startswith("#"):
+            code = tuple(int(x, 16) for x in parts[0].split("+"))
+            table += [(code, chr(int(parts[1], 16)))]
+    table.sort()
+    return table
+
+
+def base_char(byte, dec_table):
+    return chr(byte) if byte < 0x80 else dec_table[byte - 0x80]
+
+
+def graphic_bytes(dec_table):
+    """ Returns the bytes of the graphic characters that a diacritical mark
+        can apply to, as `is_graphic()` in `src/iso_6937.rs`.  0xE0, U+2126
+        OHM SIGN, is left out, since Unicode normalizes it to another
+        character before composing.
+    """
+    return [b for b in range(0x21, 0x7F)] + [
+        b for b in range(0xA0, 0x100)
+        if dec_table[b - 0x80] is not None and b not in MARKS
+        and unicodedata.normalize("NFC", dec_table[b - 0x80]) == dec_table[b - 0x80]
+    ]
+
+
+def generate_iso_6937_tables(in_path, out_path):
+    table = load_table_file(in_path)
+
+    # Create the decode table for the upper half, and the encode table
+    # sorted by code point.
+    dec_table = [None] * 128
+    enc_table = []
+    for (code, c) in table:
+        if len(code) == 1:
+            if code[0] < 0x80:
+                raise Exception("Byte in the ascii range: 0x{:02X}".format(code[0]))
+            dec_table[code[0] - 0x80] = c
+            enc_table += [(c, code[0])]
+    enc_table.sort()
+
+    # Create the tables for diacritic and base pairs, sorted by bytes for
+    # decoding and by code point for encoding.  The table file only has the
+    # pairs that glibc composes, so add every other pair of a mark and a
+    # graphic character that Unicode composes, so that decoding gives the
+    # precomposed character wherever Unicode has one.
+    compose_table = [(code[0], code[1], c) for (code, c) in table if len(code) == 2]
+    for (byte_1, byte_2, c) in compose_table:
+        if byte_2 != 0x20 and unicodedata.normalize("NFD", c) != unicodedata.normalize("NFD", base_char(byte_2, dec_table) + chr(MARKS[byte_1])):
+            raise Exception("Not a canonical composition: 0x{:02X}+0x{:02X}".format(byte_1, byte_2))
+    pairs = set([(byte_1, byte_2) for (byte_1, byte_2, _) in compose_table])
+    for byte_1 in sorted(MARKS.keys()):
+        for byte_2 in graphic_bytes(dec_table):
+            s = unicodedata.normalize("NFC", base_char(byte_2, dec_table) + chr(MARKS[byte_1]))
+            if len(s) == 1 and (byte_1, byte_2) not in pairs:
+                compose_table += [(byte_1, byte_2, s)]
+    compose_table.sort()
+
+    # Encoding also accepts the precomposed characters that Unicode doesn't
+    # compose to, such as U+212B ANGSTROM SIGN, so that canonically
+    # equivalent text encodes the same.
+    decompose_table = [(c, byte_1, byte_2) for (byte_1, byte_2, c) in compose_table]
+    composed = dict([(unicodedata.normalize("NFD", base_char(byte_2, dec_table) + chr(MARKS[byte_1])), (byte_1, byte_2))
+                     for byte_1 in MARKS.keys() for byte_2 in graphic_bytes(dec_table)])
+    encodable = set([c for (c, _, _) in decompose_table])
+    for codepoint in range(0x80, 0x110000):
+        c = chr(codepoint)
+        d = unicodedata.normalize("NFD", c)
+        if c not in encodable and len(d) == 2 and d in composed:
+            decompose_table += [(c, composed[d][0], composed[d][1])]
+    decompose_table.sort()
+
+    # Write file.
+    out_file = open(out_path, mode='w')
+    out_file.write(
+"""// This file is auto-generated.  Please see `encoding_tables/iso_6937/`
+// from the root directory for the files that generate this.
+
+""")
+
+    # Write out decode table
+    out_file.write("static DECODE_TABLE: [char; 128] = [")
+    for (i, c) in enumerate(dec_table):
+        if i % 8 == 0:
+            out_file.write("\n    ")
+        if c is None:
+            out_file.write("'�', ")
+        else:
+            out_file.write("'\\u{{{:04X}}}', ".format(ord(c)))
+    out_file.write("\n];\n")
+
+    # Write out encode table
+    out_file.write("\nstatic ENCODE_TABLE: [(char, u8); {}] = [".format(len(enc_table)))
+    for (i, (c, byte)) in enumerate(enc_table):
+        if i % 4 == 0:
+            out_file.write("\n    ")
+        out_file.write("('\\u{{{:04X}}}', 0x{:02X}), ".format(ord(c), byte))
+    out_file.write("\n];\n")
+
+    # Write out compose table
+    out_file.write("\nstatic COMPOSE_TABLE: [(u8, u8, char); {}] = [".format(len(compose_table)))
+    for (i, (byte_1, byte_2, c)) in enumerate(compose_table):
+        if i % 4 == 0:
+            out_file.write("\n    ")
+        out_file.write("(0x{:02X}, 0x{:02X}, '\\u{{{:04X}}}'), ".format(byte_1, byte_2, ord(c)))
+    out_file.write("\n];\n")
+
+    # Write out decompose table
+    out_file.write("\nstatic DECOMPOSE_TABLE: [(char, u8, u8); {}] = [".format(len(decompose_table)))
+    for (i, (c, byte_1, byte_2)) in enumerate(decompose_table):
+        if i % 4 == 0:
+            out_file.write("\n    ")
+        out_file.write("('\\u{{{:04X}}}', 0x{:02X}, 0x{:02X}), ".format(ord(c), byte_1, byte_2))
+    out_file.write("\n];\n")
+
+
+if __name__ == "__main__":
+    generate_iso_6937_tables("iso_6937.txt", "../../src/generated/iso_6937/iso_6937_tables.rs.inc")
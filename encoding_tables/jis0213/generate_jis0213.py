@@ -0,0 +1,210 @@
+#!/usr/bin/env python3
+
+
+def code_to_index(plane, code):
+    """ Map a JIS X 0213 plane and row/cell code to a pointer.
+    """
+    row = (code >> 8) - 0x20
+    cell = (code & 0xFF) - 0x20
+    return (plane - 1) * 8836 + (row - 1) * 94 + (cell - 1)
+
+
+def index_to_plane_row_cell(i):
+    return (i // 8836 + 1, (i % 8836) // 94 + 1, i % 94 + 1)
+
+
+def index_to_sjis_bytes(i):
+    """ Map a pointer to Shift_JIS-2004 bytes.
+    """
+    (plane, row, cell) = index_to_plane_row_cell(i)
+    if plane == 1:
+        k = row
+    elif row <= 5:
+        k = row + 94
+    elif row <= 15:
+        k = row + 88
+    else:
+        k = row + 26
+    lead = (k - 1) // 2
+    lead += 0x81 if lead < 31 else 0xC1
+    if k % 2 == 1:
+        trail = cell + (0x3F if cell < 64 else 0x40)
+    else:
+        trail = cell + 0x9E
+    return bytes([lead, trail])
+
+
+def index_to_euc_bytes(i):
+    """ Map a pointer to EUC-JIS-2004 bytes.
+    """
+    (plane, row, cell) = index_to_plane_row_cell(i)
+    if plane == 1:
+        return bytes([row + 0xA0, cell + 0xA0])
+    else:
+        return bytes([0x8F, row + 0xA0, cell + 0xA0])
+
+
+def load_table_file(path):
+    """ Loads the table file, returning a list of `(index, [codepoints])`
+        pairs, sorted by index.
+    """
+    in_file = open(path)
+    table = []
+    for line in in_file:
+        parts = line.split()
+        if len(parts) >= 2 and not parts[0].startswith("#"):
+            plane = 1 if parts[0].startswith("3-") else 2
+            index = code_to_index(plane, int(parts[0][2:], 16))
+            codepoints = [int(c, 16) for c in parts[1].replace("U+", "").split("+")]
+            table += [(index, codepoints)]
+    table.sort()
+    return table
+
+
+def generate_jis0213_tables(in_path, out_path):
+    table = load_table_file(in_path)
+    single = [(i, c[0]) for (i, c) in table if len(c) == 1]
+    composed = [(i, c) for (i, c) in table if len(c) == 2]
+    assert len(single) + len(composed) == len(table)
+
+    # Create the decode table
+    dec_table = []
+    i = 0
+    for (index, codepoint) in single:
+        while i < index:
+            dec_table += [None]
+            i += 1
+        dec_table += [codepoint]
+        i += 1
+
+    # Create the encode table
+    enc_table = [(codepoint, index) for (index, codepoint) in single]
+    enc_table.sort()
+    for n in range(1, len(enc_table)):
+        if enc_table[n - 1][0] == enc_table[n][0]:
+            raise Exception("Duplicate code point: {:04X}".format(enc_table[n][0]))
+
+    # Create the composed table
+    comp_table = [(codepoints[0], codepoints[1], index) for (index, codepoints) in composed]
+    comp_table.sort()
+
+    # Write file.
+    out_file = open(out_path, mode='w')
+    out_file.write(
+"""// This file is auto-generated.  Please see `encoding_tables/jis0213/`
+// from the root directory for the files that generate this.
+
+"""
+    )
+
+    # Write out decode table
+    out_file.write("static DECODE_TABLE: [char; {}] = [".format(len(dec_table)))
+    for (i, c) in enumerate(dec_table):
+        if i % 8 == 0:
+            out_file.write("\n    ")
+        if c is None:
+            out_file.write("'�',")
+        else:
+            out_file.write("'\\u{{{:04X}}}',".format(c))
+    out_file.write("\n];\n\n")
+
+    # Write out encode table
+    out_file.write("static ENCODE_TABLE: [(char, u16); {}] = [".format(len(enc_table)))
+    for (i, pair) in enumerate(enc_table):
+        if i % 4 == 0:
+            out_file.write("\n    ")
+        out_file.write("('\\u{{{:X}}}', {}), ".format(pair[0], pair[1]))
+    out_file.write("\n];\n\n")
+
+    # Write out composed table
+    out_file.write("const COMPOSED_TABLE: [(char, char, u16); {}] = [".format(len(comp_table)))
+    for item in comp_table:
+        out_file.write("\n    ('\\u{{{:04X}}}', '\\u{{{:04X}}}', {}),".format(item[0], item[1], item[2]))
+    out_file.write("\n];\n")
+
+
+def generate_jis0213_test_data(in_path, index_to_bytes, single_bytes, dec_in_path, dec_out_path, enc_in_path, enc_out_path):
+    table = load_table_file(in_path)
+
+    # Generate decoding test files.
+    decode_in_file = open(dec_in_path, "wb")
+    decode_out_file = open(dec_out_path, "wb")
+    for ascii_byte in range(1, 128):
+        # In
+        decode_in_file.write(bytes([ascii_byte]))
+        decode_in_file.write(bytes("\n", 'utf-8'))
+        # Out
+        decode_out_file.write(bytes([ascii_byte]))
+        decode_out_file.write(bytes("\n", 'utf-8'))
+    for (code, codepoint) in single_bytes:
+        # In
+        decode_in_file.write(code)
+        decode_in_file.write(bytes("\n", 'utf-8'))
+        # Out
+        decode_out_file.write(chr(codepoint).encode('utf-8'))
+        decode_out_file.write(bytes("\n", 'utf-8'))
+    for (index, codepoints) in table:
+        # In
+        decode_in_file.write(index_to_bytes(index))
+        decode_in_file.write(bytes("\n", 'utf-8'))
+        # Out
+        for codepoint in codepoints:
+            decode_out_file.write(chr(codepoint).encode('utf-8'))
+        decode_out_file.write(bytes("\n", 'utf-8'))
+
+    # Generate encoding test files.
+    encode_in_file = open(enc_in_path, "wb")
+    encode_out_file = open(enc_out_path, "wb")
+    for ascii_byte in range(1, 128):
+        # In
+        encode_in_file.write(bytes([ascii_byte]))
+        encode_in_file.write(bytes("\n", 'utf-8'))
+        # Out
+        encode_out_file.write(bytes([ascii_byte]))
+        encode_out_file.write(bytes("\n", 'utf-8'))
+    for (code, codepoint) in single_bytes:
+        # In
+        encode_in_file.write(chr(codepoint).encode('utf-8'))
+        encode_in_file.write(bytes("\n", 'utf-8'))
+        # Out
+        encode_out_file.write(code)
+        encode_out_file.write(bytes("\n", 'utf-8'))
+    for (index, codepoints) in table:
+        # In
+        for codepoint in codepoints:
+            encode_in_file.write(chr(codepoint).encode('utf-8'))
+        encode_in_file.write(bytes("\n", 'utf-8'))
+        # Out
+        encode_out_file.write(index_to_bytes(index))
+        encode_out_file.write(bytes("\n", 'utf-8'))
+
+
+if __name__ == "__main__":
+    table_root = "../../src/generated/jis0213"
+    test_root = "../../tests/test_data/jis0213"
+
+    # Generate the table files.
+    generate_jis0213_tables(
+        "jisx0213-2004.txt",
+        table_root + "/jis0213_tables.rs.inc",
+    )
+
+    # Generate the test data files.
+    generate_jis0213_test_data(
+        "jisx0213-2004.txt",
+        index_to_sjis_bytes,
+        [(bytes([b]), b - 0xA1 + 0xFF61) for b in range(0xA1, 0xE0)],
+        test_root + "/shift_jis_2004_test_decode_in.txt",
+        test_root + "/shift_jis_2004_test_decode_out.txt",
+        test_root + "/shift_jis_2004_test_encode_in.txt",
+        test_root + "/shift_jis_2004_test_encode_out.txt",
+    )
+    generate_jis0213_test_data(
+        "jisx0213-2004.txt",
+        index_to_euc_bytes,
+        [(bytes([0x8E, b]), b - 0xA1 + 0xFF61) for b in range(0xA1, 0xE0)],
+        test_root + "/euc_jis_2004_test_decode_in.txt",
+        test_root + "/euc_jis_2004_test_decode_out.txt",
+        test_root + "/euc_jis_2004_test_encode_in.txt",
+        test_root + "/euc_jis_2004_test_encode_out.txt",
+    )
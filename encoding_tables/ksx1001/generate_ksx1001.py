@@ -0,0 +1,216 @@
+#!/usr/bin/env python3
+
+
+def code_to_index(code):
+    """ Map a KS X 1001 code in its 7-bit form to a pointer.
+    """
+    return ((code >> 8) - 0x21) * 94 + (code & 0xFF) - 0x21
+
+
+def load_table_file(path):
+    """ Loads the table file, returning a list of `(index, codepoint)`
+        pairs, sorted by index.
+    """
+    in_file = open(path)
+    table = []
+    for line in in_file:
+        parts = line.split()
+        if len(parts) >= 2 and not parts[0].startswith("#"):
+            index = code_to_index(int(parts[0], 16))
+            codepoint = int(parts[1], 16)
+            table += [(index, codepoint)]
+    table.sort()
+    return table
+
+
+def index_to_gl_bytes(index):
+    """ Map a pointer to the two bytes of its 7-bit form.
+    """
+    return bytes([index // 94 + 0x21, index % 94 + 0x21])
+
+
+def generate_ksx1001_tables(in_path, out_path):
+    table = load_table_file(in_path)
+
+    # Create the decode table
+    dec_table = []
+    i = 0
+    for (index, codepoint) in table:
+        while i < index:
+            dec_table += [None]
+            i += 1
+        dec_table += [codepoint]
+        i += 1
+
+    # Create the encode table
+    enc_table = [(codepoint, index) for (index, codepoint) in table]
+    enc_table.sort()
+    for n in range(1, len(enc_table)):
+        if enc_table[n - 1][0] == enc_table[n][0]:
+            raise Exception("Duplicate code point: {:04X}".format(enc_table[n][0]))
+
+    # Write file.
+    out_file = open(out_path, mode='w')
+    out_file.write(
+"""// This file is auto-generated.  Please see `encoding_tables/ksx1001/`
+// from the root directory for the files that generate this.
+
+"""
+    )
+
+    # Write out decode table
+    out_file.write("static DECODE_TABLE: [char; {}] = [".format(len(dec_table)))
+    for (i, c) in enumerate(dec_table):
+        if i % 8 == 0:
+            out_file.write("\n    ")
+        if c is None:
+            out_file.write("'�',")
+        else:
+            out_file.write("'\\u{{{:04X}}}',".format(c))
+    out_file.write("\n];\n\n")
+
+    # Write out encode table
+    out_file.write("static ENCODE_TABLE: [(char, u16); {}] = [".format(len(enc_table)))
+    for (i, pair) in enumerate(enc_table):
+        if i % 4 == 0:
+            out_file.write("\n    ")
+        out_file.write("('\\u{{{:X}}}', {}), ".format(pair[0], pair[1]))
+    out_file.write("\n];\n")
+
+
+def generate_iso_2022_kr_test_data(in_path, dec_in_path, dec_out_path, enc_in_path, enc_out_path):
+    """ Generates ISO-2022-KR test data, with every character on its own
+        line.  Since lines always end in ASCII mode, the same data is used
+        for both decoding and encoding.
+    """
+    table = load_table_file(in_path)
+    for (in_path, out_path) in [(dec_in_path, dec_out_path), (enc_out_path, enc_in_path)]:
+        kr_file = open(in_path, "wb")
+        utf8_file = open(out_path, "wb")
+        kr_file.write(b"\x1B$)C")
+        for ascii_byte in range(1, 128):
+            if ascii_byte in [0x0E, 0x0F, 0x1B]:
+                continue
+            # ISO-2022-KR
+            kr_file.write(bytes([ascii_byte]))
+            kr_file.write(bytes("\n", 'utf-8'))
+            # Utf8
+            utf8_file.write(bytes([ascii_byte]))
+            utf8_file.write(bytes("\n", 'utf-8'))
+        for (index, codepoint) in table:
+            # ISO-2022-KR
+            kr_file.write(b"\x0E" + index_to_gl_bytes(index) + b"\x0F")
+            kr_file.write(bytes("\n", 'utf-8'))
+            # Utf8
+            utf8_file.write(chr(codepoint).encode('utf-8'))
+            utf8_file.write(bytes("\n", 'utf-8'))
+
+
+# The Johab bit field values of the initial consonants, vowels, and final
+# consonants, in Unicode order.
+JOHAB_INITIALS = list(range(2, 21))
+JOHAB_MEDIALS = [3, 4, 5, 6, 7, 10, 11, 12, 13, 14, 15, 18, 19, 20, 21, 22, 23, 26, 27, 28, 29]
+JOHAB_FINALS = [1] + list(range(2, 18)) + list(range(19, 30))
+
+# The Johab codes of the Hangul compatibility jamo, U+3131 through U+3163.
+# Consonants use their initial form, except for clusters that can only be
+# finals.
+JOHAB_JAMO = [
+    0x8841, 0x8C41, 0x8444, 0x9041, 0x8446, 0x8447, 0x9441, 0x9841,
+    0x9C41, 0x844A, 0x844B, 0x844C, 0x844D, 0x844E, 0x844F, 0x8450,
+    0xA041, 0xA441, 0xA841, 0x8454, 0xAC41, 0xB041, 0xB441, 0xB841,
+    0xBC41, 0xC041, 0xC441, 0xC841, 0xCC41, 0xD041,
+] + [0x8401 | (m << 5) for m in JOHAB_MEDIALS]
+
+
+def johab_hangul_table():
+    """ The Hangul half of Johab, as `(code, codepoint)` pairs.
+    """
+    table = []
+    for (i, initial) in enumerate(JOHAB_INITIALS):
+        for (m, medial) in enumerate(JOHAB_MEDIALS):
+            for (f, final) in enumerate(JOHAB_FINALS):
+                code = 0x8000 | (initial << 10) | (medial << 5) | final
+                table += [(code, 0xAC00 + (i * 21 + m) * 28 + f)]
+    for (n, code) in enumerate(JOHAB_JAMO):
+        table += [(code, 0x3131 + n)]
+    table.sort()
+    return table
+
+
+def index_to_johab_code(index):
+    """ Map a KS X 1001 symbol or Hanja pointer to its Johab code, or None if
+        it isn't in one of those rows.
+    """
+    row = index // 94 + 0x21
+    cell = index % 94 + 0x21
+    if 0x21 <= row <= 0x2C:
+        lead = 0xD9 + (row - 0x21) // 2
+        is_first = (row - 0x21) % 2 == 0
+    elif 0x4A <= row <= 0x7D:
+        lead = 0xE0 + (row - 0x4A) // 2
+        is_first = (row - 0x4A) % 2 == 0
+    else:
+        return None
+    if is_first:
+        trail = cell + (0x10 if cell <= 0x6E else 0x22)
+    else:
+        trail = cell + 0x80
+    return (lead << 8) | trail
+
+
+def generate_johab_test_data(in_path, dec_in_path, dec_out_path, enc_in_path, enc_out_path):
+    """ Generates Johab test data.  The compatibility jamo in KS X 1001 row 4
+        are encoded in the Hangul half of Johab instead.
+    """
+    table = johab_hangul_table()
+    for (index, codepoint) in load_table_file(in_path):
+        code = index_to_johab_code(index)
+        if code is not None and not (0x3131 <= codepoint <= 0x3163):
+            table += [(code, codepoint)]
+    table.sort()
+
+    for (in_path, out_path) in [(dec_in_path, dec_out_path), (enc_out_path, enc_in_path)]:
+        johab_file = open(in_path, "wb")
+        utf8_file = open(out_path, "wb")
+        for ascii_byte in range(1, 128):
+            # Johab
+            johab_file.write(bytes([ascii_byte]))
+            johab_file.write(bytes("\n", 'utf-8'))
+            # Utf8
+            utf8_file.write(bytes([ascii_byte]))
+            utf8_file.write(bytes("\n", 'utf-8'))
+        for (code, codepoint) in table:
+            # Johab
+            johab_file.write(bytes([code >> 8, code & 0xFF]))
+            johab_file.write(bytes("\n", 'utf-8'))
+            # Utf8
+            utf8_file.write(chr(codepoint).encode('utf-8'))
+            utf8_file.write(bytes("\n", 'utf-8'))
+
+
+if __name__ == "__main__":
+    table_root = "../../src/generated/ksx1001"
+    test_root = "../../tests/test_data/iso2022"
+
+    # Generate the table files.
+    generate_ksx1001_tables(
+        "ksx1001.txt",
+        table_root + "/ksx1001_tables.rs.inc",
+    )
+
+    # Generate the test data files.
+    generate_iso_2022_kr_test_data(
+        "ksx1001.txt",
+        test_root + "/iso_2022_kr_test_decode_in.txt",
+        test_root + "/iso_2022_kr_test_decode_out.txt",
+        test_root + "/iso_2022_kr_test_encode_in.txt",
+        test_root + "/iso_2022_kr_test_encode_out.txt",
+    )
+    generate_johab_test_data(
+        "ksx1001.txt",
+        "../../tests/test_data/ksx1001/johab_test_decode_in.txt",
+        "../../tests/test_data/ksx1001/johab_test_decode_out.txt",
+        "../../tests/test_data/ksx1001/johab_test_encode_in.txt",
+        "../../tests/test_data/ksx1001/johab_test_encode_out.txt",
+    )
@@ -0,0 +1,177 @@
+#!/usr/bin/env python3
+
+# For each encoding: the lead and trail byte ranges of its double-byte codes,
+# as in its `Dbcs`, and its single bytes that aren't ascii, which are
+# implemented in code.
+ENCODINGS = {
+    "mac_japanese": (
+        [(0x81, 0x9F), (0xE0, 0xED)],
+        [(0x40, 0x7E), (0x80, 0xFC)],
+        dict([(0x5C, 0xA5), (0x80, 0x5C), (0xA0, 0xA0), (0xFD, 0xA9), (0xFE, 0x2122)]
+             + [(b, b - 0xA1 + 0xFF61) for b in range(0xA1, 0xE0)]),
+    ),
+    "mac_korean": (
+        [(0xA1, 0xFD)],
+        [(0x41, 0x7D), (0x81, 0xFE)],
+        {0x80: 0xA0, 0x81: 0x20A9, 0x83: 0xA9},
+    ),
+    "mac_chinese_simp": (
+        [(0xA1, 0xF7)],
+        [(0xA1, 0xFE)],
+        {0x81: 0xF880, 0x82: 0xF881, 0xA0: 0xA0, 0xFD: 0xA9, 0xFE: 0x2122, 0xFF: 0x2026},
+    ),
+    "mac_chinese_trad": (
+        [(0xA1, 0xF9)],
+        [(0x40, 0x7E), (0xA1, 0xFE)],
+        {0x81: 0xF880, 0x82: 0xF881, 0xA0: 0xA0, 0xFD: 0xA9, 0xFE: 0x2122, 0xFF: 0x2026},
+    ),
+}
+
+
+# Prefixes for the table names, for tables that are included alongside other
+# tables.  Mac OS Japanese is a profile of the `shiftjis` module.
+TABLE_PREFIXES = {"mac_japanese": "APPLE_"}
+
+
+def range_index(ranges, byte):
+    """ Returns the index of `byte` within `ranges`, as `dbcs::Dbcs` does.
+    """
+    base = 0
+    for (first, last) in ranges:
+        if first <= byte <= last:
+            return base + byte - first
+        base += last - first + 1
+    raise Exception("Byte out of range: {:02X}".format(byte))
+
+
+def code_to_index(code, lead_ranges, trail_ranges):
+    """ Map a double-byte code to a pointer, in the same way as
+        `dbcs::Dbcs`.
+    """
+    trail_count = sum([last - first + 1 for (first, last) in trail_ranges])
+    return range_index(lead_ranges, code >> 8) * trail_count + range_index(trail_ranges, code & 0xFF)
+
+
+def code_bytes(code):
+    return bytes([code]) if code <= 0xFF else bytes([code >> 8, code & 0xFF])
+
+
+def load_table_file(path, singles):
+    """ Loads the table file, returning a list of `(code, string)` pairs,
+        sorted by code.  Single-byte codes that map to a single code point
+        are checked against `singles` and ascii.
+    """
+    in_file = open(path)
+    table = []
+    for line in in_file:
+        parts = line.split()
+        if len(parts) >= 2 and not parts[0].startswith("#"):
+            code = int(parts[0], 16)
+            string = "".join([chr(int(x, 16)) for x in parts[1].split("+")])
+            if code <= 0xFF and len(string) == 1:
+                expected = singles.get(code, code if code <= 0x7F else None)
+                if ord(string) != expected:
+                    raise Exception("Unexpected single-byte mapping: {:02X}".format(code))
+            table += [(code, string)]
+    table.sort()
+
+    # The mappings are one-to-one.
+    if len(set([s for (_, s) in table])) != len(table):
+        raise Exception("Duplicate mapping in {}".format(path))
+    return table
+
+
+def generate_mac_cjk_tables(name, in_path, out_path):
+    (lead_ranges, trail_ranges, singles) = ENCODINGS[name]
+    prefix = TABLE_PREFIXES.get(name, "")
+    table = load_table_file(in_path, singles)
+    double = [(code, s) for (code, s) in table if code > 0xFF and len(s) == 1]
+    sequences = sorted([(s, code) for (code, s) in table if len(s) > 1])
+
+    # Create the decode table
+    dec_table = []
+    for (code, s) in double:
+        index = code_to_index(code, lead_ranges, trail_ranges)
+        while len(dec_table) < index:
+            dec_table += [None]
+        dec_table += [ord(s)]
+
+    # Create the encode table
+    enc_table = sorted([(ord(s), code) for (code, s) in double])
+
+    # Write file.
+    out_file = open(out_path, mode='w')
+    out_file.write(
+"""// This file is auto-generated.  Please see `encoding_tables/mac_cjk/`
+// from the root directory for the files that generate this.
+
+"""
+    )
+
+    # Write out decode table
+    out_file.write("static {}DECODE_TABLE: [char; {}] = [".format(prefix, len(dec_table)))
+    for (i, c) in enumerate(dec_table):
+        if i % 8 == 0:
+            out_file.write("\n    ")
+        if c is None:
+            out_file.write("'�',")
+        else:
+            out_file.write("'\\u{{{:04X}}}',".format(c))
+    out_file.write("\n];\n\n")
+
+    # Write out encode table
+    out_file.write("static {}ENCODE_TABLE: [(char, [u8; 2]); {}] = [".format(prefix, len(enc_table)))
+    for (i, pair) in enumerate(enc_table):
+        if i % 3 == 0:
+            out_file.write("\n    ")
+        out_file.write("('\\u{{{:X}}}', [0x{:X}, 0x{:X}]), ".format(pair[0], pair[1] >> 8, pair[1] & 0xFF))
+    out_file.write("\n];\n\n")
+
+    # Write out sequences
+    out_file.write("static {}SEQUENCES: [(&str, &[u8]); {}] = [".format(prefix, len(sequences)))
+    for (s, code) in sequences:
+        out_file.write("\n    (\"{}\", &[{}]),".format(
+            "".join(["\\u{{{:X}}}".format(ord(c)) for c in s]),
+            ", ".join(["0x{:X}".format(b) for b in code_bytes(code)]),
+        ))
+    out_file.write("\n];\n")
+
+
+def generate_mac_cjk_test_data(name, in_path, dec_in_path, dec_out_path, enc_in_path, enc_out_path):
+    table = load_table_file(in_path, ENCODINGS[name][2])
+
+    # The mappings are one-to-one, so the encode data is the decode data
+    # swapped.
+    for (mac_path, utf8_path) in [(dec_in_path, dec_out_path), (enc_out_path, enc_in_path)]:
+        mac_file = open(mac_path, "wb")
+        utf8_file = open(utf8_path, "wb")
+        for (code, string) in table:
+            # Mac OS
+            mac_file.write(code_bytes(code))
+            mac_file.write(bytes("\n", 'utf-8'))
+            # Utf8
+            utf8_file.write(string.encode('utf-8'))
+            utf8_file.write(bytes("\n", 'utf-8'))
+
+
+if __name__ == "__main__":
+    table_root = "../../src/generated/mac_cjk"
+    test_root = "../../tests/test_data/mac_cjk"
+
+    for name in sorted(ENCODINGS.keys()):
+        # Generate the table files.
+        generate_mac_cjk_tables(
+            name,
+            name + ".txt",
+            table_root + "/" + name + "_tables.rs.inc",
+        )
+
+        # Generate the test data files.
+        generate_mac_cjk_test_data(
+            name,
+            name + ".txt",
+            test_root + "/" + name + "_test_decode_in.txt",
+            test_root + "/" + name + "_test_decode_out.txt",
+            test_root + "/" + name + "_test_encode_in.txt",
+            test_root + "/" + name + "_test_encode_out.txt",
+        )
@@ -0,0 +1,152 @@
+#!/usr/bin/env python3
+
+import unicodedata
+
+# The table files of the single-byte sets, and the name of the table
+# generated from each.
+TABLES = [
+    ("ansel.txt", "ANSEL"),
+    ("greek_symbols.txt", "GREEK_SYMBOLS"),
+    ("subscripts.txt", "SUBSCRIPTS"),
+    ("superscripts.txt", "SUPERSCRIPTS"),
+    ("basic_hebrew.txt", "BASIC_HEBREW"),
+    ("basic_cyrillic.txt", "BASIC_CYRILLIC"),
+    ("extended_cyrillic.txt", "EXTENDED_CYRILLIC"),
+    ("basic_arabic.txt", "BASIC_ARABIC"),
+    ("basic_greek.txt", "BASIC_GREEK"),
+]
+
+
+def load_table_file(path):
+    """ Loads a table file, returning a list of `(code, char)` pairs.
+    """
+    in_file = open(path)
+    table = []
+    for line in in_file:
+        parts = line.split()
+        if len(parts) >= 2 and not parts[0].startswith("#"):
+            code = int(parts[0], 16)
+            if (code & 0x7F) < 0x21 or (code & 0x7F) > 0x7E:
+                raise Exception("Not a graphic code: {}".format(parts[0]))
+            table += [(code, chr(int(parts[1], 16)))]
+    table.sort()
+    return table
+
+
+def load_eacc_table_file(path):
+    """ Loads the EACC table file, returning a list of `(code, char)` pairs,
+        sorted by code.
+    """
+    in_file = open(path)
+    table = []
+    for line in in_file:
+        parts = line.split()
+        if len(parts) >= 2 and not parts[0].startswith("#"):
+            code = int(parts[0], 16)
+            if any((b < 0x21 or b > 0x7E) for b in code.to_bytes(3, "big")):
+                raise Exception("Not a graphic code: {}".format(parts[0]))
+            table += [(code, chr(int(parts[1], 16)))]
+    table.sort()
+    for n in range(1, len(table)):
+        if table[n - 1][0] == table[n][0]:
+            raise Exception("Duplicate code: {:06X}".format(table[n][0]))
+    return table
+
+
+def decompose(c, encodable):
+    """ Returns the bytes for a precomposed character as an encodable base
+        character and combining marks, with the marks first, or None if
+        there aren't any.
+    """
+    nfd = unicodedata.normalize("NFD", c)
+    if nfd == c:
+        return None
+
+    # Use the longest base that's encodable as is, e.g. "ơ" rather than
+    # "o" and a horn for "ớ".
+    for i in range(len(nfd), 0, -1):
+        base = unicodedata.normalize("NFC", nfd[:i])
+        marks = nfd[i:]
+        if (len(base) == 1 and base != c and base in encodable
+                and not unicodedata.combining(base)
+                and all(m in encodable and unicodedata.combining(m) for m in marks)):
+            return [encodable[m] for m in marks] + [encodable[base]]
+    return None
+
+
+def generate_marc8_tables(out_path):
+    out_file = open(out_path, mode='w')
+    out_file.write(
+"""// This file is auto-generated.  Please see `encoding_tables/marc8/`
+// from the root directory for the files that generate this.
+""")
+
+    combining = set()
+    for (in_path, name) in TABLES:
+        table = load_table_file(in_path)
+
+        # Create the decode table, indexed by the 7-bit code.
+        dec_table = [None] * 94
+        for (code, c) in table:
+            dec_table[(code & 0x7F) - 0x21] = c
+            if unicodedata.combining(c):
+                combining.add(c)
+
+        # Write out decode table
+        out_file.write("\nstatic {}_DECODE_TABLE: [char; 94] = [".format(name))
+        for (i, c) in enumerate(dec_table):
+            if i % 8 == 0:
+                out_file.write("\n    ")
+            if c is None:
+                out_file.write("'�', ")
+            else:
+                out_file.write("'\\u{{{:04X}}}', ".format(ord(c)))
+        out_file.write("\n];\n")
+
+    # Write out the EACC decode table, sorted by the 7-bit code.
+    eacc = load_eacc_table_file("eacc.txt")
+    out_file.write("\nstatic EACC_DECODE_TABLE: [(u32, char); {}] = [".format(len(eacc)))
+    for (i, (code, c)) in enumerate(eacc):
+        if i % 4 == 0:
+            out_file.write("\n    ")
+        out_file.write("(0x{:06X}, '\\u{{{:04X}}}'), ".format(code, ord(c)))
+    out_file.write("\n];\n")
+
+    # The encoder only uses Ascii and ANSEL, the latter in G1.
+    ansel = load_table_file("ansel.txt")
+    enc_table = sorted((c, code) for (code, c) in ansel)
+    encodable = {chr(i): i for i in range(0x20, 0x7F)}
+    encodable.update(dict(enc_table))
+    decomp_table = []
+    for i in range(0x80, 0x10000):
+        bytes = decompose(chr(i), encodable)
+        if bytes is not None:
+            decomp_table += [(chr(i), bytes)]
+
+    # Write out combining table
+    combining = sorted(combining)
+    out_file.write("\nstatic COMBINING_TABLE: [char; {}] = [".format(len(combining)))
+    for (i, c) in enumerate(combining):
+        if i % 8 == 0:
+            out_file.write("\n    ")
+        out_file.write("'\\u{{{:04X}}}', ".format(ord(c)))
+    out_file.write("\n];\n")
+
+    # Write out encode table
+    out_file.write("\nstatic ANSEL_ENCODE_TABLE: [(char, u8); {}] = [".format(len(enc_table)))
+    for (i, (c, code)) in enumerate(enc_table):
+        if i % 4 == 0:
+            out_file.write("\n    ")
+        out_file.write("('\\u{{{:04X}}}', 0x{:02X}), ".format(ord(c), code))
+    out_file.write("\n];\n")
+
+    # Write out decompose table
+    out_file.write("\nstatic DECOMPOSE_TABLE: [(char, &[u8]); {}] = [".format(len(decomp_table)))
+    for (c, bytes) in decomp_table:
+        out_file.write("\n    ('\\u{{{:04X}}}', &[{}]),".format(
+            ord(c), ", ".join("0x{:02X}".format(b) for b in bytes)))
+    out_file.write("\n];\n")
+
+
+if __name__ == "__main__":
+    generate_marc8_tables("../../src/generated/marc8/marc8_tables.rs.inc")
@@ -0,0 +1,153 @@
+#!/usr/bin/env python3
+
+
+def index_to_bytes(i):
+    """ Map a JIS X 0208 pointer back to the original ShiftJIS bytes.
+    """
+    lead = i // 188
+    lead_offset = 0x81 if lead < 0x1F else 0xC1
+    trail = i % 188
+    trail_offset = 0x40 if trail < 0x3F else 0x41
+    return (lead + lead_offset, trail + trail_offset)
+
+
+def single_byte_table():
+    """ The JIS X 0201 half of ShiftJIS, as `(byte, codepoint)` pairs.
+    """
+    table = []
+    for byte in range(0, 0x80):
+        if byte == 0x5C:
+            table += [(byte, 0xA5)]
+        elif byte == 0x7E:
+            table += [(byte, 0x203E)]
+        else:
+            table += [(byte, byte)]
+    for byte in range(0xA1, 0xE0):
+        table += [(byte, byte - 0xA1 + 0xFF61)]
+    return table
+
+
+def load_table_file(path):
+    in_file = open(path)
+    table = []
+    for line in in_file:
+        parts = line.split()
+        if len(parts) >= 2 and not parts[0].startswith("#"):
+            index = int(parts[0])
+            unicode = int(parts[1], 16)
+            table += [(index, unicode)]
+    table.sort()
+    return table
+
+
+def generate_shiftjis_jis_tables(in_path, out_path):
+    table = load_table_file(in_path)
+
+    # Create the decode table
+    dec_table = []
+    i = 0
+    for (index, codepoint) in table:
+        while i < index:
+            dec_table += [None]
+            i += 1
+        dec_table += [codepoint]
+        i += 1
+
+    # Create the encode table
+    enc_table = [(codepoint, index_to_bytes(index)) for (index, codepoint) in table]
+    enc_table.sort()
+    for n in range(1, len(enc_table)):
+        if enc_table[n - 1][0] == enc_table[n][0]:
+            raise Exception("Duplicate code point: {:04X}".format(enc_table[n][0]))
+
+    # Write file.
+    out_file = open(out_path, mode='w')
+    out_file.write(
+"""// This file is auto-generated.  Please see `encoding_tables/shiftjis/`
+// from the root directory for the files that generate this.
+
+"""
+    )
+
+    # Write out decode table
+    out_file.write("static JIS_DECODE_TABLE: [char; {}] = [".format(len(dec_table)))
+    for (i, c) in enumerate(dec_table):
+        if i % 8 == 0:
+            out_file.write("\n    ")
+        if c is None:
+            out_file.write("'�',")
+        else:
+            out_file.write("'\\u{{{:04X}}}',".format(c))
+    out_file.write("\n];\n\n")
+
+    # Write out encode table
+    out_file.write("static JIS_ENCODE_TABLE: [(char, [u8; 2]); {}] = [".format(len(enc_table)))
+    for (i, pair) in enumerate(enc_table):
+        if i % 3 == 0:
+            out_file.write("\n    ")
+        out_file.write("('\\u{{{:X}}}', [0x{:X}, 0x{:X}]), ".format(pair[0], pair[1][0], pair[1][1]))
+    out_file.write("\n];\n")
+
+
+def generate_shiftjis_jis_test_data(in_path, dec_in_path, dec_out_path, enc_in_path, enc_out_path):
+    table = load_table_file(in_path)
+
+    # Generate decoding test files.
+    decode_in_file = open(dec_in_path, "wb")
+    decode_out_file = open(dec_out_path, "wb")
+    for (byte, codepoint) in single_byte_table():
+        if byte == 0:
+            continue
+        # In
+        decode_in_file.write(bytes([byte]))
+        decode_in_file.write(bytes("\n", 'utf-8'))
+        # Out
+        decode_out_file.write(chr(codepoint).encode('utf-8'))
+        decode_out_file.write(bytes("\n", 'utf-8'))
+    for (index, codepoint) in table:
+        # In
+        decode_in_file.write(bytes(index_to_bytes(index)))
+        decode_in_file.write(bytes("\n", 'utf-8'))
+        # Out
+        decode_out_file.write(chr(codepoint).encode('utf-8'))
+        decode_out_file.write(bytes("\n", 'utf-8'))
+
+    # Generate encoding test files.
+    encode_in_file = open(enc_in_path, "wb")
+    encode_out_file = open(enc_out_path, "wb")
+    for (byte, codepoint) in single_byte_table():
+        if byte == 0:
+            continue
+        # In
+        encode_in_file.write(chr(codepoint).encode('utf-8'))
+        encode_in_file.write(bytes("\n", 'utf-8'))
+        # Out
+        encode_out_file.write(bytes([byte]))
+        encode_out_file.write(bytes("\n", 'utf-8'))
+    for (index, codepoint) in table:
+        # In
+        encode_in_file.write(chr(codepoint).encode('utf-8'))
+        encode_in_file.write(bytes("\n", 'utf-8'))
+        # Out
+        encode_out_file.write(bytes(index_to_bytes(index)))
+        encode_out_file.write(bytes("\n", 'utf-8'))
+
+
+if __name__ == "__main__":
+    table_root = "../../src/generated/shiftjis"
+    test_root = "../../tests/test_data/shiftjis"
+
+    # Generate the table files.
+    generate_shiftjis_jis_tables(
+        "jis0208-1997.txt",
+        table_root + "/shiftjis_jis_tables.rs.inc",
+    )
+
+    # Generate the test data files.
+    generate_shiftjis_jis_test_data(
+        "jis0208-1997.txt",
+        test_root + "/shiftjis_jis_test_decode_in.txt",
+        test_root + "/shiftjis_jis_test_decode_out.txt",
+        test_root + "/shiftjis_jis_test_encode_in.txt",
+        test_root + "/shiftjis_jis_test_encode_out.txt",
+    )
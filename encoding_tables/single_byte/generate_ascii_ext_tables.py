@@ -1,6 +1,18 @@
 #!/usr/bin/env python3
 
-def generate_ascii_ext_encoding(in_path, out_path):
+import unicodedata
+
+# The combining tone marks used in Vietnamese.
+TONE_MARKS = [0x0300, 0x0301, 0x0303, 0x0309, 0x0323]
+
+
+def generate_ascii_ext_encoding(in_path, out_path, compose=False):
+    """ Generates the module contents for a single-byte encoding that is an
+        extension of ascii.  If `compose` is true, the encoding is one in
+        which Vietnamese is written with combining tone marks, and the module
+        gets an encoder that decomposes precomposed letters as well as a
+        decoder that composes them.
+    """
     in_file = open(in_path)
     out_file = open(out_path, mode='w')
 
@@ -39,15 +51,21 @@ def generate_ascii_ext_encoding(in_path, out_path):
     while len(dec_table) < 128:
         dec_table += [None]
 
-    # Create the encode table
+    # Create the encode table, using the first byte for code points that are
+    # mapped more than once.  Code points that are also mapped by an ascii
+    # byte always encode to that.
     enc_table = []
-    seen = set()  # Used to eliminate duplicate code points.
+    seen = set(range(0, 128))  # Used to eliminate duplicate code points.
     for item in table:
         if not item[1] in seen:
-            seen &= {item[1]}
+            seen |= {item[1]}
             enc_table += [(item[1], item[0])]
     enc_table.sort()
 
+    if compose:
+        write_compose_encoding(out_file, dec_table, enc_table)
+        return
+
     # Write out shared code.
     out_file.write(
 """// This file is auto-generated.  Please see `encoding_tables/single_byte/`
@@ -71,6 +89,97 @@ pub fn encode_from_str<'a>(
 """
     )
 
+    write_tables(out_file, dec_table, enc_table)
+
+
+def write_compose_encoding(out_file, dec_table, enc_table):
+    """ Writes the module contents for an encoding with combining tone marks.
+
+        The compose table has every pair of a base byte and a tone mark byte
+        that composes to a single code point.  The decompose table has just
+        the code points that can't be encoded directly, which are encoded as
+        their pair.
+    """
+    enc_map = dict(enc_table)
+    marks = [(enc_map[mark], mark) for mark in TONE_MARKS if mark in enc_map]
+    compose_table = []
+    decompose_table = []
+    for (i, base) in enumerate(dec_table):
+        if base is None or base in TONE_MARKS:
+            continue
+        for (mark_byte, mark) in marks:
+            composed = unicodedata.normalize("NFC", chr(base) + chr(mark))
+            if len(composed) == 1:
+                compose_table += [(i + 128, mark_byte, ord(composed))]
+                if ord(composed) not in enc_map:
+                    decompose_table += [(ord(composed), i + 128, mark_byte)]
+    # The ascii letters can be bases too.
+    for base in range(0x20, 0x7F):
+        for (mark_byte, mark) in marks:
+            composed = unicodedata.normalize("NFC", chr(base) + chr(mark))
+            if len(composed) == 1:
+                compose_table += [(base, mark_byte, ord(composed))]
+                if ord(composed) not in enc_map:
+                    decompose_table += [(ord(composed), base, mark_byte)]
+    compose_table.sort()
+    decompose_table.sort()
+
+    out_file.write(
+"""// This file is auto-generated.  Please see `encoding_tables/single_byte/`
+// from the root directory for the files that generate this.
+
+use {DecodeResult, EncodeResult};
+
+pub fn decode_to_str<'a>(
+    input: &[u8],
+    out_buffer: &'a mut [u8],
+) -> DecodeResult<'a> {
+    super::ascii_ext_decode_to_str(&DECODE_TABLE, input, out_buffer)
+}
+
+/// Like `decode_to_str()`, but composes a letter followed by a tone mark
+/// into a single precomposed `char` where there is one.
+///
+/// Since a letter at the end of the input may be followed by a tone mark,
+/// this takes an `is_end` parameter.
+pub fn decode_to_str_composed<'a>(
+    input: &[u8],
+    out_buffer: &'a mut [u8],
+    is_end: bool,
+) -> DecodeResult<'a> {
+    super::ascii_ext_compose_decode_to_str(&DECODE_TABLE, &COMPOSE_TABLE, input, out_buffer, is_end)
+}
+
+/// Precomposed letters that have no byte of their own are encoded as the
+/// letter followed by the tone mark.
+pub fn encode_from_str<'a>(
+    input: &str,
+    out_buffer: &'a mut [u8],
+) -> EncodeResult<'a> {
+    super::ascii_ext_decompose_encode_from_str(&ENCODE_TABLE, &DECOMPOSE_TABLE, input, out_buffer)
+}\n
+"""
+    )
+    write_tables(out_file, dec_table, enc_table)
+
+    # Write out compose table
+    out_file.write("\nconst COMPOSE_TABLE: [(u8, u8, char); {}] = [".format(len(compose_table)))
+    for (i, (base, mark, composed)) in enumerate(compose_table):
+        if i % 3 == 0:
+            out_file.write("\n    ")
+        out_file.write("(0x{:02X}, 0x{:02X}, '\\u{{{:04X}}}'), ".format(base, mark, composed))
+    out_file.write("\n];\n")
+
+    # Write out decompose table
+    out_file.write("\nconst DECOMPOSE_TABLE: [(char, u8, u8); {}] = [".format(len(decompose_table)))
+    for (i, (composed, base, mark)) in enumerate(decompose_table):
+        if i % 3 == 0:
+            out_file.write("\n    ")
+        out_file.write("('\\u{{{:04X}}}', 0x{:02X}, 0x{:02X}), ".format(composed, base, mark))
+    out_file.write("\n];\n")
+
+
+def write_tables(out_file, dec_table, enc_table):
     # Write out decode table
     out_file.write("const DECODE_TABLE: [char; 128] = [")
     for (i, c) in enumerate(dec_table):
@@ -95,10 +204,74 @@ if __name__ == "__main__":
     root = "../../src/generated/single_byte"
 
     # Generate the table files.
+    generate_ascii_ext_encoding(
+        "ascii_ext/armscii-8.txt",
+        root + "/armscii-8_tables.rs.inc",
+    )
+    generate_ascii_ext_encoding(
+        "ascii_ext/georgian-ps.txt",
+        root + "/georgian-ps_tables.rs.inc",
+    )
+    generate_ascii_ext_encoding(
+        "ascii_ext/ibm-cp437.txt",
+        root + "/ibm-cp437_tables.rs.inc",
+    )
+    generate_ascii_ext_encoding(
+        "ascii_ext/ibm-cp737.txt",
+        root + "/ibm-cp737_tables.rs.inc",
+    )
+    generate_ascii_ext_encoding(
+        "ascii_ext/ibm-cp775.txt",
+        root + "/ibm-cp775_tables.rs.inc",
+    )
+    generate_ascii_ext_encoding(
+        "ascii_ext/ibm-cp850.txt",
+        root + "/ibm-cp850_tables.rs.inc",
+    )
+    generate_ascii_ext_encoding(
+        "ascii_ext/ibm-cp852.txt",
+        root + "/ibm-cp852_tables.rs.inc",
+    )
+    generate_ascii_ext_encoding(
+        "ascii_ext/ibm-cp855.txt",
+        root + "/ibm-cp855_tables.rs.inc",
+    )
+    generate_ascii_ext_encoding(
+        "ascii_ext/ibm-cp857.txt",
+        root + "/ibm-cp857_tables.rs.inc",
+    )
+    generate_ascii_ext_encoding(
+        "ascii_ext/ibm-cp860.txt",
+        root + "/ibm-cp860_tables.rs.inc",
+    )
+    generate_ascii_ext_encoding(
+        "ascii_ext/ibm-cp861.txt",
+        root + "/ibm-cp861_tables.rs.inc",
+    )
+    generate_ascii_ext_encoding(
+        "ascii_ext/ibm-cp862.txt",
+        root + "/ibm-cp862_tables.rs.inc",
+    )
+    generate_ascii_ext_encoding(
+        "ascii_ext/ibm-cp863.txt",
+        root + "/ibm-cp863_tables.rs.inc",
+    )
+    generate_ascii_ext_encoding(
+        "ascii_ext/ibm-cp865.txt",
+        root + "/ibm-cp865_tables.rs.inc",
+    )
     generate_ascii_ext_encoding(
         "ascii_ext/ibm-cp866.txt",
         root + "/ibm-cp866_tables.rs.inc",
     )
+    generate_ascii_ext_encoding(
+        "ascii_ext/ibm-cp869.txt",
+        root + "/ibm-cp869_tables.rs.inc",
+    )
+    generate_ascii_ext_encoding(
+        "ascii_ext/ibm-cp1125.txt",
+        root + "/ibm-cp1125_tables.rs.inc",
+    )
     generate_ascii_ext_encoding(
         "ascii_ext/iso-8859-1.txt",
         root + "/iso-8859-1_tables.rs.inc",
@@ -163,10 +336,22 @@ if __name__ == "__main__":
         "ascii_ext/koi8-r.txt",
         root + "/koi8-r_tables.rs.inc",
     )
+    generate_ascii_ext_encoding(
+        "ascii_ext/koi8-ru.txt",
+        root + "/koi8-ru_tables.rs.inc",
+    )
+    generate_ascii_ext_encoding(
+        "ascii_ext/koi8-t.txt",
+        root + "/koi8-t_tables.rs.inc",
+    )
     generate_ascii_ext_encoding(
         "ascii_ext/koi8-u.txt",
         root + "/koi8-u_tables.rs.inc",
     )
+    generate_ascii_ext_encoding(
+        "ascii_ext/kz-1048.txt",
+        root + "/kz-1048_tables.rs.inc",
+    )
     generate_ascii_ext_encoding(
         "ascii_ext/mac-roman.txt",
         root + "/mac-roman_tables.rs.inc",
@@ -175,6 +360,38 @@ if __name__ == "__main__":
         "ascii_ext/mac-cyrillic.txt",
         root + "/mac-cyrillic_tables.rs.inc",
     )
+    generate_ascii_ext_encoding(
+        "ascii_ext/mac-centeuro.txt",
+        root + "/mac-centeuro_tables.rs.inc",
+    )
+    generate_ascii_ext_encoding(
+        "ascii_ext/mac-croatian.txt",
+        root + "/mac-croatian_tables.rs.inc",
+    )
+    generate_ascii_ext_encoding(
+        "ascii_ext/mac-greek.txt",
+        root + "/mac-greek_tables.rs.inc",
+    )
+    generate_ascii_ext_encoding(
+        "ascii_ext/mac-iceland.txt",
+        root + "/mac-iceland_tables.rs.inc",
+    )
+    generate_ascii_ext_encoding(
+        "ascii_ext/mac-romanian.txt",
+        root + "/mac-romanian_tables.rs.inc",
+    )
+    generate_ascii_ext_encoding(
+        "ascii_ext/mac-turkish.txt",
+        root + "/mac-turkish_tables.rs.inc",
+    )
+    generate_ascii_ext_encoding(
+        "ascii_ext/mac-ukrainian.txt",
+        root + "/mac-ukrainian_tables.rs.inc",
+    )
+    generate_ascii_ext_encoding(
+        "ascii_ext/pt154.txt",
+        root + "/pt154_tables.rs.inc",
+    )
     generate_ascii_ext_encoding(
         "ascii_ext/windows-cp874.txt",
         root + "/windows-cp874_tables.rs.inc",
@@ -214,4 +431,25 @@ if __name__ == "__main__":
     generate_ascii_ext_encoding(
         "ascii_ext/windows-cp1258.txt",
         root + "/windows-cp1258_tables.rs.inc",
+        compose=True,
+    )
+    generate_ascii_ext_encoding(
+        "ascii_ext/tcvn3.txt",
+        root + "/tcvn3_tables.rs.inc",
+    )
+    generate_ascii_ext_encoding(
+        "ascii_ext/hp-roman8.txt",
+        root + "/hp-roman8_tables.rs.inc",
+    )
+    generate_ascii_ext_encoding(
+        "ascii_ext/dec-mcs.txt",
+        root + "/dec-mcs_tables.rs.inc",
+    )
+    generate_ascii_ext_encoding(
+        "ascii_ext/nextstep.txt",
+        root + "/nextstep_tables.rs.inc",
+    )
+    generate_ascii_ext_encoding(
+        "ascii_ext/atari-st.txt",
+        root + "/atari-st_tables.rs.inc",
     )
@@ -0,0 +1,350 @@
+#!/usr/bin/env python3
+
+# The EBCDIC NL and LF bytes, which can optionally be swapped.
+EBCDIC_NL = 0x15
+EBCDIC_LF = 0x25
+
+
+def load_table_file(path):
+    """ Loads a table file, returning a list of `(byte, codepoint)` pairs,
+        sorted by byte.  Bytes that map to a sequence of code points, written
+        like `0x05B8+0xF87F`, have a tuple of code points instead.
+    """
+    in_file = open(path)
+    table = []
+    for line in in_file:
+        parts = line.split()
+        if len(parts) >= 2 and not parts[0].startswith("#"):
+            byte = int(parts[0], 16)
+            if parts[1].startswith("#"):
+                continue
+            unicode = tuple(int(x, 16) for x in parts[1].split("+"))
+            if len(unicode) == 1:
+                unicode = unicode[0]
+            table += [(byte, unicode)]
+    table.sort()
+    return table
+
+
+def load_adobe_table_file(path):
+    """ Loads a table file in the format of Adobe's mapping files for font
+        encodings, with the code point first and then the byte, returning a
+        list of `(byte, codepoint)` pairs, sorted by byte.  A byte that's
+        listed more than once keeps the order of its lines.
+    """
+    in_file = open(path)
+    table = []
+    for line in in_file:
+        parts = line.split()
+        if len(parts) >= 2 and not parts[0].startswith("#"):
+            table += [(int(parts[1], 16), int(parts[0], 16))]
+    table.sort(key=lambda x: x[0])
+    return table
+
+
+def generate_full_encoding(in_path, out_path, swap_nl_lf=False, adobe=False):
+    """ Generates the module contents for a single-byte encoding that isn't
+        necessarily an ascii extension, with a decode table covering all 256
+        bytes.  If `swap_nl_lf` is true, the module also gets variants of the
+        encode and decode functions that swap EBCDIC NL and LF.  If `adobe` is
+        true, the table file is in the format of Adobe's mapping files.
+    """
+    if adobe:
+        table = load_adobe_table_file(in_path)
+    else:
+        table = load_table_file(in_path)
+    out_file = open(out_path, mode='w')
+
+    if swap_nl_lf:
+        nl_lf = [unicode for (byte, unicode) in table if byte in [EBCDIC_NL, EBCDIC_LF]]
+        if nl_lf != [0x85, 0x0A]:
+            raise Exception("Not EBCDIC NL and LF: file {}".format(in_path))
+
+    # Split off the sequences, longest first so that the encoder finds the
+    # longest match.
+    sequences = [(unicode, byte) for (byte, unicode) in table if type(unicode) is tuple]
+    sequences.sort(key=lambda x: (-len(x[0]), x[0]))
+    table = [(byte, unicode) for (byte, unicode) in table if type(unicode) is not tuple]
+    if sequences and swap_nl_lf:
+        raise Exception("Sequences with swapped NL and LF: file {}".format(in_path))
+
+    # Create the decode table, using the first code point for bytes that are
+    # mapped more than once.
+    dec_table = [None] * 256
+    for (byte, unicode) in table:
+        if dec_table[byte] is None:
+            dec_table[byte] = unicode
+
+    # Create the encode table, using the first byte for code points that are
+    # mapped more than once.
+    enc_table = {}
+    for (byte, unicode) in table:
+        if unicode not in enc_table:
+            enc_table[unicode] = byte
+    enc_table = sorted(enc_table.items())
+
+    if sequences:
+        write_sequence_encoding(out_file, dec_table, enc_table, sequences)
+        return
+
+    # Write out shared code.
+    out_file.write(
+"""// This file is auto-generated.  Please see `encoding_tables/single_byte/`
+// from the root directory for the files that generate this.
+
+use {DecodeResult, EncodeResult};
+
+pub fn decode_to_str<'a>(
+    input: &[u8],
+    out_buffer: &'a mut [u8],
+) -> DecodeResult<'a> {
+    super::full_decode_to_str(&DECODE_TABLE, false, input, out_buffer)
+}
+
+pub fn encode_from_str<'a>(
+    input: &str,
+    out_buffer: &'a mut [u8],
+) -> EncodeResult<'a> {
+    super::full_encode_from_str(&ENCODE_TABLE, false, input, out_buffer)
+}\n
+"""
+    )
+    if swap_nl_lf:
+        out_file.write(
+"""/// Like `decode_to_str()`, but with NL (0x15) decoding to U+000A LINE FEED
+/// and LF (0x25) to U+0085 NEXT LINE.
+pub fn decode_to_str_swap_nl_lf<'a>(
+    input: &[u8],
+    out_buffer: &'a mut [u8],
+) -> DecodeResult<'a> {
+    super::full_decode_to_str(&DECODE_TABLE, true, input, out_buffer)
+}
+
+/// Like `encode_from_str()`, but with U+000A LINE FEED encoding to NL (0x15)
+/// and U+0085 NEXT LINE to LF (0x25).
+pub fn encode_from_str_swap_nl_lf<'a>(
+    input: &str,
+    out_buffer: &'a mut [u8],
+) -> EncodeResult<'a> {
+    super::full_encode_from_str(&ENCODE_TABLE, true, input, out_buffer)
+}\n
+"""
+        )
+
+    write_tables(out_file, "", dec_table, enc_table)
+
+
+def write_sequence_encoding(out_file, dec_table, enc_table, sequences):
+    """ Writes the module contents for an encoding in which some bytes map to
+        a sequence of code points.  Its encoder needs to know whether it's at
+        the end of the input, since a code point at the end of the input may
+        be the start of a sequence.
+    """
+    out_file.write(
+"""// This file is auto-generated.  Please see `encoding_tables/single_byte/`
+// from the root directory for the files that generate this.
+
+use {DecodeResult, EncodeResult};
+
+pub fn decode_to_str<'a>(
+    input: &[u8],
+    out_buffer: &'a mut [u8],
+) -> DecodeResult<'a> {
+    super::seq_decode_to_str(&DECODE_TABLE, &SEQUENCES, input, out_buffer)
+}
+
+pub fn encode_from_str<'a>(
+    input: &str,
+    out_buffer: &'a mut [u8],
+    is_end: bool,
+) -> EncodeResult<'a> {
+    super::seq_encode_from_str(&ENCODE_TABLE, &SEQUENCES, input, out_buffer, is_end)
+}\n
+"""
+    )
+    write_tables(out_file, "", dec_table, enc_table)
+
+    # Write out sequence table
+    out_file.write("\nconst SEQUENCES: [(&str, u8); {}] = [".format(len(sequences)))
+    for (seq, byte) in sequences:
+        out_file.write("\n    (\"{}\", 0x{:02X}),".format("".join("\\u{{{:04X}}}".format(c) for c in seq), byte))
+    out_file.write("\n];\n")
+
+
+def generate_graphics_tables(in_path, graphics_path, out_path):
+    """ Generates the tables for an alternate mapping of an encoding, in
+        which some bytes decode to display glyphs instead.  The bytes still
+        encode from their usual code points as well, so only the tables are
+        written, with a `GRAPHICS_` prefix, and the module wraps them by hand.
+    """
+    table = load_table_file(in_path)
+    graphics = load_table_file(graphics_path)
+    out_file = open(out_path, mode='w')
+
+    # Create the decode table
+    dec_table = [None] * 256
+    for (byte, unicode) in table + graphics:
+        dec_table[byte] = unicode
+
+    # Create the encode table, with both the glyphs and the usual code
+    # points.
+    enc_table = {}
+    for (byte, unicode) in graphics + table:
+        if unicode in enc_table:
+            raise Exception("Duplicate code point: file {}, codepoint {}".format(graphics_path, unicode))
+        enc_table[unicode] = byte
+    enc_table = sorted(enc_table.items())
+
+    out_file.write(
+"""// This file is auto-generated.  Please see `encoding_tables/single_byte/`
+// from the root directory for the files that generate this.
+
+"""
+    )
+    write_tables(out_file, "GRAPHICS_", dec_table, enc_table)
+
+
+def generate_petscii_tables(unshifted_path, shifted_path, out_path):
+    """ Generates the tables for PETSCII's unshifted and shifted character
+        sets, with `UNSHIFTED_` and `SHIFTED_` prefixes, and the module wraps
+        them by hand.  The table files leave out 0x60-0x7F, 0xE0-0xFE, and
+        0xFF, which repeat 0xC0-0xDF, 0xA0-0xBE, and 0xDE, so those bytes are
+        only added to the decode tables.
+    """
+    out_file = open(out_path, mode='w')
+    out_file.write(
+"""// This file is auto-generated.  Please see `encoding_tables/single_byte/`
+// from the root directory for the files that generate this.
+"""
+    )
+    for (in_path, prefix) in [(unshifted_path, "UNSHIFTED_"), (shifted_path, "SHIFTED_")]:
+        table = load_table_file(in_path)
+
+        # Create the decode table, with the repeated bytes.
+        dec_table = [None] * 256
+        for (byte, unicode) in table:
+            dec_table[byte] = unicode
+        for byte in range(0x60, 0x80):
+            dec_table[byte] = dec_table[byte + 0x60]
+        for byte in range(0xE0, 0xFF):
+            dec_table[byte] = dec_table[byte - 0x40]
+        dec_table[0xFF] = dec_table[0xDE]
+
+        # Create the encode table
+        enc_table = {}
+        for (byte, unicode) in table:
+            if unicode in enc_table:
+                raise Exception("Duplicate code point: file {}, codepoint {}".format(in_path, unicode))
+            enc_table[unicode] = byte
+        enc_table = sorted(enc_table.items())
+
+        out_file.write("\n")
+        write_tables(out_file, prefix, dec_table, enc_table)
+
+
+def write_tables(out_file, prefix, dec_table, enc_table):
+    # Write out decode table
+    out_file.write("const {}DECODE_TABLE: [char; 256] = [".format(prefix))
+    for (i, c) in enumerate(dec_table):
+        if i % 8 == 0:
+            out_file.write("\n    ")
+        if c is None:
+            out_file.write("'�',")
+        else:
+            out_file.write("'\\u{{{:04X}}}',".format(c))
+    out_file.write("\n];\n\n")
+
+    # Write out encode table
+    out_file.write("const {}ENCODE_TABLE: [(char, u8); {}] = [".format(prefix, len(enc_table)))
+    for (i, pair) in enumerate(enc_table):
+        if i % 4 == 0:
+            out_file.write("\n    ")
+        out_file.write("('\\u{{{:04X}}}', 0x{:02X}), ".format(pair[0], pair[1]))
+    out_file.write("\n];\n")
+
+
+if __name__ == "__main__":
+    root = "../../src/generated/single_byte"
+
+    # EBCDIC code pages.
+    for name in [
+        "ibm-cp037",
+        "ibm-cp273",
+        "ibm-cp277",
+        "ibm-cp278",
+        "ibm-cp280",
+        "ibm-cp284",
+        "ibm-cp285",
+        "ibm-cp297",
+        "ibm-cp500",
+        "ibm-cp871",
+        "ibm-cp1047",
+        "ibm-cp1140",
+        "ibm-cp1141",
+        "ibm-cp1142",
+        "ibm-cp1143",
+        "ibm-cp1144",
+        "ibm-cp1145",
+        "ibm-cp1146",
+        "ibm-cp1147",
+        "ibm-cp1148",
+    ]:
+        generate_full_encoding(
+            "full/" + name + ".txt",
+            root + "/" + name + "_tables.rs.inc",
+            swap_nl_lf=True,
+        )
+
+    # DOS code pages.
+    generate_full_encoding(
+        "full/ibm-cp864.txt",
+        root + "/ibm-cp864_tables.rs.inc",
+    )
+    generate_graphics_tables(
+        "ascii_ext/ibm-cp437.txt",
+        "full/ibm-cp437-graphics.txt",
+        root + "/ibm-cp437-graphics_tables.rs.inc",
+    )
+
+    # Mac OS encodings.
+    for name in [
+        "mac-arabic",
+        "mac-hebrew",
+        "mac-symbol",
+        "mac-thai",
+    ]:
+        generate_full_encoding(
+            "full/" + name + ".txt",
+            root + "/" + name + "_tables.rs.inc",
+        )
+
+    # Vietnamese encodings.
+    generate_full_encoding(
+        "full/viscii.txt",
+        root + "/viscii_tables.rs.inc",
+    )
+
+    # PDF encodings.
+    generate_full_encoding(
+        "full/pdf-doc.txt",
+        root + "/pdf-doc_tables.rs.inc",
+    )
+
+    # Adobe font encodings.
+    for name in [
+        "adobe-standard",
+        "adobe-symbol",
+        "zapf-dingbats",
+    ]:
+        generate_full_encoding(
+            "full/" + name + ".txt",
+            root + "/" + name + "_tables.rs.inc",
+            adobe=True,
+        )
+
+    # Commodore PETSCII.
+    generate_petscii_tables(
+        "full/petscii-unshifted.txt",
+        "full/petscii-shifted.txt",
+        root + "/petscii_tables.rs.inc",
+    )
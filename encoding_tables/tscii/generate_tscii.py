@@ -0,0 +1,70 @@
+#!/usr/bin/env python3
+
+
+def load_table_file(path):
+    """ Loads the table file, returning a list of `(byte, string)` pairs,
+        where `string` is the sequence of code points the byte maps to.
+    """
+    in_file = open(path)
+    table = []
+    for line in in_file:
+        parts = line.split()
+        if len(parts) >= 2 and not parts[0].startswith("#"):
+            byte = int(parts[0], 16)
+            s = "".join(chr(int(x, 16)) for x in parts[1].split("+"))
+            table += [(byte, s)]
+    table.sort()
+    return table
+
+
+def rust_str(s):
+    return "".join("\\u{{{:04X}}}".format(ord(c)) for c in s)
+
+
+def generate_tscii_tables(in_path, out_path):
+    table = load_table_file(in_path)
+
+    # Create the decode table for the upper half.
+    dec_table = [""] * 128
+    for (byte, s) in table:
+        if byte < 0x80:
+            raise Exception("Byte in the ascii range: 0x{:02X}".format(byte))
+        dec_table[byte - 0x80] = s
+
+    # Create the encode table, sorted by string for lookups of the longest
+    # match.  Duplicates are encoded as their first byte.
+    enc_table = []
+    seen = set()
+    for (byte, s) in table:
+        if s not in seen:
+            enc_table += [(s, byte)]
+            seen |= {s}
+    enc_table.sort()
+
+    # Write file.
+    out_file = open(out_path, mode='w')
+    out_file.write(
+"""// This file is auto-generated.  Please see `encoding_tables/tscii/`
+// from the root directory for the files that generate this.
+
+""")
+
+    # Write out decode table
+    out_file.write("static DECODE_TABLE: [&str; 128] = [")
+    for (i, s) in enumerate(dec_table):
+        if i % 4 == 0:
+            out_file.write("\n    ")
+        out_file.write("\"{}\", ".format(rust_str(s)))
+    out_file.write("\n];\n")
+
+    # Write out encode table
+    out_file.write("\nstatic ENCODE_TABLE: [(&str, u8); {}] = [".format(len(enc_table)))
+    for (i, (s, byte)) in enumerate(enc_table):
+        if i % 4 == 0:
+            out_file.write("\n    ")
+        out_file.write("(\"{}\", 0x{:02X}), ".format(rust_str(s), byte))
+    out_file.write("\n];\n")
+
+
+if __name__ == "__main__":
+    generate_tscii_tables("tscii.txt", "../../src/generated/tscii/tscii_tables.rs.inc")
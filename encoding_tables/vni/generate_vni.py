@@ -0,0 +1,140 @@
+#!/usr/bin/env python3
+
+import unicodedata
+
+# The tone marks, and the lowercase byte that follows a vowel for each
+# combination of a vowel mark (circumflex or breve) and a tone mark.  The
+# uppercase bytes, used after capitals, are 0x20 less.
+ACUTE = 0x0301
+GRAVE = 0x0300
+HOOK = 0x0309
+TILDE = 0x0303
+DOT = 0x0323
+CIRCUMFLEX = 0x0302
+BREVE = 0x0306
+HORN = 0x031B
+MARK_BYTES = {
+    (None, ACUTE): 0xF9,
+    (None, GRAVE): 0xF8,
+    (None, HOOK): 0xFB,
+    (None, TILDE): 0xF5,
+    (None, DOT): 0xEF,
+    (CIRCUMFLEX, None): 0xE2,
+    (CIRCUMFLEX, ACUTE): 0xE1,
+    (CIRCUMFLEX, GRAVE): 0xE0,
+    (CIRCUMFLEX, HOOK): 0xE5,
+    (CIRCUMFLEX, TILDE): 0xE3,
+    (CIRCUMFLEX, DOT): 0xE4,
+    (BREVE, None): 0xEA,
+    (BREVE, ACUTE): 0xE9,
+    (BREVE, GRAVE): 0xE8,
+    (BREVE, HOOK): 0xFA,
+    (BREVE, TILDE): 0xFC,
+    (BREVE, DOT): 0xEB,
+}
+
+# The vowel marks that each ascii vowel can take.
+VOWELS = {
+    "a": [None, CIRCUMFLEX, BREVE],
+    "e": [None, CIRCUMFLEX],
+    "o": [None, CIRCUMFLEX],
+    "u": [None],
+    "y": [None],
+}
+
+# Letters with a byte of their own, in lowercase.  The uppercase bytes are
+# 0x20 less.  The horn letters can also be followed by a tone mark byte.
+SINGLE_BYTES = {
+    "đ": 0xF1,
+    "ơ": 0xF4,
+    "ư": 0xF6,
+    "ì": 0xEC,
+    "í": 0xED,
+    "ỉ": 0xE6,
+    "ĩ": 0xF3,
+    "ị": 0xF2,
+}
+HORN_VOWELS = ["ơ", "ư"]
+TONES = [ACUTE, GRAVE, HOOK, TILDE, DOT]
+
+
+def compose(*code_points):
+    composed = unicodedata.normalize("NFC", "".join(chr(c) for c in code_points if c is not None))
+    if len(composed) != 1:
+        raise Exception("Doesn't compose: {}".format(code_points))
+    return composed
+
+
+def generate_vni_tables(out_path):
+    single_table = []  # (byte, char)
+    pair_table = []  # (base byte, mark byte, char)
+    enc_table = []  # (char, [base byte, mark byte])
+
+    for (letter, byte) in SINGLE_BYTES.items():
+        single_table += [(byte, letter), (byte - 0x20, letter.upper())]
+
+    def add_pair(letter, base_byte, mark_byte, upper):
+        # The mark byte is normally in the same case as the base, but either
+        # case is accepted when decoding.
+        if upper:
+            letter = letter.upper()
+            base_byte -= 0x20
+            pair_table.append((base_byte, mark_byte, letter))
+            mark_byte -= 0x20
+        else:
+            pair_table.append((base_byte, mark_byte - 0x20, letter))
+        pair_table.append((base_byte, mark_byte, letter))
+        enc_table.append((letter, base_byte, mark_byte))
+
+    for upper in [False, True]:
+        for (vowel, vowel_marks) in VOWELS.items():
+            for vowel_mark in vowel_marks:
+                for tone in [None] + TONES:
+                    if vowel_mark is None and tone is None:
+                        continue
+                    letter = compose(ord(vowel), vowel_mark, tone)
+                    add_pair(letter, ord(vowel), MARK_BYTES[(vowel_mark, tone)], upper)
+        for vowel in HORN_VOWELS:
+            for tone in TONES:
+                letter = compose(ord(vowel), tone)
+                add_pair(letter, SINGLE_BYTES[vowel], MARK_BYTES[(None, tone)], upper)
+
+    single_table.sort()
+    pair_table.sort()
+    enc_table.sort()
+
+    # Write file.
+    out_file = open(out_path, mode='w')
+    out_file.write(
+"""// This file is auto-generated.  Please see `encoding_tables/vni/`
+// from the root directory for the files that generate this.
+
+""")
+
+    # Write out single table
+    out_file.write("const SINGLE_TABLE: [(u8, char); {}] = [".format(len(single_table)))
+    for (i, (byte, letter)) in enumerate(single_table):
+        if i % 4 == 0:
+            out_file.write("\n    ")
+        out_file.write("(0x{:02X}, '\\u{{{:04X}}}'), ".format(byte, ord(letter)))
+    out_file.write("\n];\n")
+
+    # Write out pair table
+    out_file.write("\nconst PAIR_TABLE: [(u8, u8, char); {}] = [".format(len(pair_table)))
+    for (i, (base, mark, letter)) in enumerate(pair_table):
+        if i % 3 == 0:
+            out_file.write("\n    ")
+        out_file.write("(0x{:02X}, 0x{:02X}, '\\u{{{:04X}}}'), ".format(base, mark, ord(letter)))
+    out_file.write("\n];\n")
+
+    # Write out encode table
+    out_file.write("\nconst ENCODE_TABLE: [(char, [u8; 2]); {}] = [".format(len(enc_table)))
+    for (i, (letter, base, mark)) in enumerate(enc_table):
+        if i % 3 == 0:
+            out_file.write("\n    ")
+        out_file.write("('\\u{{{:04X}}}', [0x{:02X}, 0x{:02X}]), ".format(ord(letter), base, mark))
+    out_file.write("\n];\n")
+
+
+if __name__ == "__main__":
+    generate_vni_tables("../../src/generated/vni/vni_tables.rs.inc")
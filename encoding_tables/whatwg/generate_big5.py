@@ -86,7 +86,7 @@ def generate_big5_tables(in_path, out_path):
     )
 
     # Write out decode table
-    out_file.write("const DECODE_TABLE: [char; {}] = [".format(len(dec_table)))
+    out_file.write("static DECODE_TABLE: [char; {}] = [".format(len(dec_table)))
     for (i, c) in enumerate(dec_table):
         if i % 8 == 0:
             out_file.write("\n    ")
@@ -97,7 +97,7 @@ def generate_big5_tables(in_path, out_path):
     out_file.write("\n];\n\n")
 
     # Write out encode table
-    out_file.write("const ENCODE_TABLE: [(char, [u8; 2]); {}] = [".format(len(enc_table)))
+    out_file.write("static ENCODE_TABLE: [(char, [u8; 2]); {}] = [".format(len(enc_table)))
     for (i, pair) in enumerate(enc_table):
         if i % 3 == 0:
             out_file.write("\n    ")
@@ -1,17 +1,17 @@
 extern crate text_encoding;
 
-use text_encoding::{single_byte::*, *};
+use text_encoding::{ebcdic_dbcs::*, single_byte::*, *};
 
 fn main() {
     let text = "Hello world!";
 
     let mut buf = [0u8; 100];
-    let utf16 = encode_from_str(Encoding::Utf16BE, text, &mut buf, true)
+    let utf16 = encode_from_str(&mut Encoding::Utf16BE, text, &mut buf, true)
         .unwrap()
         .0;
 
     let mut buf_2 = [0u8; 100];
-    let text_2 = decode_to_str(Encoding::Utf16BE, utf16, &mut buf_2, true)
+    let text_2 = decode_to_str(&mut Encoding::Utf16BE, utf16, &mut buf_2, true)
         .unwrap()
         .0;
 
@@ -19,6 +19,9 @@ fn main() {
 }
 
 /// Describes a text encoding.
+///
+/// Stateful encodings carry their state, so the same `Encoding` must be
+/// passed for every chunk of a piece of text.
 #[derive(Debug, Copy, Clone)]
 pub enum Encoding {
     Utf8,
@@ -26,58 +29,216 @@ pub enum Encoding {
     Utf16LE,
     Utf32BE,
     Utf32LE,
-    Big5WHATWG,     // BIG5, WHATWG variant
-    ShiftJISWHATWG, // Shift JIS, WHATWG variant
-    Ascii,          // US Ascii
-    Ibm866,         // IBM 866
-    Iso8859_1,      // ISO/IEC 8859-1, Latin1
-    Iso8859_2,      // ISO/IEC 8859-2, Latin2
-    Iso8859_3,      // ISO/IEC 8859-3, Latin3
-    Iso8859_4,      // ISO/IEC 8859-4, Latin4
-    Iso8859_5,      // ISO/IEC 8859-5
-    Iso8859_6,      // ISO/IEC 8859-6
-    Iso8859_7,      // ISO/IEC 8859-7
-    Iso8859_8,      // ISO/IEC 8859-8
-    Iso8859_9,      // ISO/IEC 8859-9
-    Iso8859_10,     // ISO/IEC 8859-10
-    Iso8859_11,     // ISO/IEC 8859-11, also called TIS-620
-    Iso8859_13,     // ISO/IEC 8859-13
-    Iso8859_14,     // ISO/IEC 8859-14
-    Iso8859_15,     // ISO/IEC 8859-15
-    Iso8859_16,     // ISO/IEC 8859-16
-    Koi8R,          // KOI8-R
-    Koi8U,          // KOI8-U
-    Macintosh,      // Macintosh
-    MacCyrillic,    // x-max-cyrillic
-    Windows874,     // Windows code page 874
-    Windows1250,    // Windows code page 1250
-    Windows1251,    // Windows code page 1251
-    Windows1252,    // Windows code page 1252
-    Windows1253,    // Windows code page 1253
-    Windows1254,    // Windows code page 1254
-    Windows1255,    // Windows code page 1255
-    Windows1256,    // Windows code page 1256
-    Windows1257,    // Windows code page 1257
-    Windows1258,    // Windows code page 1258
+    Big5HKSCS,          // BIG5 with HKSCS-2008
+    Big5UAO,            // BIG5 with Unicode-at-On 2.50
+    Big5WHATWG,         // BIG5, WHATWG variant
+    EucJis2004,         // EUC-JIS-2004
+    EucTw,              // EUC-TW
+    Gsm0338,            // GSM 03.38, unpacked
+    Iso6937,            // ISO/IEC 6937
+    Johab,              // Johab
+    MacChineseSimp,     // Mac OS Chinese Simplified
+    MacChineseTrad,     // Mac OS Chinese Traditional
+    MacJapanese,        // Mac OS Japanese
+    MacKorean,          // Mac OS Korean
+    ShiftJis2004,       // Shift_JIS-2004
+    ShiftJISWHATWG,     // Shift JIS, WHATWG variant
+    ShiftJISJIS,        // Shift JIS, strict JIS X 0208:1997
+    ShiftJISCP932,      // Shift JIS, Microsoft code page 932
+    Tscii,              // TSCII 1.7
+    Vni,                // VNI
+    AdobeStandard,      // Adobe Standard Encoding
+    AdobeSymbol,        // Adobe Symbol
+    Armscii8,           // ARMSCII-8
+    Ascii,              // US Ascii
+    AtariSt,            // Atari ST
+    DecMcs,             // DEC Multinational Character Set
+    GeorgianPs,         // Georgian-PS
+    HpRoman8,           // HP Roman-8
+    Ibm037,             // IBM EBCDIC 037
+    Ibm273,             // IBM EBCDIC 273
+    Ibm277,             // IBM EBCDIC 277
+    Ibm278,             // IBM EBCDIC 278
+    Ibm280,             // IBM EBCDIC 280
+    Ibm284,             // IBM EBCDIC 284
+    Ibm285,             // IBM EBCDIC 285
+    Ibm297,             // IBM EBCDIC 297
+    Ibm437,             // IBM 437
+    Ibm500,             // IBM EBCDIC 500
+    Ibm737,             // IBM 737
+    Ibm775,             // IBM 775
+    Ibm850,             // IBM 850
+    Ibm852,             // IBM 852
+    Ibm855,             // IBM 855
+    Ibm857,             // IBM 857
+    Ibm860,             // IBM 860
+    Ibm861,             // IBM 861
+    Ibm862,             // IBM 862
+    Ibm863,             // IBM 863
+    Ibm864,             // IBM 864
+    Ibm865,             // IBM 865
+    Ibm866,             // IBM 866
+    Ibm869,             // IBM 869
+    Ibm871,             // IBM EBCDIC 871
+    Ibm1047,            // IBM EBCDIC 1047
+    Ibm1125,            // IBM 1125
+    Ibm1140,            // IBM EBCDIC 1140
+    Ibm1141,            // IBM EBCDIC 1141
+    Ibm1142,            // IBM EBCDIC 1142
+    Ibm1143,            // IBM EBCDIC 1143
+    Ibm1144,            // IBM EBCDIC 1144
+    Ibm1145,            // IBM EBCDIC 1145
+    Ibm1146,            // IBM EBCDIC 1146
+    Ibm1147,            // IBM EBCDIC 1147
+    Ibm1148,            // IBM EBCDIC 1148
+    Iso8859_1,          // ISO/IEC 8859-1, Latin1
+    Iso8859_2,          // ISO/IEC 8859-2, Latin2
+    Iso8859_3,          // ISO/IEC 8859-3, Latin3
+    Iso8859_4,          // ISO/IEC 8859-4, Latin4
+    Iso8859_5,          // ISO/IEC 8859-5
+    Iso8859_6,          // ISO/IEC 8859-6
+    Iso8859_7,          // ISO/IEC 8859-7
+    Iso8859_8,          // ISO/IEC 8859-8
+    Iso8859_9,          // ISO/IEC 8859-9
+    Iso8859_10,         // ISO/IEC 8859-10
+    Iso8859_11,         // ISO/IEC 8859-11, also called TIS-620
+    Iso8859_13,         // ISO/IEC 8859-13
+    Iso8859_14,         // ISO/IEC 8859-14
+    Iso8859_15,         // ISO/IEC 8859-15
+    Iso8859_16,         // ISO/IEC 8859-16
+    Koi8R,              // KOI8-R
+    Koi8Ru,             // KOI8-RU
+    Koi8T,              // KOI8-T
+    Koi8U,              // KOI8-U
+    Kz1048,             // KZ-1048
+    Macintosh,          // Macintosh
+    MacCyrillic,        // x-max-cyrillic
+    MacArabic,          // Mac OS Arabic
+    MacCentralEuropean, // x-mac-ce
+    MacCroatian,        // Mac OS Croatian
+    MacGreek,           // Mac OS Greek
+    MacHebrew,          // Mac OS Hebrew
+    MacIcelandic,       // Mac OS Icelandic
+    MacRomanian,        // Mac OS Romanian
+    MacSymbol,          // Mac OS Symbol
+    MacThai,            // Mac OS Thai
+    MacTurkish,         // Mac OS Turkish
+    MacUkrainian,       // Mac OS Ukrainian
+    NextStep,           // NeXTSTEP
+    PdfDoc,             // PDFDocEncoding
+    PetsciiUnshifted,   // PETSCII, unshifted set
+    PetsciiShifted,     // PETSCII, shifted set
+    Pt154,              // PT154
+    Tcvn3,              // TCVN3 (ABC)
+    Viscii,             // VISCII
+    Windows874,         // Windows code page 874
+    Windows1250,        // Windows code page 1250
+    Windows1251,        // Windows code page 1251
+    Windows1252,        // Windows code page 1252
+    Windows1253,        // Windows code page 1253
+    Windows1254,        // Windows code page 1254
+    Windows1255,        // Windows code page 1255
+    Windows1256,        // Windows code page 1256
+    Windows1257,        // Windows code page 1257
+    Windows1258,        // Windows code page 1258
+    ZapfDingbats,       // ITC Zapf Dingbats
+
+    // Stateful encodings.
+    AribB24(arib_b24::State),              // ARIB STD-B24
+    Dvb(dvb::State),                       // DVB text strings, EN 300 468
+    Gsm0338Packed(gsm_0338::State),        // GSM 03.38, packed
+    HzGb2312(hz_gb_2312::State),           // HZ-GB-2312
+    Ibm930(ebcdic_dbcs::State),            // IBM 930
+    Ibm933(ebcdic_dbcs::State),            // IBM 933
+    Ibm935(ebcdic_dbcs::State),            // IBM 935
+    Ibm937(ebcdic_dbcs::State),            // IBM 937
+    Ibm939(ebcdic_dbcs::State),            // IBM 939
+    Iscii(iscii::State),                   // ISCII-91
+    Iso2022CN(iso_2022_cn::State),         // ISO-2022-CN
+    Iso2022KR(iso_2022_kr::State),         // ISO-2022-KR
+    Marc8(marc8::State),                   // MARC-8
+    PdfTextString(pdf_text_string::State), // PDF text strings
 }
 
 /// Encodes text from utf8 to a destination encoding.
 pub fn encode_from_str<'a>(
-    output_encoding: Encoding,
+    output_encoding: &mut Encoding,
     input: &str,
     output: &'a mut [u8],
     is_end: bool,
 ) -> EncodeResult<'a> {
-    match output_encoding {
+    match *output_encoding {
         Encoding::Utf8 => utf8::encode_from_str(input, output),
         Encoding::Utf16BE => utf16_be::encode_from_str(input, output),
         Encoding::Utf16LE => utf16_le::encode_from_str(input, output),
         Encoding::Utf32BE => utf32_be::encode_from_str(input, output),
         Encoding::Utf32LE => utf32_le::encode_from_str(input, output),
+        Encoding::Big5HKSCS => big5_hkscs::encode_from_str(input, output, is_end),
+        Encoding::Big5UAO => big5_uao::encode_from_str(input, output),
         Encoding::Big5WHATWG => big5_whatwg::encode_from_str(input, output, is_end),
+        Encoding::EucJis2004 => euc_jis_2004::encode_from_str(input, output, is_end),
+        Encoding::EucTw => euc_tw::encode_from_str(input, output),
+        Encoding::Gsm0338 => gsm_0338::encode_from_str(input, output, &gsm_0338::Tables::default()),
+        Encoding::Iso6937 => iso_6937::encode_from_str(input, output, is_end),
+        Encoding::Johab => johab::encode_from_str(input, output),
+        Encoding::MacChineseSimp => mac_chinese_simp::encode_from_str(input, output, is_end),
+        Encoding::MacChineseTrad => mac_chinese_trad::encode_from_str(input, output, is_end),
+        Encoding::MacJapanese => mac_japanese::encode_from_str(input, output, is_end),
+        Encoding::MacKorean => mac_korean::encode_from_str(input, output, is_end),
+        Encoding::ShiftJis2004 => shift_jis_2004::encode_from_str(input, output, is_end),
         Encoding::ShiftJISWHATWG => shiftjis_whatwg::encode_from_str(input, output),
+        Encoding::ShiftJISJIS => {
+            shiftjis::encode_from_str(input, output, is_end, shiftjis::Profile::Jis)
+        }
+        Encoding::ShiftJISCP932 => {
+            shiftjis::encode_from_str(input, output, is_end, shiftjis::Profile::Cp932)
+        }
+        Encoding::Tscii => tscii::encode_from_str(input, output, is_end),
+        Encoding::Vni => vni::encode_from_str(input, output),
+        Encoding::AdobeStandard => adobe_standard::encode_from_str(input, output),
+        Encoding::AdobeSymbol => adobe_symbol::encode_from_str(input, output),
+        Encoding::Armscii8 => armscii_8::encode_from_str(input, output),
         Encoding::Ascii => ascii::encode_from_str(input, output),
+        Encoding::AtariSt => atari_st::encode_from_str(input, output),
+        Encoding::DecMcs => dec_mcs::encode_from_str(input, output),
+        Encoding::GeorgianPs => georgian_ps::encode_from_str(input, output),
+        Encoding::HpRoman8 => hp_roman8::encode_from_str(input, output),
+        Encoding::Ibm037 => ibm_037::encode_from_str(input, output),
+        Encoding::Ibm273 => ibm_273::encode_from_str(input, output),
+        Encoding::Ibm277 => ibm_277::encode_from_str(input, output),
+        Encoding::Ibm278 => ibm_278::encode_from_str(input, output),
+        Encoding::Ibm280 => ibm_280::encode_from_str(input, output),
+        Encoding::Ibm284 => ibm_284::encode_from_str(input, output),
+        Encoding::Ibm285 => ibm_285::encode_from_str(input, output),
+        Encoding::Ibm297 => ibm_297::encode_from_str(input, output),
+        Encoding::Ibm437 => ibm_437::encode_from_str(input, output),
+        Encoding::Ibm500 => ibm_500::encode_from_str(input, output),
+        Encoding::Ibm737 => ibm_737::encode_from_str(input, output),
+        Encoding::Ibm775 => ibm_775::encode_from_str(input, output),
+        Encoding::Ibm850 => ibm_850::encode_from_str(input, output),
+        Encoding::Ibm852 => ibm_852::encode_from_str(input, output),
+        Encoding::Ibm855 => ibm_855::encode_from_str(input, output),
+        Encoding::Ibm857 => ibm_857::encode_from_str(input, output),
+        Encoding::Ibm860 => ibm_860::encode_from_str(input, output),
+        Encoding::Ibm861 => ibm_861::encode_from_str(input, output),
+        Encoding::Ibm862 => ibm_862::encode_from_str(input, output),
+        Encoding::Ibm863 => ibm_863::encode_from_str(input, output),
+        Encoding::Ibm864 => ibm_864::encode_from_str(input, output),
+        Encoding::Ibm865 => ibm_865::encode_from_str(input, output),
         Encoding::Ibm866 => ibm_866::encode_from_str(input, output),
+        Encoding::Ibm869 => ibm_869::encode_from_str(input, output),
+        Encoding::Ibm871 => ibm_871::encode_from_str(input, output),
+        Encoding::Ibm1047 => ibm_1047::encode_from_str(input, output),
+        Encoding::Ibm1125 => ibm_1125::encode_from_str(input, output),
+        Encoding::Ibm1140 => ibm_1140::encode_from_str(input, output),
+        Encoding::Ibm1141 => ibm_1141::encode_from_str(input, output),
+        Encoding::Ibm1142 => ibm_1142::encode_from_str(input, output),
+        Encoding::Ibm1143 => ibm_1143::encode_from_str(input, output),
+        Encoding::Ibm1144 => ibm_1144::encode_from_str(input, output),
+        Encoding::Ibm1145 => ibm_1145::encode_from_str(input, output),
+        Encoding::Ibm1146 => ibm_1146::encode_from_str(input, output),
+        Encoding::Ibm1147 => ibm_1147::encode_from_str(input, output),
+        Encoding::Ibm1148 => ibm_1148::encode_from_str(input, output),
         Encoding::Iso8859_1 => iso_8859_1::encode_from_str(input, output),
         Encoding::Iso8859_2 => iso_8859_2::encode_from_str(input, output),
         Encoding::Iso8859_3 => iso_8859_3::encode_from_str(input, output),
@@ -94,9 +255,32 @@ pub fn encode_from_str<'a>(
         Encoding::Iso8859_15 => iso_8859_15::encode_from_str(input, output),
         Encoding::Iso8859_16 => iso_8859_16::encode_from_str(input, output),
         Encoding::Koi8R => koi8_r::encode_from_str(input, output),
+        Encoding::Koi8Ru => koi8_ru::encode_from_str(input, output),
+        Encoding::Koi8T => koi8_t::encode_from_str(input, output),
         Encoding::Koi8U => koi8_u::encode_from_str(input, output),
+        Encoding::Kz1048 => kz_1048::encode_from_str(input, output),
         Encoding::Macintosh => mac_roman::encode_from_str(input, output),
         Encoding::MacCyrillic => mac_cyrillic::encode_from_str(input, output),
+        Encoding::MacArabic => mac_arabic::encode_from_str(input, output, is_end),
+        Encoding::MacCentralEuropean => mac_central_european::encode_from_str(input, output),
+        Encoding::MacCroatian => mac_croatian::encode_from_str(input, output),
+        Encoding::MacGreek => mac_greek::encode_from_str(input, output),
+        Encoding::MacHebrew => mac_hebrew::encode_from_str(input, output, is_end),
+        Encoding::MacIcelandic => mac_icelandic::encode_from_str(input, output),
+        Encoding::MacRomanian => mac_romanian::encode_from_str(input, output),
+        Encoding::MacSymbol => mac_symbol::encode_from_str(input, output, is_end),
+        Encoding::MacThai => mac_thai::encode_from_str(input, output, is_end),
+        Encoding::MacTurkish => mac_turkish::encode_from_str(input, output),
+        Encoding::MacUkrainian => mac_ukrainian::encode_from_str(input, output),
+        Encoding::NextStep => nextstep::encode_from_str(input, output),
+        Encoding::PdfDoc => pdf_doc::encode_from_str(input, output),
+        Encoding::PetsciiUnshifted => {
+            petscii::encode_from_str(input, output, petscii::Mode::Unshifted)
+        }
+        Encoding::PetsciiShifted => petscii::encode_from_str(input, output, petscii::Mode::Shifted),
+        Encoding::Pt154 => pt154::encode_from_str(input, output),
+        Encoding::Tcvn3 => tcvn3::encode_from_str(input, output),
+        Encoding::Viscii => viscii::encode_from_str(input, output),
         Encoding::Windows874 => windows_874::encode_from_str(input, output),
         Encoding::Windows1250 => windows_1250::encode_from_str(input, output),
         Encoding::Windows1251 => windows_1251::encode_from_str(input, output),
@@ -107,26 +291,115 @@ pub fn encode_from_str<'a>(
         Encoding::Windows1256 => windows_1256::encode_from_str(input, output),
         Encoding::Windows1257 => windows_1257::encode_from_str(input, output),
         Encoding::Windows1258 => windows_1258::encode_from_str(input, output),
+        Encoding::ZapfDingbats => zapf_dingbats::encode_from_str(input, output),
+        Encoding::AribB24(ref mut state) => arib_b24::encode_from_str(input, output, state, is_end),
+        Encoding::Dvb(ref mut state) => dvb::encode_from_str(input, output, state, is_end),
+        Encoding::Gsm0338Packed(ref mut state) => {
+            gsm_0338::encode_packed_from_str(input, output, state, is_end)
+        }
+        Encoding::HzGb2312(ref mut state) => {
+            hz_gb_2312::encode_from_str(input, output, state, is_end)
+        }
+        Encoding::Ibm930(ref mut state) => ibm_930::encode_from_str(input, output, state, is_end),
+        Encoding::Ibm933(ref mut state) => ibm_933::encode_from_str(input, output, state, is_end),
+        Encoding::Ibm935(ref mut state) => ibm_935::encode_from_str(input, output, state, is_end),
+        Encoding::Ibm937(ref mut state) => ibm_937::encode_from_str(input, output, state, is_end),
+        Encoding::Ibm939(ref mut state) => ibm_939::encode_from_str(input, output, state, is_end),
+        Encoding::Iscii(ref mut state) => iscii::encode_from_str(input, output, state, is_end),
+        Encoding::Iso2022CN(ref mut state) => {
+            iso_2022_cn::encode_from_str(input, output, state, is_end)
+        }
+        Encoding::Iso2022KR(ref mut state) => {
+            iso_2022_kr::encode_from_str(input, output, state, is_end)
+        }
+        Encoding::Marc8(ref mut state) => marc8::encode_from_str(input, output, state, is_end),
+        Encoding::PdfTextString(ref mut state) => {
+            pdf_text_string::encode_from_str(input, output, state)
+        }
     }
 }
 
 /// Decodes text from a source encoding to utf8.
 pub fn decode_to_str<'a>(
-    input_encoding: Encoding,
+    input_encoding: &mut Encoding,
     input: &[u8],
     output: &'a mut [u8],
     is_end: bool,
 ) -> DecodeResult<'a> {
-    match input_encoding {
+    match *input_encoding {
         Encoding::Utf8 => utf8::decode_to_str(input, output, is_end),
         Encoding::Utf16BE => utf16_be::decode_to_str(input, output, is_end),
         Encoding::Utf16LE => utf16_le::decode_to_str(input, output, is_end),
         Encoding::Utf32BE => utf32_be::decode_to_str(input, output, is_end),
         Encoding::Utf32LE => utf32_le::decode_to_str(input, output, is_end),
+        Encoding::Big5HKSCS => big5_hkscs::decode_to_str(input, output, is_end),
+        Encoding::Big5UAO => big5_uao::decode_to_str(input, output, is_end),
         Encoding::Big5WHATWG => big5_whatwg::decode_to_str(input, output, is_end),
+        Encoding::EucJis2004 => euc_jis_2004::decode_to_str(input, output, is_end),
+        Encoding::EucTw => euc_tw::decode_to_str(input, output, is_end),
+        Encoding::Gsm0338 => {
+            gsm_0338::decode_to_str(input, output, is_end, &gsm_0338::Tables::default())
+        }
+        Encoding::Iso6937 => iso_6937::decode_to_str(input, output, is_end),
+        Encoding::Johab => johab::decode_to_str(input, output, is_end),
+        Encoding::MacChineseSimp => mac_chinese_simp::decode_to_str(input, output, is_end),
+        Encoding::MacChineseTrad => mac_chinese_trad::decode_to_str(input, output, is_end),
+        Encoding::MacJapanese => mac_japanese::decode_to_str(input, output, is_end),
+        Encoding::MacKorean => mac_korean::decode_to_str(input, output, is_end),
+        Encoding::ShiftJis2004 => shift_jis_2004::decode_to_str(input, output, is_end),
         Encoding::ShiftJISWHATWG => shiftjis_whatwg::decode_to_str(input, output, is_end),
+        Encoding::ShiftJISJIS => {
+            shiftjis::decode_to_str(input, output, is_end, shiftjis::Profile::Jis)
+        }
+        Encoding::ShiftJISCP932 => {
+            shiftjis::decode_to_str(input, output, is_end, shiftjis::Profile::Cp932)
+        }
+        Encoding::Tscii => tscii::decode_to_str(input, output, is_end),
+        Encoding::Vni => vni::decode_to_str(input, output, is_end),
+        Encoding::AdobeStandard => adobe_standard::decode_to_str(input, output),
+        Encoding::AdobeSymbol => adobe_symbol::decode_to_str(input, output),
+        Encoding::Armscii8 => armscii_8::decode_to_str(input, output),
         Encoding::Ascii => ascii::decode_to_str(input, output),
+        Encoding::AtariSt => atari_st::decode_to_str(input, output),
+        Encoding::DecMcs => dec_mcs::decode_to_str(input, output),
+        Encoding::GeorgianPs => georgian_ps::decode_to_str(input, output),
+        Encoding::HpRoman8 => hp_roman8::decode_to_str(input, output),
+        Encoding::Ibm037 => ibm_037::decode_to_str(input, output),
+        Encoding::Ibm273 => ibm_273::decode_to_str(input, output),
+        Encoding::Ibm277 => ibm_277::decode_to_str(input, output),
+        Encoding::Ibm278 => ibm_278::decode_to_str(input, output),
+        Encoding::Ibm280 => ibm_280::decode_to_str(input, output),
+        Encoding::Ibm284 => ibm_284::decode_to_str(input, output),
+        Encoding::Ibm285 => ibm_285::decode_to_str(input, output),
+        Encoding::Ibm297 => ibm_297::decode_to_str(input, output),
+        Encoding::Ibm437 => ibm_437::decode_to_str(input, output),
+        Encoding::Ibm500 => ibm_500::decode_to_str(input, output),
+        Encoding::Ibm737 => ibm_737::decode_to_str(input, output),
+        Encoding::Ibm775 => ibm_775::decode_to_str(input, output),
+        Encoding::Ibm850 => ibm_850::decode_to_str(input, output),
+        Encoding::Ibm852 => ibm_852::decode_to_str(input, output),
+        Encoding::Ibm855 => ibm_855::decode_to_str(input, output),
+        Encoding::Ibm857 => ibm_857::decode_to_str(input, output),
+        Encoding::Ibm860 => ibm_860::decode_to_str(input, output),
+        Encoding::Ibm861 => ibm_861::decode_to_str(input, output),
+        Encoding::Ibm862 => ibm_862::decode_to_str(input, output),
+        Encoding::Ibm863 => ibm_863::decode_to_str(input, output),
+        Encoding::Ibm864 => ibm_864::decode_to_str(input, output),
+        Encoding::Ibm865 => ibm_865::decode_to_str(input, output),
         Encoding::Ibm866 => ibm_866::decode_to_str(input, output),
+        Encoding::Ibm869 => ibm_869::decode_to_str(input, output),
+        Encoding::Ibm871 => ibm_871::decode_to_str(input, output),
+        Encoding::Ibm1047 => ibm_1047::decode_to_str(input, output),
+        Encoding::Ibm1125 => ibm_1125::decode_to_str(input, output),
+        Encoding::Ibm1140 => ibm_1140::decode_to_str(input, output),
+        Encoding::Ibm1141 => ibm_1141::decode_to_str(input, output),
+        Encoding::Ibm1142 => ibm_1142::decode_to_str(input, output),
+        Encoding::Ibm1143 => ibm_1143::decode_to_str(input, output),
+        Encoding::Ibm1144 => ibm_1144::decode_to_str(input, output),
+        Encoding::Ibm1145 => ibm_1145::decode_to_str(input, output),
+        Encoding::Ibm1146 => ibm_1146::decode_to_str(input, output),
+        Encoding::Ibm1147 => ibm_1147::decode_to_str(input, output),
+        Encoding::Ibm1148 => ibm_1148::decode_to_str(input, output),
         Encoding::Iso8859_1 => iso_8859_1::decode_to_str(input, output),
         Encoding::Iso8859_2 => iso_8859_2::decode_to_str(input, output),
         Encoding::Iso8859_3 => iso_8859_3::decode_to_str(input, output),
@@ -143,9 +416,32 @@ pub fn decode_to_str<'a>(
         Encoding::Iso8859_15 => iso_8859_15::decode_to_str(input, output),
         Encoding::Iso8859_16 => iso_8859_16::decode_to_str(input, output),
         Encoding::Koi8R => koi8_r::decode_to_str(input, output),
+        Encoding::Koi8Ru => koi8_ru::decode_to_str(input, output),
+        Encoding::Koi8T => koi8_t::decode_to_str(input, output),
         Encoding::Koi8U => koi8_u::decode_to_str(input, output),
+        Encoding::Kz1048 => kz_1048::decode_to_str(input, output),
         Encoding::Macintosh => mac_roman::decode_to_str(input, output),
         Encoding::MacCyrillic => mac_cyrillic::decode_to_str(input, output),
+        Encoding::MacArabic => mac_arabic::decode_to_str(input, output),
+        Encoding::MacCentralEuropean => mac_central_european::decode_to_str(input, output),
+        Encoding::MacCroatian => mac_croatian::decode_to_str(input, output),
+        Encoding::MacGreek => mac_greek::decode_to_str(input, output),
+        Encoding::MacHebrew => mac_hebrew::decode_to_str(input, output),
+        Encoding::MacIcelandic => mac_icelandic::decode_to_str(input, output),
+        Encoding::MacRomanian => mac_romanian::decode_to_str(input, output),
+        Encoding::MacSymbol => mac_symbol::decode_to_str(input, output),
+        Encoding::MacThai => mac_thai::decode_to_str(input, output),
+        Encoding::MacTurkish => mac_turkish::decode_to_str(input, output),
+        Encoding::MacUkrainian => mac_ukrainian::decode_to_str(input, output),
+        Encoding::NextStep => nextstep::decode_to_str(input, output),
+        Encoding::PdfDoc => pdf_doc::decode_to_str(input, output),
+        Encoding::PetsciiUnshifted => {
+            petscii::decode_to_str(input, output, petscii::Mode::Unshifted)
+        }
+        Encoding::PetsciiShifted => petscii::decode_to_str(input, output, petscii::Mode::Shifted),
+        Encoding::Pt154 => pt154::decode_to_str(input, output),
+        Encoding::Tcvn3 => tcvn3::decode_to_str(input, output),
+        Encoding::Viscii => viscii::decode_to_str(input, output),
         Encoding::Windows874 => windows_874::decode_to_str(input, output),
         Encoding::Windows1250 => windows_1250::decode_to_str(input, output),
         Encoding::Windows1251 => windows_1251::decode_to_str(input, output),
@@ -156,5 +452,30 @@ pub fn decode_to_str<'a>(
         Encoding::Windows1256 => windows_1256::decode_to_str(input, output),
         Encoding::Windows1257 => windows_1257::decode_to_str(input, output),
         Encoding::Windows1258 => windows_1258::decode_to_str(input, output),
+        Encoding::ZapfDingbats => zapf_dingbats::decode_to_str(input, output),
+        Encoding::AribB24(ref mut state) => arib_b24::decode_to_str(input, output, state, is_end),
+        Encoding::Dvb(ref mut state) => dvb::decode_to_str(input, output, state, is_end),
+        Encoding::Gsm0338Packed(ref mut state) => {
+            gsm_0338::decode_packed_to_str(input, output, state, is_end)
+        }
+        Encoding::HzGb2312(ref mut state) => {
+            hz_gb_2312::decode_to_str(input, output, state, is_end)
+        }
+        Encoding::Ibm930(ref mut state) => ibm_930::decode_to_str(input, output, state, is_end),
+        Encoding::Ibm933(ref mut state) => ibm_933::decode_to_str(input, output, state, is_end),
+        Encoding::Ibm935(ref mut state) => ibm_935::decode_to_str(input, output, state, is_end),
+        Encoding::Ibm937(ref mut state) => ibm_937::decode_to_str(input, output, state, is_end),
+        Encoding::Ibm939(ref mut state) => ibm_939::decode_to_str(input, output, state, is_end),
+        Encoding::Iscii(ref mut state) => iscii::decode_to_str(input, output, state, is_end),
+        Encoding::Iso2022CN(ref mut state) => {
+            iso_2022_cn::decode_to_str(input, output, state, is_end)
+        }
+        Encoding::Iso2022KR(ref mut state) => {
+            iso_2022_kr::decode_to_str(input, output, state, is_end)
+        }
+        Encoding::Marc8(ref mut state) => marc8::decode_to_str(input, output, state, is_end),
+        Encoding::PdfTextString(ref mut state) => {
+            pdf_text_string::decode_to_str(input, output, state, is_end)
+        }
     }
 }
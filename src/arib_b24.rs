@@ -0,0 +1,935 @@
+//! ARIB STD-B24, the 8-bit code used for captions and program information in
+//! Japanese digital television.
+//!
+//! This is an ISO 2022 based code with four graphic sets G0-G3, which are
+//! initially the Kanji, alphanumeric, hiragana, and katakana sets.  G0 is
+//! initially invoked into GL (0x21-0x7E) and G2 into GR (0xA1-0xFE).
+//! Escape sequences designate other sets into G0-G3, and the locking shifts
+//! (LS0, LS1, LS2, LS3, LS1R, LS2R, and LS3R) and single shifts (SS2 and
+//! SS3) change which of them are invoked.  Encoding and decoding are
+//! therefore stateful and take a `State`.
+//!
+//! The following graphic sets are supported:
+//!
+//! * Kanji: JIS X 0208, plus the additional kanji in rows 85-86 and the
+//!   additional symbols in rows 90-94.
+//! * Alphanumeric: the JIS X 0201 Latin set, so 0x5C is U+00A5 YEN SIGN and
+//!   0x7E is U+203E OVERLINE.
+//! * Hiragana and katakana.
+//! * The proportional alphanumeric, hiragana, and katakana sets, which
+//!   decode the same as the non-proportional ones.
+//! * JIS X 0201 katakana, which decodes to halfwidth katakana.
+//! * JIS compatible Kanji planes 1 and 2, i.e. JIS X 0213.
+//! * Additional symbols, which is rows 85-94 of the Kanji set on their own
+//!   (rows 87-89 are unused).  The additional kanji are
+//!   name variants such as 髙 and 﨑, and map to CJK ideographs.  The symbols
+//!   map to the characters added for them in Unicode 5.2 and later, except
+//!   for 30 cells in row 92 with no Unicode equivalent, which map to the
+//!   private use code points U+E2A5-U+E2C2.
+//!
+//! The mosaic sets, DRCS (downloaded glyphs), and the macro set can be
+//! designated and invoked, but their characters have no Unicode equivalent,
+//! so decoding them is an `UnknownConversion` error.  The error range
+//! covers the whole character, so it can be skipped or replaced with a
+//! substitute.
+//!
+//! Of the control functions, SP decodes to a space and APR (0x0D) to a line
+//! feed, and RPC repeats the following character.  RPC's repeat to the end
+//! of the line depends on the display format, so it's an `UnknownConversion`
+//! error as well.  The rest, such as the color, size, and positioning
+//! controls, are skipped along with their parameters.  Macro definitions
+//! are skipped as well, and the default macros aren't expanded.
+//!
+//! The encoder only uses the Kanji, alphanumeric, hiragana, and katakana
+//! sets in their initial designations, switching between them with LS0,
+//! LS1, LS2R, and LS3R, and returns to the initial invocations at the end of
+//! the text.  Spaces are encoded as SP and line feeds as APR.  Other control
+//! characters, and U+005C and U+007E, can't be encoded.
+
+use core;
+use jis0213;
+use {DecodeError, DecodeErrorCause, DecodeResult, EncodeError, EncodeResult};
+
+// Generated by `encoding_tables/shiftjis/generate_shiftjis_jis.py`.
+// Contains JIS_ENCODE_TABLE and JIS_DECODE_TABLE.
+include!("generated/shiftjis/shiftjis_jis_tables.rs.inc");
+
+// Generated by `encoding_tables/arib/generate_arib.py`.
+// Contains SYMBOL_ENCODE_TABLE and SYMBOL_DECODE_TABLE.
+include!("generated/arib/arib_tables.rs.inc");
+
+const APR: u8 = 0x0D;
+const LS1: u8 = 0x0E;
+const LS0: u8 = 0x0F;
+const SS2: u8 = 0x19;
+const ESC: u8 = 0x1B;
+const SS3: u8 = 0x1D;
+const SP: u8 = 0x20;
+
+// The first row of the additional symbols, row 85, as the lead byte of its
+// 7-bit code.
+const SYMBOL_ROW: u8 = 0x75;
+
+// The symbols at the end of both the hiragana and katakana sets.
+const KANA_SYMBOLS: [char; 6] = ['ー', '。', '「', '」', '、', '・'];
+
+/// The encoding/decoding state of an ARIB STD-B24 stream.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct State {
+    g: [Charset; 4],
+    gl: u8,
+    gr: u8,
+    // The number of copies of the next character still to be written, from
+    // RPC.
+    repeat: u8,
+}
+
+impl State {
+    pub fn new() -> State {
+        State {
+            g: [
+                Charset::Kanji,
+                Charset::Alphanumeric,
+                Charset::Hiragana,
+                Charset::Katakana,
+            ],
+            gl: 0,
+            gr: 2,
+            repeat: 0,
+        }
+    }
+}
+
+impl Default for State {
+    fn default() -> State {
+        State::new()
+    }
+}
+
+/// A graphic set that can be designated into G0-G3.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum Charset {
+    Kanji,
+    Alphanumeric,
+    Hiragana,
+    Katakana,
+    JisX0201Katakana,
+    JisKanjiPlane1,
+    JisKanjiPlane2,
+    AdditionalSymbols,
+    Mosaic,
+    Drcs0,
+    Drcs,
+    Macro,
+}
+
+impl Charset {
+    /// Returns the set with the given final byte of its designation.
+    fn from_final(byte: u8, is_two_byte: bool, is_drcs: bool) -> Option<Charset> {
+        match (is_two_byte, is_drcs, byte) {
+            (true, false, 0x42) => Some(Charset::Kanji),
+            (true, false, 0x39) => Some(Charset::JisKanjiPlane1),
+            (true, false, 0x3A) => Some(Charset::JisKanjiPlane2),
+            (true, false, 0x3B) => Some(Charset::AdditionalSymbols),
+            (false, false, 0x4A) | (false, false, 0x36) => Some(Charset::Alphanumeric),
+            (false, false, 0x30) | (false, false, 0x37) => Some(Charset::Hiragana),
+            (false, false, 0x31) | (false, false, 0x38) => Some(Charset::Katakana),
+            (false, false, 0x49) => Some(Charset::JisX0201Katakana),
+            (false, false, 0x32..=0x35) => Some(Charset::Mosaic),
+            (true, true, 0x40) => Some(Charset::Drcs0),
+            (false, true, 0x41..=0x4F) => Some(Charset::Drcs),
+            (false, true, 0x70) => Some(Charset::Macro),
+            _ => None,
+        }
+    }
+
+    /// Whether the set's characters have Unicode equivalents.
+    fn has_unicode(self) -> bool {
+        !matches!(
+            self,
+            Charset::Mosaic | Charset::Drcs0 | Charset::Drcs | Charset::Macro
+        )
+    }
+
+    fn is_two_byte(self) -> bool {
+        matches!(
+            self,
+            Charset::Kanji
+                | Charset::JisKanjiPlane1
+                | Charset::JisKanjiPlane2
+                | Charset::AdditionalSymbols
+                | Charset::Drcs0
+        )
+    }
+
+    /// The G0-G3 element that the encoder uses for the set, and the escape
+    /// sequence that designates it there.
+    fn encoder_designation(self) -> (u8, &'static [u8]) {
+        match self {
+            Charset::Kanji => (0, b"\x1B$B"),
+            Charset::Alphanumeric => (1, b"\x1B)J"),
+            Charset::Hiragana => (2, b"\x1B*0"),
+            Charset::Katakana => (3, b"\x1B+1"),
+            _ => unreachable!(),
+        }
+    }
+}
+
+pub fn encode_from_str<'a>(
+    input: &str,
+    out_buffer: &'a mut [u8],
+    state: &mut State,
+    is_end: bool,
+) -> EncodeResult<'a> {
+    // Do the encode.
+    let mut input_i = 0;
+    let mut output_i = 0;
+    for (offset, c) in input.char_indices() {
+        let (charset, mut code, code_len) = if let Some(x) = encode_char(c) {
+            x
+        } else {
+            return Err(EncodeError {
+                character: c,
+                error_range: (offset, offset + c.len_utf8()),
+                output_bytes_written: output_i,
+            });
+        };
+
+        // Work out the designation and invocation needed first, if any.
+        let mut new_state = *state;
+        let mut prefix = [0u8; 5];
+        let mut prefix_len = 0;
+        if let Some(charset) = charset {
+            let (g, designation) = charset.encoder_designation();
+            if new_state.g[g as usize] != charset {
+                prefix[..designation.len()].copy_from_slice(designation);
+                prefix_len += designation.len();
+                new_state.g[g as usize] = charset;
+            }
+            if g < 2 {
+                if new_state.gl != g {
+                    prefix[prefix_len] = if g == 0 { LS0 } else { LS1 };
+                    prefix_len += 1;
+                    new_state.gl = g;
+                }
+            } else {
+                if new_state.gr != g {
+                    prefix[prefix_len] = ESC;
+                    prefix[prefix_len + 1] = if g == 2 { 0x7D } else { 0x7C };
+                    prefix_len += 2;
+                    new_state.gr = g;
+                }
+                code[0] |= 0x80;
+                code[1] |= 0x80;
+            }
+        }
+
+        // If this is the last character of the text, make sure there's room
+        // to return to the initial invocations after it.
+        let is_last = is_end && (offset + c.len_utf8()) == input.len();
+        let end_len = if is_last { reset_len(&new_state) } else { 0 };
+        if (output_i + prefix_len + code_len + end_len) > out_buffer.len() {
+            break;
+        }
+        out_buffer[output_i..(output_i + prefix_len)].copy_from_slice(&prefix[..prefix_len]);
+        output_i += prefix_len;
+        out_buffer[output_i..(output_i + code_len)].copy_from_slice(&code[..code_len]);
+        output_i += code_len;
+        if is_last {
+            output_i += write_reset(&mut new_state, &mut out_buffer[output_i..]);
+        }
+        *state = new_state;
+        input_i = offset + 1;
+    }
+
+    // Return to the initial invocations at the end of the text, if that
+    // hasn't happened already (e.g. when the final chunk is empty).
+    if is_end && input_i >= input.len() && (output_i + reset_len(state)) <= out_buffer.len() {
+        output_i += write_reset(state, &mut out_buffer[output_i..]);
+    }
+
+    // Calculate how much of the input was consumed.
+    if input_i > input.len() {
+        input_i = input.len();
+    } else {
+        while !input.is_char_boundary(input_i) {
+            input_i += 1;
+        }
+    }
+
+    Ok((&out_buffer[..output_i], input_i))
+}
+
+pub fn decode_to_str<'a>(
+    input: &[u8],
+    out_buffer: &'a mut [u8],
+    state: &mut State,
+    is_end: bool,
+) -> DecodeResult<'a> {
+    let mut input_i = 0;
+    let mut output_i = 0;
+    let mut buf = [0u8; 8]; // For encoding utf8 codepoints.
+
+    while input_i < input.len() {
+        if output_i >= out_buffer.len() {
+            break;
+        }
+
+        let (unit, unit_len) = match parse_unit(&input[input_i..], state) {
+            Ok(x) => x,
+            Err(ParseError::Incomplete) => {
+                if !is_end {
+                    // Not enough bytes yet, but not end-of-input, so no
+                    // problem.
+                    break;
+                }
+                // Error: truncated sequence at end of input.
+                return Err(DecodeError {
+                    cause: DecodeErrorCause::InvalidData,
+                    error_range: (input_i, input.len()),
+                    output_bytes_written: output_i,
+                });
+            }
+            Err(ParseError::Invalid(len)) => {
+                // Error: invalid byte or sequence.
+                return Err(DecodeError {
+                    cause: DecodeErrorCause::InvalidData,
+                    error_range: (input_i, input_i + len),
+                    output_bytes_written: output_i,
+                });
+            }
+        };
+
+        // Get our decoded data, or apply the control function.
+        let string = match unit {
+            Unit::Char(charset, byte_1, byte_2) => {
+                if let Some(s) = decode_char(charset, byte_1, byte_2, &mut buf) {
+                    s
+                } else if charset.has_unicode() {
+                    // Error: undefined code.
+                    return Err(DecodeError {
+                        cause: DecodeErrorCause::InvalidData,
+                        error_range: (input_i, input_i + unit_len),
+                        output_bytes_written: output_i,
+                    });
+                } else {
+                    // Error: character with no Unicode equivalent.
+                    return Err(DecodeError {
+                        cause: DecodeErrorCause::UnknownConversion,
+                        error_range: (input_i, input_i + unit_len),
+                        output_bytes_written: output_i,
+                    });
+                }
+            }
+            Unit::Space => " ",
+            Unit::LineBreak => "\n",
+            Unit::Designate(g, charset) => {
+                state.g[g as usize] = charset;
+                input_i += unit_len;
+                continue;
+            }
+            Unit::InvokeGl(g) => {
+                state.gl = g;
+                input_i += unit_len;
+                continue;
+            }
+            Unit::InvokeGr(g) => {
+                state.gr = g;
+                input_i += unit_len;
+                continue;
+            }
+            Unit::Repeat(0) => {
+                // Error: repeat to the end of the line, which depends on the
+                // display format rather than on the text.
+                return Err(DecodeError {
+                    cause: DecodeErrorCause::UnknownConversion,
+                    error_range: (input_i, input_i + unit_len),
+                    output_bytes_written: output_i,
+                });
+            }
+            Unit::Repeat(count) => {
+                state.repeat = count;
+                input_i += unit_len;
+                continue;
+            }
+            Unit::Ignore => {
+                input_i += unit_len;
+                continue;
+            }
+        };
+
+        // Copy decoded data to output.
+        if (output_i + string.len()) > out_buffer.len() {
+            // Not enough space in output buffer.
+            break;
+        }
+        out_buffer[output_i..(output_i + string.len())].copy_from_slice(string.as_bytes());
+        output_i += string.len();
+
+        // If RPC asked for more copies, leave the character in the input
+        // for the next one.
+        if state.repeat > 1 {
+            state.repeat -= 1;
+            continue;
+        }
+
+        // Update our counters.
+        state.repeat = 0;
+        input_i += unit_len;
+    }
+
+    Ok((
+        unsafe { core::str::from_utf8_unchecked(&out_buffer[..output_i]) },
+        input_i,
+    ))
+}
+
+/// Returns the set to use for `c`, its code in that set in 7-bit form, and
+/// the length of the code.  The set is `None` for the characters that are
+/// encoded as control functions.
+fn encode_char(c: char) -> Option<(Option<Charset>, [u8; 2], usize)> {
+    let code = c as u32;
+    match code {
+        0x20 => Some((None, [SP, 0], 1)),
+        0x0A => Some((None, [APR, 0], 1)),
+        0x21..=0x7E if code != 0x5C && code != 0x7E => {
+            Some((Some(Charset::Alphanumeric), [code as u8, 0], 1))
+        }
+        0xA5 => Some((Some(Charset::Alphanumeric), [0x5C, 0], 1)),
+        0x203E => Some((Some(Charset::Alphanumeric), [0x7E, 0], 1)),
+        0x3041..=0x3093 => Some((
+            Some(Charset::Hiragana),
+            [(code - 0x3041 + 0x21) as u8, 0],
+            1,
+        )),
+        0x30A1..=0x30F6 => Some((
+            Some(Charset::Katakana),
+            [(code - 0x30A1 + 0x21) as u8, 0],
+            1,
+        )),
+        _ => {
+            let ptr = if let Ok(i) = JIS_ENCODE_TABLE.binary_search_by_key(&c, |x| x.0) {
+                // Convert the table's Shift JIS bytes back to a pointer.
+                let bytes = JIS_ENCODE_TABLE[i].1;
+                let lead_offset = if bytes[0] < 0xA0 { 0x81 } else { 0xC1 };
+                let trail_offset = if bytes[1] < 0x7F { 0x40 } else { 0x41 };
+                (bytes[0] as usize - lead_offset) * 188 + bytes[1] as usize - trail_offset
+            } else if let Ok(i) = SYMBOL_ENCODE_TABLE.binary_search_by_key(&c, |x| x.0) {
+                (SYMBOL_ROW as usize - 0x21) * 94 + SYMBOL_ENCODE_TABLE[i].1 as usize
+            } else {
+                return None;
+            };
+            Some((
+                Some(Charset::Kanji),
+                [(ptr / 94 + 0x21) as u8, (ptr % 94 + 0x21) as u8],
+                2,
+            ))
+        }
+    }
+}
+
+/// The number of bytes needed to return to the initial invocations.
+fn reset_len(state: &State) -> usize {
+    (if state.gl != 0 { 1 } else { 0 }) + (if state.gr != 2 { 2 } else { 0 })
+}
+
+/// Writes the locking shifts that return to the initial invocations,
+/// returning the number of bytes written.
+fn write_reset(state: &mut State, out_buffer: &mut [u8]) -> usize {
+    let mut len = 0;
+    if state.gl != 0 {
+        out_buffer[len] = LS0;
+        len += 1;
+        state.gl = 0;
+    }
+    if state.gr != 2 {
+        out_buffer[len] = ESC;
+        out_buffer[len + 1] = 0x7D;
+        len += 2;
+        state.gr = 2;
+    }
+    len
+}
+
+/// A single unit of the input: a character or a control function.
+enum Unit {
+    /// A character of a graphic set, in 7-bit form.  The second byte is only
+    /// used for two-byte sets.
+    Char(Charset, u8, u8),
+    Space,
+    LineBreak,
+    Designate(u8, Charset),
+    InvokeGl(u8),
+    InvokeGr(u8),
+    Repeat(u8),
+    Ignore,
+}
+
+enum ParseError {
+    /// The unit continues past the end of the input.
+    Incomplete,
+    /// The unit is invalid.  Contains the length of the error.
+    Invalid(usize),
+}
+
+/// Parses the unit at the start of `input`, returning it and its length.
+fn parse_unit(input: &[u8], state: &State) -> Result<(Unit, usize), ParseError> {
+    let get = |i: usize| input.get(i).cloned().ok_or(ParseError::Incomplete);
+
+    match input[0] {
+        // NUL, BEL, APB, APF, APD, APU, CS, CAN, RS, US, and DEL.
+        0x00 | 0x07..=0x0C | 0x18 | 0x1E | 0x1F | 0x7F => Ok((Unit::Ignore, 1)),
+        APR => Ok((Unit::LineBreak, 1)),
+        LS1 => Ok((Unit::InvokeGl(1), 1)),
+        LS0 => Ok((Unit::InvokeGl(0), 1)),
+        // PAPF.
+        0x16 => get(1).map(|_| (Unit::Ignore, 2)),
+        SS2 | SS3 => {
+            let g = if input[0] == SS2 { 2 } else { 3 };
+            parse_char(&input[1..], state.g[g], false)
+                .map(|(unit, len)| (unit, len + 1))
+                .map_err(|e| match e {
+                    ParseError::Invalid(_) => ParseError::Invalid(1),
+                    e => e,
+                })
+        }
+        ESC => parse_escape(input),
+        // APS.
+        0x1C => get(2).map(|_| (Unit::Ignore, 3)),
+        SP => Ok((Unit::Space, 1)),
+        0x21..=0x7E => parse_char(input, state.g[state.gl as usize], false),
+        // The color controls, SSZ, MSZ, NSZ, SPL, and STL.
+        0x80..=0x8A | 0x99 | 0x9A => Ok((Unit::Ignore, 1)),
+        // SZX, FLC, POL, WMM, and HLC.
+        0x8B | 0x91 | 0x93 | 0x94 | 0x97 => get(1).map(|_| (Unit::Ignore, 2)),
+        // COL and CDC, which take an extra parameter after 0x20.
+        0x90 | 0x92 => get(1).and_then(|p| {
+            if p == 0x20 {
+                get(2).map(|_| (Unit::Ignore, 3))
+            } else {
+                Ok((Unit::Ignore, 2))
+            }
+        }),
+        // MACRO.  A macro definition runs until MACRO 0x4F.
+        0x95 => match get(1)? {
+            0x40 | 0x41 => (2..input.len())
+                .find(|&i| input[i - 1] == 0x95 && input[i] == 0x4F)
+                .map(|i| (Unit::Ignore, i + 1))
+                .ok_or(ParseError::Incomplete),
+            0x4F => Ok((Unit::Ignore, 2)),
+            _ => Err(ParseError::Invalid(1)),
+        },
+        // RPC.
+        0x98 => match get(1)? {
+            p @ 0x40..=0x7F => Ok((Unit::Repeat(p - 0x40), 2)),
+            _ => Err(ParseError::Invalid(1)),
+        },
+        // CSI, whose parameters run until the final byte.
+        0x9B => match input[1..].iter().position(|&b| !(0x20..=0x3F).contains(&b)) {
+            Some(i) if (0x40..=0x7E).contains(&input[i + 1]) => Ok((Unit::Ignore, i + 2)),
+            Some(_) => Err(ParseError::Invalid(1)),
+            None => Err(ParseError::Incomplete),
+        },
+        // TIME.
+        0x9D => match get(1)? {
+            0x20 | 0x28 => get(2).map(|_| (Unit::Ignore, 3)),
+            0x29 => (2..input.len())
+                .find(|&i| (0x40..=0x43).contains(&input[i]))
+                .map(|i| (Unit::Ignore, i + 1))
+                .ok_or(ParseError::Incomplete),
+            _ => Err(ParseError::Invalid(1)),
+        },
+        0xA1..=0xFE => parse_char(input, state.g[state.gr as usize], true),
+        _ => Err(ParseError::Invalid(1)),
+    }
+}
+
+/// Parses a character of `charset` at the start of `input`, in GR if
+/// `is_gr` is true and in GL otherwise.
+fn parse_char(input: &[u8], charset: Charset, is_gr: bool) -> Result<(Unit, usize), ParseError> {
+    let range = if is_gr { 0xA1..=0xFE } else { 0x21..=0x7E };
+    let byte_1 = *input.first().ok_or(ParseError::Incomplete)?;
+    if !range.contains(&byte_1) {
+        return Err(ParseError::Invalid(1));
+    }
+    if !charset.is_two_byte() {
+        return Ok((Unit::Char(charset, byte_1 & 0x7F, 0), 1));
+    }
+    let byte_2 = *input.get(1).ok_or(ParseError::Incomplete)?;
+    if !range.contains(&byte_2) {
+        // Error: invalid trailing byte.  The trailing byte remains part of
+        // the stream, and thus is not treated as part of the error.
+        return Err(ParseError::Invalid(1));
+    }
+    Ok((Unit::Char(charset, byte_1 & 0x7F, byte_2 & 0x7F), 2))
+}
+
+/// Parses the escape sequence at the start of `input`.
+fn parse_escape(input: &[u8]) -> Result<(Unit, usize), ParseError> {
+    let get = |i: usize| input.get(i).cloned().ok_or(ParseError::Incomplete);
+    let designate = |g: u8, byte: u8, is_two_byte: bool, is_drcs: bool, len: usize| {
+        Charset::from_final(byte, is_two_byte, is_drcs)
+            .map(|charset| (Unit::Designate(g, charset), len))
+            .ok_or(ParseError::Invalid(1))
+    };
+
+    match get(1)? {
+        // LS2, LS3, LS1R, LS2R, and LS3R.
+        0x6E => Ok((Unit::InvokeGl(2), 2)),
+        0x6F => Ok((Unit::InvokeGl(3), 2)),
+        0x7E => Ok((Unit::InvokeGr(1), 2)),
+        0x7D => Ok((Unit::InvokeGr(2), 2)),
+        0x7C => Ok((Unit::InvokeGr(3), 2)),
+        // One-byte sets.
+        i @ 0x28..=0x2B => match get(2)? {
+            0x20 => designate(i - 0x28, get(3)?, false, true, 4),
+            byte => designate(i - 0x28, byte, false, false, 3),
+        },
+        // Two-byte sets.  G0 can be designated without an intermediate byte.
+        0x24 => match get(2)? {
+            i @ 0x28..=0x2B => match get(3)? {
+                0x20 => designate(i - 0x28, get(4)?, true, true, 5),
+                byte => designate(i - 0x28, byte, true, false, 4),
+            },
+            byte => designate(0, byte, true, false, 3),
+        },
+        _ => Err(ParseError::Invalid(1)),
+    }
+}
+
+/// Decodes a character of `charset`, given in 7-bit form, to utf8 in `buf`,
+/// returning the resulting `str`.  Returns `None` if the character is
+/// unassigned or has no Unicode equivalent.
+fn decode_char(charset: Charset, byte_1: u8, byte_2: u8, buf: &mut [u8; 8]) -> Option<&str> {
+    let ptr = (byte_1 as usize - 0x21) * 94 + (byte_2 as usize).saturating_sub(0x21);
+    let c = match charset {
+        Charset::Alphanumeric => match byte_1 {
+            0x5C => '\u{A5}',
+            0x7E => '\u{203E}',
+            _ => byte_1 as char,
+        },
+        Charset::Hiragana | Charset::Katakana => {
+            let (first, last) = if charset == Charset::Hiragana {
+                (0x3041, 0x73)
+            } else {
+                (0x30A1, 0x76)
+            };
+            match byte_1 {
+                _ if byte_1 <= last => core::char::from_u32(byte_1 as u32 - 0x21 + first).unwrap(),
+                // The iteration marks, which are at the same offset from the
+                // start of the kana in both blocks.
+                0x77 | 0x78 => core::char::from_u32(first + 0x5C + byte_1 as u32 - 0x77).unwrap(),
+                0x79..=0x7E => KANA_SYMBOLS[byte_1 as usize - 0x79],
+                _ => return None,
+            }
+        }
+        Charset::JisX0201Katakana => match byte_1 {
+            0x21..=0x5F => core::char::from_u32(byte_1 as u32 - 0x21 + 0xFF61).unwrap(),
+            _ => return None,
+        },
+        Charset::Kanji | Charset::AdditionalSymbols => {
+            let table_c = if byte_1 >= SYMBOL_ROW {
+                SYMBOL_DECODE_TABLE.get(ptr - (SYMBOL_ROW as usize - 0x21) * 94)
+            } else if charset == Charset::Kanji {
+                JIS_DECODE_TABLE.get(ptr)
+            } else {
+                None
+            };
+            match table_c {
+                Some(&'�') | None => return None,
+                Some(&c) => c,
+            }
+        }
+        Charset::JisKanjiPlane1 => return jis0213::decode_ptr(ptr, buf),
+        Charset::JisKanjiPlane2 => return jis0213::decode_ptr(8836 + ptr, buf),
+        Charset::Mosaic | Charset::Drcs0 | Charset::Drcs | Charset::Macro => return None,
+    };
+    Some(c.encode_utf8(&mut buf[..]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use {DecodeError, DecodeErrorCause, EncodeError};
+
+    #[test]
+    fn encode_01() {
+        let text = "字幕 ABCあカ\n¥🈐";
+        let mut buf = [0u8; 64];
+        let (encoded, consumed_count) =
+            encode_from_str(text, &mut buf, &mut State::new(), true).unwrap();
+        assert_eq!(consumed_count, text.len());
+        assert_eq!(
+            encoded,
+            &b"\x3B\x7A\x4B\x6B\x20\x0EABC\xA2\x1B\x7C\xAB\x0D\\\x0F\x7A\x55\x1B\x7D"[..]
+        );
+    }
+
+    #[test]
+    fn encode_02() {
+        // Not enough room for the shift and the character.
+        let mut buf = [0u8; 2];
+        let (encoded, consumed_count) =
+            encode_from_str("字a", &mut buf, &mut State::new(), true).unwrap();
+        assert_eq!(consumed_count, 3);
+        assert_eq!(encoded, b"\x3B\x7A");
+    }
+
+    #[test]
+    fn encode_03() {
+        // The state carries over between chunks, and the final empty chunk
+        // returns to the initial invocations.
+        let mut buf = [0u8; 64];
+        let mut state = State::new();
+        {
+            let (encoded, consumed_count) =
+                encode_from_str("aカ", &mut buf, &mut state, false).unwrap();
+            assert_eq!(consumed_count, 4);
+            assert_eq!(encoded, b"\x0Ea\x1B\x7C\xAB");
+        }
+        {
+            let (encoded, consumed_count) =
+                encode_from_str("b", &mut buf, &mut state, false).unwrap();
+            assert_eq!(consumed_count, 1);
+            assert_eq!(encoded, b"b");
+        }
+        {
+            let (encoded, consumed_count) =
+                encode_from_str("", &mut buf, &mut state, true).unwrap();
+            assert_eq!(consumed_count, 0);
+            assert_eq!(encoded, b"\x0F\x1B\x7D");
+        }
+    }
+
+    #[test]
+    fn encode_04() {
+        // Not enough room to return to the initial invocations after the
+        // last character.
+        let mut buf = [0u8; 3];
+        let (encoded, consumed_count) =
+            encode_from_str("ab", &mut buf, &mut State::new(), true).unwrap();
+        assert_eq!(consumed_count, 1);
+        assert_eq!(encoded, b"\x0Ea");
+    }
+
+    #[test]
+    fn encode_05() {
+        // The additional kanji, for characters that aren't in JIS X 0208.
+        let mut buf = [0u8; 64];
+        let (encoded, consumed_count) =
+            encode_from_str("高髙崎﨑", &mut buf, &mut State::new(), true).unwrap();
+        assert_eq!(consumed_count, 12);
+        assert_eq!(encoded, b"\x39\x62\x76\x47\x3A\x6A\x75\x40");
+    }
+
+    #[test]
+    fn encode_error_01() {
+        let mut buf = [0u8; 64];
+        assert_eq!(
+            encode_from_str("a~", &mut buf, &mut State::new(), true),
+            Err(EncodeError {
+                character: '~',
+                error_range: (1, 2),
+                output_bytes_written: 2,
+            }),
+        );
+    }
+
+    #[test]
+    fn encode_error_02() {
+        let mut buf = [0u8; 64];
+        assert_eq!(
+            encode_from_str("字\t", &mut buf, &mut State::new(), true),
+            Err(EncodeError {
+                character: '\t',
+                error_range: (3, 4),
+                output_bytes_written: 2,
+            }),
+        );
+    }
+
+    #[test]
+    fn decode_01() {
+        let mut buf = [0u8; 64];
+        let (decoded, consumed_count) = decode_to_str(
+            b"\x3B\x7A\x4B\x6B\x20\x0EABC\xA2\x1B\x7C\xAB\x0D\\\x0F\x7A\x55\x1B\x7D",
+            &mut buf,
+            &mut State::new(),
+            true,
+        )
+        .unwrap();
+        assert_eq!(consumed_count, 20);
+        assert_eq!(decoded, "字幕 ABCあカ\n¥🈐");
+    }
+
+    #[test]
+    fn decode_02() {
+        // Single shifts, and designations of other sets.
+        let mut buf = [0u8; 64];
+        let (decoded, consumed_count) = decode_to_str(
+            b"\x19\x22\x1D\x22\x1B\x29\x49\x0E\x31\x1B\x24\x2B\x3A\x1D\x21\x21\x0F\x1B\x28\x4A\x21",
+            &mut buf,
+            &mut State::new(),
+            true,
+        )
+        .unwrap();
+        assert_eq!(consumed_count, 21);
+        assert_eq!(decoded, "あア\u{FF71}𠂉!");
+    }
+
+    #[test]
+    fn decode_03() {
+        // Control functions are skipped, and RPC repeats.
+        let mut buf = [0u8; 64];
+        let (decoded, consumed_count) = decode_to_str(
+            b"\x89\x90\x20\x47\x9B\x31\x37\x30\x3B\x33\x30\x20\x61\xA2\x98\x43\xA1\x9D\x20\x41",
+            &mut buf,
+            &mut State::new(),
+            true,
+        )
+        .unwrap();
+        assert_eq!(consumed_count, 20);
+        assert_eq!(decoded, "あぁぁぁ");
+    }
+
+    #[test]
+    fn decode_04() {
+        // Chunks split in the middle of an escape sequence.
+        let mut buf = [0u8; 64];
+        let mut state = State::new();
+        {
+            let (decoded, consumed_count) =
+                decode_to_str(b"\xA2\x1B\x28", &mut buf, &mut state, false).unwrap();
+            assert_eq!(consumed_count, 1);
+            assert_eq!(decoded, "あ");
+        }
+        {
+            let (decoded, consumed_count) =
+                decode_to_str(b"\x1B\x28\x4A\x21", &mut buf, &mut state, true).unwrap();
+            assert_eq!(consumed_count, 4);
+            assert_eq!(decoded, "!");
+        }
+    }
+
+    #[test]
+    fn decode_05() {
+        // RPC with more copies than fit in the output buffer.
+        let input = [0x98, 0x7F, 0x20, 0x20];
+        let mut buf = [0u8; 32];
+        let mut state = State::new();
+        let mut decoded = [0u8; 64];
+        let mut input_i = 0;
+        let mut output_i = 0;
+        for _ in 0..4 {
+            let (chunk, consumed_count) =
+                decode_to_str(&input[input_i..], &mut buf, &mut state, true).unwrap();
+            decoded[output_i..(output_i + chunk.len())].copy_from_slice(chunk.as_bytes());
+            input_i += consumed_count;
+            output_i += chunk.len();
+        }
+        assert_eq!(input_i, 4);
+        assert_eq!(output_i, 64);
+        assert!(decoded.iter().all(|&b| b == b' '));
+    }
+
+    #[test]
+    fn decode_06() {
+        // The additional kanji in rows 85-86, in both the Kanji and the
+        // additional symbols sets.
+        let mut buf = [0u8; 64];
+        let (decoded, consumed_count) = decode_to_str(
+            b"\x75\x21\x76\x47\x1B\x24\x3B\x75\x40",
+            &mut buf,
+            &mut State::new(),
+            true,
+        )
+        .unwrap();
+        assert_eq!(consumed_count, 9);
+        assert_eq!(decoded, "\u{3402}髙﨑");
+    }
+
+    #[test]
+    fn decode_error_01() {
+        // DRCS characters have no Unicode equivalent.
+        let mut buf = [0u8; 64];
+        let mut state = State::new();
+        assert_eq!(
+            decode_to_str(b"\x1B\x24\x28\x20\x40\x21\x21", &mut buf, &mut state, true),
+            Err(DecodeError {
+                cause: DecodeErrorCause::UnknownConversion,
+                error_range: (5, 7),
+                output_bytes_written: 0,
+            }),
+        );
+    }
+
+    #[test]
+    fn decode_error_02() {
+        // Unknown final byte.
+        let mut buf = [0u8; 64];
+        assert_eq!(
+            decode_to_str(b"\xA2\x1B\x28\x7A", &mut buf, &mut State::new(), true),
+            Err(DecodeError {
+                cause: DecodeErrorCause::InvalidData,
+                error_range: (1, 2),
+                output_bytes_written: 3,
+            }),
+        );
+    }
+
+    #[test]
+    fn decode_error_03() {
+        // Truncated at the end of the input.
+        let mut buf = [0u8; 64];
+        assert_eq!(
+            decode_to_str(b"\xA2\x3B", &mut buf, &mut State::new(), true),
+            Err(DecodeError {
+                cause: DecodeErrorCause::InvalidData,
+                error_range: (1, 2),
+                output_bytes_written: 3,
+            }),
+        );
+    }
+
+    #[test]
+    fn decode_error_04() {
+        // Undefined Kanji code.
+        let mut buf = [0u8; 64];
+        assert_eq!(
+            decode_to_str(b"\x7E\x7E", &mut buf, &mut State::new(), true),
+            Err(DecodeError {
+                cause: DecodeErrorCause::InvalidData,
+                error_range: (0, 2),
+                output_bytes_written: 0,
+            }),
+        );
+    }
+
+    #[test]
+    fn decode_error_05() {
+        // RPC to the end of the line.
+        let mut buf = [0u8; 64];
+        assert_eq!(
+            decode_to_str(b"\xA2\x98\x40\xA1", &mut buf, &mut State::new(), true),
+            Err(DecodeError {
+                cause: DecodeErrorCause::UnknownConversion,
+                error_range: (1, 3),
+                output_bytes_written: 3,
+            }),
+        );
+    }
+
+    #[test]
+    fn decode_error_06() {
+        // The unused rows 87-89.
+        let mut buf = [0u8; 64];
+        assert_eq!(
+            decode_to_str(b"\x77\x21", &mut buf, &mut State::new(), true),
+            Err(DecodeError {
+                cause: DecodeErrorCause::InvalidData,
+                error_range: (0, 2),
+                output_bytes_written: 0,
+            }),
+        );
+    }
+}
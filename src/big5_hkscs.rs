@@ -0,0 +1,237 @@
+//! BIG5 with the Hong Kong Supplementary Character Set, HKSCS-2008.
+//!
+//! Unlike the WHATWG variant of BIG5, the encoder covers the entire HKSCS
+//! repertoire, including the rows with lead bytes 0x87-0xA0 and the codes
+//! that map to composed sequences of two Unicode code points.
+//!
+//! A handful of codes in row 0xA2 are duplicates of other codes, so decoding
+//! them is lossy:
+//!
+//! - The box-drawing codes 0xA27E and 0xA2A1-0xA2A7 are duplicates of codes
+//!   in row 0xF9, and encoding always produces the row 0xF9 versions.
+//! - 0xA2CC and 0xA2CE decode to the Han characters 十 (U+5341) and 卅
+//!   (U+5345), which encode to 0xA451 and 0xA4CA.
+//!
+//! Apart from that, conversion in both directions is lossless.
+
+use dbcs::{self, Dbcs};
+use {DecodeResult, EncodeResult};
+
+// Generated by `encoding_tables/big5_hkscs/generate_big5_hkscs.py`.
+// Contains ENCODE_TABLE, DECODE_TABLE, and SEQUENCES.
+include!("generated/big5_hkscs/big5_hkscs_tables.rs.inc");
+
+static BIG5_HKSCS: Dbcs = Dbcs {
+    lead_ranges: &[(0x81, 0xFE)],
+    trail_ranges: &[(0x40, 0x7E), (0xA1, 0xFE)],
+    decode_single: |byte| {
+        if byte <= 127 {
+            Some(byte as char)
+        } else {
+            None
+        }
+    },
+    encode_single: |c| {
+        if (c as u32) <= 127 {
+            Some(c as u8)
+        } else {
+            None
+        }
+    },
+    decode_table: &DECODE_TABLE,
+    encode_table: &ENCODE_TABLE,
+    pua_ranges: &[],
+    sequences: &SEQUENCES,
+    encode_aliases: &[],
+};
+
+pub fn encode_from_str<'a>(
+    input: &str,
+    out_buffer: &'a mut [u8],
+    is_end: bool,
+) -> EncodeResult<'a> {
+    dbcs::encode_from_str(&BIG5_HKSCS, input, out_buffer, is_end)
+}
+
+/// Note: is potentially lossy.
+pub fn decode_to_str<'a>(input: &[u8], out_buffer: &'a mut [u8], is_end: bool) -> DecodeResult<'a> {
+    dbcs::decode_to_str(&BIG5_HKSCS, input, out_buffer, is_end)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use {DecodeError, DecodeErrorCause, EncodeError};
+
+    // Helper function.
+    fn correct_decode(input: &[u8], expected_output: &str) {
+        let mut buf = [0u8; 256];
+        assert_eq!(
+            decode_to_str(input, &mut buf, true),
+            Ok((expected_output, input.len())),
+        );
+    }
+
+    // Helper function.
+    fn error_decode(input: &[u8], error_range: (usize, usize), bytes_written: usize) {
+        let mut buf = [0u8; 256];
+        assert_eq!(
+            decode_to_str(input, &mut buf, true),
+            Err(DecodeError {
+                cause: DecodeErrorCause::InvalidData,
+                error_range,
+                output_bytes_written: bytes_written,
+            }),
+        );
+    }
+
+    // Helper function.
+    fn correct_encode(input: &str, expected_output: &[u8]) {
+        let mut buf = [0u8; 256];
+        assert_eq!(
+            encode_from_str(input, &mut buf, true),
+            Ok((expected_output, input.len())),
+        );
+    }
+
+    #[test]
+    fn decode_01() {
+        // ASCII
+        correct_decode(&[0x61u8, 0x62u8], "\u{0061}\u{0062}");
+
+        // HKSCS rows
+        correct_decode(&[0x87u8, 0x40u8], "\u{43F0}");
+        correct_decode(&[0x88u8, 0x45u8], "\u{2010C}");
+        correct_decode(&[0x8Bu8, 0xFAu8], "\u{20087}");
+        correct_decode(&[0x9Du8, 0xEFu8], "\u{5605}");
+        correct_decode(&[0xFEu8, 0xFEu8], "\u{79D4}");
+
+        // Standard BIG5 rows
+        correct_decode(&[0xA1u8, 0x40u8], "\u{3000}");
+        correct_decode(&[0xA4u8, 0xA4u8], "\u{4E2D}");
+        correct_decode(&[0xC6u8, 0xA1u8], "\u{2460}");
+        correct_decode(&[0xF9u8, 0xFEu8], "\u{FFED}");
+
+        // Composed sequences
+        correct_decode(&[0x88u8, 0x62u8], "\u{00CA}\u{0304}");
+        correct_decode(&[0x88u8, 0x64u8], "\u{00CA}\u{030C}");
+        correct_decode(&[0x88u8, 0x66u8], "\u{00CA}");
+        correct_decode(&[0x88u8, 0xA3u8], "\u{00EA}\u{0304}");
+        correct_decode(&[0x88u8, 0xA5u8], "\u{00EA}\u{030C}");
+        correct_decode(&[0x88u8, 0xA7u8], "\u{00EA}");
+        correct_decode(
+            &[0x61u8, 0x88u8, 0x62u8, 0x62u8],
+            "\u{0061}\u{00CA}\u{0304}\u{0062}",
+        );
+
+        // Decode-only duplicates
+        correct_decode(&[0xA2u8, 0xA4u8], "\u{2550}");
+        correct_decode(&[0xF9u8, 0xF9u8], "\u{2550}");
+        correct_decode(&[0xA2u8, 0xCCu8], "\u{5341}");
+        correct_decode(&[0xA2u8, 0xCEu8], "\u{5345}");
+    }
+
+    #[test]
+    fn decode_02() {
+        let data = [0xA4, 0xB5, 0x87, 0x40, 0x88, 0x62]; // "今䏰Ê̄"
+        let mut buf = [0u8; 6];
+        let (decoded, consumed_count) = decode_to_str(&data, &mut buf, true).unwrap();
+        assert_eq!(consumed_count, 4);
+        assert_eq!(decoded, "今䏰");
+    }
+
+    #[test]
+    fn decode_03() {
+        let data = [0xA4, 0xB5, 0x87, 0x40, 0x88]; // "今䏰" with a dangling lead byte.
+        let mut buf = [0u8; 64];
+        let (decoded, consumed_count) = decode_to_str(&data, &mut buf, false).unwrap();
+        assert_eq!(consumed_count, 4);
+        assert_eq!(decoded, "今䏰");
+    }
+
+    #[test]
+    fn decode_error_01() {
+        error_decode(&[0x80u8, 0x61u8], (0, 1), 0); // Invalid sequence
+        error_decode(&[0xFFu8, 0x61u8], (0, 1), 0); // Invalid sequence
+        error_decode(&[0xFEu8, 0x39u8], (0, 1), 0); // Invalid sequence
+        error_decode(&[0x81u8, 0x40u8], (0, 1), 0); // Undefined code
+        error_decode(&[0x81u8, 0xA0u8], (0, 2), 0); // Invalid sequence
+        error_decode(&[0x81u8, 0xFEu8], (0, 2), 0); // Undefined code
+        error_decode(&[0xA4u8, 0xB5u8, 0x88u8], (2, 3), 3); // Truncated
+    }
+
+    #[test]
+    fn encode_01() {
+        correct_encode("ab", &[0x61, 0x62]);
+        correct_encode("\u{43F0}", &[0x87, 0x40]);
+        correct_encode("\u{2010C}", &[0x88, 0x45]);
+        correct_encode("\u{20087}", &[0x8B, 0xFA]);
+        correct_encode("\u{5605}", &[0x9D, 0xEF]);
+        correct_encode("\u{3000}", &[0xA1, 0x40]);
+        correct_encode("\u{2550}", &[0xF9, 0xF9]);
+        correct_encode("\u{79D4}", &[0xFE, 0xFE]);
+
+        // Code points with decode-only duplicates
+        correct_encode("\u{5341}", &[0xA4, 0x51]);
+        correct_encode("\u{5345}", &[0xA4, 0xCA]);
+    }
+
+    #[test]
+    fn encode_02() {
+        correct_encode("\u{00CA}\u{0304}", &[0x88, 0x62]);
+        correct_encode("\u{00CA}\u{030C}", &[0x88, 0x64]);
+        correct_encode("\u{00EA}\u{0304}", &[0x88, 0xA3]);
+        correct_encode("\u{00EA}\u{030C}", &[0x88, 0xA5]);
+        correct_encode("\u{00CA}", &[0x88, 0x66]);
+        correct_encode("\u{00EA}a", &[0x88, 0xA7, 0x61]);
+        correct_encode("\u{00EA}\u{00CA}\u{030C}", &[0x88, 0xA7, 0x88, 0x64]);
+    }
+
+    #[test]
+    fn encode_03() {
+        // A potential composed sequence split across chunks.
+        let mut buf = [0u8; 64];
+        let (encoded, consumed_count) = encode_from_str("今\u{00CA}", &mut buf, false).unwrap();
+        assert_eq!(consumed_count, 3);
+        assert_eq!(encoded, &[0xA4, 0xB5]);
+    }
+
+    #[test]
+    fn encode_04() {
+        // Output buffer too small for the composed sequence's lead char.
+        let mut buf = [0u8; 3];
+        let (encoded, consumed_count) =
+            encode_from_str("今\u{00CA}\u{0304}", &mut buf, true).unwrap();
+        assert_eq!(consumed_count, 3);
+        assert_eq!(encoded, &[0xA4, 0xB5]);
+    }
+
+    #[test]
+    fn encode_error_01() {
+        let text = "今日😺";
+        let mut buf = [0u8; 64];
+        assert_eq!(
+            encode_from_str(text, &mut buf, true),
+            Err(EncodeError {
+                character: '😺',
+                error_range: (6, 10),
+                output_bytes_written: 4,
+            })
+        );
+    }
+
+    #[test]
+    fn encode_error_02() {
+        // A combining mark that isn't part of a composed sequence.
+        let text = "\u{00CA}\u{0301}";
+        let mut buf = [0u8; 64];
+        assert_eq!(
+            encode_from_str(text, &mut buf, true),
+            Err(EncodeError {
+                character: '\u{0301}',
+                error_range: (2, 4),
+                output_bytes_written: 2,
+            })
+        );
+    }
+}
@@ -0,0 +1,201 @@
+//! BIG5 with the Unicode-at-On 2.50 extensions, also known as BIG5-UAO.
+//!
+//! UAO fills the BIG5 user-defined and undefined areas with thousands of
+//! additional characters, including simplified Chinese and Japanese kana.
+//! It is widely used in Taiwanese BBS archives.
+//!
+//! Some UAO codes duplicate characters from the standard BIG5 area.
+//! Therefore, decoding from BIG5-UAO to `str` is lossy, because it throws
+//! away the distinction between the duplicates.  Encoding from `str` to
+//! BIG5-UAO, however, is lossless, and always produces the code specified by
+//! the UAO 2.50 Unicode-to-BIG5 table.
+//!
+//! The UAO tables also specify "best fit" fallbacks for some characters that
+//! aren't otherwise representable (e.g. '©' to "c ").  Since those are lossy,
+//! they are not used, and the encoder returns an error instead.
+
+use dbcs::{self, Dbcs};
+use {DecodeResult, EncodeResult};
+
+// Generated by `encoding_tables/big5_uao/generate_big5_uao.py`.
+// Contains ENCODE_TABLE and DECODE_TABLE.
+include!("generated/big5_uao/big5_uao_tables.rs.inc");
+
+static BIG5_UAO: Dbcs = Dbcs {
+    lead_ranges: &[(0x81, 0xFE)],
+    trail_ranges: &[(0x40, 0x7E), (0xA1, 0xFE)],
+    decode_single: |byte| {
+        if byte <= 127 {
+            Some(byte as char)
+        } else {
+            None
+        }
+    },
+    encode_single: |c| {
+        if (c as u32) <= 127 {
+            Some(c as u8)
+        } else {
+            None
+        }
+    },
+    decode_table: &DECODE_TABLE,
+    encode_table: &ENCODE_TABLE,
+    pua_ranges: &[],
+    sequences: &[],
+    encode_aliases: &[],
+};
+
+pub fn encode_from_str<'a>(input: &str, out_buffer: &'a mut [u8]) -> EncodeResult<'a> {
+    dbcs::encode_from_str(&BIG5_UAO, input, out_buffer, true)
+}
+
+/// Note: is potentially lossy.
+pub fn decode_to_str<'a>(input: &[u8], out_buffer: &'a mut [u8], is_end: bool) -> DecodeResult<'a> {
+    dbcs::decode_to_str(&BIG5_UAO, input, out_buffer, is_end)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use {DecodeError, DecodeErrorCause, EncodeError};
+
+    // Helper function.
+    fn correct_decode(input: &[u8], expected_output: &str) {
+        let mut buf = [0u8; 256];
+        assert_eq!(
+            decode_to_str(input, &mut buf, true),
+            Ok((expected_output, input.len())),
+        );
+    }
+
+    // Helper function.
+    fn error_decode(input: &[u8], error_range: (usize, usize), bytes_written: usize) {
+        let mut buf = [0u8; 256];
+        assert_eq!(
+            decode_to_str(input, &mut buf, true),
+            Err(DecodeError {
+                cause: DecodeErrorCause::InvalidData,
+                error_range,
+                output_bytes_written: bytes_written,
+            }),
+        );
+    }
+
+    // Helper function.
+    fn correct_encode(input: &str, expected_output: &[u8]) {
+        let mut buf = [0u8; 256];
+        assert_eq!(
+            encode_from_str(input, &mut buf),
+            Ok((expected_output, input.len())),
+        );
+    }
+
+    #[test]
+    fn decode_01() {
+        // ASCII
+        correct_decode(&[0x61u8, 0x62u8], "\u{0061}\u{0062}");
+
+        // Standard BIG5 area
+        correct_decode(&[0xA4u8, 0x40u8], "\u{4E00}");
+        correct_decode(&[0xF9u8, 0xFEu8], "\u{2593}");
+
+        // UAO extensions
+        correct_decode(&[0x81u8, 0x40u8], "\u{4E17}");
+        correct_decode(&[0x84u8, 0xF2u8, 0x86u8, 0x49u8], "测试");
+        correct_decode(&[0xC7u8, 0x55u8], "の");
+        correct_decode(&[0xFEu8, 0xFEu8], "\u{8288}");
+
+        // UAO duplicate of a standard BIG5 character.
+        correct_decode(&[0x82u8, 0x43u8], "\u{845B}");
+        correct_decode(&[0xB8u8, 0xAFu8], "\u{845B}");
+    }
+
+    #[test]
+    fn decode_02() {
+        let data = [0xA4, 0xA4, 0x83, 0xF6, 0xC7, 0x55]; // "中国の"
+        let mut buf = [0u8; 8];
+        let (decoded, consumed_count) = decode_to_str(&data, &mut buf, true).unwrap();
+        assert_eq!(consumed_count, 4);
+        assert_eq!(decoded, "中国");
+    }
+
+    #[test]
+    fn decode_03() {
+        let data = [0xA4, 0xA4, 0x83, 0xF6, 0xC7]; // "中国の" with last byte chopped off.
+        let mut buf = [0u8; 64];
+        let (decoded, consumed_count) = decode_to_str(&data, &mut buf, false).unwrap();
+        assert_eq!(consumed_count, 4);
+        assert_eq!(decoded, "中国");
+    }
+
+    #[test]
+    fn decode_error_01() {
+        error_decode(&[0x80u8, 0x61u8], (0, 1), 0); // Invalid sequence
+        error_decode(&[0xFFu8, 0x61u8], (0, 1), 0); // Invalid sequence
+        error_decode(&[0xFEu8, 0x39u8], (0, 1), 0); // Invalid sequence
+        error_decode(&[0x81u8, 0xA0u8], (0, 2), 0); // Invalid sequence
+        error_decode(&[0xA4u8, 0xA4u8, 0x83u8], (2, 3), 3); // Truncated
+    }
+
+    #[test]
+    fn encode_01() {
+        correct_encode("ab", &[0x61, 0x62]);
+        correct_encode("\u{4E00}", &[0xA4, 0x40]);
+        correct_encode("\u{4E17}", &[0x81, 0x40]);
+        correct_encode(
+            "一小段中文测试♥",
+            &[
+                0xA4, 0x40, 0xA4, 0x70, 0xAC, 0x71, 0xA4, 0xA4, 0xA4, 0xE5, 0x84, 0xF2, 0x86, 0x49,
+                0x9D, 0xDE,
+            ],
+        );
+        correct_encode(
+            "中国の短いテスト",
+            &[
+                0xA4, 0xA4, 0x83, 0xF6, 0xC7, 0x55, 0xB5, 0x75, 0xC6, 0xEA, 0xC7, 0xC2, 0xC7, 0xB5,
+                0xC7, 0xC4,
+            ],
+        );
+
+        // Duplicated characters encode to the standard BIG5 code.
+        correct_encode("\u{845B}", &[0xB8, 0xAF]);
+    }
+
+    #[test]
+    fn encode_02() {
+        let text = "中国の";
+        let mut buf = [0u8; 5];
+        let (encoded, consumed_count) = encode_from_str(text, &mut buf).unwrap();
+        assert_eq!(consumed_count, 6);
+        assert_eq!(encoded, &[0xA4, 0xA4, 0x83, 0xF6]);
+    }
+
+    #[test]
+    fn encode_error_01() {
+        // Best-fit fallbacks from the UAO tables aren't used.
+        let text = "中©";
+        let mut buf = [0u8; 64];
+        assert_eq!(
+            encode_from_str(text, &mut buf),
+            Err(EncodeError {
+                character: '©',
+                error_range: (3, 5),
+                output_bytes_written: 2,
+            })
+        );
+    }
+
+    #[test]
+    fn encode_error_02() {
+        let text = "中😺";
+        let mut buf = [0u8; 64];
+        assert_eq!(
+            encode_from_str(text, &mut buf),
+            Err(EncodeError {
+                character: '😺',
+                error_range: (3, 7),
+                output_bytes_written: 2,
+            })
+        );
+    }
+}
@@ -6,192 +6,59 @@
 //! BIG5 that don't exist in Unicode.  Encoding from `str` to
 //! WHATWG BIG5, however, is lossless.
 
-use core;
-use {DecodeError, DecodeErrorCause, DecodeResult, EncodeError, EncodeResult};
+use dbcs::{self, Dbcs};
+use {DecodeResult, EncodeResult};
 
 // Generated by build.rs  Contains ENCODE_TABLE and DECODE_TABLE.
 include!("generated/whatwg/big5_whatwg_tables.rs.inc");
 
+static BIG5: Dbcs = Dbcs {
+    lead_ranges: &[(0x81, 0xFE)],
+    trail_ranges: &[(0x40, 0x7E), (0xA1, 0xFE)],
+    decode_single: |byte| {
+        if byte <= 127 {
+            Some(byte as char)
+        } else {
+            None
+        }
+    },
+    encode_single: |c| {
+        if (c as u32) <= 127 {
+            Some(c as u8)
+        } else {
+            None
+        }
+    },
+    decode_table: &DECODE_TABLE,
+    encode_table: &ENCODE_TABLE,
+    pua_ranges: &[],
+    // Codes that map to graphemes.
+    sequences: &[
+        ("\u{00CA}\u{0304}", &[0x88, 0x62]),
+        ("\u{00CA}\u{030C}", &[0x88, 0x64]),
+        ("\u{00EA}\u{0304}", &[0x88, 0xA3]),
+        ("\u{00EA}\u{030C}", &[0x88, 0xA5]),
+    ],
+    encode_aliases: &[],
+};
+
 pub fn encode_from_str<'a>(
     input: &str,
     out_buffer: &'a mut [u8],
     is_end: bool,
 ) -> EncodeResult<'a> {
-    // Do the encode.
-    let mut input_i = 0;
-    let mut output_i = 0;
-    let mut itr = input.char_indices();
-    while let Some((offset, c)) = itr.next() {
-        let code = c as u32;
-        if output_i >= out_buffer.len() {
-            break;
-        } else if code <= 127 {
-            // Ascii
-            out_buffer[output_i] = code as u8;
-            output_i += 1;
-            input_i = offset + 1;
-        } else if let Ok(ptr_i) = ENCODE_TABLE.binary_search_by_key(&c, |x| x.0) {
-            if (output_i + 1) < out_buffer.len() {
-                // Handle graphemes.
-                if code == 0xCA || code == 0xEA {
-                    if let Some((offset2, c2)) = itr.clone().next() {
-                        let code2 = c2 as u32;
-                        if let Some(bytes) = map_grapheme(code, code2) {
-                            itr.next();
-                            out_buffer[output_i] = bytes[0];
-                            out_buffer[output_i + 1] = bytes[1];
-                            output_i += 2;
-                            input_i = offset2 + 1;
-                            continue;
-                        }
-                    } else if !is_end {
-                        break;
-                    }
-                }
-
-                // Common case, fetch from table.
-                let big5_bytes = ENCODE_TABLE[ptr_i].1;
-                out_buffer[output_i] = big5_bytes[0];
-                out_buffer[output_i + 1] = big5_bytes[1];
-                output_i += 2;
-                input_i = offset + 1;
-            } else {
-                break;
-            }
-        } else {
-            return Err(EncodeError {
-                character: c,
-                error_range: (offset, offset + c.len_utf8()),
-                output_bytes_written: output_i,
-            });
-        }
-    }
-
-    // Calculate how much of the input was consumed.
-    if input_i > input.len() {
-        input_i = input.len();
-    } else {
-        while !input.is_char_boundary(input_i) {
-            input_i += 1;
-        }
-    }
-
-    Ok((&out_buffer[..output_i], input_i))
-}
-
-fn map_grapheme(a: u32, b: u32) -> Option<[u8; 2]> {
-    match (a, b) {
-        (0xCA, 0x304) => Some([0x88, 0x62]),
-        (0xCA, 0x30C) => Some([0x88, 0x64]),
-        (0xEA, 0x304) => Some([0x88, 0xA3]),
-        (0xEA, 0x30C) => Some([0x88, 0xA5]),
-        _ => None,
-    }
+    dbcs::encode_from_str(&BIG5, input, out_buffer, is_end)
 }
 
 /// Note: is potentially lossy.
 pub fn decode_to_str<'a>(input: &[u8], out_buffer: &'a mut [u8], is_end: bool) -> DecodeResult<'a> {
-    let mut input_i = 0;
-    let mut output_i = 0;
-    let mut buf = [0u8; 4]; // For encoding utf8 codepoints.
-
-    // Loop through the input, getting a byte at a time.
-    let mut itr = input.iter();
-    while let Some(&byte_1) = itr.next() {
-        if output_i >= out_buffer.len() {
-            break;
-        } else if byte_1 <= 127 {
-            // Ascii
-            out_buffer[output_i] = byte_1;
-            output_i += 1;
-            input_i += 1;
-        } else if byte_1 == 0x80 || byte_1 == 0xFF {
-            // Error: invalid leading byte.
-            return Err(DecodeError {
-                cause: DecodeErrorCause::InvalidData,
-                error_range: (input_i, input_i + 1),
-                output_bytes_written: output_i,
-            });
-        } else if let Some(&byte_2) = itr.next() {
-            if byte_2 < 0x40 || byte_2 > 0xFE || (byte_2 > 0x7E && byte_2 < 0xA1) {
-                // Error: invalid trailing byte.
-                // WHATWG dictates that if the second byte is ascii, it
-                // remains part of the stream, and thus (in our case) is
-                // not treated as part of the error.
-                return Err(DecodeError {
-                    cause: DecodeErrorCause::InvalidData,
-                    error_range: (input_i, input_i + if byte_2 <= 127 { 1 } else { 2 }),
-                    output_bytes_written: output_i,
-                });
-            }
-            let big5_ptr = {
-                let lead = (byte_1 as usize - 0x81) * 157;
-                let trail = if byte_2 < 0x7f {
-                    byte_2 as usize - 0x40
-                } else {
-                    byte_2 as usize - 0x62
-                };
-                lead + trail
-            };
-
-            // Get our decoded data, either from the table or by special handling.
-            let string = if big5_ptr >= DECODE_TABLE.len() || DECODE_TABLE[big5_ptr] == '�' {
-                match big5_ptr {
-                    // Special handling for codes that map to graphemes.
-                    1133 => "\u{00CA}\u{0304}",
-                    1135 => "\u{00CA}\u{030C}",
-                    1164 => "\u{00EA}\u{0304}",
-                    1166 => "\u{00EA}\u{030C}",
-                    _ => {
-                        // Error: correctly formed but undefined code.
-                        // WHATWG dictates that if the second byte is ascii, it
-                        // remains part of the stream, and thus (in our case) is
-                        // not treated as part of the error.
-                        return Err(DecodeError {
-                            cause: DecodeErrorCause::InvalidData,
-                            error_range: (input_i, input_i + if byte_2 <= 127 { 1 } else { 2 }),
-                            output_bytes_written: output_i,
-                        });
-                    }
-                }
-            } else {
-                // Encode codepoint to utf8.
-                DECODE_TABLE[big5_ptr].encode_utf8(&mut buf)
-            };
-
-            // Copy decoded data to output.
-            if (output_i + string.len()) > out_buffer.len() {
-                break;
-            }
-            out_buffer[output_i..(output_i + string.len())].copy_from_slice(string.as_bytes());
-
-            // Update our counters.
-            input_i += 2;
-            output_i += string.len();
-        } else {
-            // No available trailing byte.  If not end-of-input, no problem.
-            // Otherwise it's an error.
-            if !is_end {
-                break;
-            } else {
-                return Err(DecodeError {
-                    cause: DecodeErrorCause::InvalidData,
-                    error_range: (input_i, input_i + 1),
-                    output_bytes_written: output_i,
-                });
-            }
-        }
-    }
-
-    Ok((
-        unsafe { core::str::from_utf8_unchecked(&out_buffer[..output_i]) },
-        input_i,
-    ))
+    dbcs::decode_to_str(&BIG5, input, out_buffer, is_end)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use {DecodeError, DecodeErrorCause, EncodeError};
 
     // Helper function.
     fn correct_decode(input: &[u8], expected_output: &str) {
@@ -209,7 +76,7 @@ mod tests {
             decode_to_str(input, &mut buf, true),
             Err(DecodeError {
                 cause: DecodeErrorCause::InvalidData,
-                error_range: error_range,
+                error_range,
                 output_bytes_written: bytes_written,
             }),
         );
@@ -0,0 +1,28 @@
+//! Table lookups for the CNS 11643 character set, shared by `euc_tw` and
+//! `iso_2022_cn`.
+//!
+//! Characters are identified by a pointer, which is computed from the plane,
+//! row, and cell of the character as
+//! `(plane - 1) * 8836 + (row - 1) * 94 + (cell - 1)`.  Planes 1 through 7
+//! are included.  A character that's in more than one plane encodes to the
+//! lowest one.
+
+// Generated by `encoding_tables/cns11643/generate_cns11643.py`.
+// Contains ENCODE_TABLE and DECODE_TABLE.
+include!("generated/cns11643/cns11643_tables.rs.inc");
+
+/// Returns the pointer for `c`, if it's encodable.
+pub fn encode_char(c: char) -> Option<usize> {
+    ENCODE_TABLE
+        .binary_search_by_key(&c, |x| x.0)
+        .ok()
+        .map(|i| ENCODE_TABLE[i].1 as usize)
+}
+
+/// Returns the `char` at `ptr`, if it's assigned.
+pub fn decode_ptr(ptr: usize) -> Option<char> {
+    match DECODE_TABLE.get(ptr) {
+        Some(&'�') | None => None,
+        Some(&c) => Some(c),
+    }
+}
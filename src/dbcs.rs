@@ -0,0 +1,503 @@
+//! A table-driven engine for double-byte code pages, shared by the BIG5
+//! variants, `shiftjis`, and the Mac OS CJK encodings.
+//!
+//! A code page is described by a `Dbcs`, which gives its single-byte area,
+//! its lead and trail byte ranges, and its tables.  Double-byte codes are
+//! identified by a pointer, which is computed from the index of the lead
+//! byte within all of the lead byte ranges and of the trail byte within all
+//! of the trail byte ranges, as `lead_index * trail_count + trail_index`.
+//! This is the same as the pointers of the WHATWG indexes.
+//!
+//! Decoding errors follow the WHATWG rules for resynchronizing: if the
+//! trailing byte of an invalid or undefined code is ascii, it remains part of
+//! the stream, and thus is not treated as part of the error.
+
+use core;
+use {DecodeError, DecodeErrorCause, DecodeResult, EncodeError, EncodeResult};
+
+/// Describes a double-byte code page.
+pub struct Dbcs {
+    /// The valid lead bytes, as inclusive ranges in ascending order.
+    pub lead_ranges: &'static [(u8, u8)],
+    /// The valid trail bytes, as inclusive ranges in ascending order.
+    pub trail_ranges: &'static [(u8, u8)],
+    /// Returns the `char` that a single byte decodes to, if any.
+    pub decode_single: fn(u8) -> Option<char>,
+    /// Returns the single byte that a `char` encodes to, if any.
+    pub encode_single: fn(char) -> Option<u8>,
+    /// The `char` for each pointer, with '�' for unassigned pointers.
+    pub decode_table: &'static [char],
+    /// The double-byte code of each encodable `char`, sorted by `char`.
+    pub encode_table: &'static [(char, [u8; 2])],
+    /// Ranges of pointers that map one-to-one to consecutive private use
+    /// code points, as `(first pointer, last pointer, first code point)`.
+    pub pua_ranges: &'static [(usize, usize, u32)],
+    /// Single and double-byte codes that map to a sequence of `char`s,
+    /// sorted by sequence.
+    pub sequences: &'static [(&'static str, &'static [u8])],
+    /// Encode-only mappings, as `(char, char whose code it encodes to)`.
+    pub encode_aliases: &'static [(char, char)],
+}
+
+impl Dbcs {
+    /// Returns the index of `byte` within `ranges`, if it's in them.
+    fn range_index(ranges: &[(u8, u8)], byte: u8) -> Option<usize> {
+        let mut base = 0;
+        for &(first, last) in ranges {
+            if byte >= first && byte <= last {
+                return Some(base + (byte - first) as usize);
+            }
+            base += (last - first) as usize + 1;
+        }
+        None
+    }
+
+    /// Returns the byte at `index` within `ranges`.
+    fn range_byte(ranges: &[(u8, u8)], mut index: usize) -> Option<u8> {
+        for &(first, last) in ranges {
+            let len = (last - first) as usize + 1;
+            if index < len {
+                return Some(first + index as u8);
+            }
+            index -= len;
+        }
+        None
+    }
+
+    fn trail_count(&self) -> usize {
+        self.trail_ranges
+            .iter()
+            .map(|&(first, last)| (last - first) as usize + 1)
+            .sum()
+    }
+
+    fn is_lead(&self, byte: u8) -> bool {
+        Self::range_index(self.lead_ranges, byte).is_some()
+    }
+
+    /// Returns the pointer for a double-byte code, if it's well formed.
+    fn pointer(&self, lead: u8, trail: u8) -> Option<usize> {
+        let lead_i = Self::range_index(self.lead_ranges, lead)?;
+        let trail_i = Self::range_index(self.trail_ranges, trail)?;
+        Some(lead_i * self.trail_count() + trail_i)
+    }
+
+    /// Returns the double-byte code for a pointer.
+    fn pointer_bytes(&self, ptr: usize) -> Option<[u8; 2]> {
+        let trail_count = self.trail_count();
+        let lead = Self::range_byte(self.lead_ranges, ptr / trail_count)?;
+        let trail = Self::range_byte(self.trail_ranges, ptr % trail_count)?;
+        Some([lead, trail])
+    }
+
+    /// Returns the double-byte code that `c` encodes to on its own, if any.
+    fn encode_double(&self, c: char) -> Option<[u8; 2]> {
+        let c = self
+            .encode_aliases
+            .iter()
+            .find(|x| x.0 == c)
+            .map(|x| x.1)
+            .unwrap_or(c);
+        if let Ok(i) = self.encode_table.binary_search_by_key(&c, |x| x.0) {
+            return Some(self.encode_table[i].1);
+        }
+        let code = c as u32;
+        self.pua_ranges
+            .iter()
+            .find(|&&(first, last, first_code)| {
+                code >= first_code && (code - first_code) as usize <= last - first
+            })
+            .and_then(|&(first, _, first_code)| {
+                self.pointer_bytes(first + (code - first_code) as usize)
+            })
+    }
+
+    /// Returns the sequences that start with `c`, in descending order, so
+    /// that longer sequences come before the sequences they start with.
+    fn sequences_from(
+        &self,
+        c: char,
+    ) -> impl Iterator<Item = &'static (&'static str, &'static [u8])> {
+        let mut buf = [0u8; 4];
+        let c_str = &*c.encode_utf8(&mut buf);
+        let sequences = self.sequences;
+        let start = sequences.partition_point(|x| x.0 < c_str);
+        let len = sequences[start..]
+            .iter()
+            .take_while(|x| x.0.starts_with(c_str))
+            .count();
+        sequences[start..(start + len)].iter().rev()
+    }
+
+    /// Returns the sequence that a single or double-byte code decodes to,
+    /// if any.
+    fn decode_sequence(&self, bytes: &[u8]) -> Option<&'static str> {
+        self.sequences.iter().find(|x| x.1 == bytes).map(|x| x.0)
+    }
+
+    /// Decodes the double-byte code at `ptr` to utf8 in `buf`, returning the
+    /// resulting `str`.  Returns `None` if the code is unassigned.
+    fn decode_double<'a>(
+        &self,
+        ptr: usize,
+        bytes: [u8; 2],
+        buf: &'a mut [u8; 8],
+    ) -> Option<&'a str> {
+        match self.decode_table.get(ptr) {
+            Some(&'�') | None => {}
+            Some(&c) => return Some(c.encode_utf8(&mut buf[..])),
+        }
+        if let Some(&(first, _, first_code)) = self
+            .pua_ranges
+            .iter()
+            .find(|&&(first, last, _)| ptr >= first && ptr <= last)
+        {
+            let c = core::char::from_u32(first_code + (ptr - first) as u32)?;
+            return Some(c.encode_utf8(&mut buf[..]));
+        }
+        self.decode_sequence(&bytes)
+    }
+}
+
+pub fn encode_from_str<'a>(
+    dbcs: &Dbcs,
+    input: &str,
+    out_buffer: &'a mut [u8],
+    is_end: bool,
+) -> EncodeResult<'a> {
+    // Do the encode.
+    let mut input_i = 0;
+    let mut output_i = 0;
+    let mut itr = input.char_indices();
+    'outer: while let Some((offset, c)) = itr.next() {
+        if output_i >= out_buffer.len() {
+            break;
+        }
+
+        // Check for sequences first, longest first.
+        let rest = &input[offset..];
+        for &(seq, bytes) in dbcs.sequences_from(c) {
+            if rest.starts_with(seq) {
+                if (output_i + bytes.len()) > out_buffer.len() {
+                    break 'outer;
+                }
+                out_buffer[output_i..(output_i + bytes.len())].copy_from_slice(bytes);
+                output_i += bytes.len();
+                input_i = offset + seq.len();
+                for _ in 1..seq.chars().count() {
+                    itr.next();
+                }
+                continue 'outer;
+            } else if !is_end && seq.starts_with(rest) {
+                // The rest of the input may be the start of this
+                // sequence, so wait for more input.
+                break 'outer;
+            }
+        }
+
+        if let Some(byte) = (dbcs.encode_single)(c) {
+            out_buffer[output_i] = byte;
+            output_i += 1;
+            input_i = offset + 1;
+        } else if let Some(bytes) = dbcs.encode_double(c) {
+            if (output_i + 1) >= out_buffer.len() {
+                break;
+            }
+            out_buffer[output_i] = bytes[0];
+            out_buffer[output_i + 1] = bytes[1];
+            output_i += 2;
+            input_i = offset + 1;
+        } else {
+            return Err(EncodeError {
+                character: c,
+                error_range: (offset, offset + c.len_utf8()),
+                output_bytes_written: output_i,
+            });
+        }
+    }
+
+    // Calculate how much of the input was consumed.
+    if input_i > input.len() {
+        input_i = input.len();
+    } else {
+        while !input.is_char_boundary(input_i) {
+            input_i += 1;
+        }
+    }
+
+    Ok((&out_buffer[..output_i], input_i))
+}
+
+pub fn decode_to_str<'a>(
+    dbcs: &Dbcs,
+    input: &[u8],
+    out_buffer: &'a mut [u8],
+    is_end: bool,
+) -> DecodeResult<'a> {
+    let mut input_i = 0;
+    let mut output_i = 0;
+    let mut buf = [0u8; 8]; // For encoding utf8 codepoints.
+
+    // Loop through the input, getting a byte at a time.
+    let mut itr = input.iter();
+    while let Some(&byte_1) = itr.next() {
+        if output_i >= out_buffer.len() {
+            break;
+        }
+
+        // Get our decoded data, either from the tables or by special handling.
+        let (string, input_consumed) = if let Some(c) = (dbcs.decode_single)(byte_1) {
+            (&*c.encode_utf8(&mut buf), 1)
+        } else if dbcs.is_lead(byte_1) {
+            let byte_2 = if let Some(&byte) = itr.next() {
+                byte
+            } else if !is_end {
+                // No trailing byte available, but not end-of-input, so no
+                // problem.
+                break;
+            } else {
+                // Error: truncated sequence at end of input.
+                return Err(DecodeError {
+                    cause: DecodeErrorCause::InvalidData,
+                    error_range: (input_i, input_i + 1),
+                    output_bytes_written: output_i,
+                });
+            };
+
+            // Invalid trailing bytes and undefined codes are both errors.
+            // If the second byte is ascii, it remains part of the stream,
+            // and thus is not treated as part of the error.
+            let string = dbcs
+                .pointer(byte_1, byte_2)
+                .and_then(|ptr| dbcs.decode_double(ptr, [byte_1, byte_2], &mut buf));
+            if let Some(string) = string {
+                (string, 2)
+            } else {
+                return Err(DecodeError {
+                    cause: DecodeErrorCause::InvalidData,
+                    error_range: (input_i, input_i + if byte_2 <= 127 { 1 } else { 2 }),
+                    output_bytes_written: output_i,
+                });
+            }
+        } else if let Some(string) = dbcs.decode_sequence(&[byte_1]) {
+            (string, 1)
+        } else {
+            // Error: invalid leading byte.
+            return Err(DecodeError {
+                cause: DecodeErrorCause::InvalidData,
+                error_range: (input_i, input_i + 1),
+                output_bytes_written: output_i,
+            });
+        };
+
+        // Copy decoded data to output.
+        if (output_i + string.len()) > out_buffer.len() {
+            // Not enough space in output buffer.
+            break;
+        }
+        out_buffer[output_i..(output_i + string.len())].copy_from_slice(string.as_bytes());
+
+        // Update our counters.
+        input_i += input_consumed;
+        output_i += string.len();
+    }
+
+    Ok((
+        unsafe { core::str::from_utf8_unchecked(&out_buffer[..output_i]) },
+        input_i,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use {DecodeError, DecodeErrorCause, EncodeError};
+
+    // A small made-up code page with every kind of mapping.  Its lead bytes
+    // are 0x81-0x82 and 0x90, and its trail bytes 0x40-0x41 and 0xA1-0xA2,
+    // so there are four codes per lead byte.
+    static TEST_DBCS: Dbcs = Dbcs {
+        lead_ranges: &[(0x81, 0x82), (0x90, 0x90)],
+        trail_ranges: &[(0x40, 0x41), (0xA1, 0xA2)],
+        decode_single: test_decode_single,
+        encode_single: test_encode_single,
+        decode_table: &['日', '本', '�', 'Ê', '語'],
+        encode_table: &[
+            ('Ê', [0x81, 0xA2]),
+            ('日', [0x81, 0x40]),
+            ('本', [0x81, 0x41]),
+            ('語', [0x82, 0x40]),
+        ],
+        pua_ranges: &[(8, 11, 0xE000)],
+        sequences: &[("Ê\u{304}", &[0x82, 0x41]), ("Ê\u{304}\u{300}", &[0x80])],
+        encode_aliases: &[('\u{2F47}', '日')],
+    };
+
+    fn test_decode_single(byte: u8) -> Option<char> {
+        if byte <= 0x7F {
+            Some(byte as char)
+        } else {
+            None
+        }
+    }
+
+    fn test_encode_single(c: char) -> Option<u8> {
+        if (c as u32) <= 0x7F {
+            Some(c as u8)
+        } else {
+            None
+        }
+    }
+
+    #[test]
+    fn pointers() {
+        assert_eq!(TEST_DBCS.pointer(0x81, 0x40), Some(0));
+        assert_eq!(TEST_DBCS.pointer(0x81, 0xA1), Some(2));
+        assert_eq!(TEST_DBCS.pointer(0x90, 0xA2), Some(11));
+        assert_eq!(TEST_DBCS.pointer(0x83, 0x40), None);
+        assert_eq!(TEST_DBCS.pointer(0x81, 0x42), None);
+        assert_eq!(TEST_DBCS.pointer_bytes(0), Some([0x81, 0x40]));
+        assert_eq!(TEST_DBCS.pointer_bytes(11), Some([0x90, 0xA2]));
+        assert_eq!(TEST_DBCS.pointer_bytes(12), None);
+    }
+
+    #[test]
+    fn encode_01() {
+        let text = "a日\u{2F47}Ê\u{304}Ê\u{E002}";
+        let mut buf = [0u8; 32];
+        let (encoded, consumed_count) = encode_from_str(&TEST_DBCS, text, &mut buf, true).unwrap();
+        assert_eq!(consumed_count, text.len());
+        assert_eq!(encoded, b"a\x81\x40\x81\x40\x82\x41\x81\xA2\x90\xA1");
+    }
+
+    #[test]
+    fn encode_02() {
+        // A possible sequence start at the end of a chunk waits for more
+        // input.
+        let mut buf = [0u8; 32];
+        let (encoded, consumed_count) = encode_from_str(&TEST_DBCS, "aÊ", &mut buf, false).unwrap();
+        assert_eq!(consumed_count, 1);
+        assert_eq!(encoded, b"a");
+    }
+
+    #[test]
+    fn encode_03() {
+        // Not enough room for both bytes.
+        let mut buf = [0u8; 2];
+        let (encoded, consumed_count) = encode_from_str(&TEST_DBCS, "a日", &mut buf, true).unwrap();
+        assert_eq!(consumed_count, 1);
+        assert_eq!(encoded, b"a");
+    }
+
+    #[test]
+    fn encode_04() {
+        // The longest sequence wins, and a sequence that could still grow
+        // waits for more input.
+        let mut buf = [0u8; 32];
+        let (encoded, consumed_count) =
+            encode_from_str(&TEST_DBCS, "Ê\u{304}\u{300}Ê\u{304}a", &mut buf, true).unwrap();
+        assert_eq!(consumed_count, 11);
+        assert_eq!(encoded, b"\x80\x82\x41a");
+        let (encoded, consumed_count) =
+            encode_from_str(&TEST_DBCS, "aÊ\u{304}", &mut buf, false).unwrap();
+        assert_eq!(consumed_count, 1);
+        assert_eq!(encoded, b"a");
+    }
+
+    #[test]
+    fn encode_error_01() {
+        let mut buf = [0u8; 32];
+        assert_eq!(
+            encode_from_str(&TEST_DBCS, "a\u{E004}", &mut buf, true),
+            Err(EncodeError {
+                character: '\u{E004}',
+                error_range: (1, 4),
+                output_bytes_written: 1,
+            }),
+        );
+    }
+
+    #[test]
+    fn decode_01() {
+        let mut buf = [0u8; 32];
+        let (decoded, consumed_count) = decode_to_str(
+            &TEST_DBCS,
+            b"a\x81\x40\x82\x41\x81\xA2\x90\xA1",
+            &mut buf,
+            true,
+        )
+        .unwrap();
+        assert_eq!(consumed_count, 9);
+        assert_eq!(decoded, "a日Ê\u{304}Ê\u{E002}");
+    }
+
+    #[test]
+    fn decode_02() {
+        // A lead byte at the end of a chunk waits for more input.
+        let mut buf = [0u8; 32];
+        let (decoded, consumed_count) =
+            decode_to_str(&TEST_DBCS, b"a\x81", &mut buf, false).unwrap();
+        assert_eq!(consumed_count, 1);
+        assert_eq!(decoded, "a");
+    }
+
+    #[test]
+    fn decode_03() {
+        // A single byte that maps to a sequence.
+        let mut buf = [0u8; 32];
+        let (decoded, consumed_count) =
+            decode_to_str(&TEST_DBCS, b"a\x80a", &mut buf, true).unwrap();
+        assert_eq!(consumed_count, 3);
+        assert_eq!(decoded, "aÊ\u{304}\u{300}a");
+    }
+
+    #[test]
+    fn decode_error_01() {
+        // Invalid trail byte that is ascii.
+        let mut buf = [0u8; 32];
+        assert_eq!(
+            decode_to_str(&TEST_DBCS, b"a\x81a", &mut buf, true),
+            Err(DecodeError {
+                cause: DecodeErrorCause::InvalidData,
+                error_range: (1, 2),
+                output_bytes_written: 1,
+            }),
+        );
+    }
+
+    #[test]
+    fn decode_error_02() {
+        // Undefined code.
+        let mut buf = [0u8; 32];
+        assert_eq!(
+            decode_to_str(&TEST_DBCS, b"\x81\xA1", &mut buf, true),
+            Err(DecodeError {
+                cause: DecodeErrorCause::InvalidData,
+                error_range: (0, 2),
+                output_bytes_written: 0,
+            }),
+        );
+    }
+
+    #[test]
+    fn decode_error_03() {
+        // Invalid lead byte, and truncated code at the end of input.
+        let mut buf = [0u8; 32];
+        assert_eq!(
+            decode_to_str(&TEST_DBCS, b"\x83\x40", &mut buf, true),
+            Err(DecodeError {
+                cause: DecodeErrorCause::InvalidData,
+                error_range: (0, 1),
+                output_bytes_written: 0,
+            }),
+        );
+        assert_eq!(
+            decode_to_str(&TEST_DBCS, b"\x90", &mut buf, true),
+            Err(DecodeError {
+                cause: DecodeErrorCause::InvalidData,
+                error_range: (0, 1),
+                output_bytes_written: 0,
+            }),
+        );
+    }
+}
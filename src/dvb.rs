@@ -0,0 +1,466 @@
+//! DVB service information text strings (ETSI EN 300 468, Annex A).
+//!
+//! Text strings in DVB service information, e.g. in event and service
+//! descriptors, start with optional selector bytes that choose the
+//! character table for the rest of the string:
+//!
+//! - No selector (the first byte is 0x20 or more): ISO/IEC 6937.
+//! - 0x01-0x0B: ISO/IEC 8859-5 to 8859-15, in order, except 0x08, which
+//!   would be the nonexistent 8859-12.
+//! - 0x10 0x00 0xNN: ISO/IEC 8859-NN, for NN of 1 to 15 except 12.
+//! - 0x11: UCS-2, big endian.
+//! - 0x15: UTF-8.
+//!
+//! The KS X 1001, GB 2312, and Big5 tables (0x12-0x14) and the
+//! encoding_type_id selector (0x1F) aren't supported, and are errors like
+//! the reserved selectors.  The DVB control codes, e.g. 0x86 and 0x87 for
+//! emphasis and 0x8A for a line break in the single-byte tables, are passed
+//! through as the C1 control characters they decode to.
+//!
+//! Since the selector bytes come first, encoding and decoding are stateful
+//! and take a `State`.  For decoding, the table is chosen by the selector
+//! bytes.  For encoding, the table is given up front and its selector bytes
+//! are written at the start.
+
+use core;
+use single_byte::{
+    iso_8859_1, iso_8859_10, iso_8859_11, iso_8859_13, iso_8859_14, iso_8859_15, iso_8859_2,
+    iso_8859_3, iso_8859_4, iso_8859_5, iso_8859_6, iso_8859_7, iso_8859_8, iso_8859_9,
+};
+use {iso_6937, utf16_be, utf8};
+use {DecodeError, DecodeErrorCause, DecodeResult, EncodeError, EncodeResult};
+
+/// The character tables that a DVB text string can select.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Table {
+    Iso6937,
+    Iso8859_1,
+    Iso8859_2,
+    Iso8859_3,
+    Iso8859_4,
+    Iso8859_5,
+    Iso8859_6,
+    Iso8859_7,
+    Iso8859_8,
+    Iso8859_9,
+    Iso8859_10,
+    Iso8859_11,
+    Iso8859_13,
+    Iso8859_14,
+    Iso8859_15,
+    Ucs2,
+    Utf8,
+}
+
+// The ISO/IEC 8859 tables, by part number.
+const ISO_8859_TABLES: [Option<Table>; 16] = [
+    None,
+    Some(Table::Iso8859_1),
+    Some(Table::Iso8859_2),
+    Some(Table::Iso8859_3),
+    Some(Table::Iso8859_4),
+    Some(Table::Iso8859_5),
+    Some(Table::Iso8859_6),
+    Some(Table::Iso8859_7),
+    Some(Table::Iso8859_8),
+    Some(Table::Iso8859_9),
+    Some(Table::Iso8859_10),
+    Some(Table::Iso8859_11),
+    None,
+    Some(Table::Iso8859_13),
+    Some(Table::Iso8859_14),
+    Some(Table::Iso8859_15),
+];
+
+impl Table {
+    /// Returns the selector bytes for the table, using the one-byte
+    /// selectors where there is one.
+    fn selector(self) -> &'static [u8] {
+        match self {
+            Table::Iso6937 => &[],
+            Table::Iso8859_1 => &[0x10, 0x00, 0x01],
+            Table::Iso8859_2 => &[0x10, 0x00, 0x02],
+            Table::Iso8859_3 => &[0x10, 0x00, 0x03],
+            Table::Iso8859_4 => &[0x10, 0x00, 0x04],
+            Table::Iso8859_5 => &[0x01],
+            Table::Iso8859_6 => &[0x02],
+            Table::Iso8859_7 => &[0x03],
+            Table::Iso8859_8 => &[0x04],
+            Table::Iso8859_9 => &[0x05],
+            Table::Iso8859_10 => &[0x06],
+            Table::Iso8859_11 => &[0x07],
+            Table::Iso8859_13 => &[0x09],
+            Table::Iso8859_14 => &[0x0A],
+            Table::Iso8859_15 => &[0x0B],
+            Table::Ucs2 => &[0x11],
+            Table::Utf8 => &[0x15],
+        }
+    }
+}
+
+/// The encoding/decoding state of a DVB text string.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct State {
+    table: Option<Table>,
+    has_selector: bool,
+}
+
+impl State {
+    /// Creates a state for decoding, where the table is chosen by the
+    /// selector bytes, or for encoding with ISO/IEC 6937.
+    pub fn new() -> State {
+        State {
+            table: None,
+            has_selector: false,
+        }
+    }
+
+    /// Creates a state for encoding with the given table.
+    pub fn with_table(table: Table) -> State {
+        State {
+            table: Some(table),
+            has_selector: false,
+        }
+    }
+
+    /// Returns the table, once it's known.
+    pub fn table(&self) -> Option<Table> {
+        self.table
+    }
+}
+
+impl Default for State {
+    fn default() -> State {
+        State::new()
+    }
+}
+
+pub fn encode_from_str<'a>(
+    input: &str,
+    out_buffer: &'a mut [u8],
+    state: &mut State,
+    is_end: bool,
+) -> EncodeResult<'a> {
+    // Write the selector bytes first.
+    let table = state.table.unwrap_or(Table::Iso6937);
+    let mut output_i = 0;
+    if !state.has_selector {
+        let selector = table.selector();
+        if selector.len() > out_buffer.len() {
+            return Ok((&out_buffer[..0], 0));
+        }
+        out_buffer[..selector.len()].copy_from_slice(selector);
+        output_i = selector.len();
+        state.table = Some(table);
+        state.has_selector = true;
+    }
+
+    let out = &mut out_buffer[output_i..];
+    let result = match table {
+        Table::Iso6937 => iso_6937::encode_from_str(input, out, is_end),
+        Table::Iso8859_1 => iso_8859_1::encode_from_str(input, out),
+        Table::Iso8859_2 => iso_8859_2::encode_from_str(input, out),
+        Table::Iso8859_3 => iso_8859_3::encode_from_str(input, out),
+        Table::Iso8859_4 => iso_8859_4::encode_from_str(input, out),
+        Table::Iso8859_5 => iso_8859_5::encode_from_str(input, out),
+        Table::Iso8859_6 => iso_8859_6::encode_from_str(input, out),
+        Table::Iso8859_7 => iso_8859_7::encode_from_str(input, out),
+        Table::Iso8859_8 => iso_8859_8::encode_from_str(input, out),
+        Table::Iso8859_9 => iso_8859_9::encode_from_str(input, out),
+        Table::Iso8859_10 => iso_8859_10::encode_from_str(input, out),
+        Table::Iso8859_11 => iso_8859_11::encode_from_str(input, out),
+        Table::Iso8859_13 => iso_8859_13::encode_from_str(input, out),
+        Table::Iso8859_14 => iso_8859_14::encode_from_str(input, out),
+        Table::Iso8859_15 => iso_8859_15::encode_from_str(input, out),
+        Table::Ucs2 => {
+            // UCS-2 has no surrogates, so characters outside the BMP are
+            // errors.
+            match input.char_indices().find(|x| (x.1 as u32) > 0xFFFF) {
+                Some((offset, c)) => match utf16_be::encode_from_str(&input[..offset], out) {
+                    Ok((encoded, input_consumed)) if input_consumed == offset => Err(EncodeError {
+                        character: c,
+                        error_range: (offset, offset + c.len_utf8()),
+                        output_bytes_written: encoded.len(),
+                    }),
+                    result => result,
+                },
+                None => utf16_be::encode_from_str(input, out),
+            }
+        }
+        Table::Utf8 => utf8::encode_from_str(input, out),
+    };
+
+    // Account for the selector bytes.
+    match result {
+        Ok((encoded, input_consumed)) => {
+            let len = output_i + encoded.len();
+            Ok((&out_buffer[..len], input_consumed))
+        }
+        Err(e) => Err(EncodeError {
+            output_bytes_written: output_i + e.output_bytes_written,
+            ..e
+        }),
+    }
+}
+
+pub fn decode_to_str<'a>(
+    input: &[u8],
+    out_buffer: &'a mut [u8],
+    state: &mut State,
+    is_end: bool,
+) -> DecodeResult<'a> {
+    // Read the selector bytes first.
+    let mut input_i = 0;
+    if !state.has_selector {
+        let (table, selector_len) = match input.first() {
+            None => return Ok(("", 0)),
+            Some(&byte) if byte >= 0x20 => (Some(Table::Iso6937), 0),
+            Some(&byte) if (0x01..=0x0B).contains(&byte) => (ISO_8859_TABLES[byte as usize + 4], 1),
+            Some(&0x10) => match (input.get(1), input.get(2)) {
+                (Some(&0x00), Some(&part)) if part < 16 => (ISO_8859_TABLES[part as usize], 3),
+                (None, _) | (Some(&0x00), None) if !is_end => return Ok(("", 0)),
+                _ => (None, input.len().min(3)),
+            },
+            Some(&0x11) => (Some(Table::Ucs2), 1),
+            Some(&0x15) => (Some(Table::Utf8), 1),
+            Some(_) => (None, 1),
+        };
+        if table.is_none() {
+            // Error: reserved or unsupported selector.
+            return Err(DecodeError {
+                cause: DecodeErrorCause::InvalidData,
+                error_range: (0, selector_len.max(1)),
+                output_bytes_written: 0,
+            });
+        }
+        state.table = table;
+        state.has_selector = true;
+        input_i = selector_len;
+    }
+
+    let input = &input[input_i..];
+    let result = match state.table.unwrap_or(Table::Iso6937) {
+        Table::Iso6937 => iso_6937::decode_to_str(input, out_buffer, is_end),
+        Table::Iso8859_1 => iso_8859_1::decode_to_str(input, out_buffer),
+        Table::Iso8859_2 => iso_8859_2::decode_to_str(input, out_buffer),
+        Table::Iso8859_3 => iso_8859_3::decode_to_str(input, out_buffer),
+        Table::Iso8859_4 => iso_8859_4::decode_to_str(input, out_buffer),
+        Table::Iso8859_5 => iso_8859_5::decode_to_str(input, out_buffer),
+        Table::Iso8859_6 => iso_8859_6::decode_to_str(input, out_buffer),
+        Table::Iso8859_7 => iso_8859_7::decode_to_str(input, out_buffer),
+        Table::Iso8859_8 => iso_8859_8::decode_to_str(input, out_buffer),
+        Table::Iso8859_9 => iso_8859_9::decode_to_str(input, out_buffer),
+        Table::Iso8859_10 => iso_8859_10::decode_to_str(input, out_buffer),
+        Table::Iso8859_11 => iso_8859_11::decode_to_str(input, out_buffer),
+        Table::Iso8859_13 => iso_8859_13::decode_to_str(input, out_buffer),
+        Table::Iso8859_14 => iso_8859_14::decode_to_str(input, out_buffer),
+        Table::Iso8859_15 => iso_8859_15::decode_to_str(input, out_buffer),
+        Table::Ucs2 => {
+            // UCS-2 has no surrogates, so surrogate code units are errors,
+            // even when they form a valid UTF-16 pair.
+            let surrogate = (0..input.len())
+                .step_by(2)
+                .find(|&i| (0xD8..=0xDF).contains(&input[i]));
+            match surrogate {
+                Some(offset) => match utf16_be::decode_to_str(&input[..offset], out_buffer, is_end)
+                {
+                    Ok((decoded, input_consumed)) if input_consumed == offset => Err(DecodeError {
+                        cause: DecodeErrorCause::InvalidData,
+                        error_range: (offset, (offset + 2).min(input.len())),
+                        output_bytes_written: decoded.len(),
+                    }),
+                    result => result,
+                },
+                None => utf16_be::decode_to_str(input, out_buffer, is_end),
+            }
+        }
+        Table::Utf8 => utf8::decode_to_str(input, out_buffer, is_end),
+    };
+
+    // Account for the selector bytes.
+    match result {
+        Ok((decoded, input_consumed)) => {
+            let len = decoded.len();
+            Ok((
+                unsafe { core::str::from_utf8_unchecked(&out_buffer[..len]) },
+                input_i + input_consumed,
+            ))
+        }
+        Err(e) => Err(DecodeError {
+            error_range: (input_i + e.error_range.0, input_i + e.error_range.1),
+            ..e
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use {DecodeError, DecodeErrorCause, EncodeError};
+
+    #[test]
+    fn encode_01() {
+        let mut buf = [0u8; 64];
+        let mut state = State::new();
+        let (encoded, consumed_count) =
+            encode_from_str("Café", &mut buf, &mut state, true).unwrap();
+        assert_eq!(consumed_count, 5);
+        assert_eq!(encoded, b"Caf\xC2e");
+    }
+
+    #[test]
+    fn encode_02() {
+        let mut buf = [0u8; 64];
+        let mut state = State::with_table(Table::Iso8859_5);
+        let (encoded, consumed_count) = encode_from_str("Мир", &mut buf, &mut state, true).unwrap();
+        assert_eq!(consumed_count, 6);
+        assert_eq!(encoded, b"\x01\xBC\xD8\xE0");
+
+        // The selector is only written once.
+        let (encoded, consumed_count) = encode_from_str("!", &mut buf, &mut state, true).unwrap();
+        assert_eq!(consumed_count, 1);
+        assert_eq!(encoded, b"!");
+    }
+
+    #[test]
+    fn encode_03() {
+        let mut buf = [0u8; 64];
+        let mut state = State::with_table(Table::Iso8859_2);
+        let (encoded, consumed_count) =
+            encode_from_str("Łódź", &mut buf, &mut state, true).unwrap();
+        assert_eq!(consumed_count, 7);
+        assert_eq!(encoded, b"\x10\x00\x02\xA3\xF3d\xBC");
+    }
+
+    #[test]
+    fn encode_04() {
+        // Not enough room for the selector.
+        let mut buf = [0u8; 2];
+        let mut state = State::with_table(Table::Iso8859_1);
+        let (encoded, consumed_count) = encode_from_str("a", &mut buf, &mut state, true).unwrap();
+        assert_eq!(consumed_count, 0);
+        assert_eq!(encoded, b"");
+        assert_eq!(state, State::with_table(Table::Iso8859_1));
+    }
+
+    #[test]
+    fn encode_error_01() {
+        let mut buf = [0u8; 64];
+        let mut state = State::with_table(Table::Ucs2);
+        assert_eq!(
+            encode_from_str("a😀", &mut buf, &mut state, true),
+            Err(EncodeError {
+                character: '😀',
+                error_range: (1, 5),
+                output_bytes_written: 3,
+            }),
+        );
+    }
+
+    #[test]
+    fn decode_01() {
+        let mut buf = [0u8; 64];
+        let mut state = State::new();
+        let (decoded, consumed_count) =
+            decode_to_str(b"Caf\xC2e", &mut buf, &mut state, true).unwrap();
+        assert_eq!(consumed_count, 5);
+        assert_eq!(decoded, "Café");
+        assert_eq!(state.table(), Some(Table::Iso6937));
+    }
+
+    #[test]
+    fn decode_02() {
+        let inputs: [(&[u8], &str, Table); 5] = [
+            (b"\x01\xBC\xD8\xE0", "Мир", Table::Iso8859_5),
+            (b"\x10\x00\x02\xA3\xF3d\xBC", "Łódź", Table::Iso8859_2),
+            (b"\x0B\xA4", "€", Table::Iso8859_15),
+            (b"\x11\x00a\x04\x1C", "aМ", Table::Ucs2),
+            (b"\x15a\xC3\xA9", "aé", Table::Utf8),
+        ];
+        for &(input, text, table) in inputs.iter() {
+            let mut buf = [0u8; 64];
+            let mut state = State::new();
+            let (decoded, consumed_count) =
+                decode_to_str(input, &mut buf, &mut state, true).unwrap();
+            assert_eq!(consumed_count, input.len());
+            assert_eq!(decoded, text);
+            assert_eq!(state.table(), Some(table));
+        }
+    }
+
+    #[test]
+    fn decode_03() {
+        // A selector split across inputs.
+        let mut buf = [0u8; 64];
+        let mut state = State::new();
+        assert_eq!(
+            decode_to_str(b"\x10\x00", &mut buf, &mut state, false),
+            Ok(("", 0))
+        );
+        assert_eq!(state, State::new());
+        assert_eq!(
+            decode_to_str(b"\x10\x00\x01\xE9", &mut buf, &mut state, false),
+            Ok(("é", 4))
+        );
+        assert_eq!(
+            decode_to_str(b"\xE9", &mut buf, &mut state, true),
+            Ok(("é", 1))
+        );
+    }
+
+    #[test]
+    fn decode_error_01() {
+        // Reserved and unsupported selectors.
+        let inputs: [(&[u8], (usize, usize)); 5] = [
+            (b"\x00\xBC", (0, 1)),
+            (b"\x08abc", (0, 1)),
+            (b"\x13abc", (0, 1)),
+            (b"\x10\x00\x0Cabc", (0, 3)),
+            (b"\x10\x00", (0, 2)),
+        ];
+        for &(input, error_range) in inputs.iter() {
+            let mut buf = [0u8; 64];
+            assert_eq!(
+                decode_to_str(input, &mut buf, &mut State::new(), true),
+                Err(DecodeError {
+                    cause: DecodeErrorCause::InvalidData,
+                    error_range,
+                    output_bytes_written: 0,
+                }),
+            );
+        }
+    }
+
+    #[test]
+    fn decode_error_02() {
+        // Errors after the selector are offset by it.
+        let mut buf = [0u8; 64];
+        assert_eq!(
+            decode_to_str(b"\x15a\xFF", &mut buf, &mut State::new(), true),
+            Err(DecodeError {
+                cause: DecodeErrorCause::InvalidData,
+                error_range: (2, 3),
+                output_bytes_written: 1,
+            }),
+        );
+    }
+
+    #[test]
+    fn decode_error_03() {
+        // UCS-2 doesn't decode surrogates, even as a valid pair.
+        let mut buf = [0u8; 64];
+        assert_eq!(
+            decode_to_str(
+                b"\x11\x00a\xD8\x3D\xDE\x00",
+                &mut buf,
+                &mut State::new(),
+                true
+            ),
+            Err(DecodeError {
+                cause: DecodeErrorCause::InvalidData,
+                error_range: (3, 5),
+                output_bytes_written: 1,
+            }),
+        );
+    }
+}
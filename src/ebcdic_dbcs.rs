@@ -0,0 +1,459 @@
+//! Mixed single/double-byte EBCDIC encodings.
+//!
+//! These are IBM's mainframe encodings for Chinese, Japanese, and Korean.
+//! Text starts out in single-byte EBCDIC, Shift Out (0x0E) switches to
+//! double-byte characters, and Shift In (0x0F) switches back.  Encoding and
+//! decoding are therefore stateful and take a `State`, which is the same
+//! type for all of the modules.
+//!
+//! The encoder only shifts out before a run of double-byte characters, and
+//! only shifts back in before a single-byte character and at the end of the
+//! text.  The decoder accepts redundant shifts, but not control characters
+//! between Shift Out and Shift In, since they are single-byte characters.
+//!
+//! The mappings are the same as those of ICU, including IBM's mapping of the
+//! user-defined areas to private use code points.  They are also the same
+//! as glibc's iconv, except that glibc encodes U+005C and U+007E to the yen
+//! sign and overline in IBM code pages 930 and 939.
+//! Conversion in both directions is lossless, except where noted in the
+//! modules.
+
+use core;
+use {DecodeError, DecodeErrorCause, DecodeResult, EncodeError, EncodeResult};
+
+// Generated by `encoding_tables/ebcdic_dbcs/generate_ebcdic_dbcs.py`.
+include!("generated/ebcdic_dbcs/ibm930_dbcs_tables.rs.inc");
+include!("generated/ebcdic_dbcs/ibm933_dbcs_tables.rs.inc");
+include!("generated/ebcdic_dbcs/ibm935_dbcs_tables.rs.inc");
+include!("generated/ebcdic_dbcs/ibm937_dbcs_tables.rs.inc");
+
+const SO: u8 = 0x0E;
+const SI: u8 = 0x0F;
+
+pub mod ibm_930 {
+    //! IBM code page 930, Japanese with katakana in the single-byte half.
+    //!
+    //! This is the single-byte code page 290 and the double-byte code page
+    //! 300.
+
+    // Generated by:
+    // `encoding_tables/ebcdic_dbcs/generate_ebcdic_dbcs.py`.
+    include!("generated/ebcdic_dbcs/ibm930_tables.rs.inc");
+}
+
+pub mod ibm_933 {
+    //! IBM code page 933, Korean.
+    //!
+    //! This is the single-byte code page 833 and the double-byte code page
+    //! 834.
+
+    // Generated by:
+    // `encoding_tables/ebcdic_dbcs/generate_ebcdic_dbcs.py`.
+    include!("generated/ebcdic_dbcs/ibm933_tables.rs.inc");
+}
+
+pub mod ibm_935 {
+    //! IBM code page 935, Simplified Chinese.
+    //!
+    //! This is the single-byte code page 836 and the double-byte code page
+    //! 837.
+
+    // Generated by:
+    // `encoding_tables/ebcdic_dbcs/generate_ebcdic_dbcs.py`.
+    include!("generated/ebcdic_dbcs/ibm935_tables.rs.inc");
+}
+
+pub mod ibm_937 {
+    //! IBM code page 937, Traditional Chinese.
+    //!
+    //! This is the single-byte code page 37 and the double-byte code page
+    //! 835.  Some characters have more than one double-byte code, and always
+    //! encode to the highest of them, so decoding is lossy.
+
+    // Generated by:
+    // `encoding_tables/ebcdic_dbcs/generate_ebcdic_dbcs.py`.
+    include!("generated/ebcdic_dbcs/ibm937_tables.rs.inc");
+}
+
+pub mod ibm_939 {
+    //! IBM code page 939, Japanese with Latin letters in the single-byte half.
+    //!
+    //! This is the single-byte code page 1027 and the double-byte code page
+    //! 300, which is shared with IBM code page 930.
+
+    // Generated by:
+    // `encoding_tables/ebcdic_dbcs/generate_ebcdic_dbcs.py`.
+    include!("generated/ebcdic_dbcs/ibm939_tables.rs.inc");
+}
+
+/// The encoding/decoding state of a mixed single/double-byte EBCDIC stream.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub struct State {
+    shifted: bool,
+}
+
+impl State {
+    pub fn new() -> State {
+        State { shifted: false }
+    }
+}
+
+fn ebcdic_dbcs_encode_from_str<'a>(
+    sbcs_table: &[(char, u8)],
+    dbcs_table: &[(char, u16)],
+    input: &str,
+    out_buffer: &'a mut [u8],
+    state: &mut State,
+    is_end: bool,
+) -> EncodeResult<'a> {
+    // Do the encode.
+    let mut input_i = 0;
+    let mut output_i = 0;
+    for (offset, c) in input.char_indices() {
+        if let Ok(i) = sbcs_table.binary_search_by_key(&c, |x| x.0) {
+            // Single byte, shifting in first if needed.
+            let shift_len = if state.shifted { 1 } else { 0 };
+            if (output_i + shift_len + 1) > out_buffer.len() {
+                break;
+            }
+            if state.shifted {
+                out_buffer[output_i] = SI;
+                output_i += 1;
+                state.shifted = false;
+            }
+            out_buffer[output_i] = sbcs_table[i].1;
+            output_i += 1;
+            input_i = offset + 1;
+        } else if let Ok(i) = dbcs_table.binary_search_by_key(&c, |x| x.0) {
+            // If this is the last character of the text, make sure there's
+            // room to shift back in after it.
+            let is_last = is_end && (offset + c.len_utf8()) == input.len();
+            let shift_len = if state.shifted { 0 } else { 1 };
+            let end_len = if is_last { 1 } else { 0 };
+            if (output_i + shift_len + 2 + end_len) > out_buffer.len() {
+                break;
+            }
+            if !state.shifted {
+                out_buffer[output_i] = SO;
+                output_i += 1;
+                state.shifted = true;
+            }
+            let code = dbcs_table[i].1;
+            out_buffer[output_i] = (code >> 8) as u8;
+            out_buffer[output_i + 1] = (code & 0xFF) as u8;
+            output_i += 2;
+            if is_last {
+                out_buffer[output_i] = SI;
+                output_i += 1;
+                state.shifted = false;
+            }
+            input_i = offset + 1;
+        } else {
+            return Err(EncodeError {
+                character: c,
+                error_range: (offset, offset + c.len_utf8()),
+                output_bytes_written: output_i,
+            });
+        }
+    }
+
+    // Shift back in at the end of the text, if that hasn't happened already
+    // (e.g. when the final chunk is empty).
+    if is_end && input_i >= input.len() && state.shifted && output_i < out_buffer.len() {
+        out_buffer[output_i] = SI;
+        output_i += 1;
+        state.shifted = false;
+    }
+
+    // Calculate how much of the input was consumed.
+    if input_i > input.len() {
+        input_i = input.len();
+    } else {
+        while !input.is_char_boundary(input_i) {
+            input_i += 1;
+        }
+    }
+
+    Ok((&out_buffer[..output_i], input_i))
+}
+
+fn ebcdic_dbcs_decode_to_str<'a>(
+    sbcs_table: &[char; 256],
+    dbcs_table: &[char],
+    input: &[u8],
+    out_buffer: &'a mut [u8],
+    state: &mut State,
+    is_end: bool,
+) -> DecodeResult<'a> {
+    let mut input_i = 0;
+    let mut output_i = 0;
+    let mut buf = [0u8; 4]; // For encoding utf8 codepoints.
+
+    while input_i < input.len() {
+        let byte_1 = input[input_i];
+        if output_i >= out_buffer.len() {
+            break;
+        } else if byte_1 == SO {
+            state.shifted = true;
+            input_i += 1;
+            continue;
+        } else if byte_1 == SI {
+            state.shifted = false;
+            input_i += 1;
+            continue;
+        }
+
+        // Get our decoded data.
+        let (string, input_consumed) = if !state.shifted {
+            match sbcs_table[byte_1 as usize] {
+                '�' => {
+                    // Error: undefined byte.
+                    return Err(DecodeError {
+                        cause: DecodeErrorCause::InvalidData,
+                        error_range: (input_i, input_i + 1),
+                        output_bytes_written: output_i,
+                    });
+                }
+                c => (c.encode_utf8(&mut buf), 1),
+            }
+        } else {
+            let byte_2 = if let Some(&byte) = input.get(input_i + 1) {
+                byte
+            } else if !is_end && (0x40..=0xFE).contains(&byte_1) {
+                // No trailing byte available, but not end-of-input, so no
+                // problem.
+                break;
+            } else {
+                0
+            };
+            if !(0x40..=0xFE).contains(&byte_1) || !(0x40..=0xFE).contains(&byte_2) {
+                // Error: invalid or missing byte.  An invalid trailing byte
+                // (e.g. Shift In) remains part of the stream, and thus is not
+                // treated as part of the error.
+                return Err(DecodeError {
+                    cause: DecodeErrorCause::InvalidData,
+                    error_range: (input_i, input_i + 1),
+                    output_bytes_written: output_i,
+                });
+            }
+            let ptr = (byte_1 as usize - 0x40) * 191 + byte_2 as usize - 0x40;
+            match dbcs_table.get(ptr) {
+                Some(&'�') | None => {
+                    // Error: correctly formed but undefined code.
+                    return Err(DecodeError {
+                        cause: DecodeErrorCause::InvalidData,
+                        error_range: (input_i, input_i + 2),
+                        output_bytes_written: output_i,
+                    });
+                }
+                Some(&c) => (c.encode_utf8(&mut buf), 2),
+            }
+        };
+
+        // Copy decoded data to output.
+        if (output_i + string.len()) > out_buffer.len() {
+            // Not enough space in output buffer.
+            break;
+        }
+        out_buffer[output_i..(output_i + string.len())].copy_from_slice(string.as_bytes());
+
+        // Update our counters.
+        input_i += input_consumed;
+        output_i += string.len();
+    }
+
+    Ok((
+        unsafe { core::str::from_utf8_unchecked(&out_buffer[..output_i]) },
+        input_i,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use {DecodeError, DecodeErrorCause, EncodeError};
+
+    #[test]
+    fn encode_01() {
+        // Runs of double-byte characters are shifted out once.
+        let text = "A日本B";
+        let mut buf = [0u8; 16];
+        let (encoded, consumed_count) =
+            ibm_939::encode_from_str(text, &mut buf, &mut State::new(), true).unwrap();
+        assert_eq!(consumed_count, text.len());
+        assert_eq!(encoded, b"\xC1\x0E\x45\x62\x45\x66\x0F\xC2");
+    }
+
+    #[test]
+    fn encode_02() {
+        // Shift back in at the end of the text.
+        let text = "A日";
+        let mut buf = [0u8; 16];
+        let (encoded, consumed_count) =
+            ibm_939::encode_from_str(text, &mut buf, &mut State::new(), true).unwrap();
+        assert_eq!(consumed_count, text.len());
+        assert_eq!(encoded, b"\xC1\x0E\x45\x62\x0F");
+    }
+
+    #[test]
+    fn encode_03() {
+        // Not enough room to shift back in after the last character.
+        let mut buf = [0u8; 4];
+        let (encoded, consumed_count) =
+            ibm_939::encode_from_str("A日", &mut buf, &mut State::new(), true).unwrap();
+        assert_eq!(consumed_count, 1);
+        assert_eq!(encoded, b"\xC1");
+    }
+
+    #[test]
+    fn encode_04() {
+        // The shift state carries over between chunks, and the final empty
+        // chunk shifts back in.
+        let mut buf = [0u8; 16];
+        let mut state = State::new();
+        {
+            let (encoded, consumed_count) =
+                ibm_939::encode_from_str("日", &mut buf, &mut state, false).unwrap();
+            assert_eq!(consumed_count, 3);
+            assert_eq!(encoded, b"\x0E\x45\x62");
+        }
+        {
+            let (encoded, consumed_count) =
+                ibm_939::encode_from_str("本", &mut buf, &mut state, false).unwrap();
+            assert_eq!(consumed_count, 3);
+            assert_eq!(encoded, b"\x45\x66");
+        }
+        {
+            let (encoded, consumed_count) =
+                ibm_939::encode_from_str("", &mut buf, &mut state, true).unwrap();
+            assert_eq!(consumed_count, 0);
+            assert_eq!(encoded, b"\x0F");
+        }
+    }
+
+    #[test]
+    fn encode_error_01() {
+        let mut buf = [0u8; 16];
+        assert_eq!(
+            ibm_939::encode_from_str("A日한", &mut buf, &mut State::new(), true),
+            Err(EncodeError {
+                character: '한',
+                error_range: (4, 7),
+                output_bytes_written: 4,
+            }),
+        );
+    }
+
+    #[test]
+    fn decode_01() {
+        let mut buf = [0u8; 16];
+        let (decoded, consumed_count) = ibm_939::decode_to_str(
+            b"\xC1\x0E\x45\x62\x45\x66\x0F\xC2",
+            &mut buf,
+            &mut State::new(),
+            true,
+        )
+        .unwrap();
+        assert_eq!(consumed_count, 8);
+        assert_eq!(decoded, "A日本B");
+    }
+
+    #[test]
+    fn decode_02() {
+        // The shift state carries over between chunks, including when a
+        // chunk ends in the middle of a double-byte character.
+        let mut buf = [0u8; 16];
+        let mut state = State::new();
+        {
+            let (decoded, consumed_count) =
+                ibm_939::decode_to_str(b"\x0E\x45\x62\x45", &mut buf, &mut state, false).unwrap();
+            assert_eq!(consumed_count, 3);
+            assert_eq!(decoded, "日");
+        }
+        {
+            let (decoded, consumed_count) =
+                ibm_939::decode_to_str(b"\x45\x66\x0F\xC1", &mut buf, &mut state, true).unwrap();
+            assert_eq!(consumed_count, 4);
+            assert_eq!(decoded, "本A");
+        }
+    }
+
+    #[test]
+    fn decode_03() {
+        // Redundant shifts.
+        let mut buf = [0u8; 16];
+        let (decoded, consumed_count) = ibm_939::decode_to_str(
+            b"\x0F\xC1\x0E\x0E\x45\x62\x0F\x0F",
+            &mut buf,
+            &mut State::new(),
+            true,
+        )
+        .unwrap();
+        assert_eq!(consumed_count, 8);
+        assert_eq!(decoded, "A日");
+    }
+
+    #[test]
+    fn decode_error_01() {
+        // Shift in in the middle of a double-byte character.
+        let mut buf = [0u8; 16];
+        assert_eq!(
+            ibm_939::decode_to_str(b"\x0E\x45\x0F\xC1", &mut buf, &mut State::new(), true),
+            Err(DecodeError {
+                cause: DecodeErrorCause::InvalidData,
+                error_range: (1, 2),
+                output_bytes_written: 0,
+            }),
+        );
+    }
+
+    #[test]
+    fn decode_error_02() {
+        // Control character in double-byte mode.
+        let mut buf = [0u8; 16];
+        assert_eq!(
+            ibm_939::decode_to_str(b"\x0E\x45\x62\x25\x0F", &mut buf, &mut State::new(), true),
+            Err(DecodeError {
+                cause: DecodeErrorCause::InvalidData,
+                error_range: (3, 4),
+                output_bytes_written: 3,
+            }),
+        );
+    }
+
+    #[test]
+    fn decode_error_03() {
+        // Truncated double-byte character at the end of the input.
+        let mut buf = [0u8; 16];
+        assert_eq!(
+            ibm_939::decode_to_str(b"\x0E\x45", &mut buf, &mut State::new(), true),
+            Err(DecodeError {
+                cause: DecodeErrorCause::InvalidData,
+                error_range: (1, 2),
+                output_bytes_written: 0,
+            }),
+        );
+    }
+
+    #[test]
+    fn decode_error_04() {
+        // Undefined single-byte and double-byte codes.
+        let mut buf = [0u8; 16];
+        assert_eq!(
+            ibm_939::decode_to_str(b"\xC1\xFE", &mut buf, &mut State::new(), true),
+            Err(DecodeError {
+                cause: DecodeErrorCause::InvalidData,
+                error_range: (1, 2),
+                output_bytes_written: 1,
+            }),
+        );
+        assert_eq!(
+            ibm_939::decode_to_str(b"\x0E\x40\x41", &mut buf, &mut State::new(), true),
+            Err(DecodeError {
+                cause: DecodeErrorCause::InvalidData,
+                error_range: (1, 3),
+                output_bytes_written: 0,
+            }),
+        );
+    }
+}
@@ -0,0 +1,337 @@
+//! EUC-JIS-2004, the EUC form of JIS X 0213:2004.
+//!
+//! This covers both planes of JIS X 0213, which is a superset of JIS X 0208,
+//! as well as the JIS X 0201 half-width katakana.  Plane 2 is encoded with
+//! the three-byte 0x8F prefix, in the same space that EUC-JP uses for JIS X
+//! 0212.  Bytes 0x00-0x7F are treated as ASCII.
+//!
+//! Some JIS X 0213 codes map to a sequence of two Unicode code points (a base
+//! character followed by a combining character).  When encoding, such a
+//! sequence is always encoded to its single JIS X 0213 code.  Because the
+//! encoder may need to look at the next `char` to decide that, it won't
+//! consume a trailing base character unless `is_end` is true.
+//!
+//! Conversion in both directions is lossless.
+
+use core;
+use jis0213;
+use {DecodeError, DecodeErrorCause, DecodeResult, EncodeError, EncodeResult};
+
+pub fn encode_from_str<'a>(
+    input: &str,
+    out_buffer: &'a mut [u8],
+    is_end: bool,
+) -> EncodeResult<'a> {
+    // Do the encode.
+    let mut input_i = 0;
+    let mut output_i = 0;
+    let mut itr = input.char_indices();
+    while let Some((offset, c)) = itr.next() {
+        let code = c as u32;
+        if output_i >= out_buffer.len() {
+            break;
+        } else if code <= 127 {
+            // Ascii
+            out_buffer[output_i] = code as u8;
+            output_i += 1;
+            input_i = offset + 1;
+        } else if (0xFF61..=0xFF9F).contains(&code) {
+            // Half-width katakana
+            if (output_i + 1) < out_buffer.len() {
+                out_buffer[output_i] = 0x8E;
+                out_buffer[output_i + 1] = (code - 0xFF61 + 0xA1) as u8;
+                output_i += 2;
+                input_i = offset + 1;
+            } else {
+                break;
+            }
+        } else {
+            // Handle composed sequences.
+            let mut composed = None;
+            if jis0213::is_composed_start(c) {
+                if let Some((offset2, c2)) = itr.clone().next() {
+                    composed = jis0213::encode_composed(c, c2).map(|ptr| (ptr, offset2));
+                } else if !is_end {
+                    break;
+                }
+            }
+
+            // Common case, fetch from table.
+            let (ptr, end) = if let Some(x) = composed {
+                x
+            } else if let Some(ptr) = jis0213::encode_char(c) {
+                (ptr, offset)
+            } else {
+                return Err(EncodeError {
+                    character: c,
+                    error_range: (offset, offset + c.len_utf8()),
+                    output_bytes_written: output_i,
+                });
+            };
+
+            let row = ((ptr % 8836) / 94 + 0xA1) as u8;
+            let cell = (ptr % 94 + 0xA1) as u8;
+            let len = if ptr < 8836 { 2 } else { 3 };
+            if (output_i + len) <= out_buffer.len() {
+                if composed.is_some() {
+                    itr.next();
+                }
+                if len == 3 {
+                    out_buffer[output_i] = 0x8F;
+                    output_i += 1;
+                }
+                out_buffer[output_i] = row;
+                out_buffer[output_i + 1] = cell;
+                output_i += 2;
+                input_i = end + 1;
+            } else {
+                break;
+            }
+        }
+    }
+
+    // Calculate how much of the input was consumed.
+    if input_i > input.len() {
+        input_i = input.len();
+    } else {
+        while !input.is_char_boundary(input_i) {
+            input_i += 1;
+        }
+    }
+
+    Ok((&out_buffer[..output_i], input_i))
+}
+
+pub fn decode_to_str<'a>(input: &[u8], out_buffer: &'a mut [u8], is_end: bool) -> DecodeResult<'a> {
+    let mut input_i = 0;
+    let mut output_i = 0;
+    let mut buf = [0u8; 8]; // For encoding utf8 codepoints.
+
+    // Loop through the input, getting a byte at a time.
+    let mut itr = input.iter();
+    while let Some(&byte_1) = itr.next() {
+        if output_i >= out_buffer.len() {
+            break;
+        } else if byte_1 <= 127 {
+            // Ascii
+            out_buffer[output_i] = byte_1;
+            output_i += 1;
+            input_i += 1;
+            continue;
+        } else if byte_1 != 0x8E && byte_1 != 0x8F && !(0xA1..=0xFE).contains(&byte_1) {
+            // Error: invalid leading byte.
+            return Err(DecodeError {
+                cause: DecodeErrorCause::InvalidData,
+                error_range: (input_i, input_i + 1),
+                output_bytes_written: output_i,
+            });
+        }
+
+        // Collect the rest of the bytes of the sequence.
+        let seq_len = if byte_1 == 0x8F { 3 } else { 2 };
+        let mut seq = [byte_1, 0, 0];
+        for (i, seq_byte) in seq.iter_mut().enumerate().take(seq_len).skip(1) {
+            if let Some(&byte) = itr.next() {
+                if !(0xA1..=0xFE).contains(&byte) || (byte_1 == 0x8E && byte > 0xDF) {
+                    // Error: invalid trailing byte.
+                    // Like WHATWG EUC-JP, if the invalid byte is ascii it
+                    // remains part of the stream, and thus is not treated
+                    // as part of the error.
+                    return Err(DecodeError {
+                        cause: DecodeErrorCause::InvalidData,
+                        error_range: (input_i, input_i + if byte <= 127 { i } else { i + 1 }),
+                        output_bytes_written: output_i,
+                    });
+                }
+                *seq_byte = byte;
+            } else if !is_end {
+                // No available trailing byte, but not end-of-input, so no
+                // problem.
+                return Ok((
+                    unsafe { core::str::from_utf8_unchecked(&out_buffer[..output_i]) },
+                    input_i,
+                ));
+            } else {
+                // Error: truncated sequence at end of input.
+                return Err(DecodeError {
+                    cause: DecodeErrorCause::InvalidData,
+                    error_range: (input_i, input_i + i),
+                    output_bytes_written: output_i,
+                });
+            }
+        }
+
+        // Get our decoded data.
+        let string = if byte_1 == 0x8E {
+            // Half-width katakana
+            let c = unsafe { core::char::from_u32_unchecked(seq[1] as u32 - 0xA1 + 0xFF61) };
+            c.encode_utf8(&mut buf)
+        } else {
+            let ptr = if byte_1 == 0x8F {
+                8836 + (seq[1] as usize - 0xA1) * 94 + (seq[2] as usize - 0xA1)
+            } else {
+                (seq[0] as usize - 0xA1) * 94 + (seq[1] as usize - 0xA1)
+            };
+            if let Some(string) = jis0213::decode_ptr(ptr, &mut buf) {
+                string
+            } else {
+                // Error: correctly formed but undefined code.
+                return Err(DecodeError {
+                    cause: DecodeErrorCause::InvalidData,
+                    error_range: (input_i, input_i + seq_len),
+                    output_bytes_written: output_i,
+                });
+            }
+        };
+
+        // Copy decoded data to output.
+        if (output_i + string.len()) > out_buffer.len() {
+            break;
+        }
+        out_buffer[output_i..(output_i + string.len())].copy_from_slice(string.as_bytes());
+
+        // Update our counters.
+        input_i += seq_len;
+        output_i += string.len();
+    }
+
+    Ok((
+        unsafe { core::str::from_utf8_unchecked(&out_buffer[..output_i]) },
+        input_i,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Helper function.
+    fn correct_decode(input: &[u8], expected_output: &str) {
+        let mut buf = [0u8; 256];
+        assert_eq!(
+            decode_to_str(input, &mut buf, true),
+            Ok((expected_output, input.len())),
+        );
+    }
+
+    // Helper function.
+    fn error_decode(input: &[u8], error_range: (usize, usize), bytes_written: usize) {
+        let mut buf = [0u8; 256];
+        assert_eq!(
+            decode_to_str(input, &mut buf, true),
+            Err(DecodeError {
+                cause: DecodeErrorCause::InvalidData,
+                error_range,
+                output_bytes_written: bytes_written,
+            }),
+        );
+    }
+
+    // Helper function.
+    fn correct_encode(input: &str, expected_output: &[u8]) {
+        let mut buf = [0u8; 256];
+        assert_eq!(
+            encode_from_str(input, &mut buf, true),
+            Ok((expected_output, input.len())),
+        );
+    }
+
+    #[test]
+    fn decode_01() {
+        correct_decode(&[0x61u8, 0x62u8], "ab");
+        correct_decode(&[0x8Eu8, 0xB1u8], "ｱ");
+        correct_decode(&[0xA5u8, 0xA2u8], "ア");
+        correct_decode(&[0xFEu8, 0xE5u8], "鷗");
+        correct_decode(&[0xAEu8, 0xA2u8], "𠀋");
+
+        // Plane 2
+        correct_decode(&[0x8Fu8, 0xA1u8, 0xA1u8], "𠂉");
+        correct_decode(&[0x8Fu8, 0xFEu8, 0xF6u8], "𪚲");
+
+        // Composed sequences
+        correct_decode(&[0xA4u8, 0xF7u8], "か\u{309A}");
+        correct_decode(&[0xABu8, 0xC4u8], "æ\u{0300}");
+        correct_decode(&[0xABu8, 0xE5u8], "˩˥");
+    }
+
+    #[test]
+    fn decode_02() {
+        let data = [0xA4, 0xAB, 0x8F, 0xA1, 0xA1]; // "か𠂉"
+        let mut buf = [0u8; 6];
+        let (decoded, consumed_count) = decode_to_str(&data, &mut buf, true).unwrap();
+        assert_eq!(consumed_count, 2);
+        assert_eq!(decoded, "か");
+    }
+
+    #[test]
+    fn decode_03() {
+        let data = [0xA4, 0xAB, 0x8F, 0xA1]; // "か𠂉" with last byte chopped off.
+        let mut buf = [0u8; 64];
+        let (decoded, consumed_count) = decode_to_str(&data, &mut buf, false).unwrap();
+        assert_eq!(consumed_count, 2);
+        assert_eq!(decoded, "か");
+    }
+
+    #[test]
+    fn decode_error_01() {
+        error_decode(&[0x80u8, 0x61u8], (0, 1), 0); // Invalid sequence
+        error_decode(&[0xFFu8, 0x61u8], (0, 1), 0); // Invalid sequence
+        error_decode(&[0xA4u8, 0x61u8], (0, 1), 0); // Invalid sequence
+        error_decode(&[0xA4u8, 0xFFu8], (0, 2), 0); // Invalid sequence
+        error_decode(&[0x8Eu8, 0xE0u8], (0, 2), 0); // Invalid sequence
+        error_decode(&[0x8Fu8, 0xA1u8, 0x61u8], (0, 2), 0); // Invalid sequence
+        error_decode(&[0x8Fu8, 0xA2u8, 0xA1u8], (0, 3), 0); // Undefined
+        error_decode(&[0xA4u8, 0xABu8, 0x8Fu8, 0xA1u8], (2, 4), 3); // Truncated
+    }
+
+    #[test]
+    fn encode_01() {
+        correct_encode("ab", &[0x61, 0x62]);
+        correct_encode("ｱア鷗", &[0x8E, 0xB1, 0xA5, 0xA2, 0xFE, 0xE5]);
+        correct_encode("𠀋𠂉𪚲", &[0xAE, 0xA2, 0x8F, 0xA1, 0xA1, 0x8F, 0xFE, 0xF6]);
+
+        // Composed sequences, and their parts on their own.
+        correct_encode("か\u{309A}", &[0xA4, 0xF7]);
+        correct_encode("か", &[0xA4, 0xAB]);
+        correct_encode("かa", &[0xA4, 0xAB, 0x61]);
+        correct_encode("æ\u{0300}æ", &[0xAB, 0xC4, 0xA9, 0xDC]);
+        correct_encode("˩˥˥", &[0xAB, 0xE5, 0xAB, 0xE0]);
+    }
+
+    #[test]
+    fn encode_02() {
+        let text = "か𠂉";
+        let mut buf = [0u8; 4];
+        let (encoded, consumed_count) = encode_from_str(text, &mut buf, true).unwrap();
+        assert_eq!(consumed_count, 3);
+        assert_eq!(encoded, &[0xA4, 0xAB]);
+    }
+
+    #[test]
+    fn encode_03() {
+        // A trailing base character isn't consumed until we know what follows.
+        let text = "ab\u{304B}";
+        let mut buf = [0u8; 64];
+        let (encoded, consumed_count) = encode_from_str(text, &mut buf, false).unwrap();
+        assert_eq!(consumed_count, 2);
+        assert_eq!(encoded, &[0x61, 0x62]);
+
+        let (encoded, consumed_count) = encode_from_str(text, &mut buf, true).unwrap();
+        assert_eq!(consumed_count, 5);
+        assert_eq!(encoded, &[0x61, 0x62, 0xA4, 0xAB]);
+    }
+
+    #[test]
+    fn encode_error_01() {
+        let text = "か😺";
+        let mut buf = [0u8; 64];
+        assert_eq!(
+            encode_from_str(text, &mut buf, true),
+            Err(EncodeError {
+                character: '😺',
+                error_range: (3, 7),
+                output_bytes_written: 2,
+            })
+        );
+    }
+}
@@ -0,0 +1,291 @@
+//! EUC-TW, the EUC form of CNS 11643-1992.
+//!
+//! This covers CNS 11643 planes 1 through 7.  Plane 1 is encoded as two bytes
+//! in 0xA1-0xFE, and every plane can also be encoded in the four-byte form,
+//! which is 0x8E (SS2) followed by 0xA1-0xA7 for the plane and then the two
+//! bytes of the code.  The encoder uses the two-byte form for plane 1 and the
+//! four-byte form for the others.  Bytes 0x00-0x7F are treated as ASCII.
+//!
+//! Decoding is lossy: the two forms of plane 1 decode the same way, and the
+//! one code point that's in two planes (U+5344, in planes 1 and 3) decodes
+//! from either of them.
+//!
+//! Encoding is lossless.  U+5344 always encodes to its plane 1 code.
+
+use cns11643;
+use core;
+use {DecodeError, DecodeErrorCause, DecodeResult, EncodeError, EncodeResult};
+
+pub fn encode_from_str<'a>(input: &str, out_buffer: &'a mut [u8]) -> EncodeResult<'a> {
+    // Do the encode.
+    let mut input_i = 0;
+    let mut output_i = 0;
+    for (offset, c) in input.char_indices() {
+        let code = c as u32;
+        if output_i >= out_buffer.len() {
+            break;
+        } else if code <= 127 {
+            // Ascii
+            out_buffer[output_i] = code as u8;
+            output_i += 1;
+            input_i = offset + 1;
+        } else if let Some(ptr) = cns11643::encode_char(c) {
+            let plane = ptr / 8836 + 1;
+            let row = ((ptr % 8836) / 94 + 0xA1) as u8;
+            let cell = (ptr % 94 + 0xA1) as u8;
+            let len = if plane == 1 { 2 } else { 4 };
+            if (output_i + len) <= out_buffer.len() {
+                if plane != 1 {
+                    out_buffer[output_i] = 0x8E;
+                    out_buffer[output_i + 1] = (plane + 0xA0) as u8;
+                    output_i += 2;
+                }
+                out_buffer[output_i] = row;
+                out_buffer[output_i + 1] = cell;
+                output_i += 2;
+                input_i = offset + 1;
+            } else {
+                break;
+            }
+        } else {
+            return Err(EncodeError {
+                character: c,
+                error_range: (offset, offset + c.len_utf8()),
+                output_bytes_written: output_i,
+            });
+        }
+    }
+
+    // Calculate how much of the input was consumed.
+    if input_i > input.len() {
+        input_i = input.len();
+    } else {
+        while !input.is_char_boundary(input_i) {
+            input_i += 1;
+        }
+    }
+
+    Ok((&out_buffer[..output_i], input_i))
+}
+
+pub fn decode_to_str<'a>(input: &[u8], out_buffer: &'a mut [u8], is_end: bool) -> DecodeResult<'a> {
+    let mut input_i = 0;
+    let mut output_i = 0;
+    let mut buf = [0u8; 4]; // For encoding utf8 codepoints.
+
+    // Loop through the input, getting a byte at a time.
+    let mut itr = input.iter();
+    while let Some(&byte_1) = itr.next() {
+        if output_i >= out_buffer.len() {
+            break;
+        } else if byte_1 <= 127 {
+            // Ascii
+            out_buffer[output_i] = byte_1;
+            output_i += 1;
+            input_i += 1;
+            continue;
+        } else if byte_1 != 0x8E && !(0xA1..=0xFE).contains(&byte_1) {
+            // Error: invalid leading byte.
+            return Err(DecodeError {
+                cause: DecodeErrorCause::InvalidData,
+                error_range: (input_i, input_i + 1),
+                output_bytes_written: output_i,
+            });
+        }
+
+        // Collect the rest of the bytes of the sequence.
+        let seq_len = if byte_1 == 0x8E { 4 } else { 2 };
+        let mut seq = [byte_1, 0, 0, 0];
+        for (i, seq_byte) in seq.iter_mut().enumerate().take(seq_len).skip(1) {
+            if let Some(&byte) = itr.next() {
+                let is_valid = if seq_len == 4 && i == 1 {
+                    (0xA1..=0xA7).contains(&byte)
+                } else {
+                    (0xA1..=0xFE).contains(&byte)
+                };
+                if !is_valid {
+                    // Error: invalid trailing byte.
+                    // Like WHATWG EUC-JP, if the invalid byte is ascii it
+                    // remains part of the stream, and thus is not treated
+                    // as part of the error.
+                    return Err(DecodeError {
+                        cause: DecodeErrorCause::InvalidData,
+                        error_range: (input_i, input_i + if byte <= 127 { i } else { i + 1 }),
+                        output_bytes_written: output_i,
+                    });
+                }
+                *seq_byte = byte;
+            } else if !is_end {
+                // No available trailing byte, but not end-of-input, so no
+                // problem.
+                return Ok((
+                    unsafe { core::str::from_utf8_unchecked(&out_buffer[..output_i]) },
+                    input_i,
+                ));
+            } else {
+                // Error: truncated sequence at end of input.
+                return Err(DecodeError {
+                    cause: DecodeErrorCause::InvalidData,
+                    error_range: (input_i, input_i + i),
+                    output_bytes_written: output_i,
+                });
+            }
+        }
+
+        // Get our decoded data.
+        let ptr = if byte_1 == 0x8E {
+            (seq[1] as usize - 0xA1) * 8836
+                + (seq[2] as usize - 0xA1) * 94
+                + (seq[3] as usize - 0xA1)
+        } else {
+            (seq[0] as usize - 0xA1) * 94 + (seq[1] as usize - 0xA1)
+        };
+        let string = if let Some(c) = cns11643::decode_ptr(ptr) {
+            c.encode_utf8(&mut buf)
+        } else {
+            // Error: correctly formed but undefined code.
+            return Err(DecodeError {
+                cause: DecodeErrorCause::InvalidData,
+                error_range: (input_i, input_i + seq_len),
+                output_bytes_written: output_i,
+            });
+        };
+
+        // Copy decoded data to output.
+        if (output_i + string.len()) > out_buffer.len() {
+            break;
+        }
+        out_buffer[output_i..(output_i + string.len())].copy_from_slice(string.as_bytes());
+
+        // Update our counters.
+        input_i += seq_len;
+        output_i += string.len();
+    }
+
+    Ok((
+        unsafe { core::str::from_utf8_unchecked(&out_buffer[..output_i]) },
+        input_i,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use {DecodeError, DecodeErrorCause, EncodeError};
+
+    #[test]
+    fn encode_01() {
+        let text = "a臺乂亅";
+        let mut buf = [0u8; 16];
+        let (encoded, consumed_count) = encode_from_str(text, &mut buf).unwrap();
+        assert_eq!(consumed_count, text.len());
+        assert_eq!(encoded, b"a\xEA\xD7\x8E\xA2\xA1\xA1\x8E\xA3\xA1\xA4");
+    }
+
+    #[test]
+    fn encode_02() {
+        // Not enough room for the four-byte form.
+        let mut buf = [0u8; 4];
+        let (encoded, consumed_count) = encode_from_str("a乂", &mut buf).unwrap();
+        assert_eq!(consumed_count, 1);
+        assert_eq!(encoded, b"a");
+    }
+
+    #[test]
+    fn encode_error_01() {
+        let mut buf = [0u8; 16];
+        assert_eq!(
+            encode_from_str("a한", &mut buf),
+            Err(EncodeError {
+                character: '한',
+                error_range: (1, 4),
+                output_bytes_written: 1,
+            }),
+        );
+    }
+
+    #[test]
+    fn decode_01() {
+        let mut buf = [0u8; 16];
+        let (decoded, consumed_count) = decode_to_str(
+            b"a\xEA\xD7\x8E\xA1\xEA\xD7\x8E\xA2\xA1\xA1\x8E\xA3\xA1\xA4",
+            &mut buf,
+            true,
+        )
+        .unwrap();
+        assert_eq!(consumed_count, 15);
+        assert_eq!(decoded, "a臺臺乂亅");
+    }
+
+    #[test]
+    fn decode_02() {
+        // Chunks split in the middle of a four-byte sequence.
+        let mut buf = [0u8; 16];
+        {
+            let (decoded, consumed_count) =
+                decode_to_str(b"a\x8E\xA2\xA1", &mut buf, false).unwrap();
+            assert_eq!(consumed_count, 1);
+            assert_eq!(decoded, "a");
+        }
+        {
+            let (decoded, consumed_count) =
+                decode_to_str(b"\x8E\xA2\xA1\xA1", &mut buf, true).unwrap();
+            assert_eq!(consumed_count, 4);
+            assert_eq!(decoded, "乂");
+        }
+    }
+
+    #[test]
+    fn decode_error_01() {
+        // Plane 8 doesn't exist.
+        let mut buf = [0u8; 16];
+        assert_eq!(
+            decode_to_str(b"a\x8E\xA8\xA1\xA1", &mut buf, true),
+            Err(DecodeError {
+                cause: DecodeErrorCause::InvalidData,
+                error_range: (1, 3),
+                output_bytes_written: 1,
+            }),
+        );
+    }
+
+    #[test]
+    fn decode_error_02() {
+        let mut buf = [0u8; 16];
+        assert_eq!(
+            decode_to_str(b"\xEAa", &mut buf, true),
+            Err(DecodeError {
+                cause: DecodeErrorCause::InvalidData,
+                error_range: (0, 1),
+                output_bytes_written: 0,
+            }),
+        );
+    }
+
+    #[test]
+    fn decode_error_03() {
+        let mut buf = [0u8; 16];
+        assert_eq!(
+            decode_to_str(b"\x8E\xA2\xA1", &mut buf, true),
+            Err(DecodeError {
+                cause: DecodeErrorCause::InvalidData,
+                error_range: (0, 3),
+                output_bytes_written: 0,
+            }),
+        );
+    }
+
+    #[test]
+    fn decode_error_04() {
+        // Undefined code.
+        let mut buf = [0u8; 16];
+        assert_eq!(
+            decode_to_str(b"\xFE\xFE", &mut buf, true),
+            Err(DecodeError {
+                cause: DecodeErrorCause::InvalidData,
+                error_range: (0, 2),
+                output_bytes_written: 0,
+            }),
+        );
+    }
+}
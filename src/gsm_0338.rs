@@ -0,0 +1,859 @@
+//! GSM 03.38 (3GPP TS 23.038), the 7-bit default alphabet used by SMS.
+//!
+//! This isn't Ascii compatible, e.g. 0x00 is "@" and 0x02 is "$".  Each
+//! character is a septet, i.e. a seven bit code, and some more characters
+//! are in an extension table, reached by the escape 0x1B followed by
+//! another septet.  The national language tables replace the main table
+//! (the locking shift table) or the extension table (the single shift
+//! table), as given by the national language identifiers in the SMS user
+//! data header.  The Turkish, Spanish, and Portuguese tables and the Indic
+//! tables, Bengali through Urdu, are supported.  Spanish has no locking
+//! shift table, so it uses the default alphabet there.
+//!
+//! There are two ways to store septets: unpacked, with one septet per byte,
+//! and packed, with the septets packed into consecutive bits as in the
+//! actual SMS.  `encode_from_str()` and `decode_to_str()` are unpacked, and
+//! `encode_packed_from_str()` and `decode_packed_to_str()` are packed.  When
+//! packed text ends with seven spare bits, they're filled with CR so that
+//! they aren't read as "@", and a CR in those bits is ignored when decoding.
+//! As the spec requires, text whose last CR would land in those bits gets
+//! another CR after it, so it decodes with a doubled CR at the end.
+//!
+//! `septet_len()` and `segment_count()` give the length of text in septets
+//! and in SMS segments, for deciding how to send it.
+//!
+//! Undefined codes are errors, and codes that are undefined in the
+//! extension table don't fall back to the main table.  The Indic single
+//! shift tables have "¡" and "*" twice, so decoding with them is lossy.
+//! Apart from that, conversion in both directions is lossless.
+
+use core;
+use {DecodeError, DecodeErrorCause, DecodeResult, EncodeError, EncodeResult};
+
+// Generated by `encoding_tables/gsm_0338/generate_gsm_0338.py`.
+// Contains the DECODE_TABLE and ENCODE_TABLE of each table.
+include!("generated/gsm_0338/gsm_0338_tables.rs.inc");
+
+const ESC: u8 = 0x1B;
+const CR: u8 = 0x0D;
+
+// The septets in a single SMS, without a user data header.
+const SMS_SEPTETS: usize = 160;
+
+// The length in bytes of the user data header's length byte, of the
+// concatenation information element, and of a national language
+// information element.
+const UDH_LENGTH_LEN: usize = 1;
+const CONCATENATION_LEN: usize = 5;
+const NATIONAL_LANGUAGE_LEN: usize = 3;
+
+/// A national language, which selects a locking shift or single shift
+/// table.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Language {
+    Default,
+    Turkish,
+    Spanish,
+    Portuguese,
+    Bengali,
+    Gujarati,
+    Hindi,
+    Kannada,
+    Malayalam,
+    Oriya,
+    Punjabi,
+    Tamil,
+    Telugu,
+    Urdu,
+}
+
+/// The tables to encode and decode with.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Tables {
+    pub locking_shift: Language,
+    pub single_shift: Language,
+}
+
+impl Tables {
+    pub fn new(locking_shift: Language, single_shift: Language) -> Tables {
+        Tables {
+            locking_shift,
+            single_shift,
+        }
+    }
+
+    fn locking_shift_table(&self) -> (&'static [char; 128], &'static [(char, u8)]) {
+        match self.locking_shift {
+            Language::Default | Language::Spanish => (&DEFAULT_DECODE_TABLE, &DEFAULT_ENCODE_TABLE),
+            Language::Turkish => (&TURKISH_DECODE_TABLE, &TURKISH_ENCODE_TABLE),
+            Language::Portuguese => (&PORTUGUESE_DECODE_TABLE, &PORTUGUESE_ENCODE_TABLE),
+            Language::Bengali => (&BENGALI_DECODE_TABLE, &BENGALI_ENCODE_TABLE),
+            Language::Gujarati => (&GUJARATI_DECODE_TABLE, &GUJARATI_ENCODE_TABLE),
+            Language::Hindi => (&HINDI_DECODE_TABLE, &HINDI_ENCODE_TABLE),
+            Language::Kannada => (&KANNADA_DECODE_TABLE, &KANNADA_ENCODE_TABLE),
+            Language::Malayalam => (&MALAYALAM_DECODE_TABLE, &MALAYALAM_ENCODE_TABLE),
+            Language::Oriya => (&ORIYA_DECODE_TABLE, &ORIYA_ENCODE_TABLE),
+            Language::Punjabi => (&PUNJABI_DECODE_TABLE, &PUNJABI_ENCODE_TABLE),
+            Language::Tamil => (&TAMIL_DECODE_TABLE, &TAMIL_ENCODE_TABLE),
+            Language::Telugu => (&TELUGU_DECODE_TABLE, &TELUGU_ENCODE_TABLE),
+            Language::Urdu => (&URDU_DECODE_TABLE, &URDU_ENCODE_TABLE),
+        }
+    }
+
+    fn single_shift_table(&self) -> (&'static [char; 128], &'static [(char, u8)]) {
+        match self.single_shift {
+            Language::Default => (&DEFAULT_EXT_DECODE_TABLE, &DEFAULT_EXT_ENCODE_TABLE),
+            Language::Turkish => (&TURKISH_EXT_DECODE_TABLE, &TURKISH_EXT_ENCODE_TABLE),
+            Language::Spanish => (&SPANISH_EXT_DECODE_TABLE, &SPANISH_EXT_ENCODE_TABLE),
+            Language::Portuguese => (&PORTUGUESE_EXT_DECODE_TABLE, &PORTUGUESE_EXT_ENCODE_TABLE),
+            Language::Bengali => (&BENGALI_EXT_DECODE_TABLE, &BENGALI_EXT_ENCODE_TABLE),
+            Language::Gujarati => (&GUJARATI_EXT_DECODE_TABLE, &GUJARATI_EXT_ENCODE_TABLE),
+            Language::Hindi => (&HINDI_EXT_DECODE_TABLE, &HINDI_EXT_ENCODE_TABLE),
+            Language::Kannada => (&KANNADA_EXT_DECODE_TABLE, &KANNADA_EXT_ENCODE_TABLE),
+            Language::Malayalam => (&MALAYALAM_EXT_DECODE_TABLE, &MALAYALAM_EXT_ENCODE_TABLE),
+            Language::Oriya => (&ORIYA_EXT_DECODE_TABLE, &ORIYA_EXT_ENCODE_TABLE),
+            Language::Punjabi => (&PUNJABI_EXT_DECODE_TABLE, &PUNJABI_EXT_ENCODE_TABLE),
+            Language::Tamil => (&TAMIL_EXT_DECODE_TABLE, &TAMIL_EXT_ENCODE_TABLE),
+            Language::Telugu => (&TELUGU_EXT_DECODE_TABLE, &TELUGU_EXT_ENCODE_TABLE),
+            Language::Urdu => (&URDU_EXT_DECODE_TABLE, &URDU_EXT_ENCODE_TABLE),
+        }
+    }
+}
+
+impl Default for Tables {
+    fn default() -> Tables {
+        Tables::new(Language::Default, Language::Default)
+    }
+}
+
+/// The encoding/decoding state of a packed GSM 03.38 stream.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct State {
+    tables: Tables,
+    bits: u16,        // Bits left over from the previous byte(s).
+    bit_count: usize, // The number of left over bits, or 8/7 for a held CR.
+    is_escaped: bool, // Whether the last septet decoded was an escape.
+}
+
+impl State {
+    pub fn new(tables: Tables) -> State {
+        State {
+            tables,
+            bits: 0,
+            bit_count: 0,
+            is_escaped: false,
+        }
+    }
+}
+
+impl Default for State {
+    fn default() -> State {
+        State::new(Tables::default())
+    }
+}
+
+/// Returns the number of septets that a character is encoded as, or `None`
+/// if it isn't encodable with the given tables.
+pub fn septet_len(tables: &Tables, c: char) -> Option<usize> {
+    encode_char(tables, c).map(|x| x.1)
+}
+
+/// Returns the number of SMS segments that text needs, or `None` if it
+/// isn't encodable with the given tables.
+///
+/// This accounts for the user data header of concatenated SMS, and for the
+/// national language information elements when the tables aren't the
+/// default ones.  Escape sequences aren't split across segments.
+pub fn segment_count(tables: &Tables, input: &str) -> Option<usize> {
+    // Get the space left for text by the user data header.  The header is
+    // padded to a septet boundary.
+    let mut header_len = 0;
+    if !matches!(tables.locking_shift, Language::Default | Language::Spanish) {
+        header_len += NATIONAL_LANGUAGE_LEN;
+    }
+    if tables.single_shift != Language::Default {
+        header_len += NATIONAL_LANGUAGE_LEN;
+    }
+    let septets_after = |header_len: usize| {
+        if header_len == 0 {
+            SMS_SEPTETS
+        } else {
+            SMS_SEPTETS - ((UDH_LENGTH_LEN + header_len) * 8).div_ceil(7)
+        }
+    };
+
+    // Does it fit in a single SMS?
+    let mut total_len = 0;
+    for c in input.chars() {
+        total_len += septet_len(tables, c)?;
+    }
+    if total_len <= septets_after(header_len) {
+        return Some(1);
+    }
+
+    // Split it into segments.
+    let segment_len = septets_after(header_len + CONCATENATION_LEN);
+    let mut count = 1;
+    let mut len = 0;
+    for c in input.chars() {
+        let c_len = septet_len(tables, c)?;
+        if (len + c_len) > segment_len {
+            count += 1;
+            len = 0;
+        }
+        len += c_len;
+    }
+    Some(count)
+}
+
+pub fn encode_from_str<'a>(
+    input: &str,
+    out_buffer: &'a mut [u8],
+    tables: &Tables,
+) -> EncodeResult<'a> {
+    // Do the encode.
+    let mut input_i = 0;
+    let mut output_i = 0;
+    for (offset, c) in input.char_indices() {
+        if let Some((septets, len)) = encode_char(tables, c) {
+            if (output_i + len) > out_buffer.len() {
+                break;
+            }
+            out_buffer[output_i..(output_i + len)].copy_from_slice(&septets[..len]);
+            output_i += len;
+            input_i = offset + 1;
+        } else {
+            return Err(EncodeError {
+                character: c,
+                error_range: (offset, offset + c.len_utf8()),
+                output_bytes_written: output_i,
+            });
+        }
+    }
+
+    // Calculate how much of the input was consumed.
+    if input_i > input.len() {
+        input_i = input.len();
+    } else {
+        while !input.is_char_boundary(input_i) {
+            input_i += 1;
+        }
+    }
+
+    Ok((&out_buffer[..output_i], input_i))
+}
+
+pub fn decode_to_str<'a>(
+    input: &[u8],
+    out_buffer: &'a mut [u8],
+    is_end: bool,
+    tables: &Tables,
+) -> DecodeResult<'a> {
+    let (locking_shift_table, _) = tables.locking_shift_table();
+    let (single_shift_table, _) = tables.single_shift_table();
+    let mut input_i = 0;
+    let mut output_i = 0;
+    let mut buf = [0u8; 4]; // For encoding utf8 codepoints.
+
+    while input_i < input.len() {
+        if output_i >= out_buffer.len() {
+            break;
+        }
+        let byte_1 = input[input_i];
+
+        // Get our decoded data.
+        let (c, input_consumed) = if byte_1 == ESC {
+            match input.get(input_i + 1) {
+                None if !is_end => {
+                    break;
+                }
+                Some(&byte_2) if byte_2 <= 0x7F && single_shift_table[byte_2 as usize] != '�' => {
+                    (single_shift_table[byte_2 as usize], 2)
+                }
+                byte_2 => {
+                    // Error: undefined or truncated escape sequence.  If the
+                    // second byte is a septet it remains part of the stream,
+                    // and thus is not treated as part of the error.
+                    let len = match byte_2 {
+                        Some(&byte_2) if byte_2 > 0x7F => 2,
+                        _ => 1,
+                    };
+                    return Err(DecodeError {
+                        cause: DecodeErrorCause::InvalidData,
+                        error_range: (input_i, input_i + len),
+                        output_bytes_written: output_i,
+                    });
+                }
+            }
+        } else if byte_1 <= 0x7F && locking_shift_table[byte_1 as usize] != '�' {
+            (locking_shift_table[byte_1 as usize], 1)
+        } else {
+            // Error: undefined code, or not a septet.
+            return Err(DecodeError {
+                cause: DecodeErrorCause::InvalidData,
+                error_range: (input_i, input_i + 1),
+                output_bytes_written: output_i,
+            });
+        };
+
+        // Copy decoded data to output.
+        let string = c.encode_utf8(&mut buf);
+        if (output_i + string.len()) > out_buffer.len() {
+            // Not enough space in output buffer.
+            break;
+        }
+        out_buffer[output_i..(output_i + string.len())].copy_from_slice(string.as_bytes());
+
+        // Update our counters.
+        input_i += input_consumed;
+        output_i += string.len();
+    }
+
+    Ok((
+        unsafe { core::str::from_utf8_unchecked(&out_buffer[..output_i]) },
+        input_i,
+    ))
+}
+
+pub fn encode_packed_from_str<'a>(
+    input: &str,
+    out_buffer: &'a mut [u8],
+    state: &mut State,
+    is_end: bool,
+) -> EncodeResult<'a> {
+    // Do the encode.
+    let mut input_i = 0;
+    let mut output_i = 0;
+    for (offset, c) in input.char_indices() {
+        let (septets, len) = if let Some(x) = encode_char(&state.tables, c) {
+            x
+        } else {
+            return Err(EncodeError {
+                character: c,
+                error_range: (offset, offset + c.len_utf8()),
+                output_bytes_written: output_i,
+            });
+        };
+
+        // Pack the septets, getting the bytes that they complete.
+        let mut bits = state.bits;
+        let mut bit_count = state.bit_count;
+        let mut bytes = [0u8; 2];
+        let mut bytes_len = 0;
+        for &septet in &septets[..len] {
+            bits |= (septet as u16) << bit_count;
+            bit_count += 7;
+            if bit_count >= 8 {
+                bytes[bytes_len] = bits as u8;
+                bytes_len += 1;
+                bits >>= 8;
+                bit_count -= 8;
+            }
+        }
+
+        // A CR that ends on a byte boundary needs another CR after it if
+        // it's the last character of the text, so that it isn't taken as
+        // fill.  That may not be known until a later chunk, so its byte is
+        // held back until the next character or the end of the text.
+        if bit_count == 0 && septets[len - 1] == CR {
+            bytes_len -= 1;
+            bits = bytes[bytes_len] as u16;
+            bit_count = 8;
+        }
+
+        // If this is the last character of the text, make sure there's
+        // room for the left over bits after it.
+        let is_last = is_end && (offset + c.len_utf8()) == input.len();
+        let end_len = if is_last { end_len(bit_count) } else { 0 };
+        if (output_i + bytes_len + end_len) > out_buffer.len() {
+            break;
+        }
+        out_buffer[output_i..(output_i + bytes_len)].copy_from_slice(&bytes[..bytes_len]);
+        output_i += bytes_len;
+        input_i = offset + c.len_utf8();
+        state.bits = bits;
+        state.bit_count = bit_count;
+    }
+
+    // Write the left over bits at the end of the text, if that hasn't
+    // happened already (e.g. when the final chunk is empty).  Seven spare
+    // bits are filled with CR, and a held back CR gets another CR after it.
+    if is_end
+        && input_i >= input.len()
+        && state.bit_count > 0
+        && (output_i + end_len(state.bit_count)) <= out_buffer.len()
+    {
+        if state.bit_count == 8 {
+            out_buffer[output_i] = state.bits as u8;
+            out_buffer[output_i + 1] = CR;
+            output_i += 2;
+        } else {
+            let fill = if state.bit_count == 1 {
+                (CR as u16) << 1
+            } else {
+                0
+            };
+            out_buffer[output_i] = (state.bits | fill) as u8;
+            output_i += 1;
+        }
+        state.bits = 0;
+        state.bit_count = 0;
+    }
+
+    // Calculate how much of the input was consumed.
+    if input_i > input.len() {
+        input_i = input.len();
+    } else {
+        while !input.is_char_boundary(input_i) {
+            input_i += 1;
+        }
+    }
+
+    Ok((&out_buffer[..output_i], input_i))
+}
+
+pub fn decode_packed_to_str<'a>(
+    input: &[u8],
+    out_buffer: &'a mut [u8],
+    state: &mut State,
+    is_end: bool,
+) -> DecodeResult<'a> {
+    let (locking_shift_table, _) = state.tables.locking_shift_table();
+    let (single_shift_table, _) = state.tables.single_shift_table();
+    let mut input_i = 0;
+    let mut output_i = 0;
+    let mut buf = [0u8; 8]; // For encoding utf8 codepoints.
+
+    while input_i < input.len() {
+        if output_i >= out_buffer.len() {
+            break;
+        }
+
+        // Unpack the septets that this byte completes.
+        let mut bits = state.bits | ((input[input_i] as u16) << state.bit_count);
+        let mut bit_count = state.bit_count + 8;
+        let mut septets = [0u8; 2];
+        let mut septets_len = 0;
+        while bit_count >= 7 {
+            septets[septets_len] = (bits & 0x7F) as u8;
+            septets_len += 1;
+            bits >>= 7;
+            bit_count -= 7;
+        }
+
+        // A CR in seven spare bits at the end is fill.  Whether this is the
+        // end may not be known until a later chunk, so a CR that ends on a
+        // byte boundary at the end of the input is held back until the next
+        // byte or the end of the text.
+        if (input_i + 1) == input.len() && bit_count == 0 && septets[septets_len - 1] == CR {
+            septets_len -= 1;
+            bits = CR as u16;
+            bit_count = 7;
+        }
+
+        // Decode them.
+        let mut is_escaped = state.is_escaped;
+        let mut len = 0;
+        for &septet in &septets[..septets_len] {
+            let c = if is_escaped {
+                is_escaped = false;
+                single_shift_table[septet as usize]
+            } else if septet == ESC {
+                is_escaped = true;
+                continue;
+            } else {
+                locking_shift_table[septet as usize]
+            };
+            if c == '�' {
+                // Error: undefined code or escape sequence.
+                return Err(DecodeError {
+                    cause: DecodeErrorCause::InvalidData,
+                    error_range: (input_i, input_i + 1),
+                    output_bytes_written: output_i,
+                });
+            }
+            len += c.encode_utf8(&mut buf[len..]).len();
+        }
+
+        // Copy decoded data to output.
+        if (output_i + len) > out_buffer.len() {
+            // Not enough space in output buffer.
+            break;
+        }
+        out_buffer[output_i..(output_i + len)].copy_from_slice(&buf[..len]);
+
+        // Update our counters.
+        input_i += 1;
+        output_i += len;
+        state.bits = bits;
+        state.bit_count = bit_count;
+        state.is_escaped = is_escaped;
+    }
+
+    // A held back CR at the end of the text is fill.
+    if is_end && input_i >= input.len() && state.bit_count == 7 {
+        state.bits = 0;
+        state.bit_count = 0;
+    }
+
+    if is_end && input_i >= input.len() && state.is_escaped {
+        // Error: truncated escape sequence at end of input.
+        return Err(DecodeError {
+            cause: DecodeErrorCause::InvalidData,
+            error_range: (input_i.saturating_sub(1), input_i),
+            output_bytes_written: output_i,
+        });
+    }
+
+    Ok((
+        unsafe { core::str::from_utf8_unchecked(&out_buffer[..output_i]) },
+        input_i,
+    ))
+}
+
+/// Returns the number of bytes that the left over bits of packed encoding
+/// take at the end of the text.
+fn end_len(bit_count: usize) -> usize {
+    match bit_count {
+        0 => 0,
+        8 => 2,
+        _ => 1,
+    }
+}
+
+/// Returns the septets for a character and how many there are, preferring
+/// the locking shift table over the single shift table.
+fn encode_char(tables: &Tables, c: char) -> Option<([u8; 2], usize)> {
+    let (_, locking_shift_table) = tables.locking_shift_table();
+    let (_, single_shift_table) = tables.single_shift_table();
+    if let Ok(i) = locking_shift_table.binary_search_by_key(&c, |x| x.0) {
+        Some(([locking_shift_table[i].1, 0], 1))
+    } else if let Ok(i) = single_shift_table.binary_search_by_key(&c, |x| x.0) {
+        Some(([ESC, single_shift_table[i].1], 2))
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use {DecodeError, DecodeErrorCause, EncodeError};
+
+    #[test]
+    fn encode_01() {
+        let text = "@£$ Hello {Δ} €5";
+        let mut buf = [0u8; 64];
+        let (encoded, consumed_count) =
+            encode_from_str(text, &mut buf, &Tables::default()).unwrap();
+        assert_eq!(consumed_count, text.len());
+        assert_eq!(
+            encoded,
+            &[
+                0x00, 0x01, 0x02, 0x20, 0x48, 0x65, 0x6C, 0x6C, 0x6F, 0x20, 0x1B, 0x28, 0x10, 0x1B,
+                0x29, 0x20, 0x1B, 0x65, 0x35
+            ]
+        );
+    }
+
+    #[test]
+    fn encode_02() {
+        // National language tables.
+        let text = "Işık ğ ç";
+        let mut buf = [0u8; 64];
+        let tables = Tables::new(Language::Turkish, Language::Turkish);
+        let (encoded, consumed_count) = encode_from_str(text, &mut buf, &tables).unwrap();
+        assert_eq!(consumed_count, text.len());
+        assert_eq!(encoded, &[0x49, 0x1D, 0x07, 0x6B, 0x20, 0x0C, 0x20, 0x60]);
+
+        let tables = Tables::new(Language::Default, Language::Spanish);
+        let (encoded, consumed_count) = encode_from_str("Á", &mut buf, &tables).unwrap();
+        assert_eq!(consumed_count, 2);
+        assert_eq!(encoded, &[0x1B, 0x41]);
+    }
+
+    #[test]
+    fn encode_03() {
+        // Not enough room for an escape sequence.
+        let mut buf = [0u8; 2];
+        let (encoded, consumed_count) =
+            encode_from_str("a€", &mut buf, &Tables::default()).unwrap();
+        assert_eq!(consumed_count, 1);
+        assert_eq!(encoded, &[0x61]);
+    }
+
+    #[test]
+    fn encode_04() {
+        // Indic tables.
+        let text = "नमस्ते १";
+        let mut buf = [0u8; 64];
+        let tables = Tables::new(Language::Hindi, Language::Hindi);
+        let (encoded, consumed_count) = encode_from_str(text, &mut buf, &tables).unwrap();
+        assert_eq!(consumed_count, text.len());
+        assert_eq!(
+            encoded,
+            &[0x2F, 0x42, 0x4C, 0x5F, 0x27, 0x59, 0x20, 0x1B, 0x1D]
+        );
+
+        // "¡" is in the single shift table twice, and encodes to its first
+        // code.
+        let (encoded, consumed_count) = encode_from_str("¡", &mut buf, &tables).unwrap();
+        assert_eq!(consumed_count, 2);
+        assert_eq!(encoded, &[0x1B, 0x13]);
+    }
+
+    #[test]
+    fn encode_error_01() {
+        let mut buf = [0u8; 64];
+        assert_eq!(
+            encode_from_str("aç", &mut buf, &Tables::default()),
+            Err(EncodeError {
+                character: 'ç',
+                error_range: (1, 3),
+                output_bytes_written: 1,
+            }),
+        );
+    }
+
+    #[test]
+    fn decode_01() {
+        let input = [
+            0x00, 0x01, 0x02, 0x20, 0x48, 0x65, 0x6C, 0x6C, 0x6F, 0x20, 0x1B, 0x28, 0x10, 0x1B,
+            0x29, 0x20, 0x1B, 0x65, 0x35,
+        ];
+        let mut buf = [0u8; 64];
+        let (decoded, consumed_count) =
+            decode_to_str(&input, &mut buf, true, &Tables::default()).unwrap();
+        assert_eq!(consumed_count, input.len());
+        assert_eq!(decoded, "@£$ Hello {Δ} €5");
+    }
+
+    #[test]
+    fn decode_02() {
+        // An escape at the end of the input waits for the rest of the
+        // sequence.
+        let mut buf = [0u8; 64];
+        assert_eq!(
+            decode_to_str(&[0x61, 0x1B], &mut buf, false, &Tables::default()),
+            Ok(("a", 1))
+        );
+    }
+
+    #[test]
+    fn decode_03() {
+        // Indic tables.
+        let mut buf = [0u8; 64];
+        let tables = Tables::new(Language::Tamil, Language::Tamil);
+        assert_eq!(
+            decode_to_str(
+                &[0x15, 0x20, 0x1B, 0x1D, 0x1B, 0x15],
+                &mut buf,
+                true,
+                &tables
+            ),
+            Ok(("க ௧¡", 6))
+        );
+    }
+
+    #[test]
+    fn decode_error_01() {
+        // Undefined escape sequence.
+        let mut buf = [0u8; 64];
+        assert_eq!(
+            decode_to_str(&[0x61, 0x1B, 0x41], &mut buf, true, &Tables::default()),
+            Err(DecodeError {
+                cause: DecodeErrorCause::InvalidData,
+                error_range: (1, 2),
+                output_bytes_written: 1,
+            }),
+        );
+    }
+
+    #[test]
+    fn decode_error_02() {
+        // Not a septet.
+        let mut buf = [0u8; 64];
+        assert_eq!(
+            decode_to_str(&[0x61, 0x80], &mut buf, true, &Tables::default()),
+            Err(DecodeError {
+                cause: DecodeErrorCause::InvalidData,
+                error_range: (1, 2),
+                output_bytes_written: 1,
+            }),
+        );
+    }
+
+    #[test]
+    fn decode_error_03() {
+        // Undefined code in a locking shift table.
+        let mut buf = [0u8; 64];
+        let tables = Tables::new(Language::Tamil, Language::Default);
+        assert_eq!(
+            decode_to_str(&[0x15, 0x09], &mut buf, true, &tables),
+            Err(DecodeError {
+                cause: DecodeErrorCause::InvalidData,
+                error_range: (1, 2),
+                output_bytes_written: 3,
+            }),
+        );
+    }
+
+    #[test]
+    fn encode_packed_01() {
+        let mut buf = [0u8; 64];
+        let mut state = State::default();
+        let (encoded, consumed_count) =
+            encode_packed_from_str("hellohello", &mut buf, &mut state, true).unwrap();
+        assert_eq!(consumed_count, 10);
+        assert_eq!(
+            encoded,
+            &[0xE8, 0x32, 0x9B, 0xFD, 0x46, 0x97, 0xD9, 0xEC, 0x37]
+        );
+    }
+
+    #[test]
+    fn encode_packed_02() {
+        // Seven spare bits are filled with CR, including when the final
+        // chunk is empty.
+        let mut buf = [0u8; 64];
+        let mut state = State::default();
+        let (encoded, consumed_count) =
+            encode_packed_from_str("1234567", &mut buf, &mut state, false).unwrap();
+        assert_eq!(consumed_count, 7);
+        assert_eq!(encoded, &[0x31, 0xD9, 0x8C, 0x56, 0xB3, 0xDD]);
+        let (encoded, consumed_count) =
+            encode_packed_from_str("", &mut buf, &mut state, true).unwrap();
+        assert_eq!(consumed_count, 0);
+        assert_eq!(encoded, &[0x1A]);
+    }
+
+    #[test]
+    fn encode_packed_03() {
+        // A CR that ends on a byte boundary at the end of the text gets
+        // another CR after it, however the text is split into chunks.
+        let text = "1234567\r";
+        let expected = [0x31, 0xD9, 0x8C, 0x56, 0xB3, 0xDD, 0x1A, 0x0D];
+        for split in 0..=text.len() {
+            let mut buf = [0u8; 64];
+            let mut encoded = [0u8; 64];
+            let mut encoded_len = 0;
+            let mut state = State::default();
+            let chunks = [(&text[..split], false), (&text[split..], false), ("", true)];
+            for &(chunk, is_end) in chunks.iter() {
+                let (out, consumed_count) =
+                    encode_packed_from_str(chunk, &mut buf, &mut state, is_end).unwrap();
+                assert_eq!(consumed_count, chunk.len());
+                encoded[encoded_len..(encoded_len + out.len())].copy_from_slice(out);
+                encoded_len += out.len();
+            }
+            assert_eq!(&encoded[..encoded_len], &expected);
+        }
+    }
+
+    #[test]
+    fn decode_packed_01() {
+        let input = [0xE8, 0x32, 0x9B, 0xFD, 0x46, 0x97, 0xD9, 0xEC, 0x37];
+        let mut buf = [0u8; 64];
+        let mut state = State::default();
+        let (decoded, consumed_count) =
+            decode_packed_to_str(&input, &mut buf, &mut state, true).unwrap();
+        assert_eq!(consumed_count, input.len());
+        assert_eq!(decoded, "hellohello");
+    }
+
+    #[test]
+    fn decode_packed_02() {
+        // A CR in seven spare bits is ignored, and an escape sequence split
+        // across bytes and inputs.
+        let mut buf = [0u8; 64];
+        let mut state = State::default();
+        let input = [0x31, 0xD9, 0x8C, 0x56, 0xB3, 0xDD, 0x1A];
+        let (decoded, _) = decode_packed_to_str(&input, &mut buf, &mut state, true).unwrap();
+        assert_eq!(decoded, "1234567");
+
+        let mut state = State::default();
+        let (decoded, consumed_count) =
+            decode_packed_to_str(&[0x9B], &mut buf, &mut state, false).unwrap();
+        assert_eq!(consumed_count, 1);
+        assert_eq!(decoded, "");
+        let (decoded, consumed_count) =
+            decode_packed_to_str(&[0x72, 0x18], &mut buf, &mut state, true).unwrap();
+        assert_eq!(consumed_count, 2);
+        assert_eq!(decoded, "€a");
+    }
+
+    #[test]
+    fn decode_packed_03() {
+        // A CR in seven spare bits is ignored, however the input is split
+        // into chunks.
+        let inputs: [(&[u8], &str); 2] = [
+            (&[0x31, 0xD9, 0x8C, 0x56, 0xB3, 0xDD, 0x1A], "1234567"),
+            (
+                &[0x31, 0xD9, 0x8C, 0x56, 0xB3, 0xDD, 0x1A, 0x0D],
+                "1234567\r\r",
+            ),
+        ];
+        for &(input, expected) in inputs.iter() {
+            for split in 0..=input.len() {
+                let mut buf = [0u8; 64];
+                let mut decoded = [0u8; 64];
+                let mut decoded_len = 0;
+                let mut state = State::default();
+                let chunks = [
+                    (&input[..split], false),
+                    (&input[split..], false),
+                    (&[][..], true),
+                ];
+                for &(chunk, is_end) in chunks.iter() {
+                    let (out, consumed_count) =
+                        decode_packed_to_str(chunk, &mut buf, &mut state, is_end).unwrap();
+                    assert_eq!(consumed_count, chunk.len());
+                    decoded[decoded_len..(decoded_len + out.len())].copy_from_slice(out.as_bytes());
+                    decoded_len += out.len();
+                }
+                assert_eq!(&decoded[..decoded_len], expected.as_bytes());
+            }
+        }
+    }
+
+    #[test]
+    fn septet_len_01() {
+        let tables = Tables::new(Language::Default, Language::Spanish);
+        assert_eq!(septet_len(&tables, 'a'), Some(1));
+        assert_eq!(septet_len(&tables, 'á'), Some(2));
+        assert_eq!(septet_len(&tables, 'ğ'), None);
+    }
+
+    #[test]
+    fn segment_count_01() {
+        let text = [b'a'; 307];
+        let text = core::str::from_utf8(&text).unwrap();
+        let tables = Tables::default();
+        assert_eq!(segment_count(&tables, ""), Some(1));
+        assert_eq!(segment_count(&tables, &text[..160]), Some(1));
+        assert_eq!(segment_count(&tables, &text[..161]), Some(2));
+        assert_eq!(segment_count(&tables, &text[..306]), Some(2));
+        assert_eq!(segment_count(&tables, &text[..307]), Some(3));
+        assert_eq!(segment_count(&tables, "aç"), None);
+
+        // Escape sequences count as two septets, and aren't split.
+        let mut text = [b'a'; 162];
+        text[159..].copy_from_slice("€".as_bytes());
+        let text = core::str::from_utf8(&text).unwrap();
+        assert_eq!(segment_count(&tables, &text[1..]), Some(1));
+        assert_eq!(segment_count(&tables, text), Some(2));
+    }
+
+    #[test]
+    fn segment_count_02() {
+        // National language information elements take space.
+        let text = [b'a'; 155];
+        let text = core::str::from_utf8(&text).unwrap();
+        let tables = Tables::new(Language::Default, Language::Turkish);
+        assert_eq!(segment_count(&tables, text), Some(1));
+        let tables = Tables::new(Language::Turkish, Language::Turkish);
+        assert_eq!(segment_count(&tables, text), Some(2));
+        let tables = Tables::new(Language::Spanish, Language::Default);
+        assert_eq!(segment_count(&tables, text), Some(1));
+    }
+}
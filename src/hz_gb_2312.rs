@@ -0,0 +1,414 @@
+//! HZ-GB-2312, the 7-bit encoding of GB 2312 from RFC 1843.
+//!
+//! HZ text switches between ASCII and GB 2312 with the escape sequences `~{`
+//! and `~}`, so encoding and decoding are stateful and take a `State`.  A
+//! literal `~` is written as `~~`, and a `~` at the end of a line is a line
+//! continuation, which is dropped when decoding.
+//!
+//! The encoder always switches back to ASCII before any ASCII character and
+//! at the end of the text.  Like most decoders, the decoder also switches
+//! back to ASCII at a line break even without a `~}`, so that a missing
+//! escape doesn't garble the rest of the text.
+//!
+//! Conversion in both directions is otherwise lossless.
+
+use core;
+use gb2312;
+use {DecodeError, DecodeErrorCause, DecodeResult, EncodeError, EncodeResult};
+
+/// The encoding/decoding state of an HZ-GB-2312 stream.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub struct State {
+    gb_mode: bool,
+}
+
+impl State {
+    pub fn new() -> State {
+        State { gb_mode: false }
+    }
+}
+
+pub fn encode_from_str<'a>(
+    input: &str,
+    out_buffer: &'a mut [u8],
+    state: &mut State,
+    is_end: bool,
+) -> EncodeResult<'a> {
+    // Do the encode.
+    let mut input_i = 0;
+    let mut output_i = 0;
+    for (offset, c) in input.char_indices() {
+        let code = c as u32;
+        if code <= 127 {
+            // Ascii, switching out of GB 2312 first if needed.
+            let escape_len = if state.gb_mode { 2 } else { 0 };
+            let char_len = if c == '~' { 2 } else { 1 };
+            if (output_i + escape_len + char_len) > out_buffer.len() {
+                break;
+            }
+            if state.gb_mode {
+                out_buffer[output_i..(output_i + 2)].copy_from_slice(b"~}");
+                output_i += 2;
+                state.gb_mode = false;
+            }
+            if c == '~' {
+                out_buffer[output_i..(output_i + 2)].copy_from_slice(b"~~");
+            } else {
+                out_buffer[output_i] = code as u8;
+            }
+            output_i += char_len;
+            input_i = offset + 1;
+        } else if let Some(ptr) = gb2312::encode_char(c) {
+            // If this is the last character of the text, make sure there's
+            // room to switch back to ASCII after it.
+            let is_last = is_end && (offset + c.len_utf8()) == input.len();
+            let escape_len = if state.gb_mode { 0 } else { 2 };
+            let end_len = if is_last { 2 } else { 0 };
+            if (output_i + escape_len + 2 + end_len) > out_buffer.len() {
+                break;
+            }
+            if !state.gb_mode {
+                out_buffer[output_i..(output_i + 2)].copy_from_slice(b"~{");
+                output_i += 2;
+                state.gb_mode = true;
+            }
+            out_buffer[output_i] = (ptr / 94 + 0x21) as u8;
+            out_buffer[output_i + 1] = (ptr % 94 + 0x21) as u8;
+            output_i += 2;
+            if is_last {
+                out_buffer[output_i..(output_i + 2)].copy_from_slice(b"~}");
+                output_i += 2;
+                state.gb_mode = false;
+            }
+            input_i = offset + 1;
+        } else {
+            return Err(EncodeError {
+                character: c,
+                error_range: (offset, offset + c.len_utf8()),
+                output_bytes_written: output_i,
+            });
+        }
+    }
+
+    // Switch back to ASCII at the end of the text, if that hasn't happened
+    // already (e.g. when the final chunk is empty).
+    if is_end && input_i >= input.len() && state.gb_mode && (output_i + 2) <= out_buffer.len() {
+        out_buffer[output_i..(output_i + 2)].copy_from_slice(b"~}");
+        output_i += 2;
+        state.gb_mode = false;
+    }
+
+    // Calculate how much of the input was consumed.
+    if input_i > input.len() {
+        input_i = input.len();
+    } else {
+        while !input.is_char_boundary(input_i) {
+            input_i += 1;
+        }
+    }
+
+    Ok((&out_buffer[..output_i], input_i))
+}
+
+pub fn decode_to_str<'a>(
+    input: &[u8],
+    out_buffer: &'a mut [u8],
+    state: &mut State,
+    is_end: bool,
+) -> DecodeResult<'a> {
+    let mut input_i = 0;
+    let mut output_i = 0;
+    let mut buf = [0u8; 4]; // For encoding utf8 codepoints.
+
+    while input_i < input.len() {
+        let byte_1 = input[input_i];
+        if output_i >= out_buffer.len() {
+            break;
+        } else if byte_1 == b'~' {
+            // Escape sequence.
+            let byte_2 = if let Some(&byte) = input.get(input_i + 1) {
+                byte
+            } else if !is_end {
+                break;
+            } else {
+                // Error: truncated escape sequence at end of input.
+                return Err(DecodeError {
+                    cause: DecodeErrorCause::InvalidData,
+                    error_range: (input_i, input_i + 1),
+                    output_bytes_written: output_i,
+                });
+            };
+            match byte_2 {
+                b'{' => state.gb_mode = true,
+                b'}' => state.gb_mode = false,
+                b'~' if !state.gb_mode => {
+                    out_buffer[output_i] = b'~';
+                    output_i += 1;
+                }
+                b'\n' if !state.gb_mode => {} // Line continuation.
+                _ => {
+                    // Error: invalid escape sequence.
+                    return Err(DecodeError {
+                        cause: DecodeErrorCause::InvalidData,
+                        error_range: (input_i, input_i + if byte_2 <= 127 { 1 } else { 2 }),
+                        output_bytes_written: output_i,
+                    });
+                }
+            }
+            input_i += 2;
+        } else if !state.gb_mode || byte_1 == b'\n' || byte_1 == b'\r' {
+            // Ascii.  A line break also ends GB 2312 mode.
+            if byte_1 > 127 {
+                // Error: not 7-bit.
+                return Err(DecodeError {
+                    cause: DecodeErrorCause::InvalidData,
+                    error_range: (input_i, input_i + 1),
+                    output_bytes_written: output_i,
+                });
+            }
+            state.gb_mode = false;
+            out_buffer[output_i] = byte_1;
+            output_i += 1;
+            input_i += 1;
+        } else {
+            // GB 2312 byte pair.
+            if !(0x21..=0x7E).contains(&byte_1) {
+                // Error: invalid leading byte.
+                return Err(DecodeError {
+                    cause: DecodeErrorCause::InvalidData,
+                    error_range: (input_i, input_i + 1),
+                    output_bytes_written: output_i,
+                });
+            }
+            let byte_2 = if let Some(&byte) = input.get(input_i + 1) {
+                byte
+            } else if !is_end {
+                break;
+            } else {
+                // Error: truncated sequence at end of input.
+                return Err(DecodeError {
+                    cause: DecodeErrorCause::InvalidData,
+                    error_range: (input_i, input_i + 1),
+                    output_bytes_written: output_i,
+                });
+            };
+            if !(0x21..=0x7E).contains(&byte_2) {
+                // Error: invalid trailing byte.  If the invalid byte is
+                // ascii it remains part of the stream, and thus is not
+                // treated as part of the error.
+                return Err(DecodeError {
+                    cause: DecodeErrorCause::InvalidData,
+                    error_range: (input_i, input_i + if byte_2 <= 127 { 1 } else { 2 }),
+                    output_bytes_written: output_i,
+                });
+            }
+
+            let ptr = (byte_1 as usize - 0x21) * 94 + (byte_2 as usize - 0x21);
+            let string = if let Some(c) = gb2312::decode_ptr(ptr) {
+                c.encode_utf8(&mut buf)
+            } else {
+                // Error: correctly formed but undefined code.
+                return Err(DecodeError {
+                    cause: DecodeErrorCause::InvalidData,
+                    error_range: (input_i, input_i + 2),
+                    output_bytes_written: output_i,
+                });
+            };
+
+            // Copy decoded data to output.
+            if (output_i + string.len()) > out_buffer.len() {
+                break;
+            }
+            out_buffer[output_i..(output_i + string.len())].copy_from_slice(string.as_bytes());
+            output_i += string.len();
+            input_i += 2;
+        }
+    }
+
+    Ok((
+        unsafe { core::str::from_utf8_unchecked(&out_buffer[..output_i]) },
+        input_i,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use {DecodeError, DecodeErrorCause, EncodeError};
+
+    #[test]
+    fn encode_01() {
+        let mut buf = [0u8; 16];
+        let mut state = State::new();
+        let (encoded, consumed_count) =
+            encode_from_str("a中b~", &mut buf, &mut state, true).unwrap();
+        assert_eq!(consumed_count, 6);
+        assert_eq!(encoded, b"a~{VP~}b~~");
+        assert_eq!(state, State::new());
+    }
+
+    #[test]
+    fn encode_02() {
+        // The last character switches back to ASCII.
+        let mut buf = [0u8; 16];
+        let mut state = State::new();
+        let (encoded, consumed_count) =
+            encode_from_str("中文", &mut buf, &mut state, true).unwrap();
+        assert_eq!(consumed_count, 6);
+        assert_eq!(encoded, b"~{VPND~}");
+    }
+
+    #[test]
+    fn encode_03() {
+        // State carries across chunks, and an empty final chunk finishes
+        // the text.
+        let mut buf = [0u8; 16];
+        let mut state = State::new();
+        {
+            let (encoded, consumed_count) =
+                encode_from_str("中", &mut buf, &mut state, false).unwrap();
+            assert_eq!(consumed_count, 3);
+            assert_eq!(encoded, b"~{VP");
+        }
+        {
+            let (encoded, consumed_count) =
+                encode_from_str("文", &mut buf, &mut state, false).unwrap();
+            assert_eq!(consumed_count, 3);
+            assert_eq!(encoded, b"ND");
+        }
+        {
+            let (encoded, consumed_count) =
+                encode_from_str("", &mut buf, &mut state, true).unwrap();
+            assert_eq!(consumed_count, 0);
+            assert_eq!(encoded, b"~}");
+        }
+    }
+
+    #[test]
+    fn encode_04() {
+        // Not enough room for the final escape.
+        let mut buf = [0u8; 5];
+        let mut state = State::new();
+        let (encoded, consumed_count) = encode_from_str("a中", &mut buf, &mut state, true).unwrap();
+        assert_eq!(consumed_count, 1);
+        assert_eq!(encoded, b"a");
+        assert_eq!(state, State::new());
+    }
+
+    #[test]
+    fn encode_error_01() {
+        let mut buf = [0u8; 16];
+        let mut state = State::new();
+        assert_eq!(
+            encode_from_str("a中😀", &mut buf, &mut state, true),
+            Err(EncodeError {
+                character: '😀',
+                error_range: (4, 8),
+                output_bytes_written: 5,
+            }),
+        );
+    }
+
+    #[test]
+    fn decode_01() {
+        let mut buf = [0u8; 16];
+        let mut state = State::new();
+        let (decoded, consumed_count) =
+            decode_to_str(b"a~{VP~}b~~~\nc", &mut buf, &mut state, true).unwrap();
+        assert_eq!(consumed_count, 13);
+        assert_eq!(decoded, "a中b~c");
+    }
+
+    #[test]
+    fn decode_02() {
+        // A line break ends GB 2312 mode.
+        let mut buf = [0u8; 16];
+        let mut state = State::new();
+        let (decoded, consumed_count) =
+            decode_to_str(b"~{VP\nVP", &mut buf, &mut state, true).unwrap();
+        assert_eq!(consumed_count, 7);
+        assert_eq!(decoded, "中\nVP");
+    }
+
+    #[test]
+    fn decode_03() {
+        // Chunks split in the middle of escapes and pairs.
+        let mut buf = [0u8; 16];
+        let mut state = State::new();
+        {
+            let (decoded, consumed_count) =
+                decode_to_str(b"a~", &mut buf, &mut state, false).unwrap();
+            assert_eq!(consumed_count, 1);
+            assert_eq!(decoded, "a");
+        }
+        {
+            let (decoded, consumed_count) =
+                decode_to_str(b"~{V", &mut buf, &mut state, false).unwrap();
+            assert_eq!(consumed_count, 2);
+            assert_eq!(decoded, "");
+        }
+        {
+            let (decoded, consumed_count) =
+                decode_to_str(b"VP~}", &mut buf, &mut state, true).unwrap();
+            assert_eq!(consumed_count, 4);
+            assert_eq!(decoded, "中");
+        }
+    }
+
+    #[test]
+    fn decode_error_01() {
+        let mut buf = [0u8; 16];
+        let mut state = State::new();
+        assert_eq!(
+            decode_to_str(b"a~xb", &mut buf, &mut state, true),
+            Err(DecodeError {
+                cause: DecodeErrorCause::InvalidData,
+                error_range: (1, 2),
+                output_bytes_written: 1,
+            }),
+        );
+    }
+
+    #[test]
+    fn decode_error_02() {
+        let mut buf = [0u8; 16];
+        let mut state = State::new();
+        assert_eq!(
+            decode_to_str(b"~{V\n", &mut buf, &mut state, true),
+            Err(DecodeError {
+                cause: DecodeErrorCause::InvalidData,
+                error_range: (2, 3),
+                output_bytes_written: 0,
+            }),
+        );
+    }
+
+    #[test]
+    fn decode_error_03() {
+        // Undefined code.
+        let mut buf = [0u8; 16];
+        let mut state = State::new();
+        assert_eq!(
+            decode_to_str(b"~{x!~}", &mut buf, &mut state, true),
+            Err(DecodeError {
+                cause: DecodeErrorCause::InvalidData,
+                error_range: (2, 4),
+                output_bytes_written: 0,
+            }),
+        );
+    }
+
+    #[test]
+    fn decode_error_04() {
+        let mut buf = [0u8; 16];
+        let mut state = State::new();
+        assert_eq!(
+            decode_to_str(b"a\xD6\xD0", &mut buf, &mut state, true),
+            Err(DecodeError {
+                cause: DecodeErrorCause::InvalidData,
+                error_range: (1, 2),
+                output_bytes_written: 1,
+            }),
+        );
+    }
+}
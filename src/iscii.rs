@@ -0,0 +1,533 @@
+//! ISCII-91 (IS 13194:1991), the Indian Script Code for Information
+//! Interchange.
+//!
+//! This is Ascii, with the same upper half used for each of the Indic
+//! scripts.  Which script the upper half is in is switched by ATR (0xEF)
+//! followed by a script code, and returns to the default script at each
+//! line break and with ATR followed by 0x40.  ATR followed by one of the
+//! display attribute codes 0x30-0x3F is skipped, but the Roman script code
+//! 0x41 isn't supported.  Encoding and decoding are therefore stateful and
+//! take a `State`, which is created with the default script.  The text is
+//! assumed to start in the default script.
+//!
+//! Some characters are written as a pair of bytes: the nukta (0xE9)
+//! following another byte forms e.g. U+0950 OM (0xA1 0xE9) and the
+//! Devanagari nukta consonants, a halant (0xE8) followed by another halant
+//! or a nukta is a halant followed by U+200C ZERO WIDTH NON-JOINER or
+//! U+200D ZERO WIDTH JOINER, and EXT (0xF0) followed by another byte is one
+//! of a few additional signs.  INV (0xD9) is U+200D ZERO WIDTH JOINER on its
+//! own.
+//!
+//! The mapping is the same as ICU's, except that the danda and double danda
+//! (0xEA and 0xEA 0xEA) are the Devanagari characters in every script, and
+//! that Assamese has its own RA and WA (U+09F0 and U+09F1) rather than being
+//! the same as Bengali.
+//!
+//! The encoder starts in the default script, and switches scripts with ATR
+//! when a character isn't in the current one.  Nukta forms are encoded as
+//! their pair of bytes whether or not they're precomposed, so decoding the
+//! result gives the precomposed form where Unicode has one.  Two halants in
+//! a row and two dandas in a row can't be encoded as themselves either, and
+//! decode as a halant followed by U+200C and as a double danda.  Conversion
+//! is otherwise lossless.
+
+use core;
+use {DecodeError, DecodeErrorCause, DecodeResult, EncodeError, EncodeResult};
+
+// Generated by `encoding_tables/iscii/generate_iscii.py`.
+// Contains DECODE_TABLE, PAIR_TABLE, and ENCODE_TABLE.
+include!("generated/iscii/iscii_tables.rs.inc");
+
+const HALANT: u8 = 0xE8;
+const NUKTA: u8 = 0xE9;
+const ATR: u8 = 0xEF;
+const EXT: u8 = 0xF0;
+
+// The range of single bytes covered by DECODE_TABLE.
+const FIRST_BYTE: u8 = 0xA0;
+const LAST_BYTE: u8 = 0xFA;
+
+// The ATR code for the first script.  The rest follow in order.
+const FIRST_SCRIPT_CODE: u8 = 0x42;
+
+// The ATR code to switch back to the default script.
+const DEFAULT_SCRIPT_CODE: u8 = 0x40;
+
+/// The scripts that ISCII can be switched to, in the order of their ATR
+/// codes.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Script {
+    Devanagari,
+    Bengali,
+    Tamil,
+    Telugu,
+    Assamese,
+    Oriya,
+    Kannada,
+    Malayalam,
+    Gujarati,
+    Gurmukhi,
+}
+
+impl Script {
+    fn from_index(index: u8) -> Option<Script> {
+        match index {
+            0 => Some(Script::Devanagari),
+            1 => Some(Script::Bengali),
+            2 => Some(Script::Tamil),
+            3 => Some(Script::Telugu),
+            4 => Some(Script::Assamese),
+            5 => Some(Script::Oriya),
+            6 => Some(Script::Kannada),
+            7 => Some(Script::Malayalam),
+            8 => Some(Script::Gujarati),
+            9 => Some(Script::Gurmukhi),
+            _ => None,
+        }
+    }
+
+    fn atr_code(self) -> u8 {
+        FIRST_SCRIPT_CODE + self as u8
+    }
+}
+
+/// The encoding/decoding state of an ISCII stream.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct State {
+    script: Script,
+    default_script: Script,
+}
+
+impl State {
+    pub fn new(default_script: Script) -> State {
+        State {
+            script: default_script,
+            default_script,
+        }
+    }
+}
+
+impl Default for State {
+    fn default() -> State {
+        State::new(Script::Devanagari)
+    }
+}
+
+pub fn encode_from_str<'a>(
+    input: &str,
+    out_buffer: &'a mut [u8],
+    state: &mut State,
+    is_end: bool,
+) -> EncodeResult<'a> {
+    // Do the encode.
+    let mut input_i = 0;
+    let mut output_i = 0;
+    let mut itr = input.char_indices().peekable();
+    while let Some((offset, c)) = itr.next() {
+        if output_i >= out_buffer.len() {
+            break;
+        } else if (c as u32) < 0x80 {
+            out_buffer[output_i] = c as u8;
+            output_i += 1;
+            input_i = offset + 1;
+            if c == '\n' || c == '\r' {
+                state.script = state.default_script;
+            }
+        } else if let Some((script, bytes)) = encode_char(c, state) {
+            // A halant followed by a zero width non-joiner or joiner is
+            // written as a pair.
+            let pair;
+            let mut consumed = c.len_utf8();
+            let bytes = if bytes == [HALANT] {
+                match itr.peek() {
+                    Some(&(_, '\u{200C}')) => {
+                        pair = [HALANT, HALANT];
+                        consumed += 3;
+                        &pair[..]
+                    }
+                    Some(&(_, '\u{200D}')) => {
+                        pair = [HALANT, NUKTA];
+                        consumed += 3;
+                        &pair[..]
+                    }
+                    None if !is_end => {
+                        // A zero width joiner may follow in the next input,
+                        // so wait for it.
+                        break;
+                    }
+                    _ => bytes,
+                }
+            } else {
+                bytes
+            };
+
+            // Switch scripts first if needed.
+            let switch_len = if script == state.script { 0 } else { 2 };
+            if (output_i + switch_len + bytes.len()) > out_buffer.len() {
+                break;
+            }
+            if script != state.script {
+                out_buffer[output_i] = ATR;
+                out_buffer[output_i + 1] = script.atr_code();
+                output_i += 2;
+                state.script = script;
+            }
+            out_buffer[output_i..(output_i + bytes.len())].copy_from_slice(bytes);
+            output_i += bytes.len();
+            input_i = offset + consumed;
+            if consumed > c.len_utf8() {
+                itr.next();
+            }
+        } else {
+            return Err(EncodeError {
+                character: c,
+                error_range: (offset, offset + c.len_utf8()),
+                output_bytes_written: output_i,
+            });
+        }
+    }
+
+    // Calculate how much of the input was consumed.
+    if input_i > input.len() {
+        input_i = input.len();
+    } else {
+        while !input.is_char_boundary(input_i) {
+            input_i += 1;
+        }
+    }
+
+    Ok((&out_buffer[..output_i], input_i))
+}
+
+pub fn decode_to_str<'a>(
+    input: &[u8],
+    out_buffer: &'a mut [u8],
+    state: &mut State,
+    is_end: bool,
+) -> DecodeResult<'a> {
+    let mut input_i = 0;
+    let mut output_i = 0;
+    let mut buf = [0u8; 4]; // For encoding utf8 codepoints.
+
+    while input_i < input.len() {
+        let byte_1 = input[input_i];
+        let byte_2 = input.get(input_i + 1).cloned();
+        let script = state.script as u8;
+        if output_i >= out_buffer.len() {
+            break;
+        } else if byte_1 < 0x80 {
+            out_buffer[output_i] = byte_1;
+            output_i += 1;
+            input_i += 1;
+            if byte_1 == b'\n' || byte_1 == b'\r' {
+                state.script = state.default_script;
+            }
+            continue;
+        } else if byte_1 == ATR {
+            let byte_2 = if let Some(byte) = byte_2 {
+                byte
+            } else if !is_end {
+                break;
+            } else {
+                // Error: truncated sequence at end of input.
+                return Err(DecodeError {
+                    cause: DecodeErrorCause::InvalidData,
+                    error_range: (input_i, input_i + 1),
+                    output_bytes_written: output_i,
+                });
+            };
+            if byte_2 == DEFAULT_SCRIPT_CODE {
+                state.script = state.default_script;
+            } else if let Some(script) = byte_2
+                .checked_sub(FIRST_SCRIPT_CODE)
+                .and_then(Script::from_index)
+            {
+                state.script = script;
+            } else if !(0x30..=0x3F).contains(&byte_2) {
+                // Error: unknown attribute code.  If the code is ascii it
+                // remains part of the stream, and thus is not treated as
+                // part of the error.
+                return Err(DecodeError {
+                    cause: DecodeErrorCause::InvalidData,
+                    error_range: (input_i, input_i + if byte_2 <= 127 { 1 } else { 2 }),
+                    output_bytes_written: output_i,
+                });
+            }
+            input_i += 2;
+            continue;
+        }
+
+        // Check for a pair first.
+        let pair = if let Some(byte_2) = byte_2 {
+            PAIR_TABLE
+                .binary_search_by_key(&(script, byte_1, byte_2), |x| (x.0, x.1, x.2))
+                .ok()
+                .map(|i| PAIR_TABLE[i].3)
+        } else if !is_end
+            && (byte_1 == EXT || PAIR_TABLE.iter().any(|x| x.0 == script && x.1 == byte_1))
+        {
+            // The second byte of a pair may follow in the next input, so
+            // wait for it.
+            break;
+        } else {
+            None
+        };
+
+        // Get our decoded data.
+        let (string, input_consumed) = if let Some(string) = pair {
+            (string, 2)
+        } else if byte_1 == EXT {
+            // Error: undefined or truncated EXT sequence.  If the second
+            // byte is ascii it remains part of the stream, and thus is not
+            // treated as part of the error.
+            let len = match byte_2 {
+                Some(byte_2) if byte_2 > 127 => 2,
+                _ => 1,
+            };
+            return Err(DecodeError {
+                cause: DecodeErrorCause::InvalidData,
+                error_range: (input_i, input_i + len),
+                output_bytes_written: output_i,
+            });
+        } else {
+            let c = if (FIRST_BYTE..=LAST_BYTE).contains(&byte_1) {
+                DECODE_TABLE[script as usize][(byte_1 - FIRST_BYTE) as usize]
+            } else {
+                '�'
+            };
+            if c == '�' {
+                // Error: undefined byte in the current script.
+                return Err(DecodeError {
+                    cause: DecodeErrorCause::InvalidData,
+                    error_range: (input_i, input_i + 1),
+                    output_bytes_written: output_i,
+                });
+            }
+            (&*c.encode_utf8(&mut buf), 1)
+        };
+
+        // Copy decoded data to output.
+        if (output_i + string.len()) > out_buffer.len() {
+            // Not enough space in output buffer.
+            break;
+        }
+        out_buffer[output_i..(output_i + string.len())].copy_from_slice(string.as_bytes());
+
+        // Update our counters.
+        input_i += input_consumed;
+        output_i += string.len();
+    }
+
+    Ok((
+        unsafe { core::str::from_utf8_unchecked(&out_buffer[..output_i]) },
+        input_i,
+    ))
+}
+
+/// Returns the script to encode a character in and its bytes, preferring
+/// the current script, then the default script, then the first script that
+/// has it.
+fn encode_char(c: char, state: &State) -> Option<(Script, &'static [u8])> {
+    for &script in &[state.script, state.default_script] {
+        if let Ok(i) = ENCODE_TABLE.binary_search_by_key(&(c, script as u8), |x| (x.0, x.1)) {
+            return Some((script, ENCODE_TABLE[i].2));
+        }
+    }
+    let mut i = ENCODE_TABLE.binary_search_by_key(&c, |x| x.0).ok()?;
+    while i > 0 && ENCODE_TABLE[i - 1].0 == c {
+        i -= 1;
+    }
+    Some((Script::from_index(ENCODE_TABLE[i].1)?, ENCODE_TABLE[i].2))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use {DecodeError, DecodeErrorCause, EncodeError};
+
+    #[test]
+    fn encode_01() {
+        let text = "aक्षॐ।";
+        let mut buf = [0u8; 32];
+        let mut state = State::default();
+        let (encoded, consumed_count) = encode_from_str(text, &mut buf, &mut state, true).unwrap();
+        assert_eq!(consumed_count, text.len());
+        assert_eq!(encoded, &[0x61, 0xB3, 0xE8, 0xD6, 0xA1, 0xE9, 0xEA]);
+    }
+
+    #[test]
+    fn encode_02() {
+        // Switching scripts, and back to the default after a line break.
+        let text = "कক\nক";
+        let mut buf = [0u8; 32];
+        let mut state = State::new(Script::Devanagari);
+        let (encoded, consumed_count) = encode_from_str(text, &mut buf, &mut state, true).unwrap();
+        assert_eq!(consumed_count, text.len());
+        assert_eq!(encoded, &[0xB3, 0xEF, 0x43, 0xB3, 0x0A, 0xEF, 0x43, 0xB3]);
+    }
+
+    #[test]
+    fn encode_03() {
+        // Halant forms, and precomposed and decomposed nukta forms.
+        let text = "क्\u{200C}क्\u{200D}\u{0958}\u{0915}\u{093C}";
+        let mut buf = [0u8; 32];
+        let mut state = State::default();
+        let (encoded, consumed_count) = encode_from_str(text, &mut buf, &mut state, true).unwrap();
+        assert_eq!(consumed_count, text.len());
+        assert_eq!(
+            encoded,
+            &[0xB3, 0xE8, 0xE8, 0xB3, 0xE8, 0xE9, 0xB3, 0xE9, 0xB3, 0xE9]
+        );
+    }
+
+    #[test]
+    fn encode_04() {
+        // A halant at the end of the input waits for a possible zero width
+        // joiner, unless it's the end.
+        let mut buf = [0u8; 32];
+        let mut state = State::default();
+        let (encoded, consumed_count) = encode_from_str("क्", &mut buf, &mut state, false).unwrap();
+        assert_eq!(consumed_count, 3);
+        assert_eq!(encoded, &[0xB3]);
+
+        let (encoded, consumed_count) = encode_from_str("्", &mut buf, &mut state, true).unwrap();
+        assert_eq!(consumed_count, 3);
+        assert_eq!(encoded, &[0xE8]);
+    }
+
+    #[test]
+    fn encode_05() {
+        // Not enough room for the script switch and the character.
+        let mut buf = [0u8; 3];
+        let mut state = State::default();
+        let (encoded, consumed_count) = encode_from_str("aক", &mut buf, &mut state, true).unwrap();
+        assert_eq!(consumed_count, 1);
+        assert_eq!(encoded, &[0x61]);
+        assert_eq!(state, State::default());
+    }
+
+    #[test]
+    fn encode_error_01() {
+        let mut buf = [0u8; 32];
+        let mut state = State::default();
+        assert_eq!(
+            encode_from_str("क\u{200C}", &mut buf, &mut state, true),
+            Err(EncodeError {
+                character: '\u{200C}',
+                error_range: (3, 6),
+                output_bytes_written: 1,
+            }),
+        );
+    }
+
+    #[test]
+    fn decode_01() {
+        let input = [0x61, 0xB3, 0xE8, 0xD6, 0xA1, 0xE9, 0xEA, 0xEA];
+        let mut buf = [0u8; 32];
+        let mut state = State::default();
+        let (decoded, consumed_count) = decode_to_str(&input, &mut buf, &mut state, true).unwrap();
+        assert_eq!(consumed_count, input.len());
+        assert_eq!(decoded, "aक्षॐ॥");
+    }
+
+    #[test]
+    fn decode_02() {
+        // Switching scripts, skipping display attributes, and returning to
+        // the default script after a line break and with 0x40.
+        let input = [
+            0xB3, 0xEF, 0x43, 0xB3, 0xEF, 0x31, 0xB3, 0x0A, 0xB3, 0xEF, 0x4B, 0xB3, 0xEF, 0x40,
+            0xB3,
+        ];
+        let mut buf = [0u8; 64];
+        let mut state = State::new(Script::Tamil);
+        let (decoded, consumed_count) = decode_to_str(&input, &mut buf, &mut state, true).unwrap();
+        assert_eq!(consumed_count, input.len());
+        assert_eq!(decoded, "கকক\nகਕக");
+    }
+
+    #[test]
+    fn decode_03() {
+        // The second byte of a possible pair, or of ATR, may be in the next
+        // input.
+        let mut buf = [0u8; 32];
+        let mut state = State::default();
+        let (decoded, consumed_count) =
+            decode_to_str(&[0x61, 0xA1], &mut buf, &mut state, false).unwrap();
+        assert_eq!(consumed_count, 1);
+        assert_eq!(decoded, "a");
+
+        let (decoded, consumed_count) = decode_to_str(&[0xA1], &mut buf, &mut state, true).unwrap();
+        assert_eq!(consumed_count, 1);
+        assert_eq!(decoded, "ँ");
+
+        let (decoded, consumed_count) =
+            decode_to_str(&[0x61, 0xEF], &mut buf, &mut state, false).unwrap();
+        assert_eq!(consumed_count, 1);
+        assert_eq!(decoded, "a");
+    }
+
+    #[test]
+    fn decode_04() {
+        // Halant and EXT forms, and INV.
+        let input = [0xB3, 0xE8, 0xE8, 0xB3, 0xE8, 0xE9, 0xF0, 0xB8, 0xD9];
+        let mut buf = [0u8; 32];
+        let mut state = State::default();
+        let (decoded, consumed_count) = decode_to_str(&input, &mut buf, &mut state, true).unwrap();
+        assert_eq!(consumed_count, input.len());
+        assert_eq!(decoded, "क्\u{200C}क्\u{200D}\u{0952}\u{200D}");
+    }
+
+    #[test]
+    fn decode_05() {
+        // Assamese RA and WA.
+        let mut buf = [0u8; 32];
+        let mut state = State::new(Script::Assamese);
+        let (decoded, _) = decode_to_str(&[0xCF, 0xD4], &mut buf, &mut state, true).unwrap();
+        assert_eq!(decoded, "\u{09F0}\u{09F1}");
+    }
+
+    #[test]
+    fn decode_error_01() {
+        // A byte that's undefined in the current script.
+        let mut buf = [0u8; 32];
+        let mut state = State::new(Script::Tamil);
+        assert_eq!(
+            decode_to_str(&[0x61, 0xB4], &mut buf, &mut state, true),
+            Err(DecodeError {
+                cause: DecodeErrorCause::InvalidData,
+                error_range: (1, 2),
+                output_bytes_written: 1,
+            }),
+        );
+    }
+
+    #[test]
+    fn decode_error_02() {
+        // Unknown attribute code, which is ascii.
+        let mut buf = [0u8; 32];
+        let mut state = State::default();
+        assert_eq!(
+            decode_to_str(&[0xEF, 0x41, 0xB3], &mut buf, &mut state, true),
+            Err(DecodeError {
+                cause: DecodeErrorCause::InvalidData,
+                error_range: (0, 1),
+                output_bytes_written: 0,
+            }),
+        );
+    }
+
+    #[test]
+    fn decode_error_03() {
+        // Undefined EXT sequence.
+        let mut buf = [0u8; 32];
+        let mut state = State::default();
+        assert_eq!(
+            decode_to_str(&[0x61, 0xF0, 0xB3], &mut buf, &mut state, true),
+            Err(DecodeError {
+                cause: DecodeErrorCause::InvalidData,
+                error_range: (1, 3),
+                output_bytes_written: 1,
+            }),
+        );
+    }
+}
@@ -0,0 +1,615 @@
+//! ISO-2022-CN, the 7-bit encoding of GB 2312 and CNS 11643 from RFC 1922.
+//!
+//! GB 2312 (`ESC $ ) A`) or CNS 11643 plane 1 (`ESC $ ) G`) is designated
+//! to G1, which Shift Out (0x0E) and Shift In (0x0F) switch to and from.
+//! CNS 11643 plane 2 (`ESC $ * H`) is designated to G2, and each of its
+//! characters is prefixed with the single shift `ESC N`.  Encoding and
+//! decoding are therefore stateful and take a `State`.
+//!
+//! Designations only last until the end of a line, so the encoder
+//! re-designates as needed after every CR or LF, and the decoder forgets all
+//! designations at either.  The encoder prefers GB 2312 over CNS
+//! 11643 for characters that are in both, and switches back to ASCII before
+//! any ASCII character and at the end of the text.  Since the SO, SI, and ESC
+//! control characters can't be represented as themselves, the encoder
+//! returns an error for them.
+//!
+//! The ISO-2022-CN-EXT escape sequences for the other CNS 11643 planes are
+//! not supported, and are treated as errors by the decoder.
+//!
+//! Conversion in both directions is otherwise lossless.
+
+use cns11643;
+use core;
+use gb2312;
+use {DecodeError, DecodeErrorCause, DecodeResult, EncodeError, EncodeResult};
+
+const SO: u8 = 0x0E;
+const SI: u8 = 0x0F;
+const ESC: u8 = 0x1B;
+
+/// The encoding/decoding state of an ISO-2022-CN stream.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub struct State {
+    g1: Option<Charset>,
+    g2_designated: bool,
+    shifted: bool,
+}
+
+impl State {
+    pub fn new() -> State {
+        State {
+            g1: None,
+            g2_designated: false,
+            shifted: false,
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum Charset {
+    Gb2312,
+    CnsPlane1,
+    CnsPlane2,
+}
+
+impl Charset {
+    fn designation(self) -> &'static [u8] {
+        match self {
+            Charset::Gb2312 => b"\x1B$)A",
+            Charset::CnsPlane1 => b"\x1B$)G",
+            Charset::CnsPlane2 => b"\x1B$*H",
+        }
+    }
+}
+
+pub fn encode_from_str<'a>(
+    input: &str,
+    out_buffer: &'a mut [u8],
+    state: &mut State,
+    is_end: bool,
+) -> EncodeResult<'a> {
+    // Do the encode.
+    let mut input_i = 0;
+    let mut output_i = 0;
+    for (offset, c) in input.char_indices() {
+        let code = c as u32;
+        if code <= 127 && code != SO as u32 && code != SI as u32 && code != ESC as u32 {
+            // Ascii, shifting in first if needed.
+            let shift_len = if state.shifted { 1 } else { 0 };
+            if (output_i + shift_len + 1) > out_buffer.len() {
+                break;
+            }
+            if state.shifted {
+                out_buffer[output_i] = SI;
+                output_i += 1;
+                state.shifted = false;
+            }
+            out_buffer[output_i] = code as u8;
+            output_i += 1;
+            input_i = offset + 1;
+
+            // Designations end with the line.
+            if c == '\n' || c == '\r' {
+                state.g1 = None;
+                state.g2_designated = false;
+            }
+            continue;
+        }
+
+        // Find the character set to use, preferring GB 2312.
+        let (charset, ptr) = if let Some(ptr) = gb2312::encode_char(c) {
+            (Charset::Gb2312, ptr)
+        } else if let Some(ptr) = cns11643::encode_char(c).filter(|&ptr| ptr < 8836 * 2) {
+            if ptr < 8836 {
+                (Charset::CnsPlane1, ptr)
+            } else {
+                (Charset::CnsPlane2, ptr - 8836)
+            }
+        } else {
+            return Err(EncodeError {
+                character: c,
+                error_range: (offset, offset + c.len_utf8()),
+                output_bytes_written: output_i,
+            });
+        };
+
+        // Figure out what needs writing, and make sure there's room.  If
+        // this is the last character of the text, that includes shifting
+        // back in after it.
+        let is_last = is_end && (offset + c.len_utf8()) == input.len();
+        let (designate, shift_out, shift_in) = if charset == Charset::CnsPlane2 {
+            (!state.g2_designated, false, is_last && state.shifted)
+        } else {
+            (state.g1 != Some(charset), !state.shifted, is_last)
+        };
+        let needed = if designate { 4 } else { 0 }
+            + if shift_out { 1 } else { 0 }
+            + if charset == Charset::CnsPlane2 { 4 } else { 2 }
+            + if shift_in { 1 } else { 0 };
+        if (output_i + needed) > out_buffer.len() {
+            break;
+        }
+
+        // Write it.
+        if designate {
+            out_buffer[output_i..(output_i + 4)].copy_from_slice(charset.designation());
+            output_i += 4;
+            if charset == Charset::CnsPlane2 {
+                state.g2_designated = true;
+            } else {
+                state.g1 = Some(charset);
+            }
+        }
+        if shift_out {
+            out_buffer[output_i] = SO;
+            output_i += 1;
+            state.shifted = true;
+        }
+        if charset == Charset::CnsPlane2 {
+            out_buffer[output_i..(output_i + 2)].copy_from_slice(b"\x1BN");
+            output_i += 2;
+        }
+        out_buffer[output_i] = (ptr / 94 + 0x21) as u8;
+        out_buffer[output_i + 1] = (ptr % 94 + 0x21) as u8;
+        output_i += 2;
+        if shift_in {
+            out_buffer[output_i] = SI;
+            output_i += 1;
+            state.shifted = false;
+        }
+        input_i = offset + 1;
+    }
+
+    // Shift back in at the end of the text, if that hasn't happened already
+    // (e.g. when the final chunk is empty).
+    if is_end && input_i >= input.len() && state.shifted && output_i < out_buffer.len() {
+        out_buffer[output_i] = SI;
+        output_i += 1;
+        state.shifted = false;
+    }
+
+    // Calculate how much of the input was consumed.
+    if input_i > input.len() {
+        input_i = input.len();
+    } else {
+        while !input.is_char_boundary(input_i) {
+            input_i += 1;
+        }
+    }
+
+    Ok((&out_buffer[..output_i], input_i))
+}
+
+pub fn decode_to_str<'a>(
+    input: &[u8],
+    out_buffer: &'a mut [u8],
+    state: &mut State,
+    is_end: bool,
+) -> DecodeResult<'a> {
+    let mut input_i = 0;
+    let mut output_i = 0;
+    let mut buf = [0u8; 4]; // For encoding utf8 codepoints.
+
+    while input_i < input.len() {
+        let byte_1 = input[input_i];
+        if output_i >= out_buffer.len() {
+            break;
+        } else if byte_1 == ESC {
+            let rest = &input[input_i..];
+
+            // Designations.
+            let mut designated = false;
+            let mut is_prefix = false;
+            for &charset in &[Charset::Gb2312, Charset::CnsPlane1, Charset::CnsPlane2] {
+                let designation = charset.designation();
+                if rest.starts_with(designation) {
+                    if charset == Charset::CnsPlane2 {
+                        state.g2_designated = true;
+                    } else {
+                        state.g1 = Some(charset);
+                    }
+                    input_i += designation.len();
+                    designated = true;
+                    break;
+                }
+                is_prefix |= designation.starts_with(rest);
+            }
+            if designated {
+                continue;
+            }
+
+            if !rest.starts_with(b"\x1BN") {
+                if !is_end && is_prefix {
+                    break;
+                }
+                // Error: invalid or unsupported escape sequence.
+                return Err(DecodeError {
+                    cause: DecodeErrorCause::InvalidData,
+                    error_range: (input_i, input_i + 1),
+                    output_bytes_written: output_i,
+                });
+            }
+
+            // Single shift, followed by a CNS 11643 plane 2 byte pair.
+            if !state.g2_designated {
+                // Error: single shift without a designation.
+                return Err(DecodeError {
+                    cause: DecodeErrorCause::InvalidData,
+                    error_range: (input_i, input_i + 2),
+                    output_bytes_written: output_i,
+                });
+            }
+            for i in 2..4 {
+                match rest.get(i) {
+                    Some(&byte) if (0x21..=0x7E).contains(&byte) => {}
+                    None if !is_end => {
+                        return Ok((
+                            unsafe { core::str::from_utf8_unchecked(&out_buffer[..output_i]) },
+                            input_i,
+                        ));
+                    }
+                    Some(&byte) if byte > 127 => {
+                        // Error: invalid non-ascii byte.
+                        return Err(DecodeError {
+                            cause: DecodeErrorCause::InvalidData,
+                            error_range: (input_i, input_i + i + 1),
+                            output_bytes_written: output_i,
+                        });
+                    }
+                    _ => {
+                        // Error: invalid or missing byte.  If the invalid
+                        // byte is ascii it remains part of the stream, and
+                        // thus is not treated as part of the error.
+                        return Err(DecodeError {
+                            cause: DecodeErrorCause::InvalidData,
+                            error_range: (input_i, input_i + i),
+                            output_bytes_written: output_i,
+                        });
+                    }
+                }
+            }
+            let ptr = 8836 + (rest[2] as usize - 0x21) * 94 + (rest[3] as usize - 0x21);
+            let string = if let Some(c) = cns11643::decode_ptr(ptr) {
+                c.encode_utf8(&mut buf)
+            } else {
+                // Error: correctly formed but undefined code.
+                return Err(DecodeError {
+                    cause: DecodeErrorCause::InvalidData,
+                    error_range: (input_i, input_i + 4),
+                    output_bytes_written: output_i,
+                });
+            };
+
+            // Copy decoded data to output.
+            if (output_i + string.len()) > out_buffer.len() {
+                break;
+            }
+            out_buffer[output_i..(output_i + string.len())].copy_from_slice(string.as_bytes());
+            output_i += string.len();
+            input_i += 4;
+        } else if byte_1 == SO {
+            if state.g1.is_none() {
+                // Error: shift out without a designation.
+                return Err(DecodeError {
+                    cause: DecodeErrorCause::InvalidData,
+                    error_range: (input_i, input_i + 1),
+                    output_bytes_written: output_i,
+                });
+            }
+            state.shifted = true;
+            input_i += 1;
+        } else if byte_1 == SI {
+            state.shifted = false;
+            input_i += 1;
+        } else if !state.shifted || !(0x21..=0x7E).contains(&byte_1) {
+            // Ascii, including spaces and control characters in shifted
+            // mode.  A line break shifts back in and ends all designations.
+            if byte_1 > 127 {
+                // Error: not 7-bit.
+                return Err(DecodeError {
+                    cause: DecodeErrorCause::InvalidData,
+                    error_range: (input_i, input_i + 1),
+                    output_bytes_written: output_i,
+                });
+            }
+            if byte_1 == b'\n' || byte_1 == b'\r' {
+                *state = State::new();
+            }
+            out_buffer[output_i] = byte_1;
+            output_i += 1;
+            input_i += 1;
+        } else {
+            // G1 byte pair.
+            let byte_2 = if let Some(&byte) = input.get(input_i + 1) {
+                byte
+            } else if !is_end {
+                break;
+            } else {
+                // Error: truncated sequence at end of input.
+                return Err(DecodeError {
+                    cause: DecodeErrorCause::InvalidData,
+                    error_range: (input_i, input_i + 1),
+                    output_bytes_written: output_i,
+                });
+            };
+            if !(0x21..=0x7E).contains(&byte_2) {
+                // Error: invalid trailing byte.  If the invalid byte is
+                // ascii it remains part of the stream, and thus is not
+                // treated as part of the error.
+                return Err(DecodeError {
+                    cause: DecodeErrorCause::InvalidData,
+                    error_range: (input_i, input_i + if byte_2 <= 127 { 1 } else { 2 }),
+                    output_bytes_written: output_i,
+                });
+            }
+
+            let ptr = (byte_1 as usize - 0x21) * 94 + (byte_2 as usize - 0x21);
+            let c = match state.g1 {
+                Some(Charset::Gb2312) => gb2312::decode_ptr(ptr),
+                _ => cns11643::decode_ptr(ptr),
+            };
+            let string = if let Some(c) = c {
+                c.encode_utf8(&mut buf)
+            } else {
+                // Error: correctly formed but undefined code.
+                return Err(DecodeError {
+                    cause: DecodeErrorCause::InvalidData,
+                    error_range: (input_i, input_i + 2),
+                    output_bytes_written: output_i,
+                });
+            };
+
+            // Copy decoded data to output.
+            if (output_i + string.len()) > out_buffer.len() {
+                break;
+            }
+            out_buffer[output_i..(output_i + string.len())].copy_from_slice(string.as_bytes());
+            output_i += string.len();
+            input_i += 2;
+        }
+    }
+
+    Ok((
+        unsafe { core::str::from_utf8_unchecked(&out_buffer[..output_i]) },
+        input_i,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use {DecodeError, DecodeErrorCause, EncodeError};
+
+    #[test]
+    fn encode_01() {
+        let mut buf = [0u8; 64];
+        let mut state = State::new();
+        let (encoded, consumed_count) =
+            encode_from_str("a中b\n中臺\n", &mut buf, &mut state, true).unwrap();
+        assert_eq!(consumed_count, 13);
+        assert_eq!(
+            encoded,
+            &b"a\x1B$)A\x0EVP\x0Fb\n\x1B$)A\x0EVP\x1B$)GjW\x0F\n"[..]
+        );
+        assert_eq!(state, State::new());
+    }
+
+    #[test]
+    fn encode_02() {
+        // CNS 11643 plane 2 uses the single shift, without shifting in or
+        // out.
+        let mut buf = [0u8; 64];
+        let mut state = State::new();
+        let (encoded, consumed_count) =
+            encode_from_str("乂乂中", &mut buf, &mut state, true).unwrap();
+        assert_eq!(consumed_count, 9);
+        assert_eq!(encoded, &b"\x1B$*H\x1BN!!\x1BN!!\x1B$)A\x0EVP\x0F"[..]);
+    }
+
+    #[test]
+    fn encode_03() {
+        // An empty final chunk finishes the text.
+        let mut buf = [0u8; 64];
+        let mut state = State::new();
+        {
+            let (encoded, consumed_count) =
+                encode_from_str("中", &mut buf, &mut state, false).unwrap();
+            assert_eq!(consumed_count, 3);
+            assert_eq!(encoded, b"\x1B$)A\x0EVP");
+        }
+        {
+            let (encoded, consumed_count) =
+                encode_from_str("", &mut buf, &mut state, true).unwrap();
+            assert_eq!(consumed_count, 0);
+            assert_eq!(encoded, b"\x0F");
+        }
+    }
+
+    #[test]
+    fn encode_04() {
+        // Not enough room for the final shift in.
+        let mut buf = [0u8; 7];
+        let mut state = State::new();
+        let (encoded, consumed_count) = encode_from_str("中", &mut buf, &mut state, true).unwrap();
+        assert_eq!(consumed_count, 0);
+        assert_eq!(encoded, b"");
+        assert_eq!(state, State::new());
+    }
+
+    #[test]
+    fn encode_05() {
+        // A lone carriage return also ends the designations, so the
+        // output decodes back.
+        for text in &["a中\r中", "乂\r乂"] {
+            let mut buf = [0u8; 64];
+            let mut state = State::new();
+            let (encoded, consumed_count) =
+                encode_from_str(text, &mut buf, &mut state, true).unwrap();
+            assert_eq!(consumed_count, text.len());
+            let mut buf_2 = [0u8; 64];
+            let mut state = State::new();
+            let (decoded, consumed_count) =
+                decode_to_str(encoded, &mut buf_2, &mut state, true).unwrap();
+            assert_eq!(consumed_count, encoded.len());
+            assert_eq!(decoded, *text);
+        }
+    }
+
+    #[test]
+    fn encode_error_01() {
+        let mut buf = [0u8; 64];
+        let mut state = State::new();
+        assert_eq!(
+            encode_from_str("a한", &mut buf, &mut state, true),
+            Err(EncodeError {
+                character: '한',
+                error_range: (1, 4),
+                output_bytes_written: 1,
+            }),
+        );
+    }
+
+    #[test]
+    fn encode_error_02() {
+        let mut buf = [0u8; 64];
+        let mut state = State::new();
+        assert_eq!(
+            encode_from_str("a\u{1B}", &mut buf, &mut state, true),
+            Err(EncodeError {
+                character: '\u{1B}',
+                error_range: (1, 2),
+                output_bytes_written: 1,
+            }),
+        );
+    }
+
+    #[test]
+    fn encode_error_03() {
+        // CNS 11643 plane 3 isn't part of ISO-2022-CN.
+        let mut buf = [0u8; 64];
+        let mut state = State::new();
+        assert_eq!(
+            encode_from_str("a亅", &mut buf, &mut state, true),
+            Err(EncodeError {
+                character: '亅',
+                error_range: (1, 4),
+                output_bytes_written: 1,
+            }),
+        );
+    }
+
+    #[test]
+    fn decode_01() {
+        let mut buf = [0u8; 64];
+        let mut state = State::new();
+        let (decoded, consumed_count) = decode_to_str(
+            b"a\x1B$)A\x0EVP\x0Fb\n\x1B$)A\x0EVP\x1B$)GjW\x0F\n",
+            &mut buf,
+            &mut state,
+            true,
+        )
+        .unwrap();
+        assert_eq!(consumed_count, 26);
+        assert_eq!(decoded, "a中b\n中臺\n");
+    }
+
+    #[test]
+    fn decode_02() {
+        let mut buf = [0u8; 64];
+        let mut state = State::new();
+        let (decoded, consumed_count) = decode_to_str(
+            b"\x1B$*H\x1BN!!\x1B$)G\x0E!&\x1BN!!",
+            &mut buf,
+            &mut state,
+            true,
+        )
+        .unwrap();
+        assert_eq!(consumed_count, 19);
+        assert_eq!(decoded, "乂\u{30FB}乂");
+    }
+
+    #[test]
+    fn decode_03() {
+        // Chunks split in the middle of escapes and pairs.
+        let mut buf = [0u8; 64];
+        let mut state = State::new();
+        {
+            let (decoded, consumed_count) =
+                decode_to_str(b"a\x1B$)", &mut buf, &mut state, false).unwrap();
+            assert_eq!(consumed_count, 1);
+            assert_eq!(decoded, "a");
+        }
+        {
+            let (decoded, consumed_count) =
+                decode_to_str(b"\x1B$)A\x0EV", &mut buf, &mut state, false).unwrap();
+            assert_eq!(consumed_count, 5);
+            assert_eq!(decoded, "");
+        }
+        {
+            let (decoded, consumed_count) =
+                decode_to_str(b"VP\x1B", &mut buf, &mut state, false).unwrap();
+            assert_eq!(consumed_count, 2);
+            assert_eq!(decoded, "中");
+        }
+    }
+
+    #[test]
+    fn decode_error_01() {
+        // Designations end with the line.
+        let mut buf = [0u8; 64];
+        let mut state = State::new();
+        assert_eq!(
+            decode_to_str(b"\x1B$)A\x0EVP\n\x0EVP", &mut buf, &mut state, true),
+            Err(DecodeError {
+                cause: DecodeErrorCause::InvalidData,
+                error_range: (8, 9),
+                output_bytes_written: 4,
+            }),
+        );
+    }
+
+    #[test]
+    fn decode_error_02() {
+        // ISO-2022-CN-EXT designation.
+        let mut buf = [0u8; 64];
+        let mut state = State::new();
+        assert_eq!(
+            decode_to_str(b"\x1B$+I", &mut buf, &mut state, true),
+            Err(DecodeError {
+                cause: DecodeErrorCause::InvalidData,
+                error_range: (0, 1),
+                output_bytes_written: 0,
+            }),
+        );
+    }
+
+    #[test]
+    fn decode_error_03() {
+        let mut buf = [0u8; 64];
+        let mut state = State::new();
+        assert_eq!(
+            decode_to_str(b"\x1B$*H\x1BN!\n", &mut buf, &mut state, true),
+            Err(DecodeError {
+                cause: DecodeErrorCause::InvalidData,
+                error_range: (4, 7),
+                output_bytes_written: 0,
+            }),
+        );
+    }
+
+    #[test]
+    fn decode_error_04() {
+        // Single shift without a designation.
+        let mut buf = [0u8; 64];
+        let mut state = State::new();
+        assert_eq!(
+            decode_to_str(b"\x1BN!&", &mut buf, &mut state, true),
+            Err(DecodeError {
+                cause: DecodeErrorCause::InvalidData,
+                error_range: (0, 2),
+                output_bytes_written: 0,
+            }),
+        );
+    }
+}
@@ -0,0 +1,425 @@
+//! ISO-2022-KR, the 7-bit encoding of KS X 1001 from RFC 1557.
+//!
+//! The text starts with the designation `ESC $ ) C`, after which Shift Out
+//! (0x0E) switches to KS X 1001 and Shift In (0x0F) switches back to ASCII.
+//! Encoding and decoding are therefore stateful and take a `State`.
+//!
+//! The encoder writes the designation once at the very start of its output,
+//! and switches back to ASCII before any ASCII character and at the end of
+//! the text.  Since the SO, SI, and ESC control characters can't be
+//! represented as themselves, the encoder returns an error for them.  The
+//! decoder switches back to ASCII at a line break, even without a Shift In.
+//!
+//! Conversion in both directions is otherwise lossless.
+
+use core;
+use ksx1001;
+use {DecodeError, DecodeErrorCause, DecodeResult, EncodeError, EncodeResult};
+
+const DESIGNATION: &[u8] = b"\x1B$)C";
+const SO: u8 = 0x0E;
+const SI: u8 = 0x0F;
+const ESC: u8 = 0x1B;
+
+/// The encoding/decoding state of an ISO-2022-KR stream.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub struct State {
+    designated: bool,
+    shifted: bool,
+}
+
+impl State {
+    pub fn new() -> State {
+        State {
+            designated: false,
+            shifted: false,
+        }
+    }
+}
+
+pub fn encode_from_str<'a>(
+    input: &str,
+    out_buffer: &'a mut [u8],
+    state: &mut State,
+    is_end: bool,
+) -> EncodeResult<'a> {
+    // Do the encode.
+    let mut input_i = 0;
+    let mut output_i = 0;
+    for (offset, c) in input.char_indices() {
+        let code = c as u32;
+
+        // The designation goes before everything else in the text.
+        let designation_len = if state.designated {
+            0
+        } else {
+            DESIGNATION.len()
+        };
+
+        if code <= 127 && code != SO as u32 && code != SI as u32 && code != ESC as u32 {
+            // Ascii, shifting in first if needed.
+            let shift_len = if state.shifted { 1 } else { 0 };
+            if (output_i + designation_len + shift_len + 1) > out_buffer.len() {
+                break;
+            }
+            if !state.designated {
+                out_buffer[output_i..(output_i + DESIGNATION.len())].copy_from_slice(DESIGNATION);
+                output_i += DESIGNATION.len();
+                state.designated = true;
+            }
+            if state.shifted {
+                out_buffer[output_i] = SI;
+                output_i += 1;
+                state.shifted = false;
+            }
+            out_buffer[output_i] = code as u8;
+            output_i += 1;
+            input_i = offset + 1;
+        } else if let Some(ptr) = ksx1001::encode_char(c) {
+            // If this is the last character of the text, make sure there's
+            // room to shift back in after it.
+            let is_last = is_end && (offset + c.len_utf8()) == input.len();
+            let shift_len = if state.shifted { 0 } else { 1 };
+            let end_len = if is_last { 1 } else { 0 };
+            if (output_i + designation_len + shift_len + 2 + end_len) > out_buffer.len() {
+                break;
+            }
+            if !state.designated {
+                out_buffer[output_i..(output_i + DESIGNATION.len())].copy_from_slice(DESIGNATION);
+                output_i += DESIGNATION.len();
+                state.designated = true;
+            }
+            if !state.shifted {
+                out_buffer[output_i] = SO;
+                output_i += 1;
+                state.shifted = true;
+            }
+            out_buffer[output_i] = (ptr / 94 + 0x21) as u8;
+            out_buffer[output_i + 1] = (ptr % 94 + 0x21) as u8;
+            output_i += 2;
+            if is_last {
+                out_buffer[output_i] = SI;
+                output_i += 1;
+                state.shifted = false;
+            }
+            input_i = offset + 1;
+        } else {
+            return Err(EncodeError {
+                character: c,
+                error_range: (offset, offset + c.len_utf8()),
+                output_bytes_written: output_i,
+            });
+        }
+    }
+
+    // Shift back in at the end of the text, if that hasn't happened already
+    // (e.g. when the final chunk is empty).
+    if is_end && input_i >= input.len() && state.shifted && output_i < out_buffer.len() {
+        out_buffer[output_i] = SI;
+        output_i += 1;
+        state.shifted = false;
+    }
+
+    // Calculate how much of the input was consumed.
+    if input_i > input.len() {
+        input_i = input.len();
+    } else {
+        while !input.is_char_boundary(input_i) {
+            input_i += 1;
+        }
+    }
+
+    Ok((&out_buffer[..output_i], input_i))
+}
+
+pub fn decode_to_str<'a>(
+    input: &[u8],
+    out_buffer: &'a mut [u8],
+    state: &mut State,
+    is_end: bool,
+) -> DecodeResult<'a> {
+    let mut input_i = 0;
+    let mut output_i = 0;
+    let mut buf = [0u8; 4]; // For encoding utf8 codepoints.
+
+    while input_i < input.len() {
+        let byte_1 = input[input_i];
+        if output_i >= out_buffer.len() {
+            break;
+        } else if byte_1 == ESC {
+            // Escape sequence.  The only valid one is the designation.
+            let rest = &input[input_i..];
+            if rest.starts_with(DESIGNATION) {
+                state.designated = true;
+                input_i += DESIGNATION.len();
+            } else if !is_end && DESIGNATION.starts_with(rest) {
+                break;
+            } else {
+                // Error: invalid escape sequence.
+                return Err(DecodeError {
+                    cause: DecodeErrorCause::InvalidData,
+                    error_range: (input_i, input_i + 1),
+                    output_bytes_written: output_i,
+                });
+            }
+        } else if byte_1 == SO {
+            if !state.designated {
+                // Error: shift out without a designation.
+                return Err(DecodeError {
+                    cause: DecodeErrorCause::InvalidData,
+                    error_range: (input_i, input_i + 1),
+                    output_bytes_written: output_i,
+                });
+            }
+            state.shifted = true;
+            input_i += 1;
+        } else if byte_1 == SI {
+            state.shifted = false;
+            input_i += 1;
+        } else if !state.shifted || !(0x21..=0x7E).contains(&byte_1) {
+            // Ascii, including spaces and control characters in KS X 1001
+            // mode.  A line break also shifts back in.
+            if byte_1 > 127 {
+                // Error: not 7-bit.
+                return Err(DecodeError {
+                    cause: DecodeErrorCause::InvalidData,
+                    error_range: (input_i, input_i + 1),
+                    output_bytes_written: output_i,
+                });
+            }
+            if byte_1 == b'\n' || byte_1 == b'\r' {
+                state.shifted = false;
+            }
+            out_buffer[output_i] = byte_1;
+            output_i += 1;
+            input_i += 1;
+        } else {
+            // KS X 1001 byte pair.
+            let byte_2 = if let Some(&byte) = input.get(input_i + 1) {
+                byte
+            } else if !is_end {
+                break;
+            } else {
+                // Error: truncated sequence at end of input.
+                return Err(DecodeError {
+                    cause: DecodeErrorCause::InvalidData,
+                    error_range: (input_i, input_i + 1),
+                    output_bytes_written: output_i,
+                });
+            };
+            if !(0x21..=0x7E).contains(&byte_2) {
+                // Error: invalid trailing byte.  If the invalid byte is
+                // ascii it remains part of the stream, and thus is not
+                // treated as part of the error.
+                return Err(DecodeError {
+                    cause: DecodeErrorCause::InvalidData,
+                    error_range: (input_i, input_i + if byte_2 <= 127 { 1 } else { 2 }),
+                    output_bytes_written: output_i,
+                });
+            }
+
+            let ptr = (byte_1 as usize - 0x21) * 94 + (byte_2 as usize - 0x21);
+            let string = if let Some(c) = ksx1001::decode_ptr(ptr) {
+                c.encode_utf8(&mut buf)
+            } else {
+                // Error: correctly formed but undefined code.
+                return Err(DecodeError {
+                    cause: DecodeErrorCause::InvalidData,
+                    error_range: (input_i, input_i + 2),
+                    output_bytes_written: output_i,
+                });
+            };
+
+            // Copy decoded data to output.
+            if (output_i + string.len()) > out_buffer.len() {
+                break;
+            }
+            out_buffer[output_i..(output_i + string.len())].copy_from_slice(string.as_bytes());
+            output_i += string.len();
+            input_i += 2;
+        }
+    }
+
+    Ok((
+        unsafe { core::str::from_utf8_unchecked(&out_buffer[..output_i]) },
+        input_i,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use {DecodeError, DecodeErrorCause, EncodeError};
+
+    #[test]
+    fn encode_01() {
+        let mut buf = [0u8; 32];
+        let mut state = State::new();
+        let (encoded, consumed_count) =
+            encode_from_str("a한b\n한", &mut buf, &mut state, true).unwrap();
+        assert_eq!(consumed_count, 9);
+        assert_eq!(encoded, b"\x1B$)Ca\x0EGQ\x0Fb\n\x0EGQ\x0F");
+    }
+
+    #[test]
+    fn encode_02() {
+        // The designation is only written once, and an empty final chunk
+        // finishes the text.
+        let mut buf = [0u8; 32];
+        let mut state = State::new();
+        {
+            let (encoded, consumed_count) =
+                encode_from_str("한", &mut buf, &mut state, false).unwrap();
+            assert_eq!(consumed_count, 3);
+            assert_eq!(encoded, b"\x1B$)C\x0EGQ");
+        }
+        {
+            let (encoded, consumed_count) =
+                encode_from_str("한", &mut buf, &mut state, false).unwrap();
+            assert_eq!(consumed_count, 3);
+            assert_eq!(encoded, b"GQ");
+        }
+        {
+            let (encoded, consumed_count) =
+                encode_from_str("", &mut buf, &mut state, true).unwrap();
+            assert_eq!(consumed_count, 0);
+            assert_eq!(encoded, b"\x0F");
+        }
+    }
+
+    #[test]
+    fn encode_03() {
+        // Not enough room for the final shift in.
+        let mut buf = [0u8; 7];
+        let mut state = State::new();
+        let (encoded, consumed_count) = encode_from_str("한", &mut buf, &mut state, true).unwrap();
+        assert_eq!(consumed_count, 0);
+        assert_eq!(encoded, b"");
+        assert_eq!(state, State::new());
+    }
+
+    #[test]
+    fn encode_error_01() {
+        let mut buf = [0u8; 32];
+        let mut state = State::new();
+        assert_eq!(
+            encode_from_str("a\u{0E}", &mut buf, &mut state, true),
+            Err(EncodeError {
+                character: '\u{0E}',
+                error_range: (1, 2),
+                output_bytes_written: 5,
+            }),
+        );
+    }
+
+    #[test]
+    fn encode_error_02() {
+        let mut buf = [0u8; 32];
+        let mut state = State::new();
+        assert_eq!(
+            encode_from_str("a😀", &mut buf, &mut state, true),
+            Err(EncodeError {
+                character: '😀',
+                error_range: (1, 5),
+                output_bytes_written: 5,
+            }),
+        );
+    }
+
+    #[test]
+    fn decode_01() {
+        let mut buf = [0u8; 32];
+        let mut state = State::new();
+        let (decoded, consumed_count) = decode_to_str(
+            b"\x1B$)Ca\x0EGQ GQ\x0Fb\n\x0EGQ\nGQ",
+            &mut buf,
+            &mut state,
+            true,
+        )
+        .unwrap();
+        assert_eq!(consumed_count, 20);
+        assert_eq!(decoded, "a한 한b\n한\nGQ");
+    }
+
+    #[test]
+    fn decode_02() {
+        // Chunks split in the middle of the designation and pairs.
+        let mut buf = [0u8; 32];
+        let mut state = State::new();
+        {
+            let (decoded, consumed_count) =
+                decode_to_str(b"\x1B$", &mut buf, &mut state, false).unwrap();
+            assert_eq!(consumed_count, 0);
+            assert_eq!(decoded, "");
+        }
+        {
+            let (decoded, consumed_count) =
+                decode_to_str(b"\x1B$)C\x0EG", &mut buf, &mut state, false).unwrap();
+            assert_eq!(consumed_count, 5);
+            assert_eq!(decoded, "");
+        }
+        {
+            let (decoded, consumed_count) =
+                decode_to_str(b"GQ\x0F", &mut buf, &mut state, true).unwrap();
+            assert_eq!(consumed_count, 3);
+            assert_eq!(decoded, "한");
+        }
+    }
+
+    #[test]
+    fn decode_error_01() {
+        // Shift out without a designation.
+        let mut buf = [0u8; 32];
+        let mut state = State::new();
+        assert_eq!(
+            decode_to_str(b"a\x0EGQ", &mut buf, &mut state, true),
+            Err(DecodeError {
+                cause: DecodeErrorCause::InvalidData,
+                error_range: (1, 2),
+                output_bytes_written: 1,
+            }),
+        );
+    }
+
+    #[test]
+    fn decode_error_02() {
+        let mut buf = [0u8; 32];
+        let mut state = State::new();
+        assert_eq!(
+            decode_to_str(b"\x1B$)Aa", &mut buf, &mut state, true),
+            Err(DecodeError {
+                cause: DecodeErrorCause::InvalidData,
+                error_range: (0, 1),
+                output_bytes_written: 0,
+            }),
+        );
+    }
+
+    #[test]
+    fn decode_error_03() {
+        let mut buf = [0u8; 32];
+        let mut state = State::new();
+        assert_eq!(
+            decode_to_str(b"\x1B$)C\x0EG\x0F", &mut buf, &mut state, true),
+            Err(DecodeError {
+                cause: DecodeErrorCause::InvalidData,
+                error_range: (5, 6),
+                output_bytes_written: 0,
+            }),
+        );
+    }
+
+    #[test]
+    fn decode_error_04() {
+        let mut buf = [0u8; 32];
+        let mut state = State::new();
+        assert_eq!(
+            decode_to_str(b"a\xC7\xD1", &mut buf, &mut state, true),
+            Err(DecodeError {
+                cause: DecodeErrorCause::InvalidData,
+                error_range: (1, 2),
+                output_bytes_written: 1,
+            }),
+        );
+    }
+}
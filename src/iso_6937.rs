@@ -0,0 +1,387 @@
+//! ISO/IEC 6937:2001, the Latin alphabet with non-spacing diacritics.
+//!
+//! This is Ascii, with the upper half used for symbols, a few letters, and
+//! the non-spacing diacritical marks 0xC1-0xCF.  A diacritical mark comes
+//! before the character it applies to, where Unicode has combining marks
+//! after it, e.g. "é" is 0xC2 0x65.  A mark followed by a space is the
+//! spacing form of the mark, e.g. 0xC2 0x20 is "´".
+//!
+//! Decoding a mark and a character gives the precomposed character where
+//! Unicode composes the pair, e.g. 0xC8 0x77 is "ẅ", and otherwise the
+//! character followed by a combining mark.  The one exception is 0xE0, U+2126
+//! OHM SIGN, which Unicode normalizes to U+03A9 before composing, so a mark
+//! on it always decodes to a combining mark.  Encoding accepts both forms of
+//! a character, but only one mark per character, and also accepts the few
+//! precomposed characters that Unicode doesn't compose to, such as U+212B
+//! ANGSTROM SIGN.  Decoding a character followed by a combining mark, or one
+//! of those characters, therefore gives the composed form.  Apart from that,
+//! conversion in both directions is lossless.
+
+use core;
+use {DecodeError, DecodeErrorCause, DecodeResult, EncodeError, EncodeResult};
+
+// Generated by `encoding_tables/iso_6937/generate_iso_6937.py`.
+// Contains DECODE_TABLE, ENCODE_TABLE, COMPOSE_TABLE, and DECOMPOSE_TABLE.
+include!("generated/iso_6937/iso_6937_tables.rs.inc");
+
+// The non-spacing diacritical marks, and their Unicode combining marks.
+// 0xC9 and 0xCC are unassigned.
+const MARK_TABLE: [(u8, char); 13] = [
+    (0xC1, '\u{0300}'), // Grave
+    (0xC2, '\u{0301}'), // Acute
+    (0xC3, '\u{0302}'), // Circumflex
+    (0xC4, '\u{0303}'), // Tilde
+    (0xC5, '\u{0304}'), // Macron
+    (0xC6, '\u{0306}'), // Breve
+    (0xC7, '\u{0307}'), // Dot above
+    (0xC8, '\u{0308}'), // Diaeresis
+    (0xCA, '\u{030A}'), // Ring above
+    (0xCB, '\u{0327}'), // Cedilla
+    (0xCD, '\u{030B}'), // Double acute
+    (0xCE, '\u{0328}'), // Ogonek
+    (0xCF, '\u{030C}'), // Caron
+];
+
+pub fn encode_from_str<'a>(
+    input: &str,
+    out_buffer: &'a mut [u8],
+    is_end: bool,
+) -> EncodeResult<'a> {
+    // Do the encode.
+    let mut input_i = 0;
+    let mut output_i = 0;
+    let mut itr = input.char_indices().peekable();
+    while let Some((offset, c)) = itr.next() {
+        if output_i >= out_buffer.len() {
+            break;
+        }
+
+        // Get the bytes for the character, and its combining mark if it
+        // has one.
+        let mut input_consumed = c.len_utf8();
+        let bytes = if let Ok(i) = DECOMPOSE_TABLE.binary_search_by_key(&c, |x| x.0) {
+            [DECOMPOSE_TABLE[i].1, DECOMPOSE_TABLE[i].2]
+        } else {
+            let byte = if (c as u32) < 0x80 {
+                c as u8
+            } else if let Ok(i) = ENCODE_TABLE.binary_search_by_key(&c, |x| x.0) {
+                ENCODE_TABLE[i].1
+            } else {
+                return Err(EncodeError {
+                    character: c,
+                    error_range: (offset, offset + c.len_utf8()),
+                    output_bytes_written: output_i,
+                });
+            };
+            if !is_graphic(byte) {
+                [byte, 0]
+            } else if let Some(&(_, mark)) = itr.peek() {
+                if let Some(&(mark_byte, _)) = MARK_TABLE.iter().find(|x| x.1 == mark) {
+                    itr.next();
+                    input_consumed += mark.len_utf8();
+                    [mark_byte, byte]
+                } else {
+                    [byte, 0]
+                }
+            } else if !is_end {
+                // A combining mark may follow in the next input, so wait
+                // for it.
+                break;
+            } else {
+                [byte, 0]
+            }
+        };
+        let bytes = if bytes[1] == 0 {
+            &bytes[..1]
+        } else {
+            &bytes[..]
+        };
+
+        if (output_i + bytes.len()) > out_buffer.len() {
+            break;
+        }
+        out_buffer[output_i..(output_i + bytes.len())].copy_from_slice(bytes);
+        output_i += bytes.len();
+        input_i = offset + input_consumed;
+    }
+
+    // Calculate how much of the input was consumed.
+    if input_i > input.len() {
+        input_i = input.len();
+    } else {
+        while !input.is_char_boundary(input_i) {
+            input_i += 1;
+        }
+    }
+
+    Ok((&out_buffer[..output_i], input_i))
+}
+
+pub fn decode_to_str<'a>(input: &[u8], out_buffer: &'a mut [u8], is_end: bool) -> DecodeResult<'a> {
+    let mut input_i = 0;
+    let mut output_i = 0;
+    let mut buf = [0u8; 8]; // For encoding utf8 codepoints.
+
+    while input_i < input.len() {
+        if output_i >= out_buffer.len() {
+            break;
+        }
+        let byte_1 = input[input_i];
+        if byte_1 < 0x80 {
+            out_buffer[output_i] = byte_1;
+            output_i += 1;
+            input_i += 1;
+            continue;
+        }
+
+        // Get our decoded data.
+        let (c, mark, input_consumed) = if let Some(&(_, mark)) =
+            MARK_TABLE.iter().find(|x| x.0 == byte_1)
+        {
+            // A diacritical mark applies to the character after it.
+            let byte_2 = if let Some(&byte) = input.get(input_i + 1) {
+                byte
+            } else if !is_end {
+                break;
+            } else {
+                // Error: truncated sequence at end of input.
+                return Err(DecodeError {
+                    cause: DecodeErrorCause::InvalidData,
+                    error_range: (input_i, input_i + 1),
+                    output_bytes_written: output_i,
+                });
+            };
+            if let Ok(i) = COMPOSE_TABLE.binary_search_by_key(&(byte_1, byte_2), |x| (x.0, x.1)) {
+                (COMPOSE_TABLE[i].2, None, 2)
+            } else if is_graphic(byte_2) && decode_byte(byte_2) != '�' {
+                (decode_byte(byte_2), Some(mark), 2)
+            } else {
+                // Error: the mark isn't followed by a character it can
+                // apply to.  If that byte is ascii it remains part of
+                // the stream, and thus is not treated as part of the
+                // error.
+                return Err(DecodeError {
+                    cause: DecodeErrorCause::InvalidData,
+                    error_range: (input_i, input_i + if byte_2 <= 127 { 1 } else { 2 }),
+                    output_bytes_written: output_i,
+                });
+            }
+        } else if decode_byte(byte_1) != '�' {
+            (decode_byte(byte_1), None, 1)
+        } else {
+            // Error: undefined byte.
+            return Err(DecodeError {
+                cause: DecodeErrorCause::InvalidData,
+                error_range: (input_i, input_i + 1),
+                output_bytes_written: output_i,
+            });
+        };
+
+        // Copy decoded data to output.
+        let len = c.encode_utf8(&mut buf).len();
+        let len = len + mark.map_or(0, |m| m.encode_utf8(&mut buf[len..]).len());
+        if (output_i + len) > out_buffer.len() {
+            // Not enough space in output buffer.
+            break;
+        }
+        out_buffer[output_i..(output_i + len)].copy_from_slice(&buf[..len]);
+
+        // Update our counters.
+        input_i += input_consumed;
+        output_i += len;
+    }
+
+    Ok((
+        unsafe { core::str::from_utf8_unchecked(&out_buffer[..output_i]) },
+        input_i,
+    ))
+}
+
+/// Decodes a single byte, returning '�' for undefined bytes and for the
+/// diacritical marks.
+fn decode_byte(byte: u8) -> char {
+    if byte < 0x80 {
+        byte as char
+    } else {
+        DECODE_TABLE[(byte - 0x80) as usize]
+    }
+}
+
+/// Returns whether a byte is a graphic character, i.e. one that a
+/// diacritical mark can apply to.
+fn is_graphic(byte: u8) -> bool {
+    (0x20..0x7F).contains(&byte) || byte >= 0xA0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use {DecodeError, DecodeErrorCause, EncodeError};
+
+    #[test]
+    fn encode_01() {
+        let text = "Crème brûlée ½ Øre Ŋ";
+        let mut buf = [0u8; 64];
+        let (encoded, consumed_count) = encode_from_str(text, &mut buf, true).unwrap();
+        assert_eq!(consumed_count, text.len());
+        assert_eq!(encoded, &b"Cr\xC1eme br\xC3ul\xC2ee \xBD \xE9re \xEE"[..]);
+    }
+
+    #[test]
+    fn encode_02() {
+        // Combining marks, including on characters with no precomposed
+        // form, and spacing marks.
+        let text = "e\u{0301}q\u{0308}\u{0141}\u{030C} ´";
+        let mut buf = [0u8; 64];
+        let (encoded, consumed_count) = encode_from_str(text, &mut buf, true).unwrap();
+        assert_eq!(consumed_count, text.len());
+        assert_eq!(encoded, &b"\xC2e\xC8q\xCF\xE8 \xC2 "[..]);
+    }
+
+    #[test]
+    fn encode_03() {
+        // Precomposed and decomposed forms encode the same.
+        let text = "ẅw\u{0308}ǎa\u{030C}\u{212B}A\u{030A}";
+        let mut buf = [0u8; 64];
+        let (encoded, consumed_count) = encode_from_str(text, &mut buf, true).unwrap();
+        assert_eq!(consumed_count, text.len());
+        assert_eq!(encoded, &b"\xC8w\xC8w\xCFa\xCFa\xCAA\xCAA"[..]);
+    }
+
+    #[test]
+    fn encode_04() {
+        // A character at the end of the input waits for a possible
+        // combining mark, unless it's the end.
+        let mut buf = [0u8; 64];
+        {
+            let (encoded, consumed_count) = encode_from_str("ab", &mut buf, false).unwrap();
+            assert_eq!(consumed_count, 1);
+            assert_eq!(encoded, b"a");
+        }
+        {
+            let (encoded, consumed_count) = encode_from_str("a\n", &mut buf, false).unwrap();
+            assert_eq!(consumed_count, 2);
+            assert_eq!(encoded, b"a\n");
+        }
+        {
+            let (encoded, consumed_count) = encode_from_str("ab", &mut buf, true).unwrap();
+            assert_eq!(consumed_count, 2);
+            assert_eq!(encoded, b"ab");
+        }
+    }
+
+    #[test]
+    fn encode_error_01() {
+        // Only one combining mark per character.
+        let mut buf = [0u8; 64];
+        assert_eq!(
+            encode_from_str("ae\u{0301}\u{0301}", &mut buf, true),
+            Err(EncodeError {
+                character: '\u{0301}',
+                error_range: (4, 6),
+                output_bytes_written: 3,
+            }),
+        );
+    }
+
+    #[test]
+    fn encode_error_02() {
+        let mut buf = [0u8; 64];
+        assert_eq!(
+            encode_from_str("a€", &mut buf, true),
+            Err(EncodeError {
+                character: '€',
+                error_range: (1, 4),
+                output_bytes_written: 1,
+            }),
+        );
+    }
+
+    #[test]
+    fn decode_01() {
+        let input = b"Cr\xC1eme br\xC3ul\xC2ee \xBD \xE9re \xEE";
+        let mut buf = [0u8; 64];
+        let (decoded, consumed_count) = decode_to_str(input, &mut buf, true).unwrap();
+        assert_eq!(consumed_count, input.len());
+        assert_eq!(decoded, "Crème brûlée ½ Øre Ŋ");
+    }
+
+    #[test]
+    fn decode_02() {
+        // Marks with no precomposed form, and spacing marks.
+        let input = b"\xC8q\xCF\xE8\xC2 \xC1 ";
+        let mut buf = [0u8; 64];
+        let (decoded, consumed_count) = decode_to_str(input, &mut buf, true).unwrap();
+        assert_eq!(consumed_count, input.len());
+        assert_eq!(decoded, "q\u{0308}\u{0141}\u{030C}´ \u{0300}");
+    }
+
+    #[test]
+    fn decode_03() {
+        // A mark at the end of the input waits for the character it applies
+        // to, unless it's the end.
+        let mut buf = [0u8; 64];
+        assert_eq!(decode_to_str(b"a\xC2", &mut buf, false), Ok(("a", 1)));
+        assert_eq!(
+            decode_to_str(b"a\xC2", &mut buf, true),
+            Err(DecodeError {
+                cause: DecodeErrorCause::InvalidData,
+                error_range: (1, 2),
+                output_bytes_written: 1,
+            }),
+        );
+    }
+
+    #[test]
+    fn decode_04() {
+        // Every pair that Unicode composes decodes precomposed, but a mark
+        // on the ohm sign doesn't.
+        let input = b"\xC8w\xCFa\xC2\xE1\xC2\xE0";
+        let mut buf = [0u8; 64];
+        let (decoded, consumed_count) = decode_to_str(input, &mut buf, true).unwrap();
+        assert_eq!(consumed_count, input.len());
+        assert_eq!(decoded, "ẅǎǼ\u{2126}\u{0301}");
+    }
+
+    #[test]
+    fn decode_error_01() {
+        // A mark followed by a control character.
+        let mut buf = [0u8; 64];
+        assert_eq!(
+            decode_to_str(b"a\xC2\n", &mut buf, true),
+            Err(DecodeError {
+                cause: DecodeErrorCause::InvalidData,
+                error_range: (1, 2),
+                output_bytes_written: 1,
+            }),
+        );
+    }
+
+    #[test]
+    fn decode_error_02() {
+        // Two marks in a row.
+        let mut buf = [0u8; 64];
+        assert_eq!(
+            decode_to_str(b"\xC2\xC8a", &mut buf, true),
+            Err(DecodeError {
+                cause: DecodeErrorCause::InvalidData,
+                error_range: (0, 2),
+                output_bytes_written: 0,
+            }),
+        );
+    }
+
+    #[test]
+    fn decode_error_03() {
+        // Undefined byte.
+        let mut buf = [0u8; 64];
+        assert_eq!(
+            decode_to_str(b"a\xC9a", &mut buf, true),
+            Err(DecodeError {
+                cause: DecodeErrorCause::InvalidData,
+                error_range: (1, 2),
+                output_bytes_written: 1,
+            }),
+        );
+    }
+}
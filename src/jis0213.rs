@@ -0,0 +1,46 @@
+//! Table lookups for the JIS X 0213:2004 character set, shared by the
+//! `shift_jis_2004` and `euc_jis_2004` modules.
+//!
+//! Characters are identified by a pointer, which is computed from the
+//! plane, row, and cell of the character as
+//! `(plane - 1) * 8836 + (row - 1) * 94 + (cell - 1)`.
+
+// Generated by `encoding_tables/jis0213/generate_jis0213.py`.
+// Contains ENCODE_TABLE, DECODE_TABLE, and COMPOSED_TABLE.
+include!("generated/jis0213/jis0213_tables.rs.inc");
+
+/// Returns the pointer for `c`, if it's encodable on its own.
+pub fn encode_char(c: char) -> Option<usize> {
+    ENCODE_TABLE
+        .binary_search_by_key(&c, |x| x.0)
+        .ok()
+        .map(|i| ENCODE_TABLE[i].1 as usize)
+}
+
+/// Returns whether `c` is the first `char` of any composed sequence.
+pub fn is_composed_start(c: char) -> bool {
+    COMPOSED_TABLE.iter().any(|x| x.0 == c)
+}
+
+/// Returns the pointer for the composed sequence `a` + `b`, if there is one.
+pub fn encode_composed(a: char, b: char) -> Option<usize> {
+    COMPOSED_TABLE
+        .iter()
+        .find(|x| x.0 == a && x.1 == b)
+        .map(|x| x.2 as usize)
+}
+
+/// Decodes the character at `ptr` to utf8 in `buf`, returning the resulting
+/// `str`.  Returns `None` if the pointer is unassigned.
+pub fn decode_ptr(ptr: usize, buf: &mut [u8; 8]) -> Option<&str> {
+    if ptr < DECODE_TABLE.len() && DECODE_TABLE[ptr] != '�' {
+        Some(DECODE_TABLE[ptr].encode_utf8(buf))
+    } else if let Some(&(a, b, _)) = COMPOSED_TABLE.iter().find(|x| x.2 as usize == ptr) {
+        // Special handling for codes that map to composed sequences.
+        let len_a = a.encode_utf8(buf).len();
+        let len_b = b.encode_utf8(&mut buf[len_a..]).len();
+        Some(unsafe { core::str::from_utf8_unchecked(&buf[..(len_a + len_b)]) })
+    } else {
+        None
+    }
+}
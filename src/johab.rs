@@ -0,0 +1,360 @@
+//! Johab, the combinational Korean encoding from KS X 1001 annex 3.
+//!
+//! Hangul are encoded with 5-bit fields for the initial consonant, vowel,
+//! and final consonant, which are converted to and from the precomposed
+//! syllables at U+AC00 arithmetically.  The Hangul compatibility jamo are
+//! encoded the same way, with fill values for the missing parts.  The
+//! symbols and Hanja of KS X 1001 are rearranged into lead bytes 0xD9-0xDE
+//! and 0xE0-0xF9.  Bytes 0x00-0x7F are treated as ASCII, although some
+//! implementations map 0x5C to the won sign.
+//!
+//! Conversion in both directions is lossless.
+
+use core;
+use ksx1001;
+use {DecodeError, DecodeErrorCause, DecodeResult, EncodeError, EncodeResult};
+
+/// The vowel field values, in Unicode order.
+const MEDIALS: [u16; 21] = [
+    3, 4, 5, 6, 7, 10, 11, 12, 13, 14, 15, 18, 19, 20, 21, 22, 23, 26, 27, 28, 29,
+];
+
+/// The codes of the Hangul compatibility jamo, U+3131 through U+3163.
+/// Consonants use their initial form, except for clusters that can only be
+/// finals.
+const JAMO: [u16; 51] = [
+    0x8841, 0x8C41, 0x8444, 0x9041, 0x8446, 0x8447, 0x9441, 0x9841, 0x9C41, 0x844A, 0x844B, 0x844C,
+    0x844D, 0x844E, 0x844F, 0x8450, 0xA041, 0xA441, 0xA841, 0x8454, 0xAC41, 0xB041, 0xB441, 0xB841,
+    0xBC41, 0xC041, 0xC441, 0xC841, 0xCC41, 0xD041, 0x8461, 0x8481, 0x84A1, 0x84C1, 0x84E1, 0x8541,
+    0x8561, 0x8581, 0x85A1, 0x85C1, 0x85E1, 0x8641, 0x8661, 0x8681, 0x86A1, 0x86C1, 0x86E1, 0x8741,
+    0x8761, 0x8781, 0x87A1,
+];
+
+pub fn encode_from_str<'a>(input: &str, out_buffer: &'a mut [u8]) -> EncodeResult<'a> {
+    // Do the encode.
+    let mut input_i = 0;
+    let mut output_i = 0;
+    for (offset, c) in input.char_indices() {
+        let code = c as u32;
+        if output_i >= out_buffer.len() {
+            break;
+        } else if code <= 127 {
+            // Ascii
+            out_buffer[output_i] = code as u8;
+            output_i += 1;
+            input_i = offset + 1;
+            continue;
+        }
+
+        let johab = if (0xAC00..=0xD7A3).contains(&code) {
+            // Hangul syllables
+            let s = code - 0xAC00;
+            let initial = (s / 588) as u16 + 2;
+            let medial = MEDIALS[((s % 588) / 28) as usize];
+            let fin = (s % 28) as u16;
+            let fin = if fin < 17 { fin + 1 } else { fin + 2 };
+            0x8000 | (initial << 10) | (medial << 5) | fin
+        } else if (0x3131..=0x3163).contains(&code) {
+            // Hangul compatibility jamo
+            JAMO[(code - 0x3131) as usize]
+        } else if let Some(code) = ksx1001::encode_char(c).and_then(ptr_to_code) {
+            // Symbols and Hanja
+            code
+        } else {
+            return Err(EncodeError {
+                character: c,
+                error_range: (offset, offset + c.len_utf8()),
+                output_bytes_written: output_i,
+            });
+        };
+
+        if (output_i + 1) < out_buffer.len() {
+            out_buffer[output_i] = (johab >> 8) as u8;
+            out_buffer[output_i + 1] = johab as u8;
+            output_i += 2;
+            input_i = offset + 1;
+        } else {
+            break;
+        }
+    }
+
+    // Calculate how much of the input was consumed.
+    if input_i > input.len() {
+        input_i = input.len();
+    } else {
+        while !input.is_char_boundary(input_i) {
+            input_i += 1;
+        }
+    }
+
+    Ok((&out_buffer[..output_i], input_i))
+}
+
+pub fn decode_to_str<'a>(input: &[u8], out_buffer: &'a mut [u8], is_end: bool) -> DecodeResult<'a> {
+    let mut input_i = 0;
+    let mut output_i = 0;
+    let mut buf = [0u8; 4]; // For encoding utf8 codepoints.
+
+    // Loop through the input, getting a byte at a time.
+    let mut itr = input.iter();
+    while let Some(&byte_1) = itr.next() {
+        if output_i >= out_buffer.len() {
+            break;
+        } else if byte_1 <= 127 {
+            // Ascii
+            out_buffer[output_i] = byte_1;
+            output_i += 1;
+            input_i += 1;
+            continue;
+        }
+
+        let is_hangul = (0x84..=0xD3).contains(&byte_1);
+        if !is_hangul && !(0xD9..=0xDE).contains(&byte_1) && !(0xE0..=0xF9).contains(&byte_1) {
+            // Error: invalid leading byte.
+            return Err(DecodeError {
+                cause: DecodeErrorCause::InvalidData,
+                error_range: (input_i, input_i + 1),
+                output_bytes_written: output_i,
+            });
+        }
+
+        // Get the trailing byte.
+        let byte_2 = if let Some(&byte) = itr.next() {
+            let is_valid = if is_hangul {
+                (0x41..=0x7E).contains(&byte) || (0x81..=0xFE).contains(&byte)
+            } else {
+                (0x31..=0x7E).contains(&byte) || (0x91..=0xFE).contains(&byte)
+            };
+            if !is_valid {
+                // Error: invalid trailing byte.
+                // Like WHATWG EUC-KR, if the invalid byte is ascii it
+                // remains part of the stream, and thus is not treated
+                // as part of the error.
+                return Err(DecodeError {
+                    cause: DecodeErrorCause::InvalidData,
+                    error_range: (input_i, input_i + if byte <= 127 { 1 } else { 2 }),
+                    output_bytes_written: output_i,
+                });
+            }
+            byte
+        } else if !is_end {
+            // No available trailing byte, but not end-of-input, so no
+            // problem.
+            break;
+        } else {
+            // Error: truncated sequence at end of input.
+            return Err(DecodeError {
+                cause: DecodeErrorCause::InvalidData,
+                error_range: (input_i, input_i + 1),
+                output_bytes_written: output_i,
+            });
+        };
+
+        // Get our decoded data.
+        let c = if is_hangul {
+            decode_hangul(((byte_1 as u16) << 8) | byte_2 as u16)
+        } else {
+            decode_ksx1001(byte_1, byte_2)
+        };
+        let string = if let Some(c) = c {
+            c.encode_utf8(&mut buf)
+        } else {
+            // Error: correctly formed but undefined code.
+            return Err(DecodeError {
+                cause: DecodeErrorCause::InvalidData,
+                error_range: (input_i, input_i + 2),
+                output_bytes_written: output_i,
+            });
+        };
+
+        // Copy decoded data to output.
+        if (output_i + string.len()) > out_buffer.len() {
+            break;
+        }
+        out_buffer[output_i..(output_i + string.len())].copy_from_slice(string.as_bytes());
+
+        // Update our counters.
+        input_i += 2;
+        output_i += string.len();
+    }
+
+    Ok((
+        unsafe { core::str::from_utf8_unchecked(&out_buffer[..output_i]) },
+        input_i,
+    ))
+}
+
+/// Maps a KS X 1001 symbol or Hanja pointer to its Johab code.
+fn ptr_to_code(ptr: usize) -> Option<u16> {
+    let row = ptr / 94;
+    let cell = (ptr % 94) as u16 + 0x21;
+    let (lead, is_first) = match row {
+        0..=11 => (0xD9 + row / 2, row & 1 == 0),
+        41..=92 => (0xE0 + (row - 41) / 2, (row - 41) & 1 == 0),
+        _ => return None,
+    };
+    let trail = if !is_first {
+        cell + 0x80
+    } else if cell <= 0x6E {
+        cell + 0x10
+    } else {
+        cell + 0x22
+    };
+    Some(((lead as u16) << 8) | trail)
+}
+
+fn decode_hangul(code: u16) -> Option<char> {
+    let initial = (code >> 10) & 0x1F;
+    let medial = MEDIALS.iter().position(|&m| m == (code >> 5) & 0x1F);
+    let fin = code & 0x1F;
+    match medial {
+        Some(medial) if (2..=20).contains(&initial) && (1..=29).contains(&fin) && fin != 18 => {
+            let fin = if fin < 18 { fin - 1 } else { fin - 2 };
+            let s = (initial as u32 - 2) * 588 + medial as u32 * 28 + fin as u32;
+            core::char::from_u32(0xAC00 + s)
+        }
+        _ => JAMO
+            .iter()
+            .position(|&j| j == code)
+            .and_then(|n| core::char::from_u32(0x3131 + n as u32)),
+    }
+}
+
+fn decode_ksx1001(lead: u8, trail: u8) -> Option<char> {
+    let row = if lead <= 0xDE {
+        (lead as usize - 0xD9) * 2
+    } else {
+        41 + (lead as usize - 0xE0) * 2
+    };
+    let (row, cell) = match trail {
+        0x31..=0x7E => (row, trail - 0x10),
+        0x91..=0xA0 => (row, trail - 0x22),
+        _ => (row + 1, trail - 0x80),
+    };
+    match ksx1001::decode_ptr(row * 94 + (cell as usize - 0x21)) {
+        // The compatibility jamo are in the Hangul half instead.
+        Some(c) if ('\u{3131}'..='\u{3163}').contains(&c) => None,
+        c => c,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use {DecodeError, DecodeErrorCause, EncodeError};
+
+    #[test]
+    fn encode_01() {
+        let text = "a한글ㄱㅏㄳ伽";
+        let mut buf = [0u8; 32];
+        let (encoded, consumed_count) = encode_from_str(text, &mut buf).unwrap();
+        assert_eq!(consumed_count, text.len());
+        assert_eq!(
+            encoded,
+            &b"a\xD0\x65\x8B\x69\x88\x41\x84\x61\x84\x44\xE0\x31"[..]
+        );
+    }
+
+    #[test]
+    fn encode_02() {
+        let mut buf = [0u8; 2];
+        let (encoded, consumed_count) = encode_from_str("a한", &mut buf).unwrap();
+        assert_eq!(consumed_count, 1);
+        assert_eq!(encoded, b"a");
+    }
+
+    #[test]
+    fn encode_error_01() {
+        let mut buf = [0u8; 32];
+        assert_eq!(
+            encode_from_str("a中😀", &mut buf),
+            Err(EncodeError {
+                character: '😀',
+                error_range: (4, 8),
+                output_bytes_written: 3,
+            }),
+        );
+    }
+
+    #[test]
+    fn decode_01() {
+        let mut buf = [0u8; 32];
+        let (decoded, consumed_count) = decode_to_str(
+            b"a\xD0\x65\x8B\x69\x88\x41\x84\x61\x84\x44\xE0\x31",
+            &mut buf,
+            true,
+        )
+        .unwrap();
+        assert_eq!(consumed_count, 13);
+        assert_eq!(decoded, "a한글ㄱㅏㄳ伽");
+    }
+
+    #[test]
+    fn decode_02() {
+        let mut buf = [0u8; 32];
+        {
+            let (decoded, consumed_count) = decode_to_str(b"a\xD0", &mut buf, false).unwrap();
+            assert_eq!(consumed_count, 1);
+            assert_eq!(decoded, "a");
+        }
+        {
+            let (decoded, consumed_count) = decode_to_str(b"\xD0\x65", &mut buf, true).unwrap();
+            assert_eq!(consumed_count, 2);
+            assert_eq!(decoded, "한");
+        }
+    }
+
+    #[test]
+    fn decode_error_01() {
+        // The compatibility jamo in KS X 1001 row 4 aren't used.
+        let mut buf = [0u8; 32];
+        assert_eq!(
+            decode_to_str(b"a\xDA\xA1", &mut buf, true),
+            Err(DecodeError {
+                cause: DecodeErrorCause::InvalidData,
+                error_range: (1, 3),
+                output_bytes_written: 1,
+            }),
+        );
+    }
+
+    #[test]
+    fn decode_error_02() {
+        // All fill.
+        let mut buf = [0u8; 32];
+        assert_eq!(
+            decode_to_str(b"\x84\x41", &mut buf, true),
+            Err(DecodeError {
+                cause: DecodeErrorCause::InvalidData,
+                error_range: (0, 2),
+                output_bytes_written: 0,
+            }),
+        );
+    }
+
+    #[test]
+    fn decode_error_03() {
+        let mut buf = [0u8; 32];
+        assert_eq!(
+            decode_to_str(b"\xD0\x20", &mut buf, true),
+            Err(DecodeError {
+                cause: DecodeErrorCause::InvalidData,
+                error_range: (0, 1),
+                output_bytes_written: 0,
+            }),
+        );
+    }
+
+    #[test]
+    fn decode_error_04() {
+        let mut buf = [0u8; 32];
+        assert_eq!(
+            decode_to_str(b"\xD8\x31", &mut buf, true),
+            Err(DecodeError {
+                cause: DecodeErrorCause::InvalidData,
+                error_range: (0, 1),
+                output_bytes_written: 0,
+            }),
+        );
+    }
+}
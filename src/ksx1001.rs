@@ -0,0 +1,25 @@
+//! Table lookups for the KS X 1001 character set, shared by `iso_2022_kr` and
+//! `johab`.
+//!
+//! Characters are identified by a pointer, which is computed from the row and
+//! cell of the character as `(row - 1) * 94 + (cell - 1)`.
+
+// Generated by `encoding_tables/ksx1001/generate_ksx1001.py`.
+// Contains ENCODE_TABLE and DECODE_TABLE.
+include!("generated/ksx1001/ksx1001_tables.rs.inc");
+
+/// Returns the pointer for `c`, if it's encodable.
+pub fn encode_char(c: char) -> Option<usize> {
+    ENCODE_TABLE
+        .binary_search_by_key(&c, |x| x.0)
+        .ok()
+        .map(|i| ENCODE_TABLE[i].1 as usize)
+}
+
+/// Returns the `char` at `ptr`, if it's assigned.
+pub fn decode_ptr(ptr: usize) -> Option<char> {
+    match DECODE_TABLE.get(ptr) {
+        Some(&'�') | None => None,
+        Some(&c) => Some(c),
+    }
+}
@@ -219,14 +219,44 @@
 //! be documented in its module.  Otherwise you can depend on the conversion
 //! functions either being lossless or returning an error.
 
+pub mod arib_b24;
+pub mod big5_hkscs;
+pub mod big5_uao;
 pub mod big5_whatwg;
+pub mod dvb;
+pub mod ebcdic_dbcs;
+pub mod euc_jis_2004;
+pub mod euc_tw;
+pub mod gsm_0338;
+pub mod hz_gb_2312;
+pub mod iscii;
+pub mod iso_2022_cn;
+pub mod iso_2022_kr;
+pub mod iso_6937;
+pub mod johab;
+pub mod mac_chinese_simp;
+pub mod mac_chinese_trad;
+pub mod mac_japanese;
+pub mod mac_korean;
+pub mod marc8;
+pub mod pdf_text_string;
+pub mod shift_jis_2004;
+pub mod shiftjis;
 pub mod shiftjis_whatwg;
 pub mod single_byte;
+pub mod tscii;
 pub mod utf16_be;
 pub mod utf16_le;
 pub mod utf32_be;
 pub mod utf32_le;
 pub mod utf8;
+pub mod vni;
+
+mod cns11643;
+mod dbcs;
+mod gb2312;
+mod jis0213;
+mod ksx1001;
 
 /// Result type for encoding text from a `&str` to a target encoding.
 ///
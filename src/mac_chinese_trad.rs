@@ -0,0 +1,137 @@
+//! Mac OS Chinese Traditional, Apple's variant of Big Five.
+//!
+//! This is Big Five, without the ETEN extensions.  Single bytes 0xA0, 0xFD,
+//! 0xFE, and 0xFF are the no-break space, copyright sign, trade mark sign,
+//! and horizontal ellipsis, and 0x80 is the backslash with a transcoding
+//! hint.  Single bytes 0x81 and 0x82 map to Apple's corporate use characters
+//! U+F880 and U+F881.
+//!
+//! The mapping is Apple's, from its CHINTRAD.TXT mapping table.  Apple
+//! characters that have no single Unicode equivalent map to sequences of
+//! code points, which use Apple's transcoding hints in U+F860-U+F87F.  When
+//! encoding, a char that may start one of these sequences waits for more
+//! input unless `is_end` is true.
+//!
+//! The mapping is one-to-one, so both decoding and encoding are lossless.
+
+use dbcs::{self, Dbcs};
+use {DecodeResult, EncodeResult};
+
+// Generated by `encoding_tables/mac_cjk/generate_mac_cjk.py`.
+// Contains ENCODE_TABLE, DECODE_TABLE, and SEQUENCES.
+include!("generated/mac_cjk/mac_chinese_trad_tables.rs.inc");
+
+static MAC_CHINESE_TRAD: Dbcs = Dbcs {
+    lead_ranges: &[(0xA1, 0xF9)],
+    trail_ranges: &[(0x40, 0x7E), (0xA1, 0xFE)],
+    decode_single,
+    encode_single,
+    decode_table: &DECODE_TABLE,
+    encode_table: &ENCODE_TABLE,
+    pua_ranges: &[],
+    sequences: &SEQUENCES,
+    encode_aliases: &[],
+};
+
+pub fn encode_from_str<'a>(
+    input: &str,
+    out_buffer: &'a mut [u8],
+    is_end: bool,
+) -> EncodeResult<'a> {
+    dbcs::encode_from_str(&MAC_CHINESE_TRAD, input, out_buffer, is_end)
+}
+
+pub fn decode_to_str<'a>(input: &[u8], out_buffer: &'a mut [u8], is_end: bool) -> DecodeResult<'a> {
+    dbcs::decode_to_str(&MAC_CHINESE_TRAD, input, out_buffer, is_end)
+}
+
+fn encode_single(c: char) -> Option<u8> {
+    match c as u32 {
+        code @ 0..=0x7F | code @ 0xA0 => Some(code as u8),
+        0xF880 => Some(0x81),
+        0xF881 => Some(0x82),
+        0xA9 => Some(0xFD),
+        0x2122 => Some(0xFE),
+        0x2026 => Some(0xFF),
+        _ => None,
+    }
+}
+
+fn decode_single(byte: u8) -> Option<char> {
+    match byte {
+        0..=0x7F | 0xA0 => Some(byte as char),
+        0x81 => Some('\u{F880}'),
+        0x82 => Some('\u{F881}'),
+        0xFD => Some('\u{A9}'),
+        0xFE => Some('\u{2122}'),
+        0xFF => Some('\u{2026}'),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use {DecodeError, DecodeErrorCause, EncodeError};
+
+    #[test]
+    fn encode_01() {
+        let text = "a中文\u{2026}\\\\\u{F87F}";
+        let mut buf = [0u8; 32];
+        let (encoded, consumed_count) = encode_from_str(text, &mut buf, true).unwrap();
+        assert_eq!(consumed_count, text.len());
+        assert_eq!(encoded, &b"a\xA4\xA4\xA4\xE5\xFF\x5C\x80"[..]);
+    }
+
+    #[test]
+    fn encode_02() {
+        // A char that may start a sequence waits for more input.
+        let mut buf = [0u8; 32];
+        let (encoded, consumed_count) = encode_from_str("a\\", &mut buf, false).unwrap();
+        assert_eq!(consumed_count, 1);
+        assert_eq!(encoded, b"a");
+    }
+
+    #[test]
+    fn encode_error_01() {
+        let mut buf = [0u8; 32];
+        assert_eq!(
+            encode_from_str("a한", &mut buf, true),
+            Err(EncodeError {
+                character: '한',
+                error_range: (1, 4),
+                output_bytes_written: 1,
+            }),
+        );
+    }
+
+    #[test]
+    fn decode_01() {
+        let mut buf = [0u8; 64];
+        let (decoded, consumed_count) =
+            decode_to_str(b"a\xA4\xA4\xA4\xE5\xFF\x5C\x80", &mut buf, true).unwrap();
+        assert_eq!(consumed_count, 8);
+        assert_eq!(decoded, "a中文\u{2026}\\\\\u{F87F}");
+    }
+
+    #[test]
+    fn decode_error_01() {
+        let mut buf = [0u8; 32];
+        assert_eq!(
+            decode_to_str(b"a\xA4 ", &mut buf, true),
+            Err(DecodeError {
+                cause: DecodeErrorCause::InvalidData,
+                error_range: (1, 2),
+                output_bytes_written: 1,
+            }),
+        );
+        assert_eq!(
+            decode_to_str(b"\xA3\xC0", &mut buf, true),
+            Err(DecodeError {
+                cause: DecodeErrorCause::InvalidData,
+                error_range: (0, 2),
+                output_bytes_written: 0,
+            }),
+        );
+    }
+}
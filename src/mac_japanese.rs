@@ -0,0 +1,165 @@
+//! Mac OS Japanese, Apple's variant of Shift JIS.
+//!
+//! This is JIS X 0208 in Shift JIS form, plus Apple's additional symbols in
+//! lead bytes 0x85-0x88 and vertical presentation forms in lead bytes
+//! 0xEB-0xED.  Single byte 0x5C is the yen sign and 0x80 the backslash, and
+//! single bytes 0xA0, 0xFD, and 0xFE are the no-break space, copyright sign,
+//! and trade mark sign.
+//!
+//! The mapping is Apple's, from its JAPANESE.TXT mapping table.  Apple
+//! characters that have no single Unicode equivalent map to sequences of
+//! code points, many of which use Apple's transcoding hints in
+//! U+F860-U+F87F.  When encoding, a char that may start one of these
+//! sequences waits for more input unless `is_end` is true.
+//!
+//! The mapping is one-to-one, so both decoding and encoding are lossless.
+//!
+//! This is the same as the `shiftjis` module with `Profile::Apple`.
+
+use shiftjis::{self, Profile};
+use {DecodeResult, EncodeResult};
+
+pub fn encode_from_str<'a>(
+    input: &str,
+    out_buffer: &'a mut [u8],
+    is_end: bool,
+) -> EncodeResult<'a> {
+    shiftjis::encode_from_str(input, out_buffer, is_end, Profile::Apple)
+}
+
+pub fn decode_to_str<'a>(input: &[u8], out_buffer: &'a mut [u8], is_end: bool) -> DecodeResult<'a> {
+    shiftjis::decode_to_str(input, out_buffer, is_end, Profile::Apple)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use {DecodeError, DecodeErrorCause, EncodeError};
+
+    #[test]
+    fn encode_01() {
+        let text = "a\\¥ｱ©日①大\u{2026}\u{F87F}\u{2026}\u{F87E}\u{2026}";
+        let mut buf = [0u8; 32];
+        let (encoded, consumed_count) = encode_from_str(text, &mut buf, true).unwrap();
+        assert_eq!(consumed_count, text.len());
+        assert_eq!(
+            encoded,
+            &b"a\x80\x5C\xB1\xFD\x93\xFA\x85\x40\x91\xE5\xFF\xEB\x63\x81\x63"[..]
+        );
+    }
+
+    #[test]
+    fn encode_02() {
+        let mut buf = [0u8; 2];
+        let (encoded, consumed_count) = encode_from_str("a日", &mut buf, true).unwrap();
+        assert_eq!(consumed_count, 1);
+        assert_eq!(encoded, b"a");
+    }
+
+    #[test]
+    fn encode_03() {
+        // A char that may start a sequence waits for more input.
+        let mut buf = [0u8; 32];
+        let (encoded, consumed_count) = encode_from_str("a\u{2026}", &mut buf, false).unwrap();
+        assert_eq!(consumed_count, 1);
+        assert_eq!(encoded, b"a");
+        let (encoded, consumed_count) = encode_from_str("\u{F862}XII", &mut buf, false).unwrap();
+        assert_eq!(consumed_count, 0);
+        assert_eq!(encoded, b"");
+        let (encoded, consumed_count) = encode_from_str("\u{F862}XIII", &mut buf, false).unwrap();
+        assert_eq!(consumed_count, 7);
+        assert_eq!(encoded, b"\x85\xAB");
+    }
+
+    #[test]
+    fn encode_error_01() {
+        let mut buf = [0u8; 32];
+        assert_eq!(
+            encode_from_str("a한", &mut buf, true),
+            Err(EncodeError {
+                character: '한',
+                error_range: (1, 4),
+                output_bytes_written: 1,
+            }),
+        );
+    }
+
+    #[test]
+    fn decode_01() {
+        let mut buf = [0u8; 64];
+        let (decoded, consumed_count) = decode_to_str(
+            b"a\x80\x5C\xB1\xFD\x93\xFA\x85\x40\x91\xE5\xFF\xEB\x63\x81\x63\x85\xAB",
+            &mut buf,
+            true,
+        )
+        .unwrap();
+        assert_eq!(consumed_count, 18);
+        assert_eq!(
+            decoded,
+            "a\\¥ｱ©日①大\u{2026}\u{F87F}\u{2026}\u{F87E}\u{2026}\u{F862}XIII"
+        );
+    }
+
+    #[test]
+    fn decode_02() {
+        let mut buf = [0u8; 32];
+        {
+            let (decoded, consumed_count) = decode_to_str(b"a\x93", &mut buf, false).unwrap();
+            assert_eq!(consumed_count, 1);
+            assert_eq!(decoded, "a");
+        }
+        {
+            let (decoded, consumed_count) = decode_to_str(b"\x93\xFA", &mut buf, true).unwrap();
+            assert_eq!(consumed_count, 2);
+            assert_eq!(decoded, "日");
+        }
+    }
+
+    #[test]
+    fn decode_error_01() {
+        let mut buf = [0u8; 32];
+        assert_eq!(
+            decode_to_str(b"a\x93 ", &mut buf, true),
+            Err(DecodeError {
+                cause: DecodeErrorCause::InvalidData,
+                error_range: (1, 2),
+                output_bytes_written: 1,
+            }),
+        );
+    }
+
+    #[test]
+    fn decode_error_02() {
+        // Invalid lead byte, and undefined code.
+        let mut buf = [0u8; 32];
+        assert_eq!(
+            decode_to_str(b"\xF0\x40", &mut buf, true),
+            Err(DecodeError {
+                cause: DecodeErrorCause::InvalidData,
+                error_range: (0, 1),
+                output_bytes_written: 0,
+            }),
+        );
+        assert_eq!(
+            decode_to_str(b"\x81\xAD", &mut buf, true),
+            Err(DecodeError {
+                cause: DecodeErrorCause::InvalidData,
+                error_range: (0, 2),
+                output_bytes_written: 0,
+            }),
+        );
+    }
+
+    #[test]
+    fn decode_error_03() {
+        let mut buf = [0u8; 32];
+        assert_eq!(
+            decode_to_str(b"\xA0\x93", &mut buf, true),
+            Err(DecodeError {
+                cause: DecodeErrorCause::InvalidData,
+                error_range: (1, 2),
+                output_bytes_written: 2,
+            }),
+        );
+    }
+}
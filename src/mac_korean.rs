@@ -0,0 +1,139 @@
+//! Mac OS Korean, Apple's variant of EUC-KR.
+//!
+//! This is KS X 1001 in EUC-KR form, plus Apple's additional symbols in lead
+//! bytes 0xA1-0xAD, many of which use trail bytes below 0xA1.  Single bytes
+//! 0x80, 0x81, and 0x83 are the no-break space, won sign, and copyright
+//! sign.
+//!
+//! The mapping is Apple's, from its KOREAN.TXT mapping table.  Apple
+//! characters that have no single Unicode equivalent map to sequences of
+//! code points, many of which use Apple's transcoding hints in
+//! U+F860-U+F87F.  When encoding, a char that may start one of these
+//! sequences waits for more input unless `is_end` is true.
+//!
+//! The mapping is one-to-one, so both decoding and encoding are lossless.
+
+use dbcs::{self, Dbcs};
+use {DecodeResult, EncodeResult};
+
+// Generated by `encoding_tables/mac_cjk/generate_mac_cjk.py`.
+// Contains ENCODE_TABLE, DECODE_TABLE, and SEQUENCES.
+include!("generated/mac_cjk/mac_korean_tables.rs.inc");
+
+static MAC_KOREAN: Dbcs = Dbcs {
+    lead_ranges: &[(0xA1, 0xFD)],
+    trail_ranges: &[(0x41, 0x7D), (0x81, 0xFE)],
+    decode_single,
+    encode_single,
+    decode_table: &DECODE_TABLE,
+    encode_table: &ENCODE_TABLE,
+    pua_ranges: &[],
+    sequences: &SEQUENCES,
+    encode_aliases: &[],
+};
+
+pub fn encode_from_str<'a>(
+    input: &str,
+    out_buffer: &'a mut [u8],
+    is_end: bool,
+) -> EncodeResult<'a> {
+    dbcs::encode_from_str(&MAC_KOREAN, input, out_buffer, is_end)
+}
+
+pub fn decode_to_str<'a>(input: &[u8], out_buffer: &'a mut [u8], is_end: bool) -> DecodeResult<'a> {
+    dbcs::decode_to_str(&MAC_KOREAN, input, out_buffer, is_end)
+}
+
+fn encode_single(c: char) -> Option<u8> {
+    match c as u32 {
+        code @ 0..=0x7F => Some(code as u8),
+        0xA0 => Some(0x80),
+        0x20A9 => Some(0x81),
+        0xA9 => Some(0x83),
+        _ => None,
+    }
+}
+
+fn decode_single(byte: u8) -> Option<char> {
+    match byte {
+        0..=0x7F => Some(byte as char),
+        0x80 => Some('\u{A0}'),
+        0x81 => Some('\u{20A9}'),
+        0x83 => Some('\u{A9}'),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use {DecodeError, DecodeErrorCause, EncodeError};
+
+    #[test]
+    fn encode_01() {
+        let text = "a\u{A0}한글\u{2026}\u{2026}\u{F87F}1\u{20DE}\u{F87C}1";
+        let mut buf = [0u8; 32];
+        let (encoded, consumed_count) = encode_from_str(text, &mut buf, true).unwrap();
+        assert_eq!(consumed_count, text.len());
+        assert_eq!(encoded, &b"a\x80\xC7\xD1\xB1\xDB\xA1\xA6\xFF\xA2\xE61"[..]);
+    }
+
+    #[test]
+    fn encode_02() {
+        // A char that may start a sequence waits for more input.
+        let mut buf = [0u8; 32];
+        let (encoded, consumed_count) = encode_from_str("a1\u{20DE}", &mut buf, false).unwrap();
+        assert_eq!(consumed_count, 1);
+        assert_eq!(encoded, b"a");
+    }
+
+    #[test]
+    fn encode_error_01() {
+        let mut buf = [0u8; 32];
+        assert_eq!(
+            encode_from_str("aｱ", &mut buf, true),
+            Err(EncodeError {
+                character: 'ｱ',
+                error_range: (1, 4),
+                output_bytes_written: 1,
+            }),
+        );
+    }
+
+    #[test]
+    fn decode_01() {
+        let mut buf = [0u8; 64];
+        let (decoded, consumed_count) = decode_to_str(
+            b"a\x80\xC7\xD1\xB1\xDB\xA1\xA6\xFF\xA2\xE61",
+            &mut buf,
+            true,
+        )
+        .unwrap();
+        assert_eq!(consumed_count, 12);
+        assert_eq!(
+            decoded,
+            "a\u{A0}한글\u{2026}\u{2026}\u{F87F}1\u{20DE}\u{F87C}1"
+        );
+    }
+
+    #[test]
+    fn decode_error_01() {
+        let mut buf = [0u8; 32];
+        assert_eq!(
+            decode_to_str(b"a\xC7 ", &mut buf, true),
+            Err(DecodeError {
+                cause: DecodeErrorCause::InvalidData,
+                error_range: (1, 2),
+                output_bytes_written: 1,
+            }),
+        );
+        assert_eq!(
+            decode_to_str(b"\x85", &mut buf, true),
+            Err(DecodeError {
+                cause: DecodeErrorCause::InvalidData,
+                error_range: (0, 1),
+                output_bytes_written: 0,
+            }),
+        );
+    }
+}
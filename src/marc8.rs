@@ -0,0 +1,711 @@
+//! MARC-8, the character encoding of MARC 21 records.
+//!
+//! This is an ISO 2022 based code with two graphic sets: G0, invoked into
+//! GL (0x21-0x7E), and G1, invoked into GR (0xA1-0xFE).  G0 is initially
+//! Basic Latin (Ascii) and G1 is initially Extended Latin (ANSEL).  Escape
+//! sequences designate other sets into G0 or G1, so encoding and decoding
+//! are stateful and take a `State`.  Both the "ESC ( F" and "ESC , F" forms
+//! designate into G0, and both "ESC ) F" and "ESC - F" into G1.  "ESC g",
+//! "ESC b", and "ESC p" designate the Greek symbols, subscripts, and
+//! superscripts into G0, and "ESC s" returns G0 to Basic Latin.
+//!
+//! The following graphic sets are supported:
+//!
+//! * Basic Latin and Extended Latin (ANSEL).
+//! * Greek symbols, subscripts, and superscripts.
+//! * Basic Hebrew.
+//! * Basic and Extended Cyrillic, i.e. ISO 5427 and its extension.
+//! * Basic Arabic, i.e. ASMO 449.
+//! * Basic Greek, i.e. ISO 5428.
+//! * The Han characters of the three-byte EACC (East Asian) set, from the
+//!   kEACC field of the Unihan database.
+//!
+//! The rest of EACC (kana, hangul, and punctuation) and the Extended Arabic
+//! set are out of scope for now: their mappings are only published in the
+//! Library of Congress's `codetables.xml`, which the tables here aren't yet
+//! generated from.  Both sets can be designated, but decoding their
+//! characters is an error with the `UnknownConversion` cause.  The error
+//! range covers the whole character, so it can be skipped or replaced with a
+//! substitute.
+//!
+//! Diacritics and other combining marks come before the character they
+//! apply to, where Unicode has them after it, e.g. "é" is 0xE2 0x65.
+//! Decoding reorders them, giving the character followed by its combining
+//! marks in the same order.  Marks that aren't followed by a character, e.g.
+//! at the end of the text or before a control character, are decoded as
+//! they are.  The control characters, including the MARC record, field, and
+//! subfield separators 0x1D-0x1F, are passed through, and 0x88, 0x89, 0x8D,
+//! and 0x8E decode to U+0098, U+009C, U+200D, and U+200C.
+//!
+//! The encoder only uses Basic Latin and Extended Latin, designating them
+//! first if the state has other sets designated.  It accepts both
+//! precomposed characters and characters followed by combining marks, so
+//! the precomposed characters don't roundtrip.
+
+use core;
+use {DecodeError, DecodeErrorCause, DecodeResult, EncodeError, EncodeResult};
+
+// Generated by `encoding_tables/marc8/generate_marc8.py`.
+// Contains the DECODE_TABLE of each single-byte set, EACC_DECODE_TABLE,
+// COMBINING_TABLE, ANSEL_ENCODE_TABLE, and DECOMPOSE_TABLE.
+include!("generated/marc8/marc8_tables.rs.inc");
+
+const ESC: u8 = 0x1B;
+
+// The designations that the encoder uses.
+const BASIC_LATIN_DESIGNATION: &[u8] = b"\x1B(B";
+const ANSEL_DESIGNATION: &[u8] = b"\x1B)!E";
+
+// The most combining marks, in utf8 bytes when decoding and in MARC-8
+// bytes when encoding, that can apply to one character.
+const MAX_MARKS_LEN: usize = 32;
+
+/// The encoding/decoding state of a MARC-8 stream.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct State {
+    g: [Charset; 2],
+}
+
+impl State {
+    pub fn new() -> State {
+        State {
+            g: [Charset::BasicLatin, Charset::Ansel],
+        }
+    }
+}
+
+impl Default for State {
+    fn default() -> State {
+        State::new()
+    }
+}
+
+/// A graphic set that can be designated into G0 or G1.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum Charset {
+    BasicLatin,
+    Ansel,
+    GreekSymbols,
+    Subscripts,
+    Superscripts,
+    BasicHebrew,
+    BasicCyrillic,
+    ExtendedCyrillic,
+    BasicArabic,
+    ExtendedArabic,
+    BasicGreek,
+    Eacc,
+}
+
+impl Charset {
+    /// Returns the set with the given final byte of its designation.
+    /// ANSEL's final bytes are 0x21 0x45, but 0x45 alone is accepted too.
+    fn from_final(byte: u8, is_multibyte: bool) -> Option<Charset> {
+        match (is_multibyte, byte) {
+            (false, 0x42) => Some(Charset::BasicLatin),
+            (false, 0x45) => Some(Charset::Ansel),
+            (false, 0x67) => Some(Charset::GreekSymbols),
+            (false, 0x62) => Some(Charset::Subscripts),
+            (false, 0x70) => Some(Charset::Superscripts),
+            (false, 0x32) => Some(Charset::BasicHebrew),
+            (false, 0x4E) => Some(Charset::BasicCyrillic),
+            (false, 0x51) => Some(Charset::ExtendedCyrillic),
+            (false, 0x33) => Some(Charset::BasicArabic),
+            (false, 0x34) => Some(Charset::ExtendedArabic),
+            (false, 0x53) => Some(Charset::BasicGreek),
+            (true, 0x31) => Some(Charset::Eacc),
+            _ => None,
+        }
+    }
+
+    /// Returns the decode table of a single-byte set, indexed by the 7-bit
+    /// code minus 0x21.
+    fn decode_table(self) -> Option<&'static [char; 94]> {
+        match self {
+            Charset::Ansel => Some(&ANSEL_DECODE_TABLE),
+            Charset::GreekSymbols => Some(&GREEK_SYMBOLS_DECODE_TABLE),
+            Charset::Subscripts => Some(&SUBSCRIPTS_DECODE_TABLE),
+            Charset::Superscripts => Some(&SUPERSCRIPTS_DECODE_TABLE),
+            Charset::BasicHebrew => Some(&BASIC_HEBREW_DECODE_TABLE),
+            Charset::BasicCyrillic => Some(&BASIC_CYRILLIC_DECODE_TABLE),
+            Charset::ExtendedCyrillic => Some(&EXTENDED_CYRILLIC_DECODE_TABLE),
+            Charset::BasicArabic => Some(&BASIC_ARABIC_DECODE_TABLE),
+            Charset::BasicGreek => Some(&BASIC_GREEK_DECODE_TABLE),
+            Charset::BasicLatin | Charset::ExtendedArabic | Charset::Eacc => None,
+        }
+    }
+}
+
+pub fn encode_from_str<'a>(
+    input: &str,
+    out_buffer: &'a mut [u8],
+    state: &mut State,
+    is_end: bool,
+) -> EncodeResult<'a> {
+    // Do the encode.
+    let mut input_i = 0;
+    let mut output_i = 0;
+    let mut itr = input.char_indices().peekable();
+    while let Some((offset, c)) = itr.next() {
+        if output_i >= out_buffer.len() {
+            break;
+        }
+
+        // Get the bytes for the character, with the combining marks that
+        // follow it moved before it.
+        let mut input_consumed = c.len_utf8();
+        let mut bytes = [0u8; MAX_MARKS_LEN + 1];
+        let mut bytes_len = 0;
+        let base = if let Some(byte) = encode_control(c) {
+            Some(byte)
+        } else if let Ok(i) = DECOMPOSE_TABLE.binary_search_by_key(&c, |x| x.0) {
+            let decomposed = DECOMPOSE_TABLE[i].1;
+            bytes_len = decomposed.len() - 1;
+            bytes[..bytes_len].copy_from_slice(&decomposed[..bytes_len]);
+            Some(decomposed[bytes_len])
+        } else {
+            match encode_graphic(c) {
+                Some(byte) if !is_combining(c) => Some(byte),
+                _ => None,
+            }
+        };
+        let base = if let Some(byte) = base {
+            byte
+        } else {
+            // Error: unencodable character, or a combining mark without a
+            // character before it.
+            return Err(EncodeError {
+                character: c,
+                error_range: (offset, offset + c.len_utf8()),
+                output_bytes_written: output_i,
+            });
+        };
+        if encode_control(c).is_none() {
+            let mut is_waiting = false;
+            loop {
+                match itr.peek() {
+                    Some(&(_, mark)) if is_combining(mark) && bytes_len < MAX_MARKS_LEN => {
+                        if let Some(byte) = encode_graphic(mark) {
+                            bytes[bytes_len] = byte;
+                            bytes_len += 1;
+                            input_consumed += mark.len_utf8();
+                            itr.next();
+                        } else {
+                            break;
+                        }
+                    }
+                    None if !is_end => {
+                        // More combining marks may follow in the next
+                        // input, so wait for them.
+                        is_waiting = true;
+                        break;
+                    }
+                    _ => break,
+                }
+            }
+            if is_waiting {
+                break;
+            }
+        }
+        bytes[bytes_len] = base;
+        bytes_len += 1;
+
+        // Designate Basic Latin and ANSEL first, if needed.
+        let mut prefix = [0u8; 7];
+        let mut prefix_len = 0;
+        if state.g[0] != Charset::BasicLatin {
+            prefix[..3].copy_from_slice(BASIC_LATIN_DESIGNATION);
+            prefix_len += 3;
+        }
+        if state.g[1] != Charset::Ansel {
+            prefix[prefix_len..(prefix_len + 4)].copy_from_slice(ANSEL_DESIGNATION);
+            prefix_len += 4;
+        }
+
+        if (output_i + prefix_len + bytes_len) > out_buffer.len() {
+            break;
+        }
+        out_buffer[output_i..(output_i + prefix_len)].copy_from_slice(&prefix[..prefix_len]);
+        output_i += prefix_len;
+        out_buffer[output_i..(output_i + bytes_len)].copy_from_slice(&bytes[..bytes_len]);
+        output_i += bytes_len;
+        *state = State::new();
+        input_i = offset + input_consumed;
+    }
+
+    // Calculate how much of the input was consumed.
+    if input_i > input.len() {
+        input_i = input.len();
+    } else {
+        while !input.is_char_boundary(input_i) {
+            input_i += 1;
+        }
+    }
+
+    Ok((&out_buffer[..output_i], input_i))
+}
+
+pub fn decode_to_str<'a>(
+    input: &[u8],
+    out_buffer: &'a mut [u8],
+    state: &mut State,
+    is_end: bool,
+) -> DecodeResult<'a> {
+    let mut input_i = 0;
+    let mut output_i = 0;
+    let mut buf = [0u8; 4]; // For encoding utf8 codepoints.
+    let mut marks = [0u8; MAX_MARKS_LEN]; // For the combining marks.
+
+    while input_i < input.len() {
+        if output_i >= out_buffer.len() {
+            break;
+        }
+
+        let (c, marks_len, new_state, input_consumed) =
+            match parse_char(&input[input_i..], state, is_end, &mut marks) {
+                Ok(x) => x,
+                Err(ParseError::Incomplete) => {
+                    if !is_end {
+                        // Not enough bytes yet, but not end-of-input, so no
+                        // problem.
+                        break;
+                    }
+                    // Error: truncated sequence at end of input.
+                    return Err(DecodeError {
+                        cause: DecodeErrorCause::InvalidData,
+                        error_range: (input_i, input.len()),
+                        output_bytes_written: output_i,
+                    });
+                }
+                Err(ParseError::Invalid(len)) => {
+                    // Error: invalid byte or sequence.
+                    return Err(DecodeError {
+                        cause: DecodeErrorCause::InvalidData,
+                        error_range: (input_i, input_i + len),
+                        output_bytes_written: output_i,
+                    });
+                }
+                Err(ParseError::Unknown(len)) => {
+                    // Error: character of a set whose table isn't included.
+                    return Err(DecodeError {
+                        cause: DecodeErrorCause::UnknownConversion,
+                        error_range: (input_i, input_i + len),
+                        output_bytes_written: output_i,
+                    });
+                }
+            };
+
+        // Copy decoded data to output, the character first.
+        let string = match c {
+            Some(c) => &*c.encode_utf8(&mut buf),
+            None => "",
+        };
+        if (output_i + string.len() + marks_len) > out_buffer.len() {
+            // Not enough space in output buffer.
+            break;
+        }
+        out_buffer[output_i..(output_i + string.len())].copy_from_slice(string.as_bytes());
+        output_i += string.len();
+        out_buffer[output_i..(output_i + marks_len)].copy_from_slice(&marks[..marks_len]);
+        output_i += marks_len;
+
+        // Update our counters.
+        *state = new_state;
+        input_i += input_consumed;
+    }
+
+    Ok((
+        unsafe { core::str::from_utf8_unchecked(&out_buffer[..output_i]) },
+        input_i,
+    ))
+}
+
+fn is_combining(c: char) -> bool {
+    COMBINING_TABLE.binary_search(&c).is_ok()
+}
+
+/// Returns whether a character can have combining marks applied to it,
+/// i.e. whether it isn't a control character.
+fn is_base(c: char) -> bool {
+    c >= ' ' && !matches!(c, '\u{98}' | '\u{9C}' | '\u{200C}' | '\u{200D}')
+}
+
+/// Returns the byte for a control character.
+fn encode_control(c: char) -> Option<u8> {
+    match c {
+        '\u{00}'..='\u{1F}' if c as u8 != ESC => Some(c as u8),
+        '\u{98}' => Some(0x88),
+        '\u{9C}' => Some(0x89),
+        '\u{200D}' => Some(0x8D),
+        '\u{200C}' => Some(0x8E),
+        _ => None,
+    }
+}
+
+/// Returns the byte for a graphic character in Basic Latin or ANSEL.
+fn encode_graphic(c: char) -> Option<u8> {
+    if (' '..='~').contains(&c) {
+        Some(c as u8)
+    } else if let Ok(i) = ANSEL_ENCODE_TABLE.binary_search_by_key(&c, |x| x.0) {
+        Some(ANSEL_ENCODE_TABLE[i].1)
+    } else {
+        None
+    }
+}
+
+/// A single unit of the input: a character or a designation.
+enum Unit {
+    Char(char),
+    Designate(usize, Charset),
+}
+
+enum ParseError {
+    /// The unit continues past the end of the input.
+    Incomplete,
+    /// The unit is invalid.  Contains the length of the error.
+    Invalid(usize),
+    /// The unit is a character with no known conversion.  Contains its
+    /// length.
+    Unknown(usize),
+}
+
+/// Parses the character at the start of `input`, along with the combining
+/// marks before it, which are written to `marks` in utf8.  Returns the
+/// character, the length of the marks, the state after them, and the
+/// length parsed.
+///
+/// The character is `None` for a designation, and for combining marks that
+/// aren't followed by a character.
+fn parse_char(
+    input: &[u8],
+    state: &State,
+    is_end: bool,
+    marks: &mut [u8; MAX_MARKS_LEN],
+) -> Result<(Option<char>, usize, State, usize), ParseError> {
+    let mut new_state = *state;
+    let mut marks_len = 0;
+    let mut i = 0;
+    while i < input.len() {
+        let (unit, unit_len) = match parse_unit(&input[i..], &new_state) {
+            Ok(x) => x,
+            // Decode the marks before an error, so that the error is only
+            // the unit itself.
+            Err(ParseError::Incomplete) if !is_end => return Err(ParseError::Incomplete),
+            Err(_) if marks_len > 0 => return Ok((None, marks_len, new_state, i)),
+            Err(e) => return Err(e),
+        };
+        match unit {
+            Unit::Designate(g, charset) => {
+                new_state.g[g] = charset;
+                i += unit_len;
+                if marks_len == 0 {
+                    return Ok((None, 0, new_state, i));
+                }
+            }
+            Unit::Char(c) if is_combining(c) => {
+                if (marks_len + c.len_utf8()) > MAX_MARKS_LEN {
+                    // Error: too many combining marks.
+                    return Err(ParseError::Invalid(i + unit_len));
+                }
+                marks_len += c.encode_utf8(&mut marks[marks_len..]).len();
+                i += unit_len;
+            }
+            Unit::Char(c) if marks_len > 0 && !is_base(c) => {
+                return Ok((None, marks_len, new_state, i));
+            }
+            Unit::Char(c) => return Ok((Some(c), marks_len, new_state, i + unit_len)),
+        }
+    }
+
+    // Combining marks at the end of the input.
+    if is_end {
+        Ok((None, marks_len, new_state, i))
+    } else {
+        Err(ParseError::Incomplete)
+    }
+}
+
+/// Parses the unit at the start of `input`, returning it and its length.
+fn parse_unit(input: &[u8], state: &State) -> Result<(Unit, usize), ParseError> {
+    match input[0] {
+        ESC => parse_escape(input),
+        0x20 => Ok((Unit::Char(' '), 1)),
+        0x21..=0x7E => parse_graphic(input, state.g[0], false),
+        0xA1..=0xFE => parse_graphic(input, state.g[1], true),
+        0x88 => Ok((Unit::Char('\u{98}'), 1)),
+        0x89 => Ok((Unit::Char('\u{9C}'), 1)),
+        0x8D => Ok((Unit::Char('\u{200D}'), 1)),
+        0x8E => Ok((Unit::Char('\u{200C}'), 1)),
+        byte @ 0x00..=0x1F => Ok((Unit::Char(byte as char), 1)),
+        _ => Err(ParseError::Invalid(1)),
+    }
+}
+
+/// Parses a character of `charset` at the start of `input`, in GR if
+/// `is_gr` is true and in GL otherwise.
+fn parse_graphic(input: &[u8], charset: Charset, is_gr: bool) -> Result<(Unit, usize), ParseError> {
+    let code = input[0] & 0x7F;
+    match charset {
+        Charset::BasicLatin => Ok((Unit::Char(code as char), 1)),
+        Charset::ExtendedArabic => Err(ParseError::Unknown(1)),
+        Charset::Eacc => {
+            let range = if is_gr { 0xA1..=0xFE } else { 0x21..=0x7E };
+            let mut code = code as u32;
+            for i in 1..3 {
+                let byte = *input.get(i).ok_or(ParseError::Incomplete)?;
+                if !range.contains(&byte) {
+                    // Error: invalid trailing byte.  The trailing byte
+                    // remains part of the stream, and thus is not treated as
+                    // part of the error.
+                    return Err(ParseError::Invalid(i));
+                }
+                code = (code << 8) | (byte & 0x7F) as u32;
+            }
+            match EACC_DECODE_TABLE.binary_search_by_key(&code, |x| x.0) {
+                Ok(i) => Ok((Unit::Char(EACC_DECODE_TABLE[i].1), 3)),
+                // Error: not a Han character, or undefined.
+                Err(_) => Err(ParseError::Unknown(3)),
+            }
+        }
+        _ => {
+            let c = charset.decode_table().unwrap()[code as usize - 0x21];
+            if c == '�' {
+                // Error: undefined code.
+                Err(ParseError::Invalid(1))
+            } else {
+                Ok((Unit::Char(c), 1))
+            }
+        }
+    }
+}
+
+/// Parses the escape sequence at the start of `input`.
+fn parse_escape(input: &[u8]) -> Result<(Unit, usize), ParseError> {
+    let get = |i: usize| input.get(i).cloned().ok_or(ParseError::Incomplete);
+    let designate = |g: usize, byte: u8, is_multibyte: bool, len: usize| {
+        Charset::from_final(byte, is_multibyte)
+            .map(|charset| (Unit::Designate(g, charset), len))
+            .ok_or(ParseError::Invalid(1))
+    };
+
+    match get(1)? {
+        // The Greek symbols, subscripts, superscripts, and Basic Latin in
+        // G0, without an intermediate byte.
+        byte @ (0x67 | 0x62 | 0x70) => designate(0, byte, false, 2),
+        0x73 => Ok((Unit::Designate(0, Charset::BasicLatin), 2)),
+        // Single-byte sets.
+        i @ (0x28 | 0x2C | 0x29 | 0x2D) => {
+            let g = if i == 0x28 || i == 0x2C { 0 } else { 1 };
+            match get(2)? {
+                0x21 => match get(3)? {
+                    0x45 => Ok((Unit::Designate(g, Charset::Ansel), 4)),
+                    _ => Err(ParseError::Invalid(1)),
+                },
+                byte => designate(g, byte, false, 3),
+            }
+        }
+        // Multibyte sets.  G0 can be designated without an intermediate
+        // byte.
+        0x24 => match get(2)? {
+            i @ (0x28 | 0x2C | 0x29 | 0x2D) => {
+                let g = if i == 0x28 || i == 0x2C { 0 } else { 1 };
+                designate(g, get(3)?, true, 4)
+            }
+            byte => designate(0, byte, true, 3),
+        },
+        _ => Err(ParseError::Invalid(1)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use {DecodeError, DecodeErrorCause, EncodeError};
+
+    #[test]
+    fn encode_01() {
+        let text = "Łódź, Dvořák © 1999";
+        let mut buf = [0u8; 64];
+        let mut state = State::new();
+        let (encoded, consumed_count) = encode_from_str(text, &mut buf, &mut state, true).unwrap();
+        assert_eq!(consumed_count, text.len());
+        assert_eq!(encoded, &b"\xA1\xE2od\xE2z, Dvo\xE9r\xE2ak \xC3 1999"[..]);
+    }
+
+    #[test]
+    fn encode_02() {
+        // Combining marks, which can be split across inputs, and a
+        // precomposed character with two marks.
+        let mut buf = [0u8; 64];
+        let mut state = State::new();
+        let (encoded, consumed_count) =
+            encode_from_str("e\u{301}e", &mut buf, &mut state, false).unwrap();
+        assert_eq!(consumed_count, 3);
+        assert_eq!(encoded, b"\xE2e");
+        let (encoded, consumed_count) =
+            encode_from_str("e\u{302}\u{301}ǘ", &mut buf, &mut state, true).unwrap();
+        assert_eq!(consumed_count, 7);
+        assert_eq!(encoded, b"\xE3\xE2e\xE8\xE2u");
+    }
+
+    #[test]
+    fn encode_03() {
+        // Basic Latin and ANSEL are designated when needed.
+        let mut buf = [0u8; 64];
+        let mut state = State::new();
+        decode_to_str(b"\x1B(N", &mut buf, &mut state, true).unwrap();
+        let (encoded, consumed_count) = encode_from_str("ab", &mut buf, &mut state, true).unwrap();
+        assert_eq!(consumed_count, 2);
+        assert_eq!(encoded, b"\x1B(Bab");
+        assert_eq!(state, State::new());
+    }
+
+    #[test]
+    fn encode_error_01() {
+        let mut buf = [0u8; 64];
+        let mut state = State::new();
+        assert_eq!(
+            encode_from_str("aМ", &mut buf, &mut state, true),
+            Err(EncodeError {
+                character: 'М',
+                error_range: (1, 3),
+                output_bytes_written: 1,
+            }),
+        );
+    }
+
+    #[test]
+    fn encode_error_02() {
+        // A combining mark without a character before it.
+        let mut buf = [0u8; 64];
+        let mut state = State::new();
+        assert_eq!(
+            encode_from_str("\u{301}a", &mut buf, &mut state, true),
+            Err(EncodeError {
+                character: '\u{301}',
+                error_range: (0, 2),
+                output_bytes_written: 0,
+            }),
+        );
+    }
+
+    #[test]
+    fn decode_01() {
+        let input = b"\xA1\xE2od\xE2z, Dvo\xE9r\xE2ak \xC3 1999";
+        let mut buf = [0u8; 64];
+        let mut state = State::new();
+        let (decoded, consumed_count) = decode_to_str(input, &mut buf, &mut state, true).unwrap();
+        assert_eq!(consumed_count, input.len());
+        assert_eq!(decoded, "Ło\u{301}dz\u{301}, Dvor\u{30C}a\u{301}k © 1999");
+    }
+
+    #[test]
+    fn decode_02() {
+        // Other sets in G0 and G1.
+        let inputs: [(&[u8], &str); 6] = [
+            (b"\x1B(Nmir\x1B(B!", "МИР!"),
+            (b"\x1B(S\x22aj\x1Bs", "α\u{301}η"),
+            (b"H\x1Bb2\x1BsO", "H₂O"),
+            (b"\x1B(2\x60\x1B)Q\xE4", "־Ё"),
+            (b"\x1B(3\x6E\x47", "\u{627}\u{64E}"),
+            (b"\x1B,!E\x61\x1B(Ba", "a\u{300}"),
+        ];
+        for &(input, text) in inputs.iter() {
+            let mut buf = [0u8; 64];
+            let mut state = State::new();
+            let (decoded, consumed_count) =
+                decode_to_str(input, &mut buf, &mut state, true).unwrap();
+            assert_eq!(consumed_count, input.len());
+            assert_eq!(decoded, text);
+        }
+    }
+
+    #[test]
+    fn decode_03() {
+        // Combining marks split across inputs, and marks that aren't
+        // followed by a character.
+        let mut buf = [0u8; 64];
+        let mut state = State::new();
+        assert_eq!(
+            decode_to_str(b"a\xE2\xE3", &mut buf, &mut state, false),
+            Ok(("a", 1))
+        );
+        assert_eq!(
+            decode_to_str(b"\xE2\xE3e\xE2\x1Fa\xE1", &mut buf, &mut state, true),
+            Ok(("e\u{301}\u{302}\u{301}\u{1F}a\u{300}", 7))
+        );
+    }
+
+    #[test]
+    fn decode_04() {
+        // EACC, in G0 and in G1, and split across inputs.
+        let inputs: [(&[u8], &str); 3] = [
+            (b"\x1B$1\x21\x30\x21\x21\x30\x22\x1B(B!", "一丁!"),
+            (b"a\x1B$)1\xA1\xB0\xA1\xE9\xB7\xA9b", "a一\u{7C82}b"),
+            (b"\x1B$1\x2D\x31\x65\x21\x7E\x5B", "\u{349E}\u{3761}"),
+        ];
+        for &(input, text) in inputs.iter() {
+            let mut buf = [0u8; 64];
+            let mut state = State::new();
+            let (decoded, consumed_count) =
+                decode_to_str(input, &mut buf, &mut state, true).unwrap();
+            assert_eq!(consumed_count, input.len());
+            assert_eq!(decoded, text);
+        }
+
+        let mut buf = [0u8; 64];
+        let mut state = State::new();
+        assert_eq!(
+            decode_to_str(b"\x1B$1\x21\x30", &mut buf, &mut state, false),
+            Ok(("", 3))
+        );
+        assert_eq!(
+            decode_to_str(b"\x21\x30\x21", &mut buf, &mut state, true),
+            Ok(("一", 3))
+        );
+    }
+
+    #[test]
+    fn decode_error_01() {
+        // Undefined codes and escape sequences.
+        let inputs: [(&[u8], (usize, usize), usize); 4] = [
+            (b"a\xBBb", (1, 2), 1),
+            (b"a\x1B(Zb", (1, 2), 1),
+            (b"a\x1Bgdb", (3, 4), 1),
+            (b"a\xE2\x80", (2, 3), 3),
+        ];
+        for &(input, error_range, output_bytes_written) in inputs.iter() {
+            let mut buf = [0u8; 64];
+            let mut state = State::new();
+            assert_eq!(
+                decode_to_str(input, &mut buf, &mut state, true),
+                Err(DecodeError {
+                    cause: DecodeErrorCause::InvalidData,
+                    error_range,
+                    output_bytes_written,
+                }),
+            );
+        }
+    }
+
+    #[test]
+    fn decode_error_02() {
+        // EACC kana and Extended Arabic characters have no table.
+        let inputs: [(&[u8], (usize, usize), usize); 2] = [
+            (b"a\x1B$1\x21\x30\x21\x69\x24\x22", (7, 10), 4),
+            (b"a\x1B(4\x21", (4, 5), 1),
+        ];
+        for &(input, error_range, output_bytes_written) in inputs.iter() {
+            let mut buf = [0u8; 64];
+            let mut state = State::new();
+            assert_eq!(
+                decode_to_str(input, &mut buf, &mut state, true),
+                Err(DecodeError {
+                    cause: DecodeErrorCause::UnknownConversion,
+                    error_range,
+                    output_bytes_written,
+                }),
+            );
+        }
+    }
+}
@@ -0,0 +1,308 @@
+//! PDF text strings (ISO 32000-2, section 7.9.2.2).
+//!
+//! Text strings, such as the document information and annotation contents,
+//! are in one of three forms, chosen by the byte order mark at the start:
+//!
+//! - 0xFE 0xFF: UTF-16, big endian.
+//! - 0xEF 0xBB 0xBF: UTF-8, which PDF 2.0 added.
+//! - No byte order mark: PDFDocEncoding, from `single_byte::pdf_doc`.
+//!
+//! Since the byte order mark comes first, encoding and decoding are
+//! stateful and take a `State`.  For decoding, the form is chosen by the
+//! byte order mark.  For encoding, the form can be given up front, and
+//! otherwise it's PDFDocEncoding if the first input can be encoded with it,
+//! and UTF-16 if not, so the whole text should be in the first input.  Text
+//! that starts with "þÿ" or "ï»¿" is encoded as UTF-16, since those would be
+//! read as a byte order mark in PDFDocEncoding.
+//!
+//! The language and country escape sequences in UTF-16 text strings, which
+//! are delimited by U+001B, aren't interpreted, and are passed through.
+
+use core;
+use single_byte::pdf_doc;
+use {utf16_be, utf8};
+use {DecodeError, DecodeResult, EncodeError, EncodeResult};
+
+const UTF16_BOM: &[u8] = &[0xFE, 0xFF];
+const UTF8_BOM: &[u8] = &[0xEF, 0xBB, 0xBF];
+
+/// The forms that a PDF text string can be in.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Form {
+    PdfDoc,
+    Utf16Be,
+    Utf8,
+}
+
+impl Form {
+    /// Returns the byte order mark for the form.
+    fn bom(self) -> &'static [u8] {
+        match self {
+            Form::PdfDoc => &[],
+            Form::Utf16Be => UTF16_BOM,
+            Form::Utf8 => UTF8_BOM,
+        }
+    }
+}
+
+/// The encoding/decoding state of a PDF text string.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct State {
+    form: Option<Form>,
+    has_bom: bool,
+}
+
+impl State {
+    /// Creates a state for decoding, where the form is chosen by the byte
+    /// order mark, or for encoding with the most compact form.
+    pub fn new() -> State {
+        State {
+            form: None,
+            has_bom: false,
+        }
+    }
+
+    /// Creates a state for encoding with the given form.
+    pub fn with_form(form: Form) -> State {
+        State {
+            form: Some(form),
+            has_bom: false,
+        }
+    }
+
+    /// Returns the form, once it's known.
+    pub fn form(&self) -> Option<Form> {
+        self.form
+    }
+}
+
+impl Default for State {
+    fn default() -> State {
+        State::new()
+    }
+}
+
+pub fn encode_from_str<'a>(
+    input: &str,
+    out_buffer: &'a mut [u8],
+    state: &mut State,
+) -> EncodeResult<'a> {
+    // Write the byte order mark first.
+    let form = state.form.unwrap_or_else(|| {
+        if is_pdf_doc_encodable(input) {
+            Form::PdfDoc
+        } else {
+            Form::Utf16Be
+        }
+    });
+    let mut output_i = 0;
+    if !state.has_bom {
+        let bom = form.bom();
+        if bom.len() > out_buffer.len() {
+            return Ok((&out_buffer[..0], 0));
+        }
+        out_buffer[..bom.len()].copy_from_slice(bom);
+        output_i = bom.len();
+        state.form = Some(form);
+        state.has_bom = true;
+    }
+
+    let out = &mut out_buffer[output_i..];
+    let result = match form {
+        Form::PdfDoc => pdf_doc::encode_from_str(input, out),
+        Form::Utf16Be => utf16_be::encode_from_str(input, out),
+        Form::Utf8 => utf8::encode_from_str(input, out),
+    };
+
+    // Account for the byte order mark.
+    match result {
+        Ok((encoded, input_consumed)) => {
+            let len = output_i + encoded.len();
+            Ok((&out_buffer[..len], input_consumed))
+        }
+        Err(e) => Err(EncodeError {
+            output_bytes_written: output_i + e.output_bytes_written,
+            ..e
+        }),
+    }
+}
+
+pub fn decode_to_str<'a>(
+    input: &[u8],
+    out_buffer: &'a mut [u8],
+    state: &mut State,
+    is_end: bool,
+) -> DecodeResult<'a> {
+    // Read the byte order mark first.
+    let mut input_i = 0;
+    if !state.has_bom {
+        if input.is_empty() {
+            return Ok(("", 0));
+        }
+        let form = if input.starts_with(UTF16_BOM) {
+            Form::Utf16Be
+        } else if input.starts_with(UTF8_BOM) {
+            Form::Utf8
+        } else if !is_end && (UTF16_BOM.starts_with(input) || UTF8_BOM.starts_with(input)) {
+            // The rest of the byte order mark may be in the next input.
+            return Ok(("", 0));
+        } else {
+            Form::PdfDoc
+        };
+        state.form = Some(form);
+        state.has_bom = true;
+        input_i = form.bom().len();
+    }
+
+    let input = &input[input_i..];
+    let result = match state.form.unwrap_or(Form::PdfDoc) {
+        Form::PdfDoc => pdf_doc::decode_to_str(input, out_buffer),
+        Form::Utf16Be => utf16_be::decode_to_str(input, out_buffer, is_end),
+        Form::Utf8 => utf8::decode_to_str(input, out_buffer, is_end),
+    };
+
+    // Account for the byte order mark.
+    match result {
+        Ok((decoded, input_consumed)) => {
+            let len = decoded.len();
+            Ok((
+                unsafe { core::str::from_utf8_unchecked(&out_buffer[..len]) },
+                input_i + input_consumed,
+            ))
+        }
+        Err(e) => Err(DecodeError {
+            error_range: (input_i + e.error_range.0, input_i + e.error_range.1),
+            ..e
+        }),
+    }
+}
+
+/// Returns whether text can be encoded with PDFDocEncoding without being
+/// read back as another form.
+fn is_pdf_doc_encodable(input: &str) -> bool {
+    let mut buf = [0u8; 4];
+    !input.starts_with("þÿ")
+        && !input.starts_with("ï»¿")
+        && input
+            .chars()
+            .all(|c| pdf_doc::encode_from_str(c.encode_utf8(&mut buf), &mut [0u8; 1]).is_ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use {DecodeError, DecodeErrorCause, EncodeError};
+
+    #[test]
+    fn encode_01() {
+        // The most compact form.
+        let inputs: [(&str, &[u8], Form); 4] = [
+            ("Café – “Menu”", b"Caf\xE9 \x85 \x8DMenu\x8E", Form::PdfDoc),
+            ("", b"", Form::PdfDoc),
+            ("aМ", b"\xFE\xFF\x00a\x04\x1C", Form::Utf16Be),
+            ("þÿ", b"\xFE\xFF\x00\xFE\x00\xFF", Form::Utf16Be),
+        ];
+        for &(text, output, form) in inputs.iter() {
+            let mut buf = [0u8; 64];
+            let mut state = State::new();
+            let (encoded, consumed_count) = encode_from_str(text, &mut buf, &mut state).unwrap();
+            assert_eq!(consumed_count, text.len());
+            assert_eq!(encoded, output);
+            assert_eq!(state.form(), Some(form));
+        }
+    }
+
+    #[test]
+    fn encode_02() {
+        let mut buf = [0u8; 64];
+        let mut state = State::with_form(Form::Utf8);
+        let (encoded, consumed_count) = encode_from_str("é", &mut buf, &mut state).unwrap();
+        assert_eq!(consumed_count, 2);
+        assert_eq!(encoded, b"\xEF\xBB\xBF\xC3\xA9");
+
+        // The byte order mark is only written once.
+        let (encoded, consumed_count) = encode_from_str("!", &mut buf, &mut state).unwrap();
+        assert_eq!(consumed_count, 1);
+        assert_eq!(encoded, b"!");
+    }
+
+    #[test]
+    fn encode_error_01() {
+        let mut buf = [0u8; 64];
+        let mut state = State::with_form(Form::PdfDoc);
+        assert_eq!(
+            encode_from_str("aМ", &mut buf, &mut state),
+            Err(EncodeError {
+                character: 'М',
+                error_range: (1, 3),
+                output_bytes_written: 1,
+            }),
+        );
+    }
+
+    #[test]
+    fn decode_01() {
+        let inputs: [(&[u8], &str, Form); 4] = [
+            (b"Caf\xE9 \x85 \x8DMenu\x8E", "Café – “Menu”", Form::PdfDoc),
+            (b"\x18\x93\xA0", "˘ﬁ€", Form::PdfDoc),
+            (b"\xFE\xFF\x00a\x04\x1C", "aМ", Form::Utf16Be),
+            (b"\xEF\xBB\xBFa\xC3\xA9", "aé", Form::Utf8),
+        ];
+        for &(input, text, form) in inputs.iter() {
+            let mut buf = [0u8; 64];
+            let mut state = State::new();
+            let (decoded, consumed_count) =
+                decode_to_str(input, &mut buf, &mut state, true).unwrap();
+            assert_eq!(consumed_count, input.len());
+            assert_eq!(decoded, text);
+            assert_eq!(state.form(), Some(form));
+        }
+    }
+
+    #[test]
+    fn decode_02() {
+        // A byte order mark split across inputs.
+        let mut buf = [0u8; 64];
+        let mut state = State::new();
+        assert_eq!(
+            decode_to_str(b"\xEF\xBB", &mut buf, &mut state, false),
+            Ok(("", 0))
+        );
+        assert_eq!(state, State::new());
+        assert_eq!(
+            decode_to_str(b"\xEF\xBB\xBF\xC3", &mut buf, &mut state, false),
+            Ok(("", 3))
+        );
+        assert_eq!(
+            decode_to_str(b"\xC3\xA9", &mut buf, &mut state, true),
+            Ok(("é", 2))
+        );
+
+        // It's PDFDocEncoding if the input ends first.
+        let mut state = State::new();
+        assert_eq!(
+            decode_to_str(b"\xFE", &mut buf, &mut state, true),
+            Ok(("þ", 1))
+        );
+    }
+
+    #[test]
+    fn decode_error_01() {
+        let inputs: [(&[u8], (usize, usize), usize); 2] = [
+            (b"ab\x9Fc", (2, 3), 2),
+            (b"\xFE\xFF\x00a\xD8\x00\x00b", (4, 6), 1),
+        ];
+        for &(input, error_range, output_bytes_written) in inputs.iter() {
+            let mut buf = [0u8; 64];
+            let mut state = State::new();
+            assert_eq!(
+                decode_to_str(input, &mut buf, &mut state, true),
+                Err(DecodeError {
+                    cause: DecodeErrorCause::InvalidData,
+                    error_range,
+                    output_bytes_written,
+                }),
+            );
+        }
+    }
+}
@@ -0,0 +1,384 @@
+//! Shift_JIS-2004, the Shift JIS form of JIS X 0213:2004.
+//!
+//! This covers both planes of JIS X 0213, which is a superset of JIS X 0208,
+//! as well as the JIS X 0201 half-width katakana.  Bytes 0x00-0x7F are
+//! treated as ASCII.
+//!
+//! Some JIS X 0213 codes map to a sequence of two Unicode code points (a base
+//! character followed by a combining character).  When encoding, such a
+//! sequence is always encoded to its single JIS X 0213 code.  Because the
+//! encoder may need to look at the next `char` to decide that, it won't
+//! consume a trailing base character unless `is_end` is true.
+//!
+//! Conversion in both directions is lossless.
+
+use core;
+use jis0213;
+use {DecodeError, DecodeErrorCause, DecodeResult, EncodeError, EncodeResult};
+
+pub fn encode_from_str<'a>(
+    input: &str,
+    out_buffer: &'a mut [u8],
+    is_end: bool,
+) -> EncodeResult<'a> {
+    // Do the encode.
+    let mut input_i = 0;
+    let mut output_i = 0;
+    let mut itr = input.char_indices();
+    while let Some((offset, c)) = itr.next() {
+        let code = c as u32;
+        if output_i >= out_buffer.len() {
+            break;
+        } else if code <= 127 {
+            // Ascii
+            out_buffer[output_i] = code as u8;
+            output_i += 1;
+            input_i = offset + 1;
+        } else if (0xFF61..=0xFF9F).contains(&code) {
+            // Half-width katakana
+            out_buffer[output_i] = (code - 0xFF61 + 0xA1) as u8;
+            output_i += 1;
+            input_i = offset + 1;
+        } else {
+            // Handle composed sequences.
+            let mut composed = None;
+            if jis0213::is_composed_start(c) {
+                if let Some((offset2, c2)) = itr.clone().next() {
+                    composed = jis0213::encode_composed(c, c2).map(|ptr| (ptr, offset2));
+                } else if !is_end {
+                    break;
+                }
+            }
+
+            // Common case, fetch from table.
+            let (ptr, end) = if let Some(x) = composed {
+                x
+            } else if let Some(ptr) = jis0213::encode_char(c) {
+                (ptr, offset)
+            } else {
+                return Err(EncodeError {
+                    character: c,
+                    error_range: (offset, offset + c.len_utf8()),
+                    output_bytes_written: output_i,
+                });
+            };
+
+            if (output_i + 1) < out_buffer.len() {
+                if composed.is_some() {
+                    itr.next();
+                }
+                let sjis_bytes = ptr_to_bytes(ptr);
+                out_buffer[output_i] = sjis_bytes[0];
+                out_buffer[output_i + 1] = sjis_bytes[1];
+                output_i += 2;
+                input_i = end + 1;
+            } else {
+                break;
+            }
+        }
+    }
+
+    // Calculate how much of the input was consumed.
+    if input_i > input.len() {
+        input_i = input.len();
+    } else {
+        while !input.is_char_boundary(input_i) {
+            input_i += 1;
+        }
+    }
+
+    Ok((&out_buffer[..output_i], input_i))
+}
+
+pub fn decode_to_str<'a>(input: &[u8], out_buffer: &'a mut [u8], is_end: bool) -> DecodeResult<'a> {
+    let mut input_i = 0;
+    let mut output_i = 0;
+    let mut buf = [0u8; 8]; // For encoding utf8 codepoints.
+
+    // Loop through the input, getting a byte at a time.
+    let mut itr = input.iter();
+    while let Some(&byte_1) = itr.next() {
+        if output_i >= out_buffer.len() {
+            break;
+        } else if byte_1 <= 127 {
+            // Ascii
+            out_buffer[output_i] = byte_1;
+            output_i += 1;
+            input_i += 1;
+        } else if (0xA1..=0xDF).contains(&byte_1) {
+            // Half-width katakana
+            let c = unsafe { core::char::from_u32_unchecked(byte_1 as u32 - 0xA1 + 0xFF61) };
+            if (output_i + 3) > out_buffer.len() {
+                break;
+            }
+            c.encode_utf8(&mut out_buffer[output_i..]);
+            output_i += 3;
+            input_i += 1;
+        } else if byte_1 == 0x80 || byte_1 == 0xA0 || byte_1 > 0xFC {
+            // Error: invalid leading byte.
+            return Err(DecodeError {
+                cause: DecodeErrorCause::InvalidData,
+                error_range: (input_i, input_i + 1),
+                output_bytes_written: output_i,
+            });
+        } else if let Some(&byte_2) = itr.next() {
+            if byte_2 < 0x40 || byte_2 == 0x7F || byte_2 > 0xFC {
+                // Error: invalid trailing byte.
+                // Like WHATWG Shift JIS, if the second byte is ascii it
+                // remains part of the stream, and thus is not treated as part
+                // of the error.
+                return Err(DecodeError {
+                    cause: DecodeErrorCause::InvalidData,
+                    error_range: (input_i, input_i + if byte_2 <= 127 { 1 } else { 2 }),
+                    output_bytes_written: output_i,
+                });
+            }
+
+            // Get our decoded data.
+            let string =
+                if let Some(string) = jis0213::decode_ptr(bytes_to_ptr(byte_1, byte_2), &mut buf) {
+                    string
+                } else {
+                    // Error: correctly formed but undefined code.
+                    return Err(DecodeError {
+                        cause: DecodeErrorCause::InvalidData,
+                        error_range: (input_i, input_i + if byte_2 <= 127 { 1 } else { 2 }),
+                        output_bytes_written: output_i,
+                    });
+                };
+
+            // Copy decoded data to output.
+            if (output_i + string.len()) > out_buffer.len() {
+                break;
+            }
+            out_buffer[output_i..(output_i + string.len())].copy_from_slice(string.as_bytes());
+
+            // Update our counters.
+            input_i += 2;
+            output_i += string.len();
+        } else {
+            // No available trailing byte.  If not end-of-input, no problem.
+            // Otherwise it's an error.
+            if !is_end {
+                break;
+            } else {
+                return Err(DecodeError {
+                    cause: DecodeErrorCause::InvalidData,
+                    error_range: (input_i, input_i + 1),
+                    output_bytes_written: output_i,
+                });
+            }
+        }
+    }
+
+    Ok((
+        unsafe { core::str::from_utf8_unchecked(&out_buffer[..output_i]) },
+        input_i,
+    ))
+}
+
+/// Maps a JIS X 0213 pointer to Shift_JIS-2004 bytes.
+///
+/// Plane 1 is laid out exactly like JIS X 0208 in Shift JIS.  Plane 2 only
+/// has a subset of its rows assigned, which are packed into lead bytes
+/// 0xF0-0xFC.
+fn ptr_to_bytes(ptr: usize) -> [u8; 2] {
+    let row = (ptr % 8836) / 94 + 1;
+    let cell = ptr % 94 + 1;
+
+    // Position of the row in the combined sequence of plane 1 and packed
+    // plane 2 rows.
+    let n = if ptr < 8836 {
+        row
+    } else if row <= 5 {
+        row + 94
+    } else if row <= 15 {
+        row + 88
+    } else {
+        row + 26
+    };
+
+    let lead = (n - 1) / 2;
+    let lead = if lead < 31 { lead + 0x81 } else { lead + 0xC1 };
+    let trail = if n % 2 == 1 {
+        if cell < 64 {
+            cell + 0x3F
+        } else {
+            cell + 0x40
+        }
+    } else {
+        cell + 0x9E
+    };
+
+    [lead as u8, trail as u8]
+}
+
+/// Maps a pair of Shift_JIS-2004 bytes to a JIS X 0213 pointer.  The bytes
+/// must be a valid lead and trail byte.
+fn bytes_to_ptr(lead: u8, trail: u8) -> usize {
+    let lead = if lead < 0xA0 {
+        lead - 0x81
+    } else {
+        lead - 0xC1
+    } as usize;
+    let trail = trail as usize;
+    let (n, cell) = if trail < 0x9F {
+        (
+            lead * 2 + 1,
+            if trail < 0x80 {
+                trail - 0x3F
+            } else {
+                trail - 0x40
+            },
+        )
+    } else {
+        (lead * 2 + 2, trail - 0x9E)
+    };
+
+    if n <= 94 {
+        (n - 1) * 94 + (cell - 1)
+    } else {
+        let row = match n {
+            95 | 97 | 98 | 99 => n - 94,
+            96 | 100..=103 => n - 88,
+            _ => n - 26,
+        };
+        8836 + (row - 1) * 94 + (cell - 1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Helper function.
+    fn correct_decode(input: &[u8], expected_output: &str) {
+        let mut buf = [0u8; 256];
+        assert_eq!(
+            decode_to_str(input, &mut buf, true),
+            Ok((expected_output, input.len())),
+        );
+    }
+
+    // Helper function.
+    fn error_decode(input: &[u8], error_range: (usize, usize), bytes_written: usize) {
+        let mut buf = [0u8; 256];
+        assert_eq!(
+            decode_to_str(input, &mut buf, true),
+            Err(DecodeError {
+                cause: DecodeErrorCause::InvalidData,
+                error_range,
+                output_bytes_written: bytes_written,
+            }),
+        );
+    }
+
+    // Helper function.
+    fn correct_encode(input: &str, expected_output: &[u8]) {
+        let mut buf = [0u8; 256];
+        assert_eq!(
+            encode_from_str(input, &mut buf, true),
+            Ok((expected_output, input.len())),
+        );
+    }
+
+    #[test]
+    fn decode_01() {
+        correct_decode(&[0x61u8, 0x62u8], "ab");
+        correct_decode(&[0xB1u8], "ｱ");
+        correct_decode(&[0x83u8, 0x41u8], "ア");
+        correct_decode(&[0xEFu8, 0xE3u8], "鷗");
+        correct_decode(&[0x87u8, 0xA0u8], "𠀋");
+
+        // Plane 2
+        correct_decode(&[0xF0u8, 0x40u8], "𠂉");
+        correct_decode(&[0xF0u8, 0x9Fu8], "宖");
+        correct_decode(&[0xF1u8, 0x9Fu8], "唼");
+        correct_decode(&[0xF4u8, 0xFCu8], "㴞");
+        correct_decode(&[0xFCu8, 0xF4u8], "𪚲");
+
+        // Composed sequences
+        correct_decode(&[0x82u8, 0xF5u8], "か\u{309A}");
+        correct_decode(&[0x86u8, 0x63u8], "æ\u{0300}");
+        correct_decode(&[0x86u8, 0x85u8], "˩˥");
+    }
+
+    #[test]
+    fn decode_02() {
+        let data = [0x82, 0xA9, 0x82, 0xF5, 0xB1]; // "かか゚ｱ"
+        let mut buf = [0u8; 8];
+        let (decoded, consumed_count) = decode_to_str(&data, &mut buf, true).unwrap();
+        assert_eq!(consumed_count, 2);
+        assert_eq!(decoded, "か");
+    }
+
+    #[test]
+    fn decode_03() {
+        let data = [0x82, 0xA9, 0x82]; // "かか" with last byte chopped off.
+        let mut buf = [0u8; 64];
+        let (decoded, consumed_count) = decode_to_str(&data, &mut buf, false).unwrap();
+        assert_eq!(consumed_count, 2);
+        assert_eq!(decoded, "か");
+    }
+
+    #[test]
+    fn decode_error_01() {
+        error_decode(&[0x80u8, 0x61u8], (0, 1), 0); // Invalid sequence
+        error_decode(&[0xA0u8, 0x61u8], (0, 1), 0); // Invalid sequence
+        error_decode(&[0xFDu8, 0x61u8], (0, 1), 0); // Invalid sequence
+        error_decode(&[0x82u8, 0x39u8], (0, 1), 0); // Invalid sequence
+        error_decode(&[0x82u8, 0xFDu8], (0, 2), 0); // Invalid sequence
+        error_decode(&[0x82u8, 0xA9u8, 0x82u8], (2, 3), 3); // Truncated
+    }
+
+    #[test]
+    fn encode_01() {
+        correct_encode("ab", &[0x61, 0x62]);
+        correct_encode("ｱア鷗", &[0xB1, 0x83, 0x41, 0xEF, 0xE3]);
+        correct_encode("𠀋𠂉𪚲", &[0x87, 0xA0, 0xF0, 0x40, 0xFC, 0xF4]);
+
+        // Composed sequences, and their parts on their own.
+        correct_encode("か\u{309A}", &[0x82, 0xF5]);
+        correct_encode("か", &[0x82, 0xA9]);
+        correct_encode("かa", &[0x82, 0xA9, 0x61]);
+        correct_encode("æ\u{0300}æ", &[0x86, 0x63, 0x85, 0x7B]);
+        correct_encode("˩˥˥", &[0x86, 0x85, 0x86, 0x80]);
+    }
+
+    #[test]
+    fn encode_02() {
+        let text = "かか゚";
+        let mut buf = [0u8; 3];
+        let (encoded, consumed_count) = encode_from_str(text, &mut buf, true).unwrap();
+        assert_eq!(consumed_count, 3);
+        assert_eq!(encoded, &[0x82, 0xA9]);
+    }
+
+    #[test]
+    fn encode_03() {
+        // A trailing base character isn't consumed until we know what follows.
+        let text = "ab\u{304B}";
+        let mut buf = [0u8; 64];
+        let (encoded, consumed_count) = encode_from_str(text, &mut buf, false).unwrap();
+        assert_eq!(consumed_count, 2);
+        assert_eq!(encoded, &[0x61, 0x62]);
+
+        let (encoded, consumed_count) = encode_from_str(text, &mut buf, true).unwrap();
+        assert_eq!(consumed_count, 5);
+        assert_eq!(encoded, &[0x61, 0x62, 0x82, 0xA9]);
+    }
+
+    #[test]
+    fn encode_error_01() {
+        let text = "か😺";
+        let mut buf = [0u8; 64];
+        assert_eq!(
+            encode_from_str(text, &mut buf, true),
+            Err(EncodeError {
+                character: '😺',
+                error_range: (3, 7),
+                output_bytes_written: 2,
+            })
+        );
+    }
+}
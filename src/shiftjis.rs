@@ -0,0 +1,388 @@
+//! Shift JIS, with a selectable mapping profile.
+//!
+//! Different systems disagree about how some Shift JIS codes map to Unicode.
+//! For example, 0x8160 is U+301C WAVE DASH in JIS X 0208, but U+FF5E
+//! FULLWIDTH TILDE in Microsoft's code page 932.  And the NEC and IBM
+//! extensions that Microsoft added contain duplicates of each other and of
+//! JIS X 0208 characters.  The `Profile` passed to the encode and decode
+//! functions selects which of these behaviors to use:
+//!
+//! * `Profile::Whatwg`: the WHATWG variant of Shift JIS, as used on the web.
+//!   This is what the `shiftjis_whatwg` module uses.
+//! * `Profile::Jis`: strict JIS X 0208:1997 Shift JIS, with the single bytes
+//!   from JIS X 0201.  Notably, 0x5C is U+00A5 YEN SIGN and 0x7E is U+203E
+//!   OVERLINE, so U+005C and U+007E can't be encoded.  The NEC and IBM
+//!   extensions and the user-defined area are not supported.
+//! * `Profile::Cp932`: Microsoft's code page 932, as implemented by Windows.
+//!   This is the same as the WHATWG profile, except that 0xA0 and 0xFD-0xFF
+//!   map to U+F8F0-U+F8F3, and it doesn't have WHATWG's extra encode-only
+//!   mappings for U+00A5, U+203E, and U+2212.
+//! * `Profile::Apple`: Apple's Mac OS Japanese, from Apple's JAPANESE.TXT
+//!   mapping table.  This is what the `mac_japanese` module uses.  0x5C is
+//!   U+00A5 YEN SIGN and 0x80 is U+005C, and Apple's additional symbols in
+//!   lead bytes 0x85-0x88 and 0xEB-0xED include codes that map to sequences
+//!   of code points.  The user-defined area is not supported.
+//!
+//! For characters with more than one code, the WHATWG and CP932 profiles
+//! both encode to the same code that Windows does: NEC row 13 is preferred
+//! over the IBM extensions, and the IBM extensions (0xFA-0xFC) are
+//! preferred over the NEC-selected IBM extensions (0xED-0xEE).  Decoding
+//! with those profiles is therefore lossy.  The JIS profile has no
+//! duplicates, and is lossless in both directions, as is the Apple profile.
+//!
+//! When encoding with the Apple profile, a char that may start one of its
+//! sequences waits for more input unless `is_end` is true.
+//!
+//! There are no profiles for the emoji of the Japanese mobile carriers
+//! (DoCoMo, KDDI/au, and SoftBank), which are out of scope for now.  The
+//! carriers placed them in the user-defined area 0xF040-0xF9FC, so the
+//! WHATWG and CP932 profiles decode them to the private use code points
+//! U+E000-U+E757 rather than to standard Unicode emoji, and the JIS and Apple
+//! profiles reject them.
+
+use core;
+use dbcs::{self, Dbcs};
+use {DecodeResult, EncodeResult};
+
+// Generated by `encoding_tables/whatwg/generate_shiftjis.py`.
+// Contains ENCODE_TABLE and DECODE_TABLE.
+include!("generated/whatwg/shiftjis_whatwg_tables.rs.inc");
+
+// Generated by `encoding_tables/shiftjis/generate_shiftjis_jis.py`.
+// Contains JIS_ENCODE_TABLE and JIS_DECODE_TABLE.
+include!("generated/shiftjis/shiftjis_jis_tables.rs.inc");
+
+// Generated by `encoding_tables/mac_cjk/generate_mac_cjk.py`.
+// Contains APPLE_ENCODE_TABLE, APPLE_DECODE_TABLE, and APPLE_SEQUENCES.
+include!("generated/mac_cjk/mac_japanese_tables.rs.inc");
+
+/// A Shift JIS mapping profile.
+///
+/// See the module documentation for details.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Profile {
+    Whatwg,
+    Jis,
+    Cp932,
+    Apple,
+}
+
+pub fn encode_from_str<'a>(
+    input: &str,
+    out_buffer: &'a mut [u8],
+    is_end: bool,
+    profile: Profile,
+) -> EncodeResult<'a> {
+    dbcs::encode_from_str(profile.dbcs(), input, out_buffer, is_end)
+}
+
+/// Note: is potentially lossy, depending on the profile.
+pub fn decode_to_str<'a>(
+    input: &[u8],
+    out_buffer: &'a mut [u8],
+    is_end: bool,
+    profile: Profile,
+) -> DecodeResult<'a> {
+    dbcs::decode_to_str(profile.dbcs(), input, out_buffer, is_end)
+}
+
+// The lead and trail bytes of double-byte codes, which give the JIS pointers
+// of the WHATWG index: 188 codes per lead byte.
+const LEAD_RANGES: &[(u8, u8)] = &[(0x81, 0x9F), (0xE0, 0xFC)];
+const TRAIL_RANGES: &[(u8, u8)] = &[(0x40, 0x7E), (0x80, 0xFC)];
+
+// The user-defined area 0xF040-0xF9FC.  (WHATWG special case 5.)
+// Note: the WHATWG spec only specifies this for decoding, but these simply
+// don't map otherwise when encoding.  So we're just making it map back
+// properly.
+const USER_DEFINED_AREA: &[(usize, usize, u32)] = &[(8836, 10715, 0xE000)];
+
+static WHATWG: Dbcs = Dbcs {
+    lead_ranges: LEAD_RANGES,
+    trail_ranges: TRAIL_RANGES,
+    decode_single: |byte| Profile::Whatwg.decode_single(byte),
+    encode_single: |c| Profile::Whatwg.encode_single(c),
+    decode_table: &DECODE_TABLE,
+    encode_table: &ENCODE_TABLE,
+    pua_ranges: USER_DEFINED_AREA,
+    sequences: &[],
+    encode_aliases: &[('\u{2212}', '\u{FF0D}')], // Special case 4
+};
+
+static JIS: Dbcs = Dbcs {
+    lead_ranges: LEAD_RANGES,
+    trail_ranges: TRAIL_RANGES,
+    decode_single: |byte| Profile::Jis.decode_single(byte),
+    encode_single: |c| Profile::Jis.encode_single(c),
+    decode_table: &JIS_DECODE_TABLE,
+    encode_table: &JIS_ENCODE_TABLE,
+    pua_ranges: &[],
+    sequences: &[],
+    encode_aliases: &[],
+};
+
+static CP932: Dbcs = Dbcs {
+    lead_ranges: LEAD_RANGES,
+    trail_ranges: TRAIL_RANGES,
+    decode_single: |byte| Profile::Cp932.decode_single(byte),
+    encode_single: |c| Profile::Cp932.encode_single(c),
+    decode_table: &DECODE_TABLE,
+    encode_table: &ENCODE_TABLE,
+    pua_ranges: USER_DEFINED_AREA,
+    sequences: &[],
+    encode_aliases: &[],
+};
+
+// Apple has no codes with lead bytes 0xEE-0xFC.
+static APPLE: Dbcs = Dbcs {
+    lead_ranges: &[(0x81, 0x9F), (0xE0, 0xED)],
+    trail_ranges: TRAIL_RANGES,
+    decode_single: |byte| Profile::Apple.decode_single(byte),
+    encode_single: |c| Profile::Apple.encode_single(c),
+    decode_table: &APPLE_DECODE_TABLE,
+    encode_table: &APPLE_ENCODE_TABLE,
+    pua_ranges: &[],
+    sequences: &APPLE_SEQUENCES,
+    encode_aliases: &[],
+};
+
+impl Profile {
+    /// Returns the code page description for the profile.
+    fn dbcs(self) -> &'static Dbcs {
+        match self {
+            Profile::Whatwg => &WHATWG,
+            Profile::Jis => &JIS,
+            Profile::Cp932 => &CP932,
+            Profile::Apple => &APPLE,
+        }
+    }
+
+    /// Returns the single byte that `c` encodes to, if any.
+    fn encode_single(self, c: char) -> Option<u8> {
+        let code = c as u32;
+        if (0xFF61..=0xFF9F).contains(&code) {
+            // Half-width katakana.  (WHATWG special case 3.)
+            return Some((code - 0xFF61 + 0xA1) as u8);
+        }
+        match self {
+            Profile::Whatwg => match code {
+                0..=0x80 => Some(code as u8),
+                0xA5 => Some(0x5C),   // Special case 1
+                0x203E => Some(0x7E), // Special case 2
+                _ => None,
+            },
+            Profile::Jis => match code {
+                0x5C | 0x7E => None,
+                0..=0x7F => Some(code as u8),
+                0xA5 => Some(0x5C),
+                0x203E => Some(0x7E),
+                _ => None,
+            },
+            Profile::Cp932 => match code {
+                0..=0x80 => Some(code as u8),
+                0xF8F0 => Some(0xA0),
+                0xF8F1..=0xF8F3 => Some((code - 0xF8F1 + 0xFD) as u8),
+                _ => None,
+            },
+            Profile::Apple => match code {
+                0x5C => Some(0x80),
+                0xA5 => Some(0x5C),
+                0..=0x7F | 0xA0 => Some(code as u8),
+                0xA9 => Some(0xFD),
+                0x2122 => Some(0xFE),
+                _ => None,
+            },
+        }
+    }
+
+    /// Returns the `char` that a single-byte code decodes to, if any.
+    fn decode_single(self, byte: u8) -> Option<char> {
+        if (0xA1..=0xDF).contains(&byte) {
+            // Half-width katakana.  (WHATWG special case 3.)
+            return core::char::from_u32(byte as u32 + 0xFF61 - 0xA1);
+        }
+        match (self, byte) {
+            (Profile::Jis, 0x5C) => Some('\u{00A5}'),
+            (Profile::Jis, 0x7E) => Some('\u{203E}'),
+            (Profile::Apple, 0x5C) => Some('\u{00A5}'),
+            (Profile::Apple, 0x80) => Some('\u{005C}'),
+            (Profile::Apple, 0xA0) => Some('\u{00A0}'),
+            (Profile::Apple, 0xFD) => Some('\u{00A9}'),
+            (Profile::Apple, 0xFE) => Some('\u{2122}'),
+            (_, 0..=0x7F) => Some(byte as char),
+            (Profile::Whatwg, 0x80) | (Profile::Cp932, 0x80) => Some('\u{0080}'),
+            (Profile::Cp932, 0xA0) => Some('\u{F8F0}'),
+            (Profile::Cp932, 0xFD..=0xFF) => core::char::from_u32(byte as u32 - 0xFD + 0xF8F1),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use {DecodeError, DecodeErrorCause, EncodeError};
+
+    // Helper function.
+    fn correct_decode(input: &[u8], expected_output: &str, profile: Profile) {
+        let mut buf = [0u8; 256];
+        assert_eq!(
+            decode_to_str(input, &mut buf, true, profile),
+            Ok((expected_output, input.len())),
+        );
+    }
+
+    // Helper function.
+    fn error_decode(
+        input: &[u8],
+        error_range: (usize, usize),
+        bytes_written: usize,
+        profile: Profile,
+    ) {
+        let mut buf = [0u8; 256];
+        assert_eq!(
+            decode_to_str(input, &mut buf, true, profile),
+            Err(DecodeError {
+                cause: DecodeErrorCause::InvalidData,
+                error_range,
+                output_bytes_written: bytes_written,
+            }),
+        );
+    }
+
+    // Helper function.
+    fn correct_encode(input: &str, expected_output: &[u8], profile: Profile) {
+        let mut buf = [0u8; 256];
+        assert_eq!(
+            encode_from_str(input, &mut buf, true, profile),
+            Ok((expected_output, input.len())),
+        );
+    }
+
+    // Helper function.
+    fn error_encode(input: &str, character: char, profile: Profile) {
+        let mut buf = [0u8; 256];
+        let offset = input.find(character).unwrap();
+        assert_eq!(
+            encode_from_str(input, &mut buf, true, profile),
+            Err(EncodeError {
+                character,
+                error_range: (offset, offset + character.len_utf8()),
+                output_bytes_written: offset,
+            }),
+        );
+    }
+
+    #[test]
+    fn decode_01() {
+        // Wave dash
+        correct_decode(b"\x81\x60", "\u{FF5E}", Profile::Whatwg);
+        correct_decode(b"\x81\x60", "\u{301C}", Profile::Jis);
+        correct_decode(b"\x81\x60", "\u{FF5E}", Profile::Cp932);
+
+        // Other JIS vs Microsoft differences
+        correct_decode(
+            b"\x81\x61\x81\x7C\x81\x91",
+            "\u{2016}\u{2212}\u{00A2}",
+            Profile::Jis,
+        );
+        correct_decode(
+            b"\x81\x61\x81\x7C\x81\x91",
+            "\u{2225}\u{FF0D}\u{FFE0}",
+            Profile::Cp932,
+        );
+
+        // Single bytes
+        correct_decode(b"a\\~", "a\\~", Profile::Whatwg);
+        correct_decode(b"a\\~", "a\u{00A5}\u{203E}", Profile::Jis);
+        correct_decode(b"a\\~", "a\\~", Profile::Cp932);
+        correct_decode(b"\x80", "\u{0080}", Profile::Cp932);
+        correct_decode(
+            b"\xA0\xFD\xFE\xFF",
+            "\u{F8F0}\u{F8F1}\u{F8F2}\u{F8F3}",
+            Profile::Cp932,
+        );
+        correct_decode(b"\xB1", "\u{FF71}", Profile::Jis);
+
+        // Extensions
+        correct_decode(
+            b"\x87\x40\xED\x40\xFA\x40",
+            "\u{2460}\u{7E8A}\u{2170}",
+            Profile::Cp932,
+        );
+        correct_decode(b"\xF0\x40", "\u{E000}", Profile::Cp932);
+
+        // Apple
+        correct_decode(
+            b"a\\\x80\xA0\xFD\xFE",
+            "a\u{00A5}\\\u{00A0}\u{00A9}\u{2122}",
+            Profile::Apple,
+        );
+        correct_decode(b"\x85\x40\x85\xAB", "\u{2460}\u{F862}XIII", Profile::Apple);
+    }
+
+    #[test]
+    fn decode_error_01() {
+        error_decode(b"\x80", (0, 1), 0, Profile::Jis);
+        error_decode(b"\xA0", (0, 1), 0, Profile::Whatwg);
+        error_decode(b"\xA0", (0, 1), 0, Profile::Jis);
+        error_decode(b"\xFD", (0, 1), 0, Profile::Whatwg);
+        error_decode(b"\x87\x40", (0, 1), 0, Profile::Jis);
+        error_decode(b"\xED\x9F", (0, 2), 0, Profile::Jis);
+        error_decode(b"\xF0\x40", (0, 1), 0, Profile::Jis);
+        error_decode(b"a\x81", (1, 2), 1, Profile::Cp932);
+        error_decode(b"\xF0\x40", (0, 1), 0, Profile::Apple);
+    }
+
+    #[test]
+    fn encode_01() {
+        // Wave dash
+        correct_encode("\u{301C}", b"\x81\x60", Profile::Jis);
+        correct_encode("\u{FF5E}", b"\x81\x60", Profile::Whatwg);
+        correct_encode("\u{FF5E}", b"\x81\x60", Profile::Cp932);
+
+        // Yen sign and overline
+        correct_encode("\u{00A5}\u{203E}", b"\x5C\x7E", Profile::Whatwg);
+        correct_encode("\u{00A5}\u{203E}", b"\x5C\x7E", Profile::Jis);
+        correct_encode("\\~", b"\x5C\x7E", Profile::Cp932);
+
+        // Minus sign
+        correct_encode("\u{2212}", b"\x81\x7C", Profile::Whatwg);
+        correct_encode("\u{2212}", b"\x81\x7C", Profile::Jis);
+        correct_encode("\u{FF0D}", b"\x81\x7C", Profile::Cp932);
+
+        // Duplicates
+        correct_encode(
+            "\u{2252}\u{2235}\u{FFE2}",
+            b"\x81\xE0\x81\xE6\x81\xCA",
+            Profile::Cp932,
+        );
+        correct_encode(
+            "\u{2160}\u{2170}\u{7E8A}",
+            b"\x87\x54\xFA\x40\xFA\x5C",
+            Profile::Cp932,
+        );
+
+        // Single bytes
+        correct_encode("\u{F8F0}\u{F8F1}\u{F8F3}", b"\xA0\xFD\xFF", Profile::Cp932);
+        correct_encode("\u{FF71}", b"\xB1", Profile::Jis);
+        correct_encode("\u{00A5}\\\u{00A9}", b"\x5C\x80\xFD", Profile::Apple);
+
+        // Sequences
+        correct_encode("\u{F862}XIII\u{2026}", b"\x85\xAB\x81\x63", Profile::Apple);
+    }
+
+    #[test]
+    fn encode_error_01() {
+        error_encode("a\u{00A5}", '\u{00A5}', Profile::Cp932);
+        error_encode("a\u{203E}", '\u{203E}', Profile::Cp932);
+        error_encode("a\u{2212}", '\u{2212}', Profile::Cp932);
+        error_encode("a\u{FF5E}", '\u{FF5E}', Profile::Jis);
+        error_encode("a\\", '\\', Profile::Jis);
+        error_encode("a~", '~', Profile::Jis);
+        error_encode("a\u{2460}", '\u{2460}', Profile::Jis);
+        error_encode("a\u{E000}", '\u{E000}', Profile::Jis);
+        error_encode("a\u{F8F0}", '\u{F8F0}', Profile::Whatwg);
+        error_encode("a\u{E000}", '\u{E000}', Profile::Apple);
+    }
+}
@@ -4,212 +4,24 @@
 //! Therefore, decoding from WHATWG Shift JIS to `str` is lossy, because it
 //! throws away distinctions made in WHATWG Shift JIS that don't exist in
 //! Unicode.  Encoding from `str` to WHATWG Shift JIS, however, is lossless.
-use core;
-use {DecodeError, DecodeErrorCause, DecodeResult, EncodeError, EncodeResult};
-
-// Generated by build.rs  Contains ENCODE_TABLE and DECODE_TABLE.
-include!("generated/whatwg/shiftjis_whatwg_tables.rs.inc");
+//!
+//! This is the same as the `shiftjis` module with `Profile::Whatwg`.
+use shiftjis::{self, Profile};
+use {DecodeResult, EncodeResult};
 
 pub fn encode_from_str<'a>(input: &str, out_buffer: &'a mut [u8]) -> EncodeResult<'a> {
-    // Do the encode.
-    let mut input_i = 0;
-    let mut output_i = 0;
-    for (offset, c) in input.char_indices() {
-        let mut code = c as u32;
-        if output_i >= out_buffer.len() {
-            break;
-        } else if code <= 128 {
-            // Ascii
-            out_buffer[output_i] = code as u8;
-            output_i += 1;
-            input_i = offset + 1;
-        } else if code == 0xA5 {
-            // Special case 1
-            out_buffer[output_i] = 0x5C;
-            output_i += 1;
-            input_i = offset + 1;
-        } else if code == 0x203E {
-            // Special case 2
-            out_buffer[output_i] = 0x7E;
-            output_i += 1;
-            input_i = offset + 1;
-        } else if code >= 0xFF61 && code <= 0xFF9F {
-            // Special case 3
-            out_buffer[output_i] = (code - 0xFF61 + 0xA1) as u8;
-            output_i += 1;
-            input_i = offset + 1;
-        } else if (output_i + 1) < out_buffer.len() {
-            let jis_bytes = if code >= 0xE000 && code <= 0xE757 {
-                // Special case 5
-                // Note: the WHATWG spec doesn't specify this, but it does it
-                // in reverse during decoding, and these simply don't map
-                // otherwise.  So we're just making it map back properly.
-                let jis_ptr = code - 0xE000 + 8836;
-                let lead = jis_ptr / 188;
-                let lead_offset = if lead < 0x1F { 0x81 } else { 0xC1 };
-                let trail = jis_ptr % 188;
-                let trail_offset = if trail < 0x3F { 0x40 } else { 0x41 };
-                [(lead + lead_offset) as u8, (trail + trail_offset) as u8]
-            } else {
-                if code == 0x2212 {
-                    // Special case 4
-                    code = 0xFF0D;
-                }
-                let code = core::char::from_u32(code).unwrap();
-                if let Ok(ptr_i) = ENCODE_TABLE.binary_search_by_key(&code, |x| x.0) {
-                    ENCODE_TABLE[ptr_i].1
-                } else {
-                    return Err(EncodeError {
-                        character: c,
-                        error_range: (offset, offset + c.len_utf8()),
-                        output_bytes_written: output_i,
-                    });
-                }
-            };
-
-            out_buffer[output_i] = jis_bytes[0];
-            out_buffer[output_i + 1] = jis_bytes[1];
-            output_i += 2;
-            input_i = offset + 1;
-        } else {
-            break;
-        }
-    }
-
-    // Calculate how much of the input was consumed.
-    if input_i > input.len() {
-        input_i = input.len();
-    } else {
-        while !input.is_char_boundary(input_i) {
-            input_i += 1;
-        }
-    }
-
-    Ok((&out_buffer[..output_i], input_i))
+    shiftjis::encode_from_str(input, out_buffer, true, Profile::Whatwg)
 }
 
 /// Note: is potentially lossy.
 pub fn decode_to_str<'a>(input: &[u8], out_buffer: &'a mut [u8], is_end: bool) -> DecodeResult<'a> {
-    let mut input_i = 0;
-    let mut output_i = 0;
-    let mut buf = [0u8; 4]; // For encoding utf8 codepoints.
-
-    // Loop through the input, getting a byte at a time.
-    let mut itr = input.iter();
-    while let Some(&byte_1) = itr.next() {
-        if output_i >= out_buffer.len() {
-            break;
-        } else if byte_1 <= 127 {
-            // Ascii
-            out_buffer[output_i] = byte_1;
-            output_i += 1;
-            input_i += 1;
-        } else if byte_1 == 128 {
-            // Edge-case, close to ascii.
-            if output_i + 1 >= out_buffer.len() {
-                break;
-            } else {
-                out_buffer[output_i] = 0xC2;
-                out_buffer[output_i + 1] = 0x80;
-                output_i += 2;
-                input_i += 1;
-            }
-        } else {
-            // Get our decoded data, either from the table or by special handling.
-            let (string, input_consumed) = if byte_1 >= 0xA1 && byte_1 <= 0xDF {
-                // Special case 3 from encode function above.
-                (
-                    core::char::from_u32(byte_1 as u32 + 0xFF61 - 0xA1)
-                        .unwrap()
-                        .encode_utf8(&mut buf),
-                    1,
-                )
-            } else if (byte_1 > 0x9F && byte_1 < 0xE0) || byte_1 > 0xFC {
-                // Error: invalid leading byte.
-                return Err(DecodeError {
-                    cause: DecodeErrorCause::InvalidData,
-                    error_range: (input_i, input_i + 1),
-                    output_bytes_written: output_i,
-                });
-            } else if let Some(&byte_2) = itr.next() {
-                if byte_2 < 0x40 || byte_2 == 0x7F || byte_2 > 0xFC {
-                    // Error: invalid trailing byte.
-                    // WHATWG dictates that if the second byte is ascii, it
-                    // remains part of the stream, and thus (in our case) is
-                    // not treated as part of the error.
-                    return Err(DecodeError {
-                        cause: DecodeErrorCause::InvalidData,
-                        error_range: (input_i, input_i + if byte_2 <= 127 { 1 } else { 2 }),
-                        output_bytes_written: output_i,
-                    });
-                }
-
-                let jis_ptr = {
-                    let lead_offset = if byte_1 < 0xA0 { 0x81 } else { 0xC1 };
-                    let trail_offset = if byte_2 < 0x7F { 0x40 } else { 0x41 };
-                    (byte_1 as u32 - lead_offset) * 188 + byte_2 as u32 - trail_offset
-                };
-
-                if jis_ptr >= 8836 && jis_ptr <= 10715 {
-                    // Special case 5
-                    (
-                        core::char::from_u32(jis_ptr + 0xE000 - 8836)
-                            .unwrap()
-                            .encode_utf8(&mut buf),
-                        2,
-                    )
-                } else if jis_ptr as usize >= DECODE_TABLE.len()
-                    || DECODE_TABLE[jis_ptr as usize] == '�'
-                {
-                    // Error: correctly formed but undefined code.
-                    // WHATWG dictates that if the second byte is ascii, it
-                    // remains part of the stream, and thus (in our case) is
-                    // not treated as part of the error.
-                    return Err(DecodeError {
-                        cause: DecodeErrorCause::InvalidData,
-                        error_range: (input_i, input_i + if byte_2 <= 127 { 1 } else { 2 }),
-                        output_bytes_written: output_i,
-                    });
-                } else {
-                    // Encode codepoint to utf8.
-                    (DECODE_TABLE[jis_ptr as usize].encode_utf8(&mut buf), 2)
-                }
-            } else {
-                // No trailing byte available.  If it's not end-of-input, not a problem.
-                // But if it is, then that's an error.
-                if !is_end {
-                    break;
-                } else {
-                    return Err(DecodeError {
-                        cause: DecodeErrorCause::InvalidData,
-                        error_range: (input_i, input_i + 1),
-                        output_bytes_written: output_i,
-                    });
-                }
-            };
-
-            // Copy decoded data to output.
-            if (output_i + string.len()) > out_buffer.len() {
-                // Not enough space in output buffer.
-                break;
-            }
-            out_buffer[output_i..(output_i + string.len())].copy_from_slice(string.as_bytes());
-
-            // Update our counters.
-            input_i += input_consumed;
-            output_i += string.len();
-        }
-    }
-
-    Ok((
-        unsafe { core::str::from_utf8_unchecked(&out_buffer[..output_i]) },
-        input_i,
-    ))
+    shiftjis::decode_to_str(input, out_buffer, is_end, Profile::Whatwg)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use {DecodeError, DecodeErrorCause, EncodeError};
 
     // Helper function.
     fn correct_decode(input: &[u8], expected_output: &str) {
@@ -227,7 +39,7 @@ mod tests {
             decode_to_str(input, &mut buf, true),
             Err(DecodeError {
                 cause: DecodeErrorCause::InvalidData,
-                error_range: error_range,
+                error_range,
                 output_bytes_written: bytes_written,
             }),
         );
@@ -247,7 +59,7 @@ mod tests {
     #[test]
     fn decode_01() {
         // Empty
-        correct_decode(b"", &"");
+        correct_decode(b"", "");
 
         // ASCII
         correct_decode(b"\x61\x62", "\u{0061}\u{0062}");
@@ -1,10 +1,86 @@
 //! Single byte encodings.
 //!
-//! So far all of these are Ascii and extensions of Ascii.
+//! Most of these are Ascii and extensions of Ascii.  The exceptions are the
+//! EBCDIC code pages, which define all 256 bytes differently, IBM code page
+//! 864, which has an Arabic percent sign in place of Ascii's, the font
+//! encodings (Mac OS Symbol, Adobe Standard, Adobe Symbol, and ITC Zapf
+//! Dingbats), PDFDocEncoding, which has spacing diacritics in place of some
+//! C0 controls, and PETSCII, whose letters depend on the character set.
+//!
+//! In a few of the Mac OS encodings (Arabic, Hebrew, Thai, and Symbol), some
+//! bytes map to a sequence of more than one `char`.  Their encoders take an
+//! `is_end` parameter, since a `char` at the end of the input may be the
+//! start of such a sequence.
+//!
+//! In EBCDIC, NL (0x15) is the usual line ending, and decodes to U+0085 NEXT
+//! LINE, while LF (0x25) decodes to U+000A LINE FEED.  Since that's rarely
+//! what's wanted for line-oriented mainframe files, the EBCDIC modules also
+//! have `decode_to_str_swap_nl_lf()` and `encode_from_str_swap_nl_lf()`,
+//! which swap the two so that NL maps to U+000A.  This is the same as ICU's
+//! "swaplfnl" option, and is how z/OS Unix System Services maps IBM-1047.
 
 use core;
 use {DecodeError, DecodeErrorCause, DecodeResult, EncodeError, EncodeResult};
 
+pub mod adobe_standard {
+    //! Adobe Standard Encoding, the built-in encoding of Adobe's Latin text
+    //! fonts.
+    //!
+    //! The letters and digits are as in Ascii, but 0x27 and 0x60 are the
+    //! curly quotes U+2019 and U+2018, the upper half is a sparse set of
+    //! punctuation, accents, and ligatures, and the C0 control bytes are
+    //! undefined.
+    //!
+    //! As in Adobe's table, some bytes also encode from a second code point,
+    //! which doesn't survive a round trip, since the byte decodes to the
+    //! first:
+    //!
+    //! - 0x20 SPACE from U+00A0 NO-BREAK SPACE.
+    //! - 0x2D HYPHEN-MINUS from U+00AD SOFT HYPHEN.
+    //! - 0xA4 U+2044 FRACTION SLASH from U+2215 DIVISION SLASH.
+    //! - 0xB4 U+00B7 MIDDLE DOT from U+2219 BULLET OPERATOR.
+    //! - 0xC5 U+00AF MACRON from U+02C9 MODIFIER LETTER MACRON.
+
+    // Generated by:
+    // `encoding_tables/single_byte/generate_full_tables.py`.
+    include!("generated/single_byte/adobe-standard_tables.rs.inc");
+}
+
+pub mod adobe_symbol {
+    //! Adobe Symbol, the built-in encoding of the Symbol font.
+    //!
+    //! This isn't an extension of Ascii: for example, 0x61 is U+03B1 GREEK
+    //! SMALL LETTER ALPHA.  The pieces for building large brackets and
+    //! integrals, and the serif and sans-serif forms of ®, ©, and ™, map to
+    //! Adobe's private use code points, such as 0xE6, U+F8EB.
+    //!
+    //! As in Adobe's table, some bytes also encode from a second code point,
+    //! which doesn't survive a round trip, since the byte decodes to the
+    //! first:
+    //!
+    //! - 0x20 SPACE from U+00A0 NO-BREAK SPACE.
+    //! - 0x44 U+0394 GREEK CAPITAL LETTER DELTA from U+2206 INCREMENT.
+    //! - 0x57 U+03A9 GREEK CAPITAL LETTER OMEGA from U+2126 OHM SIGN.
+    //! - 0x6D U+03BC GREEK SMALL LETTER MU from U+00B5 MICRO SIGN.
+    //! - 0xA4 U+2044 FRACTION SLASH from U+2215 DIVISION SLASH.
+
+    // Generated by:
+    // `encoding_tables/single_byte/generate_full_tables.py`.
+    include!("generated/single_byte/adobe-symbol_tables.rs.inc");
+}
+
+pub mod armscii_8 {
+    //! ARMSCII-8, the Armenian standard encoding.
+    //!
+    //! Bytes 0xA4, 0xA5, 0xA9, 0xAB, and 0xAC duplicate the Ascii punctuation
+    //! ")", "(", ".", ",", and "-".  They decode to the Ascii characters, which
+    //! always encode to the Ascii bytes.
+
+    // Generated by:
+    // `encoding_tables/single_byte/generate_ascii_ext_tables.py`.
+    include!("generated/single_byte/armscii-8_tables.rs.inc");
+}
+
 pub mod ascii {
     //! US ASCII.
     use super::*;
@@ -18,6 +94,251 @@ pub mod ascii {
     }
 }
 
+pub mod atari_st {
+    //! The Atari ST character set.
+    //!
+    //! Code page 437 in 0x80-0xAF, apart from ß in 0x9E, but with Portuguese
+    //! and Dutch letters and a few symbols in 0xB0-0xC1, and Hebrew letters
+    //! in 0xC2-0xDC in place of the box drawing.  The system font also has
+    //! glyphs for the C0 control bytes, which are decoded as control
+    //! characters here.
+
+    // Generated by:
+    // `encoding_tables/single_byte/generate_ascii_ext_tables.py`.
+    include!("generated/single_byte/atari-st_tables.rs.inc");
+}
+
+pub mod dec_mcs {
+    //! DEC Multinational Character Set, of the VT220 and VMS.
+    //!
+    //! The ancestor of ISO/IEC 8859-1, which it mostly agrees with.  It has
+    //! Œ, œ, and Ÿ in 0xD7, 0xF7, and 0xDD, where 8859-1 has ×, ÷, and Ý,
+    //! and fifteen bytes in the upper half, such as 0xA0, are undefined.
+
+    // Generated by:
+    // `encoding_tables/single_byte/generate_ascii_ext_tables.py`.
+    include!("generated/single_byte/dec-mcs_tables.rs.inc");
+}
+
+pub mod georgian_ps {
+    //! Georgian-PS.
+
+    // Generated by:
+    // `encoding_tables/single_byte/generate_ascii_ext_tables.py`.
+    include!("generated/single_byte/georgian-ps_tables.rs.inc");
+}
+
+pub mod hp_roman8 {
+    //! HP Roman-8, of HP terminals, printers, and HP-UX.
+
+    // Generated by:
+    // `encoding_tables/single_byte/generate_ascii_ext_tables.py`.
+    include!("generated/single_byte/hp-roman8_tables.rs.inc");
+}
+
+pub mod ibm_037 {
+    //! IBM EBCDIC code page 037, for the US and Canada.
+
+    // Generated by:
+    // `encoding_tables/single_byte/generate_full_tables.py`.
+    include!("generated/single_byte/ibm-cp037_tables.rs.inc");
+}
+
+pub mod ibm_273 {
+    //! IBM EBCDIC code page 273, for Germany and Austria.
+
+    // Generated by:
+    // `encoding_tables/single_byte/generate_full_tables.py`.
+    include!("generated/single_byte/ibm-cp273_tables.rs.inc");
+}
+
+pub mod ibm_277 {
+    //! IBM EBCDIC code page 277, for Denmark and Norway.
+
+    // Generated by:
+    // `encoding_tables/single_byte/generate_full_tables.py`.
+    include!("generated/single_byte/ibm-cp277_tables.rs.inc");
+}
+
+pub mod ibm_278 {
+    //! IBM EBCDIC code page 278, for Finland and Sweden.
+
+    // Generated by:
+    // `encoding_tables/single_byte/generate_full_tables.py`.
+    include!("generated/single_byte/ibm-cp278_tables.rs.inc");
+}
+
+pub mod ibm_280 {
+    //! IBM EBCDIC code page 280, for Italy.
+
+    // Generated by:
+    // `encoding_tables/single_byte/generate_full_tables.py`.
+    include!("generated/single_byte/ibm-cp280_tables.rs.inc");
+}
+
+pub mod ibm_284 {
+    //! IBM EBCDIC code page 284, for Spain and Latin America.
+
+    // Generated by:
+    // `encoding_tables/single_byte/generate_full_tables.py`.
+    include!("generated/single_byte/ibm-cp284_tables.rs.inc");
+}
+
+pub mod ibm_285 {
+    //! IBM EBCDIC code page 285, for the United Kingdom.
+
+    // Generated by:
+    // `encoding_tables/single_byte/generate_full_tables.py`.
+    include!("generated/single_byte/ibm-cp285_tables.rs.inc");
+}
+
+pub mod ibm_297 {
+    //! IBM EBCDIC code page 297, for France.
+
+    // Generated by:
+    // `encoding_tables/single_byte/generate_full_tables.py`.
+    include!("generated/single_byte/ibm-cp297_tables.rs.inc");
+}
+
+pub mod ibm_437 {
+    //! IBM code page 437, the character set of the original IBM PC.
+    //!
+    //! DOS displayed bytes 0x01-0x1F and 0x7F on screen as glyphs such as
+    //! ☺, ♥, and ⌂, rather than as control characters.  The
+    //! `decode_to_str_graphics()` and `encode_from_str_graphics()` functions
+    //! map those bytes to the glyphs instead.  Encoding also still accepts
+    //! the control characters, so text that was decoded either way can be
+    //! encoded again.
+
+    // Generated by:
+    // `encoding_tables/single_byte/generate_ascii_ext_tables.py`.
+    include!("generated/single_byte/ibm-cp437_tables.rs.inc");
+
+    // Generated by:
+    // `encoding_tables/single_byte/generate_full_tables.py`.
+    // Contains GRAPHICS_DECODE_TABLE and GRAPHICS_ENCODE_TABLE.
+    include!("generated/single_byte/ibm-cp437-graphics_tables.rs.inc");
+
+    /// Like `decode_to_str()`, but decodes 0x01-0x1F and 0x7F to their
+    /// display glyphs.
+    pub fn decode_to_str_graphics<'a>(input: &[u8], out_buffer: &'a mut [u8]) -> DecodeResult<'a> {
+        super::full_decode_to_str(&GRAPHICS_DECODE_TABLE, false, input, out_buffer)
+    }
+
+    /// Like `encode_from_str()`, but also encodes the display glyphs of
+    /// 0x01-0x1F and 0x7F.
+    pub fn encode_from_str_graphics<'a>(input: &str, out_buffer: &'a mut [u8]) -> EncodeResult<'a> {
+        super::full_encode_from_str(&GRAPHICS_ENCODE_TABLE, false, input, out_buffer)
+    }
+}
+
+pub mod ibm_500 {
+    //! IBM EBCDIC code page 500, also known as International EBCDIC.
+
+    // Generated by:
+    // `encoding_tables/single_byte/generate_full_tables.py`.
+    include!("generated/single_byte/ibm-cp500_tables.rs.inc");
+}
+
+pub mod ibm_737 {
+    //! IBM code page 737, for Greek.
+
+    // Generated by:
+    // `encoding_tables/single_byte/generate_ascii_ext_tables.py`.
+    include!("generated/single_byte/ibm-cp737_tables.rs.inc");
+}
+
+pub mod ibm_775 {
+    //! IBM code page 775, for the Baltic languages.
+
+    // Generated by:
+    // `encoding_tables/single_byte/generate_ascii_ext_tables.py`.
+    include!("generated/single_byte/ibm-cp775_tables.rs.inc");
+}
+
+pub mod ibm_850 {
+    //! IBM code page 850, for Western European languages.
+
+    // Generated by:
+    // `encoding_tables/single_byte/generate_ascii_ext_tables.py`.
+    include!("generated/single_byte/ibm-cp850_tables.rs.inc");
+}
+
+pub mod ibm_852 {
+    //! IBM code page 852, for Central European languages.
+
+    // Generated by:
+    // `encoding_tables/single_byte/generate_ascii_ext_tables.py`.
+    include!("generated/single_byte/ibm-cp852_tables.rs.inc");
+}
+
+pub mod ibm_855 {
+    //! IBM code page 855, for Cyrillic.
+
+    // Generated by:
+    // `encoding_tables/single_byte/generate_ascii_ext_tables.py`.
+    include!("generated/single_byte/ibm-cp855_tables.rs.inc");
+}
+
+pub mod ibm_857 {
+    //! IBM code page 857, for Turkish.
+
+    // Generated by:
+    // `encoding_tables/single_byte/generate_ascii_ext_tables.py`.
+    include!("generated/single_byte/ibm-cp857_tables.rs.inc");
+}
+
+pub mod ibm_860 {
+    //! IBM code page 860, for Portuguese.
+
+    // Generated by:
+    // `encoding_tables/single_byte/generate_ascii_ext_tables.py`.
+    include!("generated/single_byte/ibm-cp860_tables.rs.inc");
+}
+
+pub mod ibm_861 {
+    //! IBM code page 861, for Icelandic.
+
+    // Generated by:
+    // `encoding_tables/single_byte/generate_ascii_ext_tables.py`.
+    include!("generated/single_byte/ibm-cp861_tables.rs.inc");
+}
+
+pub mod ibm_862 {
+    //! IBM code page 862, for Hebrew.
+
+    // Generated by:
+    // `encoding_tables/single_byte/generate_ascii_ext_tables.py`.
+    include!("generated/single_byte/ibm-cp862_tables.rs.inc");
+}
+
+pub mod ibm_863 {
+    //! IBM code page 863, for Canadian French.
+
+    // Generated by:
+    // `encoding_tables/single_byte/generate_ascii_ext_tables.py`.
+    include!("generated/single_byte/ibm-cp863_tables.rs.inc");
+}
+
+pub mod ibm_864 {
+    //! IBM code page 864, for Arabic.
+    //!
+    //! This isn't quite an extension of Ascii: 0x25 is U+066A ARABIC PERCENT
+    //! SIGN, and U+0025 can't be encoded.
+
+    // Generated by:
+    // `encoding_tables/single_byte/generate_full_tables.py`.
+    include!("generated/single_byte/ibm-cp864_tables.rs.inc");
+}
+
+pub mod ibm_865 {
+    //! IBM code page 865, for the Nordic languages.
+
+    // Generated by:
+    // `encoding_tables/single_byte/generate_ascii_ext_tables.py`.
+    include!("generated/single_byte/ibm-cp865_tables.rs.inc");
+}
+
 pub mod ibm_866 {
     //! IBM code page 866.
 
@@ -26,6 +347,114 @@ pub mod ibm_866 {
     include!("generated/single_byte/ibm-cp866_tables.rs.inc");
 }
 
+pub mod ibm_869 {
+    //! IBM code page 869, for Greek.
+
+    // Generated by:
+    // `encoding_tables/single_byte/generate_ascii_ext_tables.py`.
+    include!("generated/single_byte/ibm-cp869_tables.rs.inc");
+}
+
+pub mod ibm_871 {
+    //! IBM EBCDIC code page 871, for Iceland.
+
+    // Generated by:
+    // `encoding_tables/single_byte/generate_full_tables.py`.
+    include!("generated/single_byte/ibm-cp871_tables.rs.inc");
+}
+
+pub mod ibm_1047 {
+    //! IBM EBCDIC code page 1047, also known as Latin-1/Open Systems.
+    //!
+    //! This is the EBCDIC code page used by z/OS Unix System Services, which
+    //! swaps NL and LF.  Use `decode_to_str_swap_nl_lf()` and
+    //! `encode_from_str_swap_nl_lf()` for text files from there.
+
+    // Generated by:
+    // `encoding_tables/single_byte/generate_full_tables.py`.
+    include!("generated/single_byte/ibm-cp1047_tables.rs.inc");
+}
+
+pub mod ibm_1125 {
+    //! IBM code page 1125, also known as RUSCII, for Ukrainian.
+
+    // Generated by:
+    // `encoding_tables/single_byte/generate_ascii_ext_tables.py`.
+    include!("generated/single_byte/ibm-cp1125_tables.rs.inc");
+}
+
+pub mod ibm_1140 {
+    //! IBM EBCDIC code page 1140, which is code page 037 with the euro sign.
+
+    // Generated by:
+    // `encoding_tables/single_byte/generate_full_tables.py`.
+    include!("generated/single_byte/ibm-cp1140_tables.rs.inc");
+}
+
+pub mod ibm_1141 {
+    //! IBM EBCDIC code page 1141, which is code page 273 with the euro sign.
+
+    // Generated by:
+    // `encoding_tables/single_byte/generate_full_tables.py`.
+    include!("generated/single_byte/ibm-cp1141_tables.rs.inc");
+}
+
+pub mod ibm_1142 {
+    //! IBM EBCDIC code page 1142, which is code page 277 with the euro sign.
+
+    // Generated by:
+    // `encoding_tables/single_byte/generate_full_tables.py`.
+    include!("generated/single_byte/ibm-cp1142_tables.rs.inc");
+}
+
+pub mod ibm_1143 {
+    //! IBM EBCDIC code page 1143, which is code page 278 with the euro sign.
+
+    // Generated by:
+    // `encoding_tables/single_byte/generate_full_tables.py`.
+    include!("generated/single_byte/ibm-cp1143_tables.rs.inc");
+}
+
+pub mod ibm_1144 {
+    //! IBM EBCDIC code page 1144, which is code page 280 with the euro sign.
+
+    // Generated by:
+    // `encoding_tables/single_byte/generate_full_tables.py`.
+    include!("generated/single_byte/ibm-cp1144_tables.rs.inc");
+}
+
+pub mod ibm_1145 {
+    //! IBM EBCDIC code page 1145, which is code page 284 with the euro sign.
+
+    // Generated by:
+    // `encoding_tables/single_byte/generate_full_tables.py`.
+    include!("generated/single_byte/ibm-cp1145_tables.rs.inc");
+}
+
+pub mod ibm_1146 {
+    //! IBM EBCDIC code page 1146, which is code page 285 with the euro sign.
+
+    // Generated by:
+    // `encoding_tables/single_byte/generate_full_tables.py`.
+    include!("generated/single_byte/ibm-cp1146_tables.rs.inc");
+}
+
+pub mod ibm_1147 {
+    //! IBM EBCDIC code page 1147, which is code page 297 with the euro sign.
+
+    // Generated by:
+    // `encoding_tables/single_byte/generate_full_tables.py`.
+    include!("generated/single_byte/ibm-cp1147_tables.rs.inc");
+}
+
+pub mod ibm_1148 {
+    //! IBM EBCDIC code page 1148, which is code page 500 with the euro sign.
+
+    // Generated by:
+    // `encoding_tables/single_byte/generate_full_tables.py`.
+    include!("generated/single_byte/ibm-cp1148_tables.rs.inc");
+}
+
 pub mod iso_8859_1 {
     //! ISO/IEC 8859-1, also known as Latin-1.
     //!
@@ -158,6 +587,25 @@ pub mod koi8_r {
     include!("generated/single_byte/koi8-r_tables.rs.inc");
 }
 
+pub mod koi8_ru {
+    //! KOI8-RU.
+    //!
+    //! KOI8-U with the Belarusian short U, and some typographic symbols in place
+    //! of box drawing characters.
+
+    // Generated by:
+    // `encoding_tables/single_byte/generate_ascii_ext_tables.py`.
+    include!("generated/single_byte/koi8-ru_tables.rs.inc");
+}
+
+pub mod koi8_t {
+    //! KOI8-T, for Tajik.
+
+    // Generated by:
+    // `encoding_tables/single_byte/generate_ascii_ext_tables.py`.
+    include!("generated/single_byte/koi8-t_tables.rs.inc");
+}
+
 pub mod koi8_u {
     //! KOI8-U.
 
@@ -166,6 +614,16 @@ pub mod koi8_u {
     include!("generated/single_byte/koi8-u_tables.rs.inc");
 }
 
+pub mod kz_1048 {
+    //! KZ-1048 (STRK1048-2002), the Kazakh standard encoding.
+    //!
+    //! Windows code page 1251 with the letters for Kazakh.
+
+    // Generated by:
+    // `encoding_tables/single_byte/generate_ascii_ext_tables.py`.
+    include!("generated/single_byte/kz-1048_tables.rs.inc");
+}
+
 pub mod mac_roman {
     //! Mac OS Roman, also known as "macintosh".
     //!
@@ -184,72 +642,306 @@ pub mod mac_cyrillic {
     include!("generated/single_byte/mac-cyrillic_tables.rs.inc");
 }
 
-pub mod windows_874 {
-    //! Windows code page 874.
+pub mod mac_arabic {
+    //! Mac OS Arabic.
+    //!
+    //! Many of the Ascii punctuation characters, the digits, and the space
+    //! appear twice in this encoding: as left-right characters at their
+    //! Ascii bytes, and as right-left characters at higher bytes.  Unicode
+    //! doesn't distinguish them, so following Apple's direction hints, a
+    //! right-left character decodes to the character wrapped in U+202E
+    //! RIGHT-TO-LEFT OVERRIDE and U+202C POP DIRECTIONAL FORMATTING, and
+    //! encodes from exactly that sequence.  A plain character encodes to the
+    //! left-right byte.  This means that decoding and then encoding gives
+    //! back the original bytes, and encoding and then decoding gives back the
+    //! original text.
 
     // Generated by:
-    // `encoding_tables/single_byte/generate_ascii_ext_tables.py`.
-    include!("generated/single_byte/windows-cp874_tables.rs.inc");
+    // `encoding_tables/single_byte/generate_full_tables.py`.
+    include!("generated/single_byte/mac-arabic_tables.rs.inc");
 }
 
-pub mod windows_1250 {
-    //! Windows code page 1250.
+pub mod mac_central_european {
+    //! Mac OS Central European, also known as "x-mac-ce".
 
     // Generated by:
     // `encoding_tables/single_byte/generate_ascii_ext_tables.py`.
-    include!("generated/single_byte/windows-cp1250_tables.rs.inc");
+    include!("generated/single_byte/mac-centeuro_tables.rs.inc");
 }
 
-pub mod windows_1251 {
-    //! Windows code page 1251.
+pub mod mac_croatian {
+    //! Mac OS Croatian.
 
     // Generated by:
     // `encoding_tables/single_byte/generate_ascii_ext_tables.py`.
-    include!("generated/single_byte/windows-cp1251_tables.rs.inc");
+    include!("generated/single_byte/mac-croatian_tables.rs.inc");
 }
 
-pub mod windows_1252 {
-    //! Windows code page 1252.
+pub mod mac_greek {
+    //! Mac OS Greek.
 
     // Generated by:
     // `encoding_tables/single_byte/generate_ascii_ext_tables.py`.
-    include!("generated/single_byte/windows-cp1252_tables.rs.inc");
+    include!("generated/single_byte/mac-greek_tables.rs.inc");
 }
 
-pub mod windows_1253 {
-    //! Windows code page 1253.
+pub mod mac_hebrew {
+    //! Mac OS Hebrew.
+    //!
+    //! The right-left duplicates of Ascii characters are handled the same
+    //! way as in `mac_arabic`.  Note that the right-left parentheses and
+    //! brackets are mirrored: for example, 0xA8 is a right-left ")".  A few
+    //! other bytes map to sequences that include Apple's private use hints
+    //! for variant forms, such as 0xC0, which is U+F86A U+05DC U+05B9.
 
     // Generated by:
-    // `encoding_tables/single_byte/generate_ascii_ext_tables.py`.
-    include!("generated/single_byte/windows-cp1253_tables.rs.inc");
+    // `encoding_tables/single_byte/generate_full_tables.py`.
+    include!("generated/single_byte/mac-hebrew_tables.rs.inc");
 }
 
-pub mod windows_1254 {
-    //! Windows code page 1254.
+pub mod mac_icelandic {
+    //! Mac OS Icelandic.
 
     // Generated by:
     // `encoding_tables/single_byte/generate_ascii_ext_tables.py`.
-    include!("generated/single_byte/windows-cp1254_tables.rs.inc");
+    include!("generated/single_byte/mac-iceland_tables.rs.inc");
 }
 
-pub mod windows_1255 {
-    //! Windows code page 1255.
+pub mod mac_romanian {
+    //! Mac OS Romanian.
 
     // Generated by:
     // `encoding_tables/single_byte/generate_ascii_ext_tables.py`.
-    include!("generated/single_byte/windows-cp1255_tables.rs.inc");
+    include!("generated/single_byte/mac-romanian_tables.rs.inc");
 }
 
-pub mod windows_1256 {
-    //! Windows code page 1256.
+pub mod mac_symbol {
+    //! Mac OS Symbol.
+    //!
+    //! This is the encoding of Apple's Symbol font, so isn't an extension of
+    //! Ascii: for example, 0x61 is U+03B1 GREEK SMALL LETTER ALPHA.  Some of
+    //! its bytes map to Apple's private use code points, and a few to
+    //! sequences with a private use hint, such as 0xE2, which is U+00AE
+    //! U+F87F, a sans-serif registered sign.
 
     // Generated by:
-    // `encoding_tables/single_byte/generate_ascii_ext_tables.py`.
-    include!("generated/single_byte/windows-cp1256_tables.rs.inc");
+    // `encoding_tables/single_byte/generate_full_tables.py`.
+    include!("generated/single_byte/mac-symbol_tables.rs.inc");
 }
 
-pub mod windows_1257 {
-    //! Windows code page 1257.
+pub mod mac_thai {
+    //! Mac OS Thai.
+    //!
+    //! Some of the tone marks and vowels appear more than once, in variant
+    //! positions, which map to sequences of the Thai character and one of
+    //! Apple's private use hints.
+
+    // Generated by:
+    // `encoding_tables/single_byte/generate_full_tables.py`.
+    include!("generated/single_byte/mac-thai_tables.rs.inc");
+}
+
+pub mod mac_turkish {
+    //! Mac OS Turkish.
+
+    // Generated by:
+    // `encoding_tables/single_byte/generate_ascii_ext_tables.py`.
+    include!("generated/single_byte/mac-turkish_tables.rs.inc");
+}
+
+pub mod mac_ukrainian {
+    //! Mac OS Ukrainian.
+    //!
+    //! This is the same as `mac_cyrillic`, except that it predates the euro
+    //! sign, so 0xFF is U+00A4 CURRENCY SIGN.
+
+    // Generated by:
+    // `encoding_tables/single_byte/generate_ascii_ext_tables.py`.
+    include!("generated/single_byte/mac-ukrainian_tables.rs.inc");
+}
+
+pub mod nextstep {
+    //! NeXTSTEP, the encoding of NeXT computers.
+    //!
+    //! Unlike most Ascii extensions, it has no C1 control codes: 0x80 is
+    //! U+00A0 NO-BREAK SPACE, followed by the accented capitals.
+
+    // Generated by:
+    // `encoding_tables/single_byte/generate_ascii_ext_tables.py`.
+    include!("generated/single_byte/nextstep_tables.rs.inc");
+}
+
+pub mod pdf_doc {
+    //! PDFDocEncoding, the single byte encoding of PDF text strings.
+    //!
+    //! This is Latin-1 with spacing diacritics in place of the C0 control
+    //! bytes 0x18-0x1F, and typographic punctuation and a few letters in
+    //! 0x80-0x9E.  0x7F, 0x9F, and 0xAD are undefined.  See `pdf_text_string`
+    //! for the text strings themselves, which can also be UTF-16 or UTF-8.
+
+    // Generated by:
+    // `encoding_tables/single_byte/generate_full_tables.py`.
+    include!("generated/single_byte/pdf-doc_tables.rs.inc");
+}
+
+pub mod petscii {
+    //! PETSCII, the character set of Commodore's 8-bit computers, such as
+    //! the Commodore 64.
+    //!
+    //! It has two character sets, which the computer switches between on
+    //! screen: the unshifted set, with capitals and graphics, and the
+    //! shifted set, with lowercase letters and capitals.  Text doesn't record
+    //! which one it was written in, so it's given as a `Mode`.  The control
+    //! codes 0x0E and 0x8E, which switch sets, decode to U+000E and U+008E
+    //! like the other control codes, and don't change the mode.
+    //!
+    //! Many of the graphics map to the Symbols for Legacy Computing block,
+    //! such as 0xC2, U+1FB72 VERTICAL ONE EIGHTH BLOCK-4.  0x60-0x7F,
+    //! 0xE0-0xFE, and 0xFF repeat 0xC0-0xDF, 0xA0-0xBE, and 0xDE when
+    //! decoding, and the encoder uses the latter.
+
+    use {DecodeResult, EncodeResult};
+
+    // Generated by:
+    // `encoding_tables/single_byte/generate_full_tables.py`.
+    // Contains the UNSHIFTED_ and SHIFTED_ decode and encode tables.
+    include!("generated/single_byte/petscii_tables.rs.inc");
+
+    /// Which of PETSCII's character sets text is in.
+    #[derive(Debug, Copy, Clone, PartialEq, Eq)]
+    pub enum Mode {
+        /// Capitals in 0x41-0x5A and graphics in 0xC1-0xDA.  This is the set
+        /// that the computer starts in.
+        Unshifted,
+        /// Lowercase letters in 0x41-0x5A and capitals in 0xC1-0xDA.
+        Shifted,
+    }
+
+    pub fn decode_to_str<'a>(
+        input: &[u8],
+        out_buffer: &'a mut [u8],
+        mode: Mode,
+    ) -> DecodeResult<'a> {
+        let table = match mode {
+            Mode::Unshifted => &UNSHIFTED_DECODE_TABLE,
+            Mode::Shifted => &SHIFTED_DECODE_TABLE,
+        };
+        super::full_decode_to_str(table, false, input, out_buffer)
+    }
+
+    pub fn encode_from_str<'a>(
+        input: &str,
+        out_buffer: &'a mut [u8],
+        mode: Mode,
+    ) -> EncodeResult<'a> {
+        let table: &[(char, u8)] = match mode {
+            Mode::Unshifted => &UNSHIFTED_ENCODE_TABLE,
+            Mode::Shifted => &SHIFTED_ENCODE_TABLE,
+        };
+        super::full_encode_from_str(table, false, input, out_buffer)
+    }
+}
+
+pub mod pt154 {
+    //! PT154, also known as PTCP154.
+    //!
+    //! A Cyrillic encoding for Kazakh and other Central Asian languages, based on
+    //! Windows code page 1251.
+
+    // Generated by:
+    // `encoding_tables/single_byte/generate_ascii_ext_tables.py`.
+    include!("generated/single_byte/pt154_tables.rs.inc");
+}
+
+pub mod tcvn3 {
+    //! TCVN3 (TCVN 5712:1993 VN3), also known as ABC.
+    //!
+    //! The most common Vietnamese font encoding before Unicode.  It only has
+    //! lowercase letters with tone marks: the capitals with tone marks were
+    //! in a separate capitals font, and can't be encoded.
+
+    // Generated by:
+    // `encoding_tables/single_byte/generate_ascii_ext_tables.py`.
+    include!("generated/single_byte/tcvn3_tables.rs.inc");
+}
+
+pub mod viscii {
+    //! VISCII, the Vietnamese encoding from RFC 1456.
+    //!
+    //! All of the precomposed Vietnamese letters have a byte, including six
+    //! of the C0 control bytes.
+
+    // Generated by:
+    // `encoding_tables/single_byte/generate_full_tables.py`.
+    include!("generated/single_byte/viscii_tables.rs.inc");
+}
+
+pub mod windows_874 {
+    //! Windows code page 874.
+
+    // Generated by:
+    // `encoding_tables/single_byte/generate_ascii_ext_tables.py`.
+    include!("generated/single_byte/windows-cp874_tables.rs.inc");
+}
+
+pub mod windows_1250 {
+    //! Windows code page 1250.
+
+    // Generated by:
+    // `encoding_tables/single_byte/generate_ascii_ext_tables.py`.
+    include!("generated/single_byte/windows-cp1250_tables.rs.inc");
+}
+
+pub mod windows_1251 {
+    //! Windows code page 1251.
+
+    // Generated by:
+    // `encoding_tables/single_byte/generate_ascii_ext_tables.py`.
+    include!("generated/single_byte/windows-cp1251_tables.rs.inc");
+}
+
+pub mod windows_1252 {
+    //! Windows code page 1252.
+
+    // Generated by:
+    // `encoding_tables/single_byte/generate_ascii_ext_tables.py`.
+    include!("generated/single_byte/windows-cp1252_tables.rs.inc");
+}
+
+pub mod windows_1253 {
+    //! Windows code page 1253.
+
+    // Generated by:
+    // `encoding_tables/single_byte/generate_ascii_ext_tables.py`.
+    include!("generated/single_byte/windows-cp1253_tables.rs.inc");
+}
+
+pub mod windows_1254 {
+    //! Windows code page 1254.
+
+    // Generated by:
+    // `encoding_tables/single_byte/generate_ascii_ext_tables.py`.
+    include!("generated/single_byte/windows-cp1254_tables.rs.inc");
+}
+
+pub mod windows_1255 {
+    //! Windows code page 1255.
+
+    // Generated by:
+    // `encoding_tables/single_byte/generate_ascii_ext_tables.py`.
+    include!("generated/single_byte/windows-cp1255_tables.rs.inc");
+}
+
+pub mod windows_1256 {
+    //! Windows code page 1256.
+
+    // Generated by:
+    // `encoding_tables/single_byte/generate_ascii_ext_tables.py`.
+    include!("generated/single_byte/windows-cp1256_tables.rs.inc");
+}
+
+pub mod windows_1257 {
+    //! Windows code page 1257.
 
     // Generated by:
     // `encoding_tables/single_byte/generate_ascii_ext_tables.py`.
@@ -258,12 +950,34 @@ pub mod windows_1257 {
 
 pub mod windows_1258 {
     //! Windows code page 1258.
+    //!
+    //! Vietnamese in this encoding is normally written with combining tone
+    //! marks, since only some of the letters with tone marks have a byte.
+    //! The encoder encodes the remaining precomposed letters as a letter
+    //! followed by a tone mark, and `decode_to_str_composed()` reverses that.
 
     // Generated by:
     // `encoding_tables/single_byte/generate_ascii_ext_tables.py`.
     include!("generated/single_byte/windows-cp1258_tables.rs.inc");
 }
 
+pub mod zapf_dingbats {
+    //! ITC Zapf Dingbats, the built-in encoding of the ZapfDingbats font.
+    //!
+    //! Most bytes map to the Dingbats block, such as 0x21, U+2701 UPPER
+    //! BLADE SCISSORS.  As in Adobe's table, the ornamental parentheses and
+    //! brackets, 0x80-0x8D, map to Adobe's private use code points
+    //! U+F8D7-U+F8E4, rather than to U+2768-U+2775, which Unicode added
+    //! later.
+    //!
+    //! As in Adobe's table, 0x20 also encodes from U+00A0 NO-BREAK SPACE,
+    //! although it decodes to U+0020 SPACE.
+
+    // Generated by:
+    // `encoding_tables/single_byte/generate_full_tables.py`.
+    include!("generated/single_byte/zapf-dingbats_tables.rs.inc");
+}
+
 /// This is shared among the single byte encoders that are strict extensions
 /// of ascii.  It is shallowly wrapped in each of their modules.
 #[inline]
@@ -356,209 +1070,1027 @@ fn ascii_ext_decode_to_str<'a>(
     ))
 }
 
-//===========================================================================
-
-// Most of the testing below is done with iso-8859-7, since it has a few
-// undefined characters, allowing us to test handling of those.
-#[cfg(test)]
-mod tests {
-    use super::{iso_8859_7::*, *};
-    use {DecodeError, EncodeError};
-
-    #[test]
-    fn encode_01() {
-        let text = "Hello world!";
-        let mut buf = [0u8; 0];
-        let (encoded, consumed_count) = encode_from_str(text, &mut buf).unwrap();
-        assert_eq!(consumed_count, 0);
-        assert_eq!(encoded, &[]);
+/// This is shared among the single byte encoders that are extensions of
+/// ascii with combining tone marks.  It is shallowly wrapped in each of their
+/// modules.
+///
+/// `decompose` maps the precomposed code points that have no byte of their
+/// own to a base byte and a tone mark byte, and is sorted by code point.
+#[inline]
+fn ascii_ext_decompose_encode_from_str<'a>(
+    table: &[(char, u8)],
+    decompose: &[(char, u8, u8)],
+    input: &str,
+    output: &'a mut [u8],
+) -> EncodeResult<'a> {
+    // Do the encode.
+    let mut input_i = 0;
+    let mut output_i = 0;
+    for (offset, c) in input.char_indices() {
+        if output_i >= output.len() {
+            break;
+        }
+        if c as u32 <= 127 {
+            output[output_i] = c as u8;
+            output_i += 1;
+            input_i = offset + 1;
+        } else if let Ok(i) = table.binary_search_by_key(&c, |x| x.0) {
+            output[output_i] = table[i].1;
+            output_i += 1;
+            input_i = offset + 1;
+        } else if let Ok(i) = decompose.binary_search_by_key(&c, |x| x.0) {
+            if (output_i + 1) < output.len() {
+                output[output_i] = decompose[i].1;
+                output[output_i + 1] = decompose[i].2;
+                output_i += 2;
+                input_i = offset + 1;
+            } else {
+                break;
+            }
+        } else {
+            return Err(EncodeError {
+                character: c,
+                error_range: (offset, offset + c.len_utf8()),
+                output_bytes_written: output_i,
+            });
+        }
     }
 
-    #[test]
-    fn encode_02() {
-        let text = "Hello world!";
-        let mut buf = [0u8; 1];
-        let (encoded, consumed_count) = encode_from_str(text, &mut buf).unwrap();
-        assert_eq!(consumed_count, 1);
-        assert_eq!(encoded, "H".as_bytes());
+    // Calculate how much of the input was consumed.
+    if input_i > input.len() {
+        input_i = input.len();
+    } else {
+        while !input.is_char_boundary(input_i) {
+            input_i += 1;
+        }
     }
 
-    #[test]
-    fn encode_03() {
-        let text = "Hello world!";
-        let mut buf = [0u8; 2];
-        let (encoded, consumed_count) = encode_from_str(text, &mut buf).unwrap();
-        assert_eq!(consumed_count, 2);
-        assert_eq!(encoded, "He".as_bytes());
-    }
+    Ok((&output[..output_i], input_i))
+}
 
-    #[test]
-    fn encode_04() {
-        let text = "Hello world!";
-        let mut buf = [0u8; 64];
-        let (encoded, consumed_count) = encode_from_str(text, &mut buf).unwrap();
-        assert_eq!(consumed_count, 12);
-        assert_eq!(encoded, "Hello world!".as_bytes());
-    }
+/// This is shared among the single byte decoders that are extensions of
+/// ascii with combining tone marks.  It is shallowly wrapped in each of their
+/// modules.
+///
+/// `compose` maps a base byte and a tone mark byte to the precomposed code
+/// point, and is sorted by the bytes.
+#[inline]
+fn ascii_ext_compose_decode_to_str<'a>(
+    table: &[char; 128],
+    compose: &[(u8, u8, char)],
+    input: &[u8],
+    output: &'a mut [u8],
+    is_end: bool,
+) -> DecodeResult<'a> {
+    let mut input_i = 0;
+    let mut output_i = 0;
+    let mut buf = [0u8; 4];
+    while input_i < input.len() {
+        let byte = input[input_i];
+
+        // Check for a base followed by a tone mark first.
+        let composed = if let Some(&next) = input.get(input_i + 1) {
+            compose
+                .binary_search_by_key(&(byte, next), |x| (x.0, x.1))
+                .ok()
+                .map(|i| compose[i].2)
+        } else if !is_end && compose.iter().any(|x| x.0 == byte) {
+            // A tone mark may follow in the next input, so wait for it.
+            break;
+        } else {
+            None
+        };
 
-    #[test]
-    fn encode_05() {
-        let text = "Hello world!こ";
-        let mut buf = [0u8; 12];
-        let (encoded, consumed_count) = encode_from_str(text, &mut buf).unwrap();
-        assert_eq!(consumed_count, 12);
-        assert_eq!(encoded, "Hello world!".as_bytes());
-    }
+        let (code, input_consumed) = if let Some(c) = composed {
+            (c, 2)
+        } else if byte < 0x80 {
+            (byte as char, 1)
+        } else {
+            match table[byte as usize - 0x80] {
+                '�' => {
+                    // Error: undefined byte.
+                    return Err(DecodeError {
+                        cause: DecodeErrorCause::InvalidData,
+                        error_range: (input_i, input_i + 1),
+                        output_bytes_written: output_i,
+                    });
+                }
+                code => (code, 1),
+            }
+        };
 
-    #[test]
-    fn decode_01() {
-        let data = [
-            0x48, 0x65, 0x6C, 0x6C, 0x6F, 0x20, 0x77, 0x6F, 0x72, 0x6C, 0x64, 0x21,
-        ]; // "Hello world!"
-        let mut buf = [0u8; 0];
-        let (decoded, consumed_count) = decode_to_str(&data, &mut buf).unwrap();
-        assert_eq!(consumed_count, 0);
-        assert_eq!(decoded, "");
+        // Encode to utf8
+        let s = code.encode_utf8(&mut buf);
+        if (output_i + s.len()) > output.len() {
+            break;
+        }
+        output[output_i..(output_i + s.len())].copy_from_slice(s.as_bytes());
+        input_i += input_consumed;
+        output_i += s.len();
     }
 
-    #[test]
-    fn decode_02() {
-        let data = [
-            0x48, 0x65, 0x6C, 0x6C, 0x6F, 0x20, 0x77, 0x6F, 0x72, 0x6C, 0x64, 0x21,
-        ]; // "Hello world!"
-        let mut buf = [0u8; 1];
-        let (decoded, consumed_count) = decode_to_str(&data, &mut buf).unwrap();
-        assert_eq!(consumed_count, 1);
-        assert_eq!(decoded, "H");
+    Ok((
+        unsafe { core::str::from_utf8_unchecked(&output[..output_i]) },
+        input_i,
+    ))
+}
+
+/// This is shared among the single byte encoders that aren't extensions of
+/// ascii.  It is shallowly wrapped in each of their modules.
+///
+/// If `swap_nl_lf` is true, EBCDIC NL (0x15) and LF (0x25) are swapped.
+#[inline]
+fn full_encode_from_str<'a>(
+    table: &[(char, u8)],
+    swap_nl_lf: bool,
+    input: &str,
+    output: &'a mut [u8],
+) -> EncodeResult<'a> {
+    // Do the encode.
+    let mut input_i = 0;
+    let mut output_i = 0;
+    for (offset, c) in input.char_indices() {
+        if output_i >= output.len() {
+            break;
+        }
+        if let Ok(i) = table.binary_search_by_key(&c, |x| x.0) {
+            let byte = table[i].1;
+            output[output_i] = if swap_nl_lf {
+                ebcdic_swap_nl_lf(byte)
+            } else {
+                byte
+            };
+            output_i += 1;
+            input_i = offset + 1;
+        } else {
+            return Err(EncodeError {
+                character: c,
+                error_range: (offset, offset + c.len_utf8()),
+                output_bytes_written: output_i,
+            });
+        }
+    }
+
+    // Calculate how much of the input was consumed.
+    if input_i > input.len() {
+        input_i = input.len();
+    } else {
+        while !input.is_char_boundary(input_i) {
+            input_i += 1;
+        }
+    }
+
+    Ok((&output[..output_i], input_i))
+}
+
+/// This is shared among the single byte decoders that aren't extensions of
+/// ascii.  It is shallowly wrapped in each of their modules.
+///
+/// If `swap_nl_lf` is true, EBCDIC NL (0x15) and LF (0x25) are swapped.
+#[inline]
+fn full_decode_to_str<'a>(
+    table: &[char; 256],
+    swap_nl_lf: bool,
+    input: &[u8],
+    output: &'a mut [u8],
+) -> DecodeResult<'a> {
+    let mut input_i = 0;
+    let mut output_i = 0;
+    for &byte in input.iter() {
+        let byte = if swap_nl_lf {
+            ebcdic_swap_nl_lf(byte)
+        } else {
+            byte
+        };
+        let code = table[byte as usize];
+        if code == '�' {
+            // Error: undefined byte.
+            return Err(DecodeError {
+                cause: DecodeErrorCause::InvalidData,
+                error_range: (input_i, input_i + 1),
+                output_bytes_written: output_i,
+            });
+        }
+        // Encode to utf8
+        let mut buf = [0u8; 4];
+        let s = code.encode_utf8(&mut buf);
+        if (output_i + s.len()) > output.len() {
+            break;
+        }
+        output[output_i..(output_i + s.len())].copy_from_slice(s.as_bytes());
+        input_i += 1;
+        output_i += s.len();
+    }
+
+    Ok((
+        unsafe { core::str::from_utf8_unchecked(&output[..output_i]) },
+        input_i,
+    ))
+}
+
+/// This is shared among the single byte encoders that have bytes that map
+/// to sequences of more than one `char`.  It is shallowly wrapped in each of
+/// their modules.
+///
+/// The sequences must be sorted longest first, so that the longest match is
+/// used.
+#[inline]
+fn seq_encode_from_str<'a>(
+    table: &[(char, u8)],
+    sequences: &[(&str, u8)],
+    input: &str,
+    output: &'a mut [u8],
+    is_end: bool,
+) -> EncodeResult<'a> {
+    // Do the encode.
+    let mut input_i = 0;
+    let mut output_i = 0;
+    let mut itr = input.char_indices();
+    'outer: while let Some((offset, c)) = itr.next() {
+        if output_i >= output.len() {
+            break;
+        }
+
+        // Check for sequences first.
+        let rest = &input[offset..];
+        for &(seq, byte) in sequences {
+            if rest.starts_with(seq) {
+                output[output_i] = byte;
+                output_i += 1;
+                input_i = offset + seq.len();
+                for _ in 1..seq.chars().count() {
+                    itr.next();
+                }
+                continue 'outer;
+            } else if !is_end && seq.starts_with(rest) {
+                // The rest of the input may be the start of this
+                // sequence, so wait for more input.
+                break 'outer;
+            }
+        }
+
+        if let Ok(i) = table.binary_search_by_key(&c, |x| x.0) {
+            output[output_i] = table[i].1;
+            output_i += 1;
+            input_i = offset + 1;
+        } else {
+            return Err(EncodeError {
+                character: c,
+                error_range: (offset, offset + c.len_utf8()),
+                output_bytes_written: output_i,
+            });
+        }
+    }
+
+    // Calculate how much of the input was consumed.
+    if input_i > input.len() {
+        input_i = input.len();
+    } else {
+        while !input.is_char_boundary(input_i) {
+            input_i += 1;
+        }
+    }
+
+    Ok((&output[..output_i], input_i))
+}
+
+/// This is shared among the single byte decoders that have bytes that map
+/// to sequences of more than one `char`.  It is shallowly wrapped in each of
+/// their modules.
+#[inline]
+fn seq_decode_to_str<'a>(
+    table: &[char; 256],
+    sequences: &[(&str, u8)],
+    input: &[u8],
+    output: &'a mut [u8],
+) -> DecodeResult<'a> {
+    let mut input_i = 0;
+    let mut output_i = 0;
+    let mut buf = [0u8; 4];
+    for &byte in input.iter() {
+        let s = match table[byte as usize] {
+            '�' => {
+                if let Some(&(seq, _)) = sequences.iter().find(|x| x.1 == byte) {
+                    seq
+                } else {
+                    // Error: undefined byte.
+                    return Err(DecodeError {
+                        cause: DecodeErrorCause::InvalidData,
+                        error_range: (input_i, input_i + 1),
+                        output_bytes_written: output_i,
+                    });
+                }
+            }
+            code => code.encode_utf8(&mut buf),
+        };
+        if (output_i + s.len()) > output.len() {
+            break;
+        }
+        output[output_i..(output_i + s.len())].copy_from_slice(s.as_bytes());
+        input_i += 1;
+        output_i += s.len();
+    }
+
+    Ok((
+        unsafe { core::str::from_utf8_unchecked(&output[..output_i]) },
+        input_i,
+    ))
+}
+
+#[inline]
+fn ebcdic_swap_nl_lf(byte: u8) -> u8 {
+    match byte {
+        0x15 => 0x25,
+        0x25 => 0x15,
+        _ => byte,
+    }
+}
+
+//===========================================================================
+
+// Most of the testing below is done with iso-8859-7, since it has a few
+// undefined characters, allowing us to test handling of those.
+#[cfg(test)]
+mod tests {
+    use super::{iso_8859_7::*, *};
+    use {DecodeError, EncodeError};
+
+    #[test]
+    fn encode_01() {
+        let text = "Hello world!";
+        let mut buf = [0u8; 0];
+        let (encoded, consumed_count) = encode_from_str(text, &mut buf).unwrap();
+        assert_eq!(consumed_count, 0);
+        assert_eq!(encoded, &[]);
+    }
+
+    #[test]
+    fn encode_02() {
+        let text = "Hello world!";
+        let mut buf = [0u8; 1];
+        let (encoded, consumed_count) = encode_from_str(text, &mut buf).unwrap();
+        assert_eq!(consumed_count, 1);
+        assert_eq!(encoded, "H".as_bytes());
+    }
+
+    #[test]
+    fn encode_03() {
+        let text = "Hello world!";
+        let mut buf = [0u8; 2];
+        let (encoded, consumed_count) = encode_from_str(text, &mut buf).unwrap();
+        assert_eq!(consumed_count, 2);
+        assert_eq!(encoded, "He".as_bytes());
+    }
+
+    #[test]
+    fn encode_04() {
+        let text = "Hello world!";
+        let mut buf = [0u8; 64];
+        let (encoded, consumed_count) = encode_from_str(text, &mut buf).unwrap();
+        assert_eq!(consumed_count, 12);
+        assert_eq!(encoded, "Hello world!".as_bytes());
+    }
+
+    #[test]
+    fn encode_05() {
+        let text = "Hello world!こ";
+        let mut buf = [0u8; 12];
+        let (encoded, consumed_count) = encode_from_str(text, &mut buf).unwrap();
+        assert_eq!(consumed_count, 12);
+        assert_eq!(encoded, "Hello world!".as_bytes());
+    }
+
+    #[test]
+    fn decode_01() {
+        let data = [
+            0x48, 0x65, 0x6C, 0x6C, 0x6F, 0x20, 0x77, 0x6F, 0x72, 0x6C, 0x64, 0x21,
+        ]; // "Hello world!"
+        let mut buf = [0u8; 0];
+        let (decoded, consumed_count) = decode_to_str(&data, &mut buf).unwrap();
+        assert_eq!(consumed_count, 0);
+        assert_eq!(decoded, "");
+    }
+
+    #[test]
+    fn decode_02() {
+        let data = [
+            0x48, 0x65, 0x6C, 0x6C, 0x6F, 0x20, 0x77, 0x6F, 0x72, 0x6C, 0x64, 0x21,
+        ]; // "Hello world!"
+        let mut buf = [0u8; 1];
+        let (decoded, consumed_count) = decode_to_str(&data, &mut buf).unwrap();
+        assert_eq!(consumed_count, 1);
+        assert_eq!(decoded, "H");
+    }
+
+    #[test]
+    fn decode_03() {
+        let data = [
+            0x48, 0x65, 0x6C, 0x6C, 0x6F, 0x20, 0x77, 0x6F, 0x72, 0x6C, 0x64, 0x21,
+        ]; // "Hello world!"
+        let mut buf = [0u8; 2];
+        let (decoded, consumed_count) = decode_to_str(&data, &mut buf).unwrap();
+        assert_eq!(consumed_count, 2);
+        assert_eq!(decoded, "He");
+    }
+
+    #[test]
+    fn decode_04() {
+        let data = [
+            0x48, 0x65, 0x6C, 0x6C, 0x6F, 0x20, 0x77, 0x6F, 0x72, 0x6C, 0x64, 0x21,
+        ]; // "Hello world!"
+        let mut buf = [0u8; 64];
+        let (decoded, consumed_count) = decode_to_str(&data, &mut buf).unwrap();
+        assert_eq!(consumed_count, 12);
+        assert_eq!(decoded, "Hello world!");
+    }
+
+    #[test]
+    fn decode_05() {
+        let data = [
+            0xC1, 0xC2, 0xC3, 0xC4, 0xC5, 0xC6, 0xC7, 0xC8, 0xC9, 0xCA, 0xCB, 0xCC, 0xCD, 0xCE,
+            0xCF, 0xD0, 0xD1, 0xD3, 0xD4, 0xD5, 0xD6, 0xD7, 0xD8, 0xD9,
+        ]; // "ΑΒΓΔΕΖΗΘΙΚΛΜΝΞΟΠΡΣΤΥΦΧΨΩ"
+        let mut buf = [0u8; 128];
+        let (decoded, consumed_count) = decode_to_str(&data, &mut buf).unwrap();
+        assert_eq!(consumed_count, 24);
+        assert_eq!(decoded, "ΑΒΓΔΕΖΗΘΙΚΛΜΝΞΟΠΡΣΤΥΦΧΨΩ");
+    }
+
+    #[test]
+    fn encode_error_01() {
+        let text = "こello world!";
+        let mut buf = [0u8; 64];
+        assert_eq!(
+            encode_from_str(text, &mut buf),
+            Err(EncodeError {
+                character: 'こ',
+                error_range: (0, 3),
+                output_bytes_written: 0,
+            })
+        );
+    }
+
+    #[test]
+    fn encode_error_02() {
+        let text = "\u{00C0}ello world!";
+        let mut buf = [0u8; 64];
+        assert_eq!(
+            encode_from_str(text, &mut buf),
+            Err(EncodeError {
+                character: '\u{00C0}',
+                error_range: (0, 2),
+                output_bytes_written: 0,
+            })
+        );
+    }
+
+    #[test]
+    fn encode_error_03() {
+        let text = "Hこllo world!";
+        let mut buf = [0u8; 64];
+        assert_eq!(
+            encode_from_str(text, &mut buf),
+            Err(EncodeError {
+                character: 'こ',
+                error_range: (1, 4),
+                output_bytes_written: 1,
+            })
+        );
+    }
+
+    #[test]
+    fn encode_error_04() {
+        let text = "H\u{00C0}llo world!";
+        let mut buf = [0u8; 64];
+        assert_eq!(
+            encode_from_str(text, &mut buf),
+            Err(EncodeError {
+                character: '\u{00C0}',
+                error_range: (1, 3),
+                output_bytes_written: 1,
+            })
+        );
+    }
+
+    #[test]
+    fn encode_error_05() {
+        let text = "Heこlo world!";
+        let mut buf = [0u8; 3];
+        assert_eq!(
+            encode_from_str(text, &mut buf),
+            Err(EncodeError {
+                character: 'こ',
+                error_range: (2, 5),
+                output_bytes_written: 2,
+            })
+        );
+    }
+
+    #[test]
+    fn encode_error_06() {
+        let text = "He\u{00C0}lo world!";
+        let mut buf = [0u8; 3];
+        assert_eq!(
+            encode_from_str(text, &mut buf),
+            Err(EncodeError {
+                character: '\u{00C0}',
+                error_range: (2, 4),
+                output_bytes_written: 2,
+            })
+        );
+    }
+
+    #[test]
+    fn decode_error_01() {
+        let data = [
+            0x48, 0xAE, 0x6C, 0x6C, 0x6F, 0x20, 0x77, 0x6F, 0x72, 0x6C, 0x64, 0x21,
+        ]; // "Hello world!" with an error on the second byte (undefined byte).
+        let mut buf = [0u8; 64];
+        let error = decode_to_str(&data, &mut buf);
+        assert_eq!(
+            error,
+            Err(DecodeError {
+                cause: DecodeErrorCause::InvalidData,
+                error_range: (1, 2),
+                output_bytes_written: 1,
+            })
+        );
+    }
+
+    #[test]
+    fn decode_error_02() {
+        let data = [
+            0x48, 0xD2, 0x6C, 0x6C, 0x6F, 0x20, 0x77, 0x6F, 0x72, 0x6C, 0x64, 0x21,
+        ]; // "Hello world!" with an error on the second byte (undefined byte).
+        let mut buf = [0u8; 64];
+        let error = decode_to_str(&data, &mut buf);
+        assert_eq!(
+            error,
+            Err(DecodeError {
+                cause: DecodeErrorCause::InvalidData,
+                error_range: (1, 2),
+                output_bytes_written: 1,
+            })
+        );
+    }
+
+    #[test]
+    fn decode_error_03() {
+        let data = [
+            0x48, 0xFF, 0x6C, 0x6C, 0x6F, 0x20, 0x77, 0x6F, 0x72, 0x6C, 0x64, 0x21,
+        ]; // "Hello world!" with an error on the second byte (undefined byte).
+        let mut buf = [0u8; 64];
+        let error = decode_to_str(&data, &mut buf);
+        assert_eq!(
+            error,
+            Err(DecodeError {
+                cause: DecodeErrorCause::InvalidData,
+                error_range: (1, 2),
+                output_bytes_written: 1,
+            })
+        );
+    }
+
+    #[test]
+    fn ascii_encode_error_01() {
+        let text = "\u{0080}ello world!";
+        let mut buf = [0u8; 64];
+        assert_eq!(
+            ascii::encode_from_str(text, &mut buf),
+            Err(EncodeError {
+                character: '\u{0080}',
+                error_range: (0, 2),
+                output_bytes_written: 0,
+            })
+        );
+    }
+
+    #[test]
+    fn ascii_encode_error_02() {
+        let text = "\u{00FF}ello world!";
+        let mut buf = [0u8; 64];
+        assert_eq!(
+            ascii::encode_from_str(text, &mut buf),
+            Err(EncodeError {
+                character: '\u{00FF}',
+                error_range: (0, 2),
+                output_bytes_written: 0,
+            })
+        );
+    }
+
+    #[test]
+    fn ascii_decode_error_01() {
+        let data = [
+            0x48, 0x80, 0x6C, 0x6C, 0x6F, 0x20, 0x77, 0x6F, 0x72, 0x6C, 0x64, 0x21,
+        ]; // "Hello world!" with an error on the second byte (undefined byte).
+        let mut buf = [0u8; 64];
+        let error = ascii::decode_to_str(&data, &mut buf);
+        assert_eq!(
+            error,
+            Err(DecodeError {
+                cause: DecodeErrorCause::InvalidData,
+                error_range: (1, 2),
+                output_bytes_written: 1,
+            })
+        );
+    }
+
+    #[test]
+    fn ascii_decode_error_02() {
+        let data = [
+            0x48, 0xFF, 0x6C, 0x6C, 0x6F, 0x20, 0x77, 0x6F, 0x72, 0x6C, 0x64, 0x21,
+        ]; // "Hello world!" with an error on the second byte (undefined byte).
+        let mut buf = [0u8; 64];
+        let error = ascii::decode_to_str(&data, &mut buf);
+        assert_eq!(
+            error,
+            Err(DecodeError {
+                cause: DecodeErrorCause::InvalidData,
+                error_range: (1, 2),
+                output_bytes_written: 1,
+            })
+        );
+    }
+
+    #[test]
+    fn encode_latin1_01() {
+        let mut buf = [0u8; 64];
+        // Make sure all the allowed code points encode correctly.
+        for i in 0..=255u8 {
+            let (encoded, consumed_count) =
+                iso_8859_1::encode_from_str((i as char).encode_utf8(&mut [0u8; 4]), &mut buf)
+                    .unwrap();
+            assert_eq!(consumed_count, (i as char).len_utf8());
+            assert_eq!(encoded, &[i]);
+        }
+    }
+
+    #[test]
+    fn decode_latin1_01() {
+        let mut buf = [0u8; 64];
+        // Make sure all the allowed code points encode correctly.
+        for i in 0..=255u8 {
+            let (encoded, consumed_count) = iso_8859_1::decode_to_str(&[i], &mut buf).unwrap();
+            assert_eq!(consumed_count, 1);
+            assert_eq!(encoded, (i as char).encode_utf8(&mut [0u8; 4]));
+        }
+    }
+
+    #[test]
+    fn encode_ebcdic_01() {
+        let mut buf = [0u8; 64];
+        let (encoded, consumed_count) = ibm_037::encode_from_str("Hello\n[]", &mut buf).unwrap();
+        assert_eq!(consumed_count, 8);
+        assert_eq!(encoded, &[0xC8, 0x85, 0x93, 0x93, 0x96, 0x25, 0xBA, 0xBB]);
+
+        let (encoded, _) = ibm_1047::encode_from_str("[]", &mut buf).unwrap();
+        assert_eq!(encoded, &[0xAD, 0xBD]);
+
+        let (encoded, _) = ibm_1140::encode_from_str("\u{20AC}", &mut buf).unwrap();
+        assert_eq!(encoded, &[0x9F]);
+    }
+
+    #[test]
+    fn encode_ebcdic_02() {
+        let mut buf = [0u8; 64];
+        let (encoded, consumed_count) =
+            ibm_1047::encode_from_str_swap_nl_lf("a\nb\u{0085}", &mut buf).unwrap();
+        assert_eq!(consumed_count, 5);
+        assert_eq!(encoded, &[0x81, 0x15, 0x82, 0x25]);
+    }
+
+    #[test]
+    fn encode_ebcdic_error_01() {
+        let mut buf = [0u8; 64];
+        assert_eq!(
+            ibm_037::encode_from_str("Hこ", &mut buf),
+            Err(EncodeError {
+                character: 'こ',
+                error_range: (1, 4),
+                output_bytes_written: 1,
+            })
+        );
+    }
+
+    #[test]
+    fn decode_ebcdic_01() {
+        let mut buf = [0u8; 64];
+        let data = [0xC8, 0x85, 0x93, 0x93, 0x96, 0x15, 0x25];
+        let (decoded, consumed_count) = ibm_037::decode_to_str(&data, &mut buf).unwrap();
+        assert_eq!(consumed_count, 7);
+        assert_eq!(decoded, "Hello\u{0085}\n");
+
+        let (decoded, consumed_count) = ibm_037::decode_to_str_swap_nl_lf(&data, &mut buf).unwrap();
+        assert_eq!(consumed_count, 7);
+        assert_eq!(decoded, "Hello\n\u{0085}");
+    }
+
+    #[test]
+    fn ebcdic_round_trip_01() {
+        let mut buf = [0u8; 64];
+        // All 256 bytes are defined, and round trip, with or without
+        // swapping NL and LF.
+        for i in 0..=255u8 {
+            let (decoded, _) = ibm_500::decode_to_str(&[i], &mut buf).unwrap();
+            let mut buf2 = [0u8; 4];
+            let (encoded, _) = ibm_500::encode_from_str(decoded, &mut buf2).unwrap();
+            assert_eq!(encoded, &[i]);
+
+            let (decoded, _) = ibm_500::decode_to_str_swap_nl_lf(&[i], &mut buf).unwrap();
+            let (encoded, _) = ibm_500::encode_from_str_swap_nl_lf(decoded, &mut buf2).unwrap();
+            assert_eq!(encoded, &[i]);
+        }
+    }
+
+    #[test]
+    fn encode_dos_01() {
+        let mut buf = [0u8; 64];
+        let (encoded, consumed_count) = ibm_437::encode_from_str("a\nÜ½░", &mut buf).unwrap();
+        assert_eq!(consumed_count, 9);
+        assert_eq!(encoded, &[0x61, 0x0A, 0x9A, 0xAB, 0xB0]);
+
+        let (encoded, _) = ibm_850::encode_from_str("Ø", &mut buf).unwrap();
+        assert_eq!(encoded, &[0x9D]);
+    }
+
+    #[test]
+    fn encode_dos_02() {
+        // Both the glyphs and the control characters encode in graphics
+        // mode.
+        let mut buf = [0u8; 64];
+        let (encoded, consumed_count) =
+            ibm_437::encode_from_str_graphics("☺\u{0001}♪\r\n⌂", &mut buf).unwrap();
+        assert_eq!(consumed_count, 12);
+        assert_eq!(encoded, &[0x01, 0x01, 0x0D, 0x0D, 0x0A, 0x7F]);
+    }
+
+    #[test]
+    fn encode_dos_error_01() {
+        let mut buf = [0u8; 64];
+        assert_eq!(
+            ibm_437::encode_from_str("a☺", &mut buf),
+            Err(EncodeError {
+                character: '☺',
+                error_range: (1, 4),
+                output_bytes_written: 1,
+            })
+        );
+        assert_eq!(
+            ibm_864::encode_from_str("%", &mut buf),
+            Err(EncodeError {
+                character: '%',
+                error_range: (0, 1),
+                output_bytes_written: 0,
+            })
+        );
+    }
+
+    #[test]
+    fn decode_dos_01() {
+        let mut buf = [0u8; 64];
+        let data = [0x00, 0x01, 0x0D, 0x0A, 0x7F, 0x9A];
+        let (decoded, consumed_count) = ibm_437::decode_to_str(&data, &mut buf).unwrap();
+        assert_eq!(consumed_count, 6);
+        assert_eq!(decoded, "\u{0000}\u{0001}\r\n\u{007F}Ü");
+
+        let (decoded, consumed_count) = ibm_437::decode_to_str_graphics(&data, &mut buf).unwrap();
+        assert_eq!(consumed_count, 6);
+        assert_eq!(decoded, "\u{0000}☺♪◙⌂Ü");
+
+        let (decoded, _) = ibm_864::decode_to_str(&[0x25], &mut buf).unwrap();
+        assert_eq!(decoded, "\u{066A}");
+    }
+
+    #[test]
+    fn decode_dos_error_01() {
+        let mut buf = [0u8; 64];
+        assert_eq!(
+            ibm_864::decode_to_str(&[0x41, 0xFF], &mut buf),
+            Err(DecodeError {
+                cause: DecodeErrorCause::InvalidData,
+                error_range: (1, 2),
+                output_bytes_written: 1,
+            })
+        );
     }
 
     #[test]
-    fn decode_03() {
-        let data = [
-            0x48, 0x65, 0x6C, 0x6C, 0x6F, 0x20, 0x77, 0x6F, 0x72, 0x6C, 0x64, 0x21,
-        ]; // "Hello world!"
-        let mut buf = [0u8; 2];
-        let (decoded, consumed_count) = decode_to_str(&data, &mut buf).unwrap();
-        assert_eq!(consumed_count, 2);
-        assert_eq!(decoded, "He");
+    fn encode_mac_01() {
+        let mut buf = [0u8; 64];
+        let (encoded, consumed_count) = mac_greek::encode_from_str("aΩπ", &mut buf).unwrap();
+        assert_eq!(consumed_count, 5);
+        assert_eq!(encoded, &[0x61, 0xBF, 0xF0]);
+
+        let (encoded, _) = mac_romanian::encode_from_str("Ș", &mut buf).unwrap();
+        assert_eq!(encoded, &[0xAF]);
     }
 
     #[test]
-    fn decode_04() {
-        let data = [
-            0x48, 0x65, 0x6C, 0x6C, 0x6F, 0x20, 0x77, 0x6F, 0x72, 0x6C, 0x64, 0x21,
-        ]; // "Hello world!"
+    fn encode_mac_02() {
+        // Right-left characters are only encoded from the override sequence.
         let mut buf = [0u8; 64];
-        let (decoded, consumed_count) = decode_to_str(&data, &mut buf).unwrap();
-        assert_eq!(consumed_count, 12);
-        assert_eq!(decoded, "Hello world!");
+        let text = "* \u{202E}*\u{202C}\u{202E} \u{202C}";
+        let (encoded, consumed_count) = mac_arabic::encode_from_str(text, &mut buf, true).unwrap();
+        assert_eq!(consumed_count, text.len());
+        assert_eq!(encoded, &[0x2A, 0x20, 0xAA, 0xA0]);
     }
 
     #[test]
-    fn decode_05() {
-        let data = [
-            0xC1, 0xC2, 0xC3, 0xC4, 0xC5, 0xC6, 0xC7, 0xC8, 0xC9, 0xCA, 0xCB, 0xCC, 0xCD, 0xCE,
-            0xCF, 0xD0, 0xD1, 0xD3, 0xD4, 0xD5, 0xD6, 0xD7, 0xD8, 0xD9,
-        ]; // "ΑΒΓΔΕΖΗΘΙΚΛΜΝΞΟΠΡΣΤΥΦΧΨΩ"
-        let mut buf = [0u8; 128];
-        let (decoded, consumed_count) = decode_to_str(&data, &mut buf).unwrap();
-        assert_eq!(consumed_count, 24);
-        assert_eq!(decoded, "ΑΒΓΔΕΖΗΘΙΚΛΜΝΞΟΠΡΣΤΥΦΧΨΩ");
+    fn encode_mac_03() {
+        // Sequences with private use hints, and the longest match wins.
+        let mut buf = [0u8; 64];
+        let text = "\u{05B8}\u{05B8}\u{F87F}\u{F86A}\u{05DC}\u{05B9}\u{05DC}";
+        let (encoded, consumed_count) = mac_hebrew::encode_from_str(text, &mut buf, true).unwrap();
+        assert_eq!(consumed_count, text.len());
+        assert_eq!(encoded, &[0xCB, 0xDE, 0xC0, 0xEC]);
     }
 
     #[test]
-    fn encode_error_01() {
-        let text = "こello world!";
+    fn encode_mac_04() {
+        // A possible start of a sequence at the end of the input waits for
+        // more input, unless it's the end.
+        let mut buf = [0u8; 64];
+        let (encoded, consumed_count) =
+            mac_hebrew::encode_from_str("a\u{05B8}", &mut buf, false).unwrap();
+        assert_eq!(consumed_count, 1);
+        assert_eq!(encoded, &[0x61]);
+
+        let (encoded, consumed_count) =
+            mac_hebrew::encode_from_str("a\u{05B8}", &mut buf, true).unwrap();
+        assert_eq!(consumed_count, 3);
+        assert_eq!(encoded, &[0x61, 0xCB]);
+
+        let (encoded, consumed_count) =
+            mac_arabic::encode_from_str("a\u{202E}(", &mut buf, false).unwrap();
+        assert_eq!(consumed_count, 1);
+        assert_eq!(encoded, &[0x61]);
+    }
+
+    #[test]
+    fn encode_mac_error_01() {
+        // A direction override that isn't part of a sequence.
         let mut buf = [0u8; 64];
         assert_eq!(
-            encode_from_str(text, &mut buf),
+            mac_arabic::encode_from_str("a\u{202E}ab\u{202C}", &mut buf, true),
             Err(EncodeError {
-                character: 'こ',
-                error_range: (0, 3),
-                output_bytes_written: 0,
+                character: '\u{202E}',
+                error_range: (1, 4),
+                output_bytes_written: 1,
             })
         );
     }
 
     #[test]
-    fn encode_error_02() {
-        let text = "\u{00C0}ello world!";
+    fn decode_mac_01() {
         let mut buf = [0u8; 64];
+        let (decoded, consumed_count) =
+            mac_hebrew::decode_to_str(&[0x28, 0xA8, 0xEC, 0xC0], &mut buf).unwrap();
+        assert_eq!(consumed_count, 4);
         assert_eq!(
-            encode_from_str(text, &mut buf),
-            Err(EncodeError {
-                character: '\u{00C0}',
-                error_range: (0, 2),
-                output_bytes_written: 0,
-            })
+            decoded,
+            "(\u{202E})\u{202C}\u{05DC}\u{F86A}\u{05DC}\u{05B9}"
         );
+
+        let (decoded, consumed_count) =
+            mac_symbol::decode_to_str(&[0x61, 0xE2, 0xF0], &mut buf).unwrap();
+        assert_eq!(consumed_count, 3);
+        assert_eq!(decoded, "\u{03B1}\u{00AE}\u{F87F}\u{F8FF}");
     }
 
     #[test]
-    fn encode_error_03() {
-        let text = "Hこllo world!";
+    fn decode_mac_02() {
+        // Not enough room for the whole sequence.
+        let mut buf = [0u8; 6];
+        let (decoded, consumed_count) = mac_hebrew::decode_to_str(&[0x61, 0xC0], &mut buf).unwrap();
+        assert_eq!(consumed_count, 1);
+        assert_eq!(decoded, "a");
+    }
+
+    #[test]
+    fn decode_mac_error_01() {
         let mut buf = [0u8; 64];
         assert_eq!(
-            encode_from_str(text, &mut buf),
-            Err(EncodeError {
-                character: 'こ',
-                error_range: (1, 4),
+            mac_thai::decode_to_str(&[0x61, 0x90], &mut buf),
+            Err(DecodeError {
+                cause: DecodeErrorCause::InvalidData,
+                error_range: (1, 2),
                 output_bytes_written: 1,
             })
         );
     }
 
     #[test]
-    fn encode_error_04() {
-        let text = "H\u{00C0}llo world!";
+    fn mac_round_trip_01() {
+        // Every byte round trips through decoding and encoding.
+        let mut buf = [0u8; 64];
+        for i in 0..=255u8 {
+            let (decoded, _) = mac_arabic::decode_to_str(&[i], &mut buf).unwrap();
+            let mut buf2 = [0u8; 4];
+            let (encoded, _) = mac_arabic::encode_from_str(decoded, &mut buf2, true).unwrap();
+            assert_eq!(encoded, &[i]);
+
+            let (decoded, _) = mac_hebrew::decode_to_str(&[i], &mut buf).unwrap();
+            let (encoded, _) = mac_hebrew::encode_from_str(decoded, &mut buf2, true).unwrap();
+            assert_eq!(encoded, &[i]);
+        }
+    }
+
+    #[test]
+    fn encode_vietnamese_01() {
+        // Letters without a byte of their own are decomposed.
+        let mut buf = [0u8; 64];
+        let (encoded, consumed_count) = windows_1258::encode_from_str("Việt á", &mut buf).unwrap();
+        assert_eq!(consumed_count, 9);
+        assert_eq!(encoded, &[0x56, 0x69, 0xEA, 0xF2, 0x74, 0x20, 0xE1]);
+    }
+
+    #[test]
+    fn encode_vietnamese_02() {
+        // Not enough room for a decomposed letter.
+        let mut buf = [0u8; 2];
+        let (encoded, consumed_count) = windows_1258::encode_from_str("aệ", &mut buf).unwrap();
+        assert_eq!(consumed_count, 1);
+        assert_eq!(encoded, &[0x61]);
+    }
+
+    #[test]
+    fn encode_vietnamese_03() {
+        let mut buf = [0u8; 64];
+        let (encoded, consumed_count) = viscii::encode_from_str("Việt Ẳ", &mut buf).unwrap();
+        assert_eq!(consumed_count, 10);
+        assert_eq!(encoded, &[0x56, 0x69, 0xAE, 0x74, 0x20, 0x02]);
+
+        let (encoded, _) = tcvn3::encode_from_str("Việt Ư", &mut buf).unwrap();
+        assert_eq!(encoded, &[0x56, 0x69, 0xD6, 0x74, 0x20, 0xA6]);
+    }
+
+    #[test]
+    fn encode_vietnamese_error_01() {
+        // TCVN3 has no capitals with tone marks.
         let mut buf = [0u8; 64];
         assert_eq!(
-            encode_from_str(text, &mut buf),
+            tcvn3::encode_from_str("aỆ", &mut buf),
             Err(EncodeError {
-                character: '\u{00C0}',
-                error_range: (1, 3),
+                character: 'Ệ',
+                error_range: (1, 4),
                 output_bytes_written: 1,
             })
         );
     }
 
     #[test]
-    fn encode_error_05() {
-        let text = "Heこlo world!";
-        let mut buf = [0u8; 3];
-        assert_eq!(
-            encode_from_str(text, &mut buf),
-            Err(EncodeError {
-                character: 'こ',
-                error_range: (2, 5),
-                output_bytes_written: 2,
-            })
-        );
+    fn decode_vietnamese_01() {
+        let mut buf = [0u8; 64];
+        let input = [0x56, 0x69, 0xEA, 0xF2, 0x74, 0x20, 0x61, 0xEC, 0x78, 0xEC];
+        let (decoded, consumed_count) = windows_1258::decode_to_str(&input, &mut buf).unwrap();
+        assert_eq!(consumed_count, 10);
+        assert_eq!(decoded, "Viê\u{0323}t a\u{0301}x\u{0301}");
+
+        let (decoded, consumed_count) =
+            windows_1258::decode_to_str_composed(&input, &mut buf, true).unwrap();
+        assert_eq!(consumed_count, 10);
+        assert_eq!(decoded, "Việt áx\u{0301}");
     }
 
     #[test]
-    fn encode_error_06() {
-        let text = "He\u{00C0}lo world!";
-        let mut buf = [0u8; 3];
-        assert_eq!(
-            encode_from_str(text, &mut buf),
-            Err(EncodeError {
-                character: '\u{00C0}',
-                error_range: (2, 4),
-                output_bytes_written: 2,
-            })
-        );
+    fn decode_vietnamese_02() {
+        // A letter at the end of the input waits for a possible tone mark,
+        // unless it's the end.
+        let mut buf = [0u8; 64];
+        let (decoded, consumed_count) =
+            windows_1258::decode_to_str_composed(&[0x56, 0x69, 0xEA], &mut buf, false).unwrap();
+        assert_eq!(consumed_count, 2);
+        assert_eq!(decoded, "Vi");
+
+        let (decoded, consumed_count) =
+            windows_1258::decode_to_str_composed(&[0x56, 0x69, 0xEA], &mut buf, true).unwrap();
+        assert_eq!(consumed_count, 3);
+        assert_eq!(decoded, "Viê");
     }
 
     #[test]
-    fn decode_error_01() {
-        let data = [
-            0x48, 0xAE, 0x6C, 0x6C, 0x6F, 0x20, 0x77, 0x6F, 0x72, 0x6C, 0x64, 0x21,
-        ]; // "Hello world!" with an error on the second byte (undefined byte).
+    fn decode_vietnamese_03() {
+        let mut buf = [0u8; 64];
+        let (decoded, consumed_count) =
+            viscii::decode_to_str(&[0x02, 0x0A, 0xAE], &mut buf).unwrap();
+        assert_eq!(consumed_count, 3);
+        assert_eq!(decoded, "Ẳ\nệ");
+    }
+
+    #[test]
+    fn decode_vietnamese_error_01() {
         let mut buf = [0u8; 64];
-        let error = decode_to_str(&data, &mut buf);
         assert_eq!(
-            error,
+            windows_1258::decode_to_str_composed(&[0x61, 0xEC, 0x90], &mut buf, true),
+            Err(DecodeError {
+                cause: DecodeErrorCause::InvalidData,
+                error_range: (2, 3),
+                output_bytes_written: 2,
+            })
+        );
+        assert_eq!(
+            tcvn3::decode_to_str(&[0x61, 0xC0], &mut buf),
             Err(DecodeError {
                 cause: DecodeErrorCause::InvalidData,
                 error_range: (1, 2),
@@ -568,16 +2100,34 @@ mod tests {
     }
 
     #[test]
-    fn decode_error_02() {
-        let data = [
-            0x48, 0xD2, 0x6C, 0x6C, 0x6F, 0x20, 0x77, 0x6F, 0x72, 0x6C, 0x64, 0x21,
-        ]; // "Hello world!" with an error on the second byte (undefined byte).
+    fn encode_adobe_01() {
         let mut buf = [0u8; 64];
-        let error = decode_to_str(&data, &mut buf);
+        let (encoded, consumed_count) =
+            adobe_standard::encode_from_str("It’s ﬁne—", &mut buf).unwrap();
+        assert_eq!(consumed_count, 15);
         assert_eq!(
-            error,
-            Err(DecodeError {
-                cause: DecodeErrorCause::InvalidData,
+            encoded,
+            &[0x49, 0x74, 0x27, 0x73, 0x20, 0xAE, 0x6E, 0x65, 0xD0]
+        );
+
+        let (encoded, _) = adobe_symbol::encode_from_str("α≤∞ Ω", &mut buf).unwrap();
+        assert_eq!(encoded, &[0x61, 0xA3, 0xA5, 0x20, 0x57]);
+
+        // Both Ω and Ohm encode to 0x57.
+        let (encoded, _) = adobe_symbol::encode_from_str("\u{2126}", &mut buf).unwrap();
+        assert_eq!(encoded, &[0x57]);
+
+        let (encoded, _) = zapf_dingbats::encode_from_str("✈ ❶", &mut buf).unwrap();
+        assert_eq!(encoded, &[0x28, 0x20, 0xB6]);
+    }
+
+    #[test]
+    fn encode_adobe_error_01() {
+        let mut buf = [0u8; 64];
+        assert_eq!(
+            adobe_standard::encode_from_str("a\nb", &mut buf),
+            Err(EncodeError {
+                character: '\n',
                 error_range: (1, 2),
                 output_bytes_written: 1,
             })
@@ -585,59 +2135,85 @@ mod tests {
     }
 
     #[test]
-    fn decode_error_03() {
-        let data = [
-            0x48, 0xFF, 0x6C, 0x6C, 0x6F, 0x20, 0x77, 0x6F, 0x72, 0x6C, 0x64, 0x21,
-        ]; // "Hello world!" with an error on the second byte (undefined byte).
+    fn decode_adobe_01() {
+        let mut buf = [0u8; 64];
+        let (decoded, consumed_count) =
+            adobe_standard::decode_to_str(&[0x60, 0x60, 0x41, 0x27, 0x27, 0xA4], &mut buf).unwrap();
+        assert_eq!(consumed_count, 6);
+        assert_eq!(decoded, "‘‘A’’⁄");
+
+        // Bytes mapped from more than one code point decode to the first.
+        let (decoded, _) =
+            adobe_symbol::decode_to_str(&[0x20, 0x44, 0x57, 0x6D], &mut buf).unwrap();
+        assert_eq!(decoded, " ΔΩμ");
+
+        let (decoded, _) = zapf_dingbats::decode_to_str(&[0x21, 0x80, 0xAC], &mut buf).unwrap();
+        assert_eq!(decoded, "✁\u{F8D7}①");
+    }
+
+    #[test]
+    fn decode_adobe_error_01() {
         let mut buf = [0u8; 64];
-        let error = decode_to_str(&data, &mut buf);
         assert_eq!(
-            error,
+            zapf_dingbats::decode_to_str(&[0x21, 0xF0], &mut buf),
             Err(DecodeError {
                 cause: DecodeErrorCause::InvalidData,
                 error_range: (1, 2),
-                output_bytes_written: 1,
+                output_bytes_written: 3,
             })
         );
     }
 
     #[test]
-    fn ascii_encode_error_01() {
-        let text = "\u{0080}ello world!";
+    fn encode_workstation_01() {
         let mut buf = [0u8; 64];
-        assert_eq!(
-            ascii::encode_from_str(text, &mut buf),
-            Err(EncodeError {
-                character: '\u{0080}',
-                error_range: (0, 2),
-                output_bytes_written: 0,
-            })
-        );
+        let (encoded, consumed_count) = hp_roman8::encode_from_str("Ça £", &mut buf).unwrap();
+        assert_eq!(consumed_count, 6);
+        assert_eq!(encoded, &[0xB4, 0x61, 0x20, 0xBB]);
+
+        let (encoded, _) = dec_mcs::encode_from_str("Œuvre", &mut buf).unwrap();
+        assert_eq!(encoded, &[0xD7, 0x75, 0x76, 0x72, 0x65]);
+
+        let (encoded, _) = nextstep::encode_from_str("Ébène", &mut buf).unwrap();
+        assert_eq!(encoded, &[0x89, 0x62, 0xDC, 0x6E, 0x65]);
+
+        let (encoded, _) = atari_st::encode_from_str("שלום ß", &mut buf).unwrap();
+        assert_eq!(encoded, &[0xD6, 0xCD, 0xC7, 0xDA, 0x20, 0x9E]);
     }
 
     #[test]
-    fn ascii_encode_error_02() {
-        let text = "\u{00FF}ello world!";
+    fn encode_workstation_error_01() {
         let mut buf = [0u8; 64];
         assert_eq!(
-            ascii::encode_from_str(text, &mut buf),
+            dec_mcs::encode_from_str("a×", &mut buf),
             Err(EncodeError {
-                character: '\u{00FF}',
-                error_range: (0, 2),
-                output_bytes_written: 0,
+                character: '×',
+                error_range: (1, 3),
+                output_bytes_written: 1,
             })
         );
     }
 
     #[test]
-    fn ascii_decode_error_01() {
-        let data = [
-            0x48, 0x80, 0x6C, 0x6C, 0x6F, 0x20, 0x77, 0x6F, 0x72, 0x6C, 0x64, 0x21,
-        ]; // "Hello world!" with an error on the second byte (undefined byte).
+    fn decode_workstation_01() {
+        let mut buf = [0u8; 64];
+        let (decoded, consumed_count) =
+            hp_roman8::decode_to_str(&[0x41, 0xA1, 0xF3, 0xFC], &mut buf).unwrap();
+        assert_eq!(consumed_count, 4);
+        assert_eq!(decoded, "AÀµ■");
+
+        let (decoded, _) = nextstep::decode_to_str(&[0x80, 0xAE, 0xD0], &mut buf).unwrap();
+        assert_eq!(decoded, "\u{A0}ﬁ—");
+
+        let (decoded, _) = atari_st::decode_to_str(&[0xE0, 0xE1, 0xC0, 0xBF], &mut buf).unwrap();
+        assert_eq!(decoded, "αβĳ™");
+    }
+
+    #[test]
+    fn decode_workstation_error_01() {
         let mut buf = [0u8; 64];
-        let error = ascii::decode_to_str(&data, &mut buf);
         assert_eq!(
-            error,
+            dec_mcs::decode_to_str(&[0x61, 0xA0], &mut buf),
             Err(DecodeError {
                 cause: DecodeErrorCause::InvalidData,
                 error_range: (1, 2),
@@ -647,16 +2223,26 @@ mod tests {
     }
 
     #[test]
-    fn ascii_decode_error_02() {
-        let data = [
-            0x48, 0xFF, 0x6C, 0x6C, 0x6F, 0x20, 0x77, 0x6F, 0x72, 0x6C, 0x64, 0x21,
-        ]; // "Hello world!" with an error on the second byte (undefined byte).
+    fn encode_petscii_01() {
+        let mut buf = [0u8; 64];
+        let (encoded, consumed_count) =
+            petscii::encode_from_str("HELLO ♥\r", &mut buf, petscii::Mode::Unshifted).unwrap();
+        assert_eq!(consumed_count, 10);
+        assert_eq!(encoded, &[0x48, 0x45, 0x4C, 0x4C, 0x4F, 0x20, 0xD3, 0x0D]);
+
+        let (encoded, _) =
+            petscii::encode_from_str("Hello £", &mut buf, petscii::Mode::Shifted).unwrap();
+        assert_eq!(encoded, &[0xC8, 0x45, 0x4C, 0x4C, 0x4F, 0x20, 0x5C]);
+    }
+
+    #[test]
+    fn encode_petscii_error_01() {
+        // Lowercase letters are only in the shifted set.
         let mut buf = [0u8; 64];
-        let error = ascii::decode_to_str(&data, &mut buf);
         assert_eq!(
-            error,
-            Err(DecodeError {
-                cause: DecodeErrorCause::InvalidData,
+            petscii::encode_from_str("Ab", &mut buf, petscii::Mode::Unshifted),
+            Err(EncodeError {
+                character: 'b',
                 error_range: (1, 2),
                 output_bytes_written: 1,
             })
@@ -664,26 +2250,76 @@ mod tests {
     }
 
     #[test]
-    fn encode_latin1_01() {
+    fn decode_petscii_01() {
         let mut buf = [0u8; 64];
-        // Make sure all the allowed code points encode correctly.
-        for i in 0..=255u8 {
-            let (encoded, consumed_count) =
-                iso_8859_1::encode_from_str((i as char).encode_utf8(&mut [0u8; 4]), &mut buf)
-                    .unwrap();
-            assert_eq!(consumed_count, (i as char).len_utf8());
-            assert_eq!(encoded, &[i]);
-        }
+        let input = [0x41, 0xC1, 0x61, 0xC2, 0xFF];
+        let (decoded, consumed_count) =
+            petscii::decode_to_str(&input, &mut buf, petscii::Mode::Unshifted).unwrap();
+        assert_eq!(consumed_count, 5);
+        assert_eq!(decoded, "A♠♠\u{1FB72}π");
+
+        let (decoded, _) =
+            petscii::decode_to_str(&input, &mut buf, petscii::Mode::Shifted).unwrap();
+        assert_eq!(decoded, "aAAB\u{1FB96}");
     }
 
     #[test]
-    fn decode_latin1_01() {
+    fn encode_central_asian_01() {
         let mut buf = [0u8; 64];
-        // Make sure all the allowed code points encode correctly.
-        for i in 0..=255u8 {
-            let (encoded, consumed_count) = iso_8859_1::decode_to_str(&[i], &mut buf).unwrap();
-            assert_eq!(consumed_count, 1);
-            assert_eq!(encoded, (i as char).encode_utf8(&mut [0u8; 4]));
-        }
+        let (encoded, consumed_count) = kz_1048::encode_from_str("Қазақ", &mut buf).unwrap();
+        assert_eq!(consumed_count, 10);
+        assert_eq!(encoded, &[0x8D, 0xE0, 0xE7, 0xE0, 0x9D]);
+
+        let (encoded, _) = pt154::encode_from_str("Қазақ", &mut buf).unwrap();
+        assert_eq!(encoded, &[0x8D, 0xE0, 0xE7, 0xE0, 0x9D]);
+
+        let (encoded, _) = koi8_t::encode_from_str("Тоҷикӣ", &mut buf).unwrap();
+        assert_eq!(encoded, &[0xF4, 0xCF, 0x8D, 0xC9, 0xCB, 0xA5]);
+
+        let (encoded, _) = koi8_ru::encode_from_str("Ўў", &mut buf).unwrap();
+        assert_eq!(encoded, &[0xBE, 0xAE]);
+
+        let (encoded, _) = georgian_ps::encode_from_str("ქართული", &mut buf).unwrap();
+        assert_eq!(encoded, &[0xD8, 0xC0, 0xD2, 0xC8, 0xD6, 0xCB, 0xC9]);
+    }
+
+    #[test]
+    fn encode_armscii_01() {
+        // The punctuation that is duplicated always encodes to Ascii.
+        let mut buf = [0u8; 64];
+        let (encoded, consumed_count) = armscii_8::encode_from_str("(Հայ).։", &mut buf).unwrap();
+        assert_eq!(consumed_count, 11);
+        assert_eq!(encoded, &[0x28, 0xD0, 0xB3, 0xDB, 0x29, 0x2E, 0xA3]);
+    }
+
+    #[test]
+    fn decode_armscii_01() {
+        let mut buf = [0u8; 64];
+        let (decoded, consumed_count) =
+            armscii_8::decode_to_str(&[0xA5, 0xD0, 0xA4, 0xA9, 0xAB, 0xAC, 0x2D], &mut buf)
+                .unwrap();
+        assert_eq!(consumed_count, 7);
+        assert_eq!(decoded, "(Հ).,--");
+    }
+
+    #[test]
+    fn decode_central_asian_error_01() {
+        let mut buf = [0u8; 64];
+        assert_eq!(
+            koi8_t::decode_to_str(&[0x61, 0x88], &mut buf),
+            Err(DecodeError {
+                cause: DecodeErrorCause::InvalidData,
+                error_range: (1, 2),
+                output_bytes_written: 1,
+            })
+        );
+        assert_eq!(
+            armscii_8::decode_to_str(&[0xA1], &mut buf),
+            Err(DecodeError {
+                cause: DecodeErrorCause::InvalidData,
+                error_range: (0, 1),
+                output_bytes_written: 0,
+            })
+        );
     }
 }
@@ -0,0 +1,373 @@
+//! TSCII 1.7, the Tamil Standard Code for Information Interchange.
+//!
+//! This is Ascii, with the upper half mostly used for glyphs.  Many bytes
+//! therefore map to a sequence of several code points, e.g. 0xCC is "கு"
+//! (U+0B95 U+0BC1) and 0x82 is "ஸ்ரீ" (four code points).  The encoder
+//! always uses the longest sequence that has a byte of its own.
+//!
+//! The vowel signs ெ, ே, and ை (0xA6-0xA8) are written before the
+//! consonant, where Unicode has them after it, and the two-part vowel signs
+//! ொ, ோ, and ௌ are written around it, e.g. "கொ" is 0xA6 0xB8 0xA1.  Both
+//! directions reorder them.  Decoding always gives the precomposed two-part
+//! vowel signs, and a vowel sign byte that isn't followed by a consonant is
+//! decoded on its own.
+//!
+//! 0xFE is a duplicate of 0xAD (TAMIL LETTER I), and is never produced by
+//! the encoder.  Apart from that, and from a vowel sign that comes before a
+//! consonant in the Unicode text, conversion in both directions is
+//! lossless.
+
+use core;
+use {DecodeError, DecodeErrorCause, DecodeResult, EncodeError, EncodeResult};
+
+// Generated by `encoding_tables/tscii/generate_tscii.py`.
+// Contains DECODE_TABLE and ENCODE_TABLE.
+include!("generated/tscii/tscii_tables.rs.inc");
+
+// The vowel signs that are written before the consonant, with the byte
+// before the consonant and, for the two-part signs, the byte after it.
+const PREBASE_TABLE: [(char, u8, Option<u8>); 6] = [
+    ('\u{0BC6}', 0xA6, None),
+    ('\u{0BC7}', 0xA7, None),
+    ('\u{0BC8}', 0xA8, None),
+    ('\u{0BCA}', 0xA6, Some(0xA1)),
+    ('\u{0BCB}', 0xA7, Some(0xA1)),
+    ('\u{0BCC}', 0xA6, Some(0xAA)),
+];
+
+// The longest sequence of code points that has a byte of its own.
+const MAX_SEQUENCE_LEN: usize = 4;
+
+pub fn encode_from_str<'a>(
+    input: &str,
+    out_buffer: &'a mut [u8],
+    is_end: bool,
+) -> EncodeResult<'a> {
+    // Do the encode.
+    let mut input_i = 0;
+    let mut output_i = 0;
+    while let Some(c) = input[input_i..].chars().next() {
+        let rest = &input[input_i..];
+        if output_i >= out_buffer.len() {
+            break;
+        } else if (c as u32) < 0x80 {
+            out_buffer[output_i] = c as u8;
+            output_i += 1;
+            input_i += 1;
+            continue;
+        } else if !is_end && is_partial_sequence(rest) {
+            // The rest of a longer sequence may be in the next input, so
+            // wait for it.
+            break;
+        }
+
+        let (byte, mut input_consumed) = if let Some(x) = map_sequence(rest) {
+            x
+        } else {
+            return Err(EncodeError {
+                character: c,
+                error_range: (input_i, input_i + c.len_utf8()),
+                output_bytes_written: output_i,
+            });
+        };
+
+        // Move prebase vowel signs in front of the consonant.
+        let mut bytes = [byte, 0, 0];
+        let mut bytes_len = 1;
+        if is_consonant(DECODE_TABLE[(byte - 0x80) as usize]) {
+            let sign = rest[input_consumed..].chars().next();
+            if sign.is_none() && !is_end {
+                // A vowel sign may follow in the next input, so wait for
+                // it.
+                break;
+            } else if let Some(&(sign, byte_1, byte_2)) =
+                PREBASE_TABLE.iter().find(|x| Some(x.0) == sign)
+            {
+                bytes = [byte_1, byte, byte_2.unwrap_or(0)];
+                bytes_len = if byte_2.is_some() { 3 } else { 2 };
+                input_consumed += sign.len_utf8();
+            }
+        }
+
+        if (output_i + bytes_len) > out_buffer.len() {
+            break;
+        }
+        out_buffer[output_i..(output_i + bytes_len)].copy_from_slice(&bytes[..bytes_len]);
+        output_i += bytes_len;
+        input_i += input_consumed;
+    }
+
+    // Calculate how much of the input was consumed.
+    if input_i > input.len() {
+        input_i = input.len();
+    } else {
+        while !input.is_char_boundary(input_i) {
+            input_i += 1;
+        }
+    }
+
+    Ok((&out_buffer[..output_i], input_i))
+}
+
+/// Finds the longest sequence at the start of `input` that has a byte of
+/// its own, returning the byte and the length of the sequence in bytes.
+fn map_sequence(input: &str) -> Option<(u8, usize)> {
+    let mut ends = [0usize; MAX_SEQUENCE_LEN];
+    let mut count = 0;
+    for (offset, c) in input.char_indices().take(MAX_SEQUENCE_LEN) {
+        ends[count] = offset + c.len_utf8();
+        count += 1;
+    }
+    ends[..count].iter().rev().find_map(|&end| {
+        ENCODE_TABLE
+            .binary_search_by_key(&&input[..end], |x| x.0)
+            .ok()
+            .map(|i| (ENCODE_TABLE[i].1, end))
+    })
+}
+
+/// Returns whether all of `input` is the start of a longer sequence that
+/// has a byte of its own.
+fn is_partial_sequence(input: &str) -> bool {
+    let i = match ENCODE_TABLE.binary_search_by_key(&input, |x| x.0) {
+        Ok(i) => i + 1,
+        Err(i) => i,
+    };
+    matches!(ENCODE_TABLE.get(i), Some(x) if x.0.starts_with(input))
+}
+
+/// Returns whether a decoded sequence ends in a consonant, and so can take
+/// a vowel sign.
+fn is_consonant(s: &str) -> bool {
+    matches!(s.chars().last(), Some('\u{0B95}'..='\u{0BB9}'))
+}
+
+pub fn decode_to_str<'a>(input: &[u8], out_buffer: &'a mut [u8], is_end: bool) -> DecodeResult<'a> {
+    let mut input_i = 0;
+    let mut output_i = 0;
+    let mut buf = [0u8; 4]; // For encoding utf8 codepoints.
+
+    while input_i < input.len() {
+        if output_i >= out_buffer.len() {
+            break;
+        }
+        let byte_1 = input[input_i];
+        if byte_1 < 0x80 {
+            out_buffer[output_i] = byte_1;
+            output_i += 1;
+            input_i += 1;
+            continue;
+        }
+
+        let mut string = DECODE_TABLE[(byte_1 - 0x80) as usize];
+        let mut sign = "";
+        let mut input_consumed = 1;
+
+        // Prebase vowel signs are moved after the consonant that follows
+        // them.
+        if let Some(&(c, _, _)) = PREBASE_TABLE
+            .iter()
+            .find(|x| x.1 == byte_1 && x.2.is_none())
+        {
+            let consonant = match input.get(input_i + 1) {
+                None if !is_end => {
+                    // The consonant may be in the next input, so wait for it.
+                    break;
+                }
+                Some(&byte_2) if byte_2 >= 0x80 => DECODE_TABLE[(byte_2 - 0x80) as usize],
+                _ => "",
+            };
+            if is_consonant(consonant) {
+                string = consonant;
+                sign = c.encode_utf8(&mut buf);
+                input_consumed = 2;
+
+                // Check for the second part of a two-part vowel sign.
+                let is_two_part = |x: &&(char, u8, Option<u8>)| x.1 == byte_1 && x.2.is_some();
+                match input.get(input_i + 2) {
+                    None if !is_end && PREBASE_TABLE.iter().any(|x| is_two_part(&x)) => {
+                        // The second part may be in the next input, so wait
+                        // for it.
+                        break;
+                    }
+                    Some(&byte_3) => {
+                        if let Some(&(c, _, _)) = PREBASE_TABLE
+                            .iter()
+                            .find(|x| is_two_part(x) && x.2 == Some(byte_3))
+                        {
+                            sign = c.encode_utf8(&mut buf);
+                            input_consumed = 3;
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        if string.is_empty() {
+            // Error: undefined byte.
+            return Err(DecodeError {
+                cause: DecodeErrorCause::InvalidData,
+                error_range: (input_i, input_i + 1),
+                output_bytes_written: output_i,
+            });
+        }
+
+        // Copy decoded data to output.
+        let len = string.len() + sign.len();
+        if (output_i + len) > out_buffer.len() {
+            // Not enough space in output buffer.
+            break;
+        }
+        out_buffer[output_i..(output_i + string.len())].copy_from_slice(string.as_bytes());
+        out_buffer[(output_i + string.len())..(output_i + len)].copy_from_slice(sign.as_bytes());
+
+        // Update our counters.
+        input_i += input_consumed;
+        output_i += len;
+    }
+
+    Ok((
+        unsafe { core::str::from_utf8_unchecked(&out_buffer[..output_i]) },
+        input_i,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use {DecodeError, DecodeErrorCause, EncodeError};
+
+    #[test]
+    fn encode_01() {
+        let text = "தமிழ் கொ ஸ்ரீ க்ஷெ கை";
+        let mut buf = [0u8; 64];
+        let (encoded, consumed_count) = encode_from_str(text, &mut buf, true).unwrap();
+        assert_eq!(consumed_count, text.len());
+        assert_eq!(
+            encoded,
+            &[
+                0xBE, 0xC1, 0xA2, 0xFA, 0x20, 0xA6, 0xB8, 0xA1, 0x20, 0x82, 0x20, 0xA6, 0x87, 0x20,
+                0xA8, 0xB8
+            ]
+        );
+    }
+
+    #[test]
+    fn encode_02() {
+        // Decomposed two-part vowel signs.
+        let text = "க\u{0BC6}\u{0BBE}க\u{0BC6}\u{0BD7}";
+        let mut buf = [0u8; 64];
+        let (encoded, consumed_count) = encode_from_str(text, &mut buf, true).unwrap();
+        assert_eq!(consumed_count, text.len());
+        assert_eq!(encoded, &[0xA6, 0xB8, 0xA1, 0xA6, 0xB8, 0xAA]);
+    }
+
+    #[test]
+    fn encode_03() {
+        // A sequence that may continue in the next input waits for it,
+        // unless it's the end.
+        let mut buf = [0u8; 64];
+        {
+            let (encoded, consumed_count) = encode_from_str("aக்", &mut buf, false).unwrap();
+            assert_eq!(consumed_count, 1);
+            assert_eq!(encoded, b"a");
+        }
+        {
+            let (encoded, consumed_count) = encode_from_str("aக", &mut buf, false).unwrap();
+            assert_eq!(consumed_count, 1);
+            assert_eq!(encoded, b"a");
+        }
+        {
+            let (encoded, consumed_count) = encode_from_str("aக்", &mut buf, true).unwrap();
+            assert_eq!(consumed_count, 7);
+            assert_eq!(encoded, &[0x61, 0xEC]);
+        }
+    }
+
+    #[test]
+    fn encode_04() {
+        // Not enough room for a consonant with a vowel sign.
+        let mut buf = [0u8; 3];
+        let (encoded, consumed_count) = encode_from_str("aகொ", &mut buf, true).unwrap();
+        assert_eq!(consumed_count, 1);
+        assert_eq!(encoded, b"a");
+    }
+
+    #[test]
+    fn encode_error_01() {
+        let mut buf = [0u8; 64];
+        assert_eq!(
+            encode_from_str("க\u{0B80}", &mut buf, true),
+            Err(EncodeError {
+                character: '\u{0B80}',
+                error_range: (3, 6),
+                output_bytes_written: 1,
+            }),
+        );
+    }
+
+    #[test]
+    fn decode_01() {
+        let input = [
+            0xBE, 0xC1, 0xA2, 0xFA, 0x20, 0xA6, 0xB8, 0xA1, 0x20, 0x82, 0x20, 0xA6, 0x87, 0x20,
+            0xA8, 0xB8, 0xFE,
+        ];
+        let mut buf = [0u8; 64];
+        let (decoded, consumed_count) = decode_to_str(&input, &mut buf, true).unwrap();
+        assert_eq!(consumed_count, input.len());
+        assert_eq!(decoded, "தமிழ் கொ ஸ்ரீ க்ஷெ கைஇ");
+    }
+
+    #[test]
+    fn decode_02() {
+        // Vowel signs that aren't followed by a consonant.
+        let mut buf = [0u8; 64];
+        let (decoded, consumed_count) = decode_to_str(&[0xA6, 0x61, 0xA7], &mut buf, true).unwrap();
+        assert_eq!(consumed_count, 3);
+        assert_eq!(decoded, "\u{0BC6}a\u{0BC7}");
+    }
+
+    #[test]
+    fn decode_03() {
+        // A vowel sign at the end of the input waits for the rest of it,
+        // unless it's the end.
+        let mut buf = [0u8; 64];
+        {
+            let (decoded, consumed_count) = decode_to_str(&[0x61, 0xA6], &mut buf, false).unwrap();
+            assert_eq!(consumed_count, 1);
+            assert_eq!(decoded, "a");
+        }
+        {
+            let (decoded, consumed_count) =
+                decode_to_str(&[0x61, 0xA6, 0xB8], &mut buf, false).unwrap();
+            assert_eq!(consumed_count, 1);
+            assert_eq!(decoded, "a");
+        }
+        {
+            let (decoded, consumed_count) =
+                decode_to_str(&[0x61, 0xA8, 0xB8], &mut buf, false).unwrap();
+            assert_eq!(consumed_count, 3);
+            assert_eq!(decoded, "aகை");
+        }
+        {
+            let (decoded, consumed_count) =
+                decode_to_str(&[0x61, 0xA6, 0xB8], &mut buf, true).unwrap();
+            assert_eq!(consumed_count, 3);
+            assert_eq!(decoded, "aகெ");
+        }
+    }
+
+    #[test]
+    fn decode_error_01() {
+        let mut buf = [0u8; 64];
+        assert_eq!(
+            decode_to_str(&[0x61, 0xFF], &mut buf, true),
+            Err(DecodeError {
+                cause: DecodeErrorCause::InvalidData,
+                error_range: (1, 2),
+                output_bytes_written: 1,
+            }),
+        );
+    }
+}
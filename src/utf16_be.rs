@@ -71,7 +71,7 @@ pub fn decode_to_str<'a>(input: &[u8], out_buffer: &'a mut [u8], is_end: bool) -
         // Decode to scalar value.
         let code = {
             let code_1 = u16::from_be_bytes([bytes[0], bytes[1]]);
-            if code_1 < 0xD800 || code_1 > 0xDFFF {
+            if !(0xD800..=0xDFFF).contains(&code_1) {
                 // Single code unit.
                 unsafe { core::char::from_u32_unchecked(code_1 as u32) }
             } else if (code_1 & 0xFC00) == 0xDC00 {
@@ -10,7 +10,7 @@ use {DecodeError, DecodeErrorCause, DecodeResult, EncodeResult};
 
 pub fn encode_from_str<'a>(input: &str, out_buffer: &'a mut [u8]) -> EncodeResult<'a> {
     let cl = copy_len(input.as_bytes(), out_buffer.len());
-    out_buffer[..cl].copy_from_slice(input[..cl].as_bytes());
+    out_buffer[..cl].copy_from_slice(&input.as_bytes()[..cl]);
     Ok((&out_buffer[..cl], cl))
 }
 
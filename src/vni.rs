@@ -0,0 +1,238 @@
+//! VNI, the Vietnamese encoding used by the VNI fonts.
+//!
+//! This is Ascii, with most letters with diacritics written as the ascii
+//! vowel followed by a byte for its marks, e.g. "ệ" is "e" followed by 0xE4.
+//! The mark bytes come in a lowercase and an uppercase form, which are used
+//! after lowercase and uppercase vowels respectively.  Đ, Ơ, Ư, and the
+//! letter I with tone marks have bytes of their own, and Ơ and Ư can also
+//! be followed by a tone mark byte.
+//!
+//! Decoding accepts either form of mark byte after either case of vowel.
+//! Encoding always uses the form matching the vowel.  Apart from that,
+//! decoding and encoding are lossless.  Only the precomposed letters are
+//! encodable: a vowel followed by a combining mark is an error.
+
+use core;
+use {DecodeError, DecodeErrorCause, DecodeResult, EncodeError, EncodeResult};
+
+// Generated by `encoding_tables/vni/generate_vni.py`.
+// Contains SINGLE_TABLE, PAIR_TABLE, and ENCODE_TABLE.
+include!("generated/vni/vni_tables.rs.inc");
+
+pub fn encode_from_str<'a>(input: &str, out_buffer: &'a mut [u8]) -> EncodeResult<'a> {
+    // Do the encode.
+    let mut input_i = 0;
+    let mut output_i = 0;
+    for (offset, c) in input.char_indices() {
+        if output_i >= out_buffer.len() {
+            break;
+        } else if (c as u32) < 0x80 {
+            out_buffer[output_i] = c as u8;
+            output_i += 1;
+            input_i = offset + 1;
+        } else if let Some(&(byte, _)) = SINGLE_TABLE.iter().find(|x| x.1 == c) {
+            out_buffer[output_i] = byte;
+            output_i += 1;
+            input_i = offset + 1;
+        } else if let Ok(ptr_i) = ENCODE_TABLE.binary_search_by_key(&c, |x| x.0) {
+            if (output_i + 1) < out_buffer.len() {
+                let bytes = ENCODE_TABLE[ptr_i].1;
+                out_buffer[output_i] = bytes[0];
+                out_buffer[output_i + 1] = bytes[1];
+                output_i += 2;
+                input_i = offset + 1;
+            } else {
+                break;
+            }
+        } else {
+            return Err(EncodeError {
+                character: c,
+                error_range: (offset, offset + c.len_utf8()),
+                output_bytes_written: output_i,
+            });
+        }
+    }
+
+    // Calculate how much of the input was consumed.
+    if input_i > input.len() {
+        input_i = input.len();
+    } else {
+        while !input.is_char_boundary(input_i) {
+            input_i += 1;
+        }
+    }
+
+    Ok((&out_buffer[..output_i], input_i))
+}
+
+pub fn decode_to_str<'a>(input: &[u8], out_buffer: &'a mut [u8], is_end: bool) -> DecodeResult<'a> {
+    let mut input_i = 0;
+    let mut output_i = 0;
+    let mut buf = [0u8; 4]; // For encoding utf8 codepoints.
+
+    while input_i < input.len() {
+        if output_i >= out_buffer.len() {
+            break;
+        }
+        let byte_1 = input[input_i];
+
+        // Check for a vowel followed by a mark byte first.
+        let pair = if let Some(&byte_2) = input.get(input_i + 1) {
+            PAIR_TABLE
+                .binary_search_by_key(&(byte_1, byte_2), |x| (x.0, x.1))
+                .ok()
+                .map(|i| PAIR_TABLE[i].2)
+        } else if !is_end && PAIR_TABLE.iter().any(|x| x.0 == byte_1) {
+            // A mark byte may follow in the next input, so wait for it.
+            break;
+        } else {
+            None
+        };
+
+        // Get our decoded data.
+        let (string, input_consumed) = if let Some(c) = pair {
+            (c.encode_utf8(&mut buf), 2)
+        } else if byte_1 < 0x80 {
+            ((byte_1 as char).encode_utf8(&mut buf), 1)
+        } else if let Ok(i) = SINGLE_TABLE.binary_search_by_key(&byte_1, |x| x.0) {
+            (SINGLE_TABLE[i].1.encode_utf8(&mut buf), 1)
+        } else {
+            // Error: undefined byte, or a mark byte that doesn't follow a
+            // vowel it can mark.
+            return Err(DecodeError {
+                cause: DecodeErrorCause::InvalidData,
+                error_range: (input_i, input_i + 1),
+                output_bytes_written: output_i,
+            });
+        };
+
+        // Copy decoded data to output.
+        if (output_i + string.len()) > out_buffer.len() {
+            // Not enough space in output buffer.
+            break;
+        }
+        out_buffer[output_i..(output_i + string.len())].copy_from_slice(string.as_bytes());
+
+        // Update our counters.
+        input_i += input_consumed;
+        output_i += string.len();
+    }
+
+    Ok((
+        unsafe { core::str::from_utf8_unchecked(&out_buffer[..output_i]) },
+        input_i,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use {DecodeError, DecodeErrorCause, EncodeError};
+
+    #[test]
+    fn encode_01() {
+        let text = "Tiếng Việt Đà Nẵng NGƯỜI chỉ";
+        let mut buf = [0u8; 64];
+        let (encoded, consumed_count) = encode_from_str(text, &mut buf).unwrap();
+        assert_eq!(consumed_count, text.len());
+        assert_eq!(
+            encoded,
+            &b"Tie\xE1ng Vie\xE4t \xD1a\xF8 Na\xFCng NG\xD6\xD4\xD8I ch\xE6"[..]
+        );
+    }
+
+    #[test]
+    fn encode_02() {
+        // Not enough room for a letter with a mark byte.
+        let mut buf = [0u8; 2];
+        let (encoded, consumed_count) = encode_from_str("aệ", &mut buf).unwrap();
+        assert_eq!(consumed_count, 1);
+        assert_eq!(encoded, b"a");
+    }
+
+    #[test]
+    fn encode_error_01() {
+        // Combining marks aren't encodable.
+        let mut buf = [0u8; 64];
+        assert_eq!(
+            encode_from_str("ae\u{0302}", &mut buf),
+            Err(EncodeError {
+                character: '\u{0302}',
+                error_range: (2, 4),
+                output_bytes_written: 2,
+            }),
+        );
+    }
+
+    #[test]
+    fn decode_01() {
+        let mut buf = [0u8; 64];
+        let (decoded, consumed_count) = decode_to_str(
+            b"Tie\xE1ng Vie\xE4t \xD1a\xF8 Na\xFCng NG\xD6\xD4\xD8I ch\xE6",
+            &mut buf,
+            true,
+        )
+        .unwrap();
+        assert_eq!(consumed_count, 33);
+        assert_eq!(decoded, "Tiếng Việt Đà Nẵng NGƯỜI chỉ");
+    }
+
+    #[test]
+    fn decode_02() {
+        // Either form of mark byte is accepted.
+        let mut buf = [0u8; 64];
+        let (decoded, consumed_count) = decode_to_str(b"E\xE4E\xC4", &mut buf, true).unwrap();
+        assert_eq!(consumed_count, 4);
+        assert_eq!(decoded, "ỆỆ");
+    }
+
+    #[test]
+    fn decode_03() {
+        // A vowel at the end of the input waits for a possible mark byte,
+        // unless it's the end.
+        let mut buf = [0u8; 64];
+        {
+            let (decoded, consumed_count) = decode_to_str(b"Vie", &mut buf, false).unwrap();
+            assert_eq!(consumed_count, 2);
+            assert_eq!(decoded, "Vi");
+        }
+        {
+            let (decoded, consumed_count) = decode_to_str(b"\xF4", &mut buf, false).unwrap();
+            assert_eq!(consumed_count, 0);
+            assert_eq!(decoded, "");
+        }
+        {
+            let (decoded, consumed_count) = decode_to_str(b"Vie", &mut buf, true).unwrap();
+            assert_eq!(consumed_count, 3);
+            assert_eq!(decoded, "Vie");
+        }
+    }
+
+    #[test]
+    fn decode_error_01() {
+        // A mark byte that doesn't follow a vowel it can mark.
+        let mut buf = [0u8; 64];
+        assert_eq!(
+            decode_to_str(b"au\xE2", &mut buf, true),
+            Err(DecodeError {
+                cause: DecodeErrorCause::InvalidData,
+                error_range: (2, 3),
+                output_bytes_written: 2,
+            }),
+        );
+    }
+
+    #[test]
+    fn decode_error_02() {
+        // Undefined byte.
+        let mut buf = [0u8; 64];
+        assert_eq!(
+            decode_to_str(b"a\x80", &mut buf, true),
+            Err(DecodeError {
+                cause: DecodeErrorCause::InvalidData,
+                error_range: (1, 2),
+                output_bytes_written: 1,
+            }),
+        );
+    }
+}
@@ -0,0 +1,31 @@
+extern crate text_encoding;
+
+use text_encoding::arib_b24::{decode_to_str, encode_from_str, State};
+
+const ARIB_DECODE_ARIB_DATA: &[u8] = include_bytes!("test_data/arib/arib_b24_test_decode_in.txt");
+const ARIB_DECODE_UTF8_DATA: &[u8] = include_bytes!("test_data/arib/arib_b24_test_decode_out.txt");
+const ARIB_ENCODE_UTF8_DATA: &[u8] = include_bytes!("test_data/arib/arib_b24_test_encode_in.txt");
+const ARIB_ENCODE_ARIB_DATA: &[u8] = include_bytes!("test_data/arib/arib_b24_test_encode_out.txt");
+
+#[test]
+fn arib_b24_encode() {
+    let mut buf = vec![0u8; ARIB_ENCODE_ARIB_DATA.len()];
+    let (encoded, _) = encode_from_str(
+        std::str::from_utf8(ARIB_ENCODE_UTF8_DATA).unwrap(),
+        &mut buf,
+        &mut State::new(),
+        true,
+    )
+    .unwrap();
+
+    assert_eq!(ARIB_ENCODE_ARIB_DATA, encoded);
+}
+
+#[test]
+fn arib_b24_decode() {
+    let mut buf = vec![0u8; ARIB_DECODE_UTF8_DATA.len()];
+    let (utf8, _) =
+        decode_to_str(ARIB_DECODE_ARIB_DATA, &mut buf, &mut State::new(), true).unwrap();
+
+    assert_eq!(ARIB_DECODE_UTF8_DATA, utf8.as_bytes());
+}
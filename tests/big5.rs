@@ -2,13 +2,13 @@ extern crate text_encoding;
 
 use text_encoding::big5_whatwg::{decode_to_str, encode_from_str};
 
-const WHATWG_DECODE_BIG5_DATA: &'static [u8] =
+const WHATWG_DECODE_BIG5_DATA: &[u8] =
     include_bytes!("test_data/whatwg/big5_whatwg_test_decode_in.txt");
-const WHATWG_DECODE_UTF8_DATA: &'static [u8] =
+const WHATWG_DECODE_UTF8_DATA: &[u8] =
     include_bytes!("test_data/whatwg/big5_whatwg_test_decode_out.txt");
-const WHATWG_ENCODE_UTF8_DATA: &'static [u8] =
+const WHATWG_ENCODE_UTF8_DATA: &[u8] =
     include_bytes!("test_data/whatwg/big5_whatwg_test_encode_in.txt");
-const WHATWG_ENCODE_BIG5_DATA: &'static [u8] =
+const WHATWG_ENCODE_BIG5_DATA: &[u8] =
     include_bytes!("test_data/whatwg/big5_whatwg_test_encode_out.txt");
 
 #[test]
@@ -0,0 +1,33 @@
+extern crate text_encoding;
+
+use text_encoding::big5_hkscs::{decode_to_str, encode_from_str};
+
+const HKSCS_DECODE_BIG5_DATA: &[u8] =
+    include_bytes!("test_data/big5_hkscs/big5_hkscs_test_decode_in.txt");
+const HKSCS_DECODE_UTF8_DATA: &[u8] =
+    include_bytes!("test_data/big5_hkscs/big5_hkscs_test_decode_out.txt");
+const HKSCS_ENCODE_UTF8_DATA: &[u8] =
+    include_bytes!("test_data/big5_hkscs/big5_hkscs_test_encode_in.txt");
+const HKSCS_ENCODE_BIG5_DATA: &[u8] =
+    include_bytes!("test_data/big5_hkscs/big5_hkscs_test_encode_out.txt");
+
+#[test]
+fn big5_hkscs_encode() {
+    let mut buf = vec![0u8; HKSCS_ENCODE_BIG5_DATA.len()];
+    let (big5, _) = encode_from_str(
+        std::str::from_utf8(HKSCS_ENCODE_UTF8_DATA).unwrap(),
+        &mut buf,
+        true,
+    )
+    .unwrap();
+
+    assert_eq!(HKSCS_ENCODE_BIG5_DATA, big5);
+}
+
+#[test]
+fn big5_hkscs_decode() {
+    let mut buf = vec![0u8; HKSCS_DECODE_UTF8_DATA.len()];
+    let (utf8, _) = decode_to_str(HKSCS_DECODE_BIG5_DATA, &mut buf, true).unwrap();
+
+    assert_eq!(HKSCS_DECODE_UTF8_DATA, utf8.as_bytes());
+}
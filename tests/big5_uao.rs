@@ -0,0 +1,29 @@
+extern crate text_encoding;
+
+use text_encoding::big5_uao::{decode_to_str, encode_from_str};
+
+const UAO_DECODE_BIG5_DATA: &[u8] =
+    include_bytes!("test_data/big5_uao/big5_uao_test_decode_in.txt");
+const UAO_DECODE_UTF8_DATA: &[u8] =
+    include_bytes!("test_data/big5_uao/big5_uao_test_decode_out.txt");
+const UAO_ENCODE_UTF8_DATA: &[u8] =
+    include_bytes!("test_data/big5_uao/big5_uao_test_encode_in.txt");
+const UAO_ENCODE_BIG5_DATA: &[u8] =
+    include_bytes!("test_data/big5_uao/big5_uao_test_encode_out.txt");
+
+#[test]
+fn big5_uao_encode() {
+    let mut buf = vec![0u8; UAO_ENCODE_BIG5_DATA.len()];
+    let (big5, _) =
+        encode_from_str(std::str::from_utf8(UAO_ENCODE_UTF8_DATA).unwrap(), &mut buf).unwrap();
+
+    assert_eq!(UAO_ENCODE_BIG5_DATA, big5);
+}
+
+#[test]
+fn big5_uao_decode() {
+    let mut buf = vec![0u8; UAO_DECODE_UTF8_DATA.len()];
+    let (utf8, _) = decode_to_str(UAO_DECODE_BIG5_DATA, &mut buf, true).unwrap();
+
+    assert_eq!(UAO_DECODE_UTF8_DATA, utf8.as_bytes());
+}
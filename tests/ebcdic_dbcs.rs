@@ -0,0 +1,168 @@
+extern crate text_encoding;
+
+use text_encoding::ebcdic_dbcs::{ibm_930, ibm_933, ibm_935, ibm_937, ibm_939, State};
+
+const IBM930_DECODE_EBCDIC_DATA: &[u8] =
+    include_bytes!("test_data/ebcdic_dbcs/ibm930_test_decode_in.txt");
+const IBM930_DECODE_UTF8_DATA: &[u8] =
+    include_bytes!("test_data/ebcdic_dbcs/ibm930_test_decode_out.txt");
+const IBM930_ENCODE_UTF8_DATA: &[u8] =
+    include_bytes!("test_data/ebcdic_dbcs/ibm930_test_encode_in.txt");
+const IBM930_ENCODE_EBCDIC_DATA: &[u8] =
+    include_bytes!("test_data/ebcdic_dbcs/ibm930_test_encode_out.txt");
+
+const IBM933_DECODE_EBCDIC_DATA: &[u8] =
+    include_bytes!("test_data/ebcdic_dbcs/ibm933_test_decode_in.txt");
+const IBM933_DECODE_UTF8_DATA: &[u8] =
+    include_bytes!("test_data/ebcdic_dbcs/ibm933_test_decode_out.txt");
+const IBM933_ENCODE_UTF8_DATA: &[u8] =
+    include_bytes!("test_data/ebcdic_dbcs/ibm933_test_encode_in.txt");
+const IBM933_ENCODE_EBCDIC_DATA: &[u8] =
+    include_bytes!("test_data/ebcdic_dbcs/ibm933_test_encode_out.txt");
+
+const IBM935_DECODE_EBCDIC_DATA: &[u8] =
+    include_bytes!("test_data/ebcdic_dbcs/ibm935_test_decode_in.txt");
+const IBM935_DECODE_UTF8_DATA: &[u8] =
+    include_bytes!("test_data/ebcdic_dbcs/ibm935_test_decode_out.txt");
+const IBM935_ENCODE_UTF8_DATA: &[u8] =
+    include_bytes!("test_data/ebcdic_dbcs/ibm935_test_encode_in.txt");
+const IBM935_ENCODE_EBCDIC_DATA: &[u8] =
+    include_bytes!("test_data/ebcdic_dbcs/ibm935_test_encode_out.txt");
+
+const IBM937_DECODE_EBCDIC_DATA: &[u8] =
+    include_bytes!("test_data/ebcdic_dbcs/ibm937_test_decode_in.txt");
+const IBM937_DECODE_UTF8_DATA: &[u8] =
+    include_bytes!("test_data/ebcdic_dbcs/ibm937_test_decode_out.txt");
+const IBM937_ENCODE_UTF8_DATA: &[u8] =
+    include_bytes!("test_data/ebcdic_dbcs/ibm937_test_encode_in.txt");
+const IBM937_ENCODE_EBCDIC_DATA: &[u8] =
+    include_bytes!("test_data/ebcdic_dbcs/ibm937_test_encode_out.txt");
+
+const IBM939_DECODE_EBCDIC_DATA: &[u8] =
+    include_bytes!("test_data/ebcdic_dbcs/ibm939_test_decode_in.txt");
+const IBM939_DECODE_UTF8_DATA: &[u8] =
+    include_bytes!("test_data/ebcdic_dbcs/ibm939_test_decode_out.txt");
+const IBM939_ENCODE_UTF8_DATA: &[u8] =
+    include_bytes!("test_data/ebcdic_dbcs/ibm939_test_encode_in.txt");
+const IBM939_ENCODE_EBCDIC_DATA: &[u8] =
+    include_bytes!("test_data/ebcdic_dbcs/ibm939_test_encode_out.txt");
+
+#[test]
+fn ibm_930_encode() {
+    let mut buf = vec![0u8; IBM930_ENCODE_EBCDIC_DATA.len()];
+    let (encoded, _) = ibm_930::encode_from_str(
+        std::str::from_utf8(IBM930_ENCODE_UTF8_DATA).unwrap(),
+        &mut buf,
+        &mut State::new(),
+        true,
+    )
+    .unwrap();
+
+    assert_eq!(IBM930_ENCODE_EBCDIC_DATA, encoded);
+}
+
+#[test]
+fn ibm_930_decode() {
+    let mut buf = vec![0u8; IBM930_DECODE_UTF8_DATA.len()];
+    let (utf8, _) =
+        ibm_930::decode_to_str(IBM930_DECODE_EBCDIC_DATA, &mut buf, &mut State::new(), true)
+            .unwrap();
+
+    assert_eq!(IBM930_DECODE_UTF8_DATA, utf8.as_bytes());
+}
+
+#[test]
+fn ibm_933_encode() {
+    let mut buf = vec![0u8; IBM933_ENCODE_EBCDIC_DATA.len()];
+    let (encoded, _) = ibm_933::encode_from_str(
+        std::str::from_utf8(IBM933_ENCODE_UTF8_DATA).unwrap(),
+        &mut buf,
+        &mut State::new(),
+        true,
+    )
+    .unwrap();
+
+    assert_eq!(IBM933_ENCODE_EBCDIC_DATA, encoded);
+}
+
+#[test]
+fn ibm_933_decode() {
+    let mut buf = vec![0u8; IBM933_DECODE_UTF8_DATA.len()];
+    let (utf8, _) =
+        ibm_933::decode_to_str(IBM933_DECODE_EBCDIC_DATA, &mut buf, &mut State::new(), true)
+            .unwrap();
+
+    assert_eq!(IBM933_DECODE_UTF8_DATA, utf8.as_bytes());
+}
+
+#[test]
+fn ibm_935_encode() {
+    let mut buf = vec![0u8; IBM935_ENCODE_EBCDIC_DATA.len()];
+    let (encoded, _) = ibm_935::encode_from_str(
+        std::str::from_utf8(IBM935_ENCODE_UTF8_DATA).unwrap(),
+        &mut buf,
+        &mut State::new(),
+        true,
+    )
+    .unwrap();
+
+    assert_eq!(IBM935_ENCODE_EBCDIC_DATA, encoded);
+}
+
+#[test]
+fn ibm_935_decode() {
+    let mut buf = vec![0u8; IBM935_DECODE_UTF8_DATA.len()];
+    let (utf8, _) =
+        ibm_935::decode_to_str(IBM935_DECODE_EBCDIC_DATA, &mut buf, &mut State::new(), true)
+            .unwrap();
+
+    assert_eq!(IBM935_DECODE_UTF8_DATA, utf8.as_bytes());
+}
+
+#[test]
+fn ibm_937_encode() {
+    let mut buf = vec![0u8; IBM937_ENCODE_EBCDIC_DATA.len()];
+    let (encoded, _) = ibm_937::encode_from_str(
+        std::str::from_utf8(IBM937_ENCODE_UTF8_DATA).unwrap(),
+        &mut buf,
+        &mut State::new(),
+        true,
+    )
+    .unwrap();
+
+    assert_eq!(IBM937_ENCODE_EBCDIC_DATA, encoded);
+}
+
+#[test]
+fn ibm_937_decode() {
+    let mut buf = vec![0u8; IBM937_DECODE_UTF8_DATA.len()];
+    let (utf8, _) =
+        ibm_937::decode_to_str(IBM937_DECODE_EBCDIC_DATA, &mut buf, &mut State::new(), true)
+            .unwrap();
+
+    assert_eq!(IBM937_DECODE_UTF8_DATA, utf8.as_bytes());
+}
+
+#[test]
+fn ibm_939_encode() {
+    let mut buf = vec![0u8; IBM939_ENCODE_EBCDIC_DATA.len()];
+    let (encoded, _) = ibm_939::encode_from_str(
+        std::str::from_utf8(IBM939_ENCODE_UTF8_DATA).unwrap(),
+        &mut buf,
+        &mut State::new(),
+        true,
+    )
+    .unwrap();
+
+    assert_eq!(IBM939_ENCODE_EBCDIC_DATA, encoded);
+}
+
+#[test]
+fn ibm_939_decode() {
+    let mut buf = vec![0u8; IBM939_DECODE_UTF8_DATA.len()];
+    let (utf8, _) =
+        ibm_939::decode_to_str(IBM939_DECODE_EBCDIC_DATA, &mut buf, &mut State::new(), true)
+            .unwrap();
+
+    assert_eq!(IBM939_DECODE_UTF8_DATA, utf8.as_bytes());
+}
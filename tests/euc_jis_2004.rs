@@ -0,0 +1,33 @@
+extern crate text_encoding;
+
+use text_encoding::euc_jis_2004::{decode_to_str, encode_from_str};
+
+const EUC_DECODE_EUC_DATA: &[u8] =
+    include_bytes!("test_data/jis0213/euc_jis_2004_test_decode_in.txt");
+const EUC_DECODE_UTF8_DATA: &[u8] =
+    include_bytes!("test_data/jis0213/euc_jis_2004_test_decode_out.txt");
+const EUC_ENCODE_UTF8_DATA: &[u8] =
+    include_bytes!("test_data/jis0213/euc_jis_2004_test_encode_in.txt");
+const EUC_ENCODE_EUC_DATA: &[u8] =
+    include_bytes!("test_data/jis0213/euc_jis_2004_test_encode_out.txt");
+
+#[test]
+fn euc_jis_2004_encode() {
+    let mut buf = vec![0u8; EUC_ENCODE_EUC_DATA.len()];
+    let (encoded, _) = encode_from_str(
+        std::str::from_utf8(EUC_ENCODE_UTF8_DATA).unwrap(),
+        &mut buf,
+        true,
+    )
+    .unwrap();
+
+    assert_eq!(EUC_ENCODE_EUC_DATA, encoded);
+}
+
+#[test]
+fn euc_jis_2004_decode() {
+    let mut buf = vec![0u8; EUC_DECODE_UTF8_DATA.len()];
+    let (utf8, _) = decode_to_str(EUC_DECODE_EUC_DATA, &mut buf, true).unwrap();
+
+    assert_eq!(EUC_DECODE_UTF8_DATA, utf8.as_bytes());
+}
@@ -0,0 +1,32 @@
+extern crate text_encoding;
+
+use text_encoding::hz_gb_2312::{decode_to_str, encode_from_str, State};
+
+const HZ_DECODE_HZ_DATA: &[u8] = include_bytes!("test_data/iso2022/hz_gb_2312_test_decode_in.txt");
+const HZ_DECODE_UTF8_DATA: &[u8] =
+    include_bytes!("test_data/iso2022/hz_gb_2312_test_decode_out.txt");
+const HZ_ENCODE_UTF8_DATA: &[u8] =
+    include_bytes!("test_data/iso2022/hz_gb_2312_test_encode_in.txt");
+const HZ_ENCODE_HZ_DATA: &[u8] = include_bytes!("test_data/iso2022/hz_gb_2312_test_encode_out.txt");
+
+#[test]
+fn hz_gb_2312_encode() {
+    let mut buf = vec![0u8; HZ_ENCODE_HZ_DATA.len()];
+    let (encoded, _) = encode_from_str(
+        std::str::from_utf8(HZ_ENCODE_UTF8_DATA).unwrap(),
+        &mut buf,
+        &mut State::new(),
+        true,
+    )
+    .unwrap();
+
+    assert_eq!(HZ_ENCODE_HZ_DATA, encoded);
+}
+
+#[test]
+fn hz_gb_2312_decode() {
+    let mut buf = vec![0u8; HZ_DECODE_UTF8_DATA.len()];
+    let (utf8, _) = decode_to_str(HZ_DECODE_HZ_DATA, &mut buf, &mut State::new(), true).unwrap();
+
+    assert_eq!(HZ_DECODE_UTF8_DATA, utf8.as_bytes());
+}
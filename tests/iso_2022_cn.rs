@@ -0,0 +1,33 @@
+extern crate text_encoding;
+
+use text_encoding::iso_2022_cn::{decode_to_str, encode_from_str, State};
+
+const CN_DECODE_CN_DATA: &[u8] = include_bytes!("test_data/iso2022/iso_2022_cn_test_decode_in.txt");
+const CN_DECODE_UTF8_DATA: &[u8] =
+    include_bytes!("test_data/iso2022/iso_2022_cn_test_decode_out.txt");
+const CN_ENCODE_UTF8_DATA: &[u8] =
+    include_bytes!("test_data/iso2022/iso_2022_cn_test_encode_in.txt");
+const CN_ENCODE_CN_DATA: &[u8] =
+    include_bytes!("test_data/iso2022/iso_2022_cn_test_encode_out.txt");
+
+#[test]
+fn iso_2022_cn_encode() {
+    let mut buf = vec![0u8; CN_ENCODE_CN_DATA.len()];
+    let (encoded, _) = encode_from_str(
+        std::str::from_utf8(CN_ENCODE_UTF8_DATA).unwrap(),
+        &mut buf,
+        &mut State::new(),
+        true,
+    )
+    .unwrap();
+
+    assert_eq!(CN_ENCODE_CN_DATA, encoded);
+}
+
+#[test]
+fn iso_2022_cn_decode() {
+    let mut buf = vec![0u8; CN_DECODE_UTF8_DATA.len()];
+    let (utf8, _) = decode_to_str(CN_DECODE_CN_DATA, &mut buf, &mut State::new(), true).unwrap();
+
+    assert_eq!(CN_DECODE_UTF8_DATA, utf8.as_bytes());
+}
@@ -0,0 +1,33 @@
+extern crate text_encoding;
+
+use text_encoding::iso_2022_kr::{decode_to_str, encode_from_str, State};
+
+const KR_DECODE_KR_DATA: &[u8] = include_bytes!("test_data/iso2022/iso_2022_kr_test_decode_in.txt");
+const KR_DECODE_UTF8_DATA: &[u8] =
+    include_bytes!("test_data/iso2022/iso_2022_kr_test_decode_out.txt");
+const KR_ENCODE_UTF8_DATA: &[u8] =
+    include_bytes!("test_data/iso2022/iso_2022_kr_test_encode_in.txt");
+const KR_ENCODE_KR_DATA: &[u8] =
+    include_bytes!("test_data/iso2022/iso_2022_kr_test_encode_out.txt");
+
+#[test]
+fn iso_2022_kr_encode() {
+    let mut buf = vec![0u8; KR_ENCODE_KR_DATA.len()];
+    let (encoded, _) = encode_from_str(
+        std::str::from_utf8(KR_ENCODE_UTF8_DATA).unwrap(),
+        &mut buf,
+        &mut State::new(),
+        true,
+    )
+    .unwrap();
+
+    assert_eq!(KR_ENCODE_KR_DATA, encoded);
+}
+
+#[test]
+fn iso_2022_kr_decode() {
+    let mut buf = vec![0u8; KR_DECODE_UTF8_DATA.len()];
+    let (utf8, _) = decode_to_str(KR_DECODE_KR_DATA, &mut buf, &mut State::new(), true).unwrap();
+
+    assert_eq!(KR_DECODE_UTF8_DATA, utf8.as_bytes());
+}
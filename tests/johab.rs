@@ -0,0 +1,29 @@
+extern crate text_encoding;
+
+use text_encoding::johab::{decode_to_str, encode_from_str};
+
+const JOHAB_DECODE_JOHAB_DATA: &[u8] = include_bytes!("test_data/ksx1001/johab_test_decode_in.txt");
+const JOHAB_DECODE_UTF8_DATA: &[u8] = include_bytes!("test_data/ksx1001/johab_test_decode_out.txt");
+const JOHAB_ENCODE_UTF8_DATA: &[u8] = include_bytes!("test_data/ksx1001/johab_test_encode_in.txt");
+const JOHAB_ENCODE_JOHAB_DATA: &[u8] =
+    include_bytes!("test_data/ksx1001/johab_test_encode_out.txt");
+
+#[test]
+fn johab_encode() {
+    let mut buf = vec![0u8; JOHAB_ENCODE_JOHAB_DATA.len()];
+    let (encoded, _) = encode_from_str(
+        std::str::from_utf8(JOHAB_ENCODE_UTF8_DATA).unwrap(),
+        &mut buf,
+    )
+    .unwrap();
+
+    assert_eq!(JOHAB_ENCODE_JOHAB_DATA, encoded);
+}
+
+#[test]
+fn johab_decode() {
+    let mut buf = vec![0u8; JOHAB_DECODE_UTF8_DATA.len()];
+    let (utf8, _) = decode_to_str(JOHAB_DECODE_JOHAB_DATA, &mut buf, true).unwrap();
+
+    assert_eq!(JOHAB_DECODE_UTF8_DATA, utf8.as_bytes());
+}
@@ -0,0 +1,126 @@
+extern crate text_encoding;
+
+use text_encoding::{mac_chinese_simp, mac_chinese_trad, mac_japanese, mac_korean};
+
+const MAC_CHINESE_SIMP_DECODE_MAC_DATA: &[u8] =
+    include_bytes!("test_data/mac_cjk/mac_chinese_simp_test_decode_in.txt");
+const MAC_CHINESE_SIMP_DECODE_UTF8_DATA: &[u8] =
+    include_bytes!("test_data/mac_cjk/mac_chinese_simp_test_decode_out.txt");
+const MAC_CHINESE_SIMP_ENCODE_UTF8_DATA: &[u8] =
+    include_bytes!("test_data/mac_cjk/mac_chinese_simp_test_encode_in.txt");
+const MAC_CHINESE_SIMP_ENCODE_MAC_DATA: &[u8] =
+    include_bytes!("test_data/mac_cjk/mac_chinese_simp_test_encode_out.txt");
+
+#[test]
+fn mac_chinese_simp_encode() {
+    let mut buf = vec![0u8; MAC_CHINESE_SIMP_ENCODE_MAC_DATA.len()];
+    let (encoded, _) = mac_chinese_simp::encode_from_str(
+        std::str::from_utf8(MAC_CHINESE_SIMP_ENCODE_UTF8_DATA).unwrap(),
+        &mut buf,
+        true,
+    )
+    .unwrap();
+
+    assert_eq!(MAC_CHINESE_SIMP_ENCODE_MAC_DATA, encoded);
+}
+
+#[test]
+fn mac_chinese_simp_decode() {
+    let mut buf = vec![0u8; MAC_CHINESE_SIMP_DECODE_UTF8_DATA.len()];
+    let (utf8, _) =
+        mac_chinese_simp::decode_to_str(MAC_CHINESE_SIMP_DECODE_MAC_DATA, &mut buf, true).unwrap();
+
+    assert_eq!(MAC_CHINESE_SIMP_DECODE_UTF8_DATA, utf8.as_bytes());
+}
+
+const MAC_CHINESE_TRAD_DECODE_MAC_DATA: &[u8] =
+    include_bytes!("test_data/mac_cjk/mac_chinese_trad_test_decode_in.txt");
+const MAC_CHINESE_TRAD_DECODE_UTF8_DATA: &[u8] =
+    include_bytes!("test_data/mac_cjk/mac_chinese_trad_test_decode_out.txt");
+const MAC_CHINESE_TRAD_ENCODE_UTF8_DATA: &[u8] =
+    include_bytes!("test_data/mac_cjk/mac_chinese_trad_test_encode_in.txt");
+const MAC_CHINESE_TRAD_ENCODE_MAC_DATA: &[u8] =
+    include_bytes!("test_data/mac_cjk/mac_chinese_trad_test_encode_out.txt");
+
+#[test]
+fn mac_chinese_trad_encode() {
+    let mut buf = vec![0u8; MAC_CHINESE_TRAD_ENCODE_MAC_DATA.len()];
+    let (encoded, _) = mac_chinese_trad::encode_from_str(
+        std::str::from_utf8(MAC_CHINESE_TRAD_ENCODE_UTF8_DATA).unwrap(),
+        &mut buf,
+        true,
+    )
+    .unwrap();
+
+    assert_eq!(MAC_CHINESE_TRAD_ENCODE_MAC_DATA, encoded);
+}
+
+#[test]
+fn mac_chinese_trad_decode() {
+    let mut buf = vec![0u8; MAC_CHINESE_TRAD_DECODE_UTF8_DATA.len()];
+    let (utf8, _) =
+        mac_chinese_trad::decode_to_str(MAC_CHINESE_TRAD_DECODE_MAC_DATA, &mut buf, true).unwrap();
+
+    assert_eq!(MAC_CHINESE_TRAD_DECODE_UTF8_DATA, utf8.as_bytes());
+}
+
+const MAC_JAPANESE_DECODE_MAC_DATA: &[u8] =
+    include_bytes!("test_data/mac_cjk/mac_japanese_test_decode_in.txt");
+const MAC_JAPANESE_DECODE_UTF8_DATA: &[u8] =
+    include_bytes!("test_data/mac_cjk/mac_japanese_test_decode_out.txt");
+const MAC_JAPANESE_ENCODE_UTF8_DATA: &[u8] =
+    include_bytes!("test_data/mac_cjk/mac_japanese_test_encode_in.txt");
+const MAC_JAPANESE_ENCODE_MAC_DATA: &[u8] =
+    include_bytes!("test_data/mac_cjk/mac_japanese_test_encode_out.txt");
+
+#[test]
+fn mac_japanese_encode() {
+    let mut buf = vec![0u8; MAC_JAPANESE_ENCODE_MAC_DATA.len()];
+    let (encoded, _) = mac_japanese::encode_from_str(
+        std::str::from_utf8(MAC_JAPANESE_ENCODE_UTF8_DATA).unwrap(),
+        &mut buf,
+        true,
+    )
+    .unwrap();
+
+    assert_eq!(MAC_JAPANESE_ENCODE_MAC_DATA, encoded);
+}
+
+#[test]
+fn mac_japanese_decode() {
+    let mut buf = vec![0u8; MAC_JAPANESE_DECODE_UTF8_DATA.len()];
+    let (utf8, _) =
+        mac_japanese::decode_to_str(MAC_JAPANESE_DECODE_MAC_DATA, &mut buf, true).unwrap();
+
+    assert_eq!(MAC_JAPANESE_DECODE_UTF8_DATA, utf8.as_bytes());
+}
+
+const MAC_KOREAN_DECODE_MAC_DATA: &[u8] =
+    include_bytes!("test_data/mac_cjk/mac_korean_test_decode_in.txt");
+const MAC_KOREAN_DECODE_UTF8_DATA: &[u8] =
+    include_bytes!("test_data/mac_cjk/mac_korean_test_decode_out.txt");
+const MAC_KOREAN_ENCODE_UTF8_DATA: &[u8] =
+    include_bytes!("test_data/mac_cjk/mac_korean_test_encode_in.txt");
+const MAC_KOREAN_ENCODE_MAC_DATA: &[u8] =
+    include_bytes!("test_data/mac_cjk/mac_korean_test_encode_out.txt");
+
+#[test]
+fn mac_korean_encode() {
+    let mut buf = vec![0u8; MAC_KOREAN_ENCODE_MAC_DATA.len()];
+    let (encoded, _) = mac_korean::encode_from_str(
+        std::str::from_utf8(MAC_KOREAN_ENCODE_UTF8_DATA).unwrap(),
+        &mut buf,
+        true,
+    )
+    .unwrap();
+
+    assert_eq!(MAC_KOREAN_ENCODE_MAC_DATA, encoded);
+}
+
+#[test]
+fn mac_korean_decode() {
+    let mut buf = vec![0u8; MAC_KOREAN_DECODE_UTF8_DATA.len()];
+    let (utf8, _) = mac_korean::decode_to_str(MAC_KOREAN_DECODE_MAC_DATA, &mut buf, true).unwrap();
+
+    assert_eq!(MAC_KOREAN_DECODE_UTF8_DATA, utf8.as_bytes());
+}
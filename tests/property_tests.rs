@@ -4,7 +4,7 @@ extern crate text_encoding;
 
 use proptest::collection::vec;
 use proptest::test_runner::Config;
-use text_encoding::{single_byte::*, *};
+use text_encoding::{ebcdic_dbcs::*, single_byte::*, *};
 
 proptest! {
     #![proptest_config(Config::with_cases(512))]
@@ -31,7 +31,7 @@ proptest! {
         while !tmp.is_empty() {
             if let Ok((decoded, n)) = utf8::decode_to_str(tmp, &mut buf, true) {
                 tmp = &tmp[n..];
-                utf8.extend(decoded.chars());
+                utf8.push_str(decoded);
             } else {
                 panic!("Error when decoding.");
             }
@@ -81,7 +81,7 @@ proptest! {
         while !tmp.is_empty() {
             if let Ok((decoded, n)) = utf16_be::decode_to_str(tmp, &mut buf, true) {
                 tmp = &tmp[n..];
-                utf8.extend(decoded.chars());
+                utf8.push_str(decoded);
             } else {
                 panic!("Error when decoding.");
             }
@@ -129,7 +129,7 @@ proptest! {
         while !tmp.is_empty() {
             if let Ok((decoded, n)) = utf16_le::decode_to_str(tmp, &mut buf, true) {
                 tmp = &tmp[n..];
-                utf8.extend(decoded.chars());
+                utf8.push_str(decoded);
             } else {
                 panic!("Error when decoding.");
             }
@@ -177,7 +177,7 @@ proptest! {
         while !tmp.is_empty() {
             if let Ok((decoded, n)) = utf32_be::decode_to_str(tmp, &mut buf, true) {
                 tmp = &tmp[n..];
-                utf8.extend(decoded.chars());
+                utf8.push_str(decoded);
             } else {
                 panic!("Error when decoding.");
             }
@@ -225,7 +225,7 @@ proptest! {
         while !tmp.is_empty() {
             if let Ok((decoded, n)) = utf32_le::decode_to_str(tmp, &mut buf, true) {
                 tmp = &tmp[n..];
-                utf8.extend(decoded.chars());
+                utf8.push_str(decoded);
             } else {
                 panic!("Error when decoding.");
             }
@@ -262,7 +262,7 @@ proptest! {
         while !tmp.is_empty() {
             if let Ok((decoded, n)) = ascii::decode_to_str(tmp, &mut buf) {
                 tmp = &tmp[n..];
-                utf8.extend(decoded.chars());
+                utf8.push_str(decoded);
             } else {
                 panic!("Error when decoding.");
             }
@@ -309,7 +309,7 @@ proptest! {
         while !tmp.is_empty() {
             if let Ok((decoded, n)) = iso_8859_1::decode_to_str(tmp, &mut buf) {
                 tmp = &tmp[n..];
-                utf8.extend(decoded.chars());
+                utf8.push_str(decoded);
             } else {
                 panic!("Error when decoding.");
             }
@@ -365,7 +365,7 @@ proptest! {
         while !tmp.is_empty() {
             if let Ok((decoded, n)) = iso_8859_7::decode_to_str(tmp, &mut buf) {
                 tmp = &tmp[n..];
-                utf8.extend(decoded.chars());
+                utf8.push_str(decoded);
             } else {
                 panic!("Error when decoding.");
             }
@@ -401,6 +401,936 @@ proptest! {
         let _ = iso_8859_7::encode_from_str(text, &mut buf);
     }
 
+    // The ibm-037 tests are representative of all single-byte encodings
+    // that aren't ascii extensions.
+    #[test]
+    fn pt_ibm_037_roundtrip(data in vec(0u8..=255, 0..1000)) {
+        let mut buf = [0u8; 32];
+        let mut utf8 = String::new();
+        let mut ibm_037: Vec<u8> = Vec::new();
+
+        // Decode from ibm-037 to utf8, with NL and LF swapped.
+        let mut tmp = &data[..];
+        while !tmp.is_empty() {
+            if let Ok((decoded, n)) = ibm_037::decode_to_str_swap_nl_lf(tmp, &mut buf) {
+                tmp = &tmp[n..];
+                utf8.push_str(decoded);
+            } else {
+                panic!("Error when decoding.");
+            }
+        }
+
+        // Encode to from utf8 back to ibm-037
+        let mut tmp = &utf8[..];
+        while !tmp.is_empty() {
+            if let Ok((encoded, n)) = ibm_037::encode_from_str_swap_nl_lf(tmp, &mut buf) {
+                tmp = &tmp[n..];
+                ibm_037.extend_from_slice(encoded);
+            } else {
+                panic!("Error when encoding.");
+            }
+        }
+
+        assert_eq!(&data[..], &ibm_037[..]);
+    }
+
+    #[test]
+    fn pt_ibm_037_encode_random_text(ref text in "\\PC*\\PC*\\PC*") {
+        // Attempt to encode, but probably fail.  The important thing is that
+        // it should never panic, only return errors.
+        let mut buf = vec![0u8; 4096];
+        let _ = ibm_037::encode_from_str(text, &mut buf);
+    }
+
+    #[test]
+    fn pt_ibm_437_graphics_roundtrip(data in vec(0u8..=255, 0..1000)) {
+        let mut buf = [0u8; 32];
+        let mut utf8 = String::new();
+        let mut ibm_437: Vec<u8> = Vec::new();
+
+        // Decode from ibm-437 to utf8
+        let mut tmp = &data[..];
+        while !tmp.is_empty() {
+            if let Ok((decoded, n)) = ibm_437::decode_to_str_graphics(tmp, &mut buf) {
+                tmp = &tmp[n..];
+                utf8.push_str(decoded);
+            } else {
+                panic!("Error when decoding.");
+            }
+        }
+
+        // Encode to from utf8 back to ibm-437
+        let mut tmp = &utf8[..];
+        while !tmp.is_empty() {
+            if let Ok((encoded, n)) = ibm_437::encode_from_str_graphics(tmp, &mut buf) {
+                tmp = &tmp[n..];
+                ibm_437.extend_from_slice(encoded);
+            } else {
+                panic!("Error when encoding.");
+            }
+        }
+
+        assert_eq!(&data[..], &ibm_437[..]);
+    }
+
+    // The mac-hebrew tests are representative of all single-byte encodings
+    // with sequences.
+    #[test]
+    fn pt_mac_hebrew_roundtrip(data in vec(0u8..=255, 0..1000)) {
+        let mut buf = [0u8; 32];
+        let mut utf8 = String::new();
+        let mut mac_hebrew: Vec<u8> = Vec::new();
+
+        // Decode from mac-hebrew to utf8
+        let mut tmp = &data[..];
+        while !tmp.is_empty() {
+            if let Ok((decoded, n)) = mac_hebrew::decode_to_str(tmp, &mut buf) {
+                tmp = &tmp[n..];
+                utf8.push_str(decoded);
+            } else {
+                panic!("Error when decoding.");
+            }
+        }
+
+        // Encode to from utf8 back to mac-hebrew
+        let mut tmp = &utf8[..];
+        while !tmp.is_empty() {
+            if let Ok((encoded, n)) = mac_hebrew::encode_from_str(tmp, &mut buf, true) {
+                tmp = &tmp[n..];
+                mac_hebrew.extend_from_slice(encoded);
+            } else {
+                panic!("Error when encoding.");
+            }
+        }
+
+        assert_eq!(&data[..], &mac_hebrew[..]);
+    }
+
+    #[test]
+    fn pt_mac_hebrew_encode_random_text(ref text in "\\PC*\\PC*\\PC*") {
+        // Attempt to encode, but probably fail.  The important thing is that
+        // it should never panic, only return errors.
+        let mut buf = vec![0u8; 4096];
+        let _ = mac_hebrew::encode_from_str(text, &mut buf, false);
+        let _ = mac_hebrew::encode_from_str(text, &mut buf, false);
+        let _ = mac_hebrew::encode_from_str(text, &mut buf, true);
+    }
+
+    #[test]
+    fn pt_zapf_dingbats_roundtrip(data in vec(0x20u8..=0x7E, 0..1000)) {
+        let mut buf = [0u8; 32];
+        let mut utf8 = String::new();
+        let mut zapf_dingbats: Vec<u8> = Vec::new();
+
+        // Decode from zapf-dingbats to utf8
+        let mut tmp = &data[..];
+        while !tmp.is_empty() {
+            if let Ok((decoded, n)) = zapf_dingbats::decode_to_str(tmp, &mut buf) {
+                tmp = &tmp[n..];
+                utf8.push_str(decoded);
+            } else {
+                panic!("Error when decoding.");
+            }
+        }
+
+        // Encode to from utf8 back to zapf-dingbats
+        let mut tmp = &utf8[..];
+        while !tmp.is_empty() {
+            if let Ok((encoded, n)) = zapf_dingbats::encode_from_str(tmp, &mut buf) {
+                tmp = &tmp[n..];
+                zapf_dingbats.extend_from_slice(encoded);
+            } else {
+                panic!("Error when encoding.");
+            }
+        }
+
+        assert_eq!(&data[..], &zapf_dingbats[..]);
+    }
+
+    #[test]
+    fn pt_adobe_symbol_encode_random_text(ref text in "\\PC*\\PC*\\PC*") {
+        // Attempt to encode, but probably fail.  The important thing is that
+        // it should never panic, only return errors.
+        let mut buf = vec![0u8; 4096];
+        let _ = adobe_symbol::encode_from_str(text, &mut buf);
+    }
+
+    #[test]
+    fn pt_petscii_roundtrip(mut data in vec(0u8..=255, 0..1000)) {
+        let mut buf = [0u8; 32];
+
+        // Replace the repeated bytes with the ones they encode back to.
+        for b in data.iter_mut() {
+            *b = match *b {
+                0x60..=0x7F => *b + 0x60,
+                0xE0..=0xFE => *b - 0x40,
+                0xFF => 0xDE,
+                _ => *b,
+            };
+        }
+
+        for &mode in [petscii::Mode::Unshifted, petscii::Mode::Shifted].iter() {
+            let mut utf8 = String::new();
+            let mut petscii: Vec<u8> = Vec::new();
+
+            // Decode from petscii to utf8
+            let mut tmp = &data[..];
+            while !tmp.is_empty() {
+                if let Ok((decoded, n)) = petscii::decode_to_str(tmp, &mut buf, mode) {
+                    tmp = &tmp[n..];
+                    utf8.push_str(decoded);
+                } else {
+                    panic!("Error when decoding.");
+                }
+            }
+
+            // Encode to from utf8 back to petscii
+            let mut tmp = &utf8[..];
+            while !tmp.is_empty() {
+                if let Ok((encoded, n)) = petscii::encode_from_str(tmp, &mut buf, mode) {
+                    tmp = &tmp[n..];
+                    petscii.extend_from_slice(encoded);
+                } else {
+                    panic!("Error when encoding.");
+                }
+            }
+
+            assert_eq!(&data[..], &petscii[..]);
+        }
+    }
+
+    #[test]
+    fn pt_windows_1258_decompose_roundtrip(ref text in "[a-zA-Z ăâđêôơưĂÂĐÊÔƠƯàảãáạằẳẵắặầẩẫấậèẻẽéẹềểễếệìỉĩíịòỏõóọồổỗốộờởỡớợùủũúụừửữứựỳỷỹýỵ]*") {
+        let mut buf = [0u8; 32];
+        let mut encoded: Vec<u8> = Vec::new();
+        let mut utf8 = String::new();
+
+        // Encode from utf8 to windows-1258, decomposing
+        let mut tmp = &text[..];
+        while !tmp.is_empty() {
+            if let Ok((enc, n)) = windows_1258::encode_from_str(tmp, &mut buf) {
+                tmp = &tmp[n..];
+                encoded.extend_from_slice(enc);
+            } else {
+                panic!("Error when encoding.");
+            }
+        }
+
+        // Decode back to utf8, composing
+        let mut tmp = &encoded[..];
+        while !tmp.is_empty() {
+            if let Ok((decoded, n)) = windows_1258::decode_to_str_composed(tmp, &mut buf, true) {
+                tmp = &tmp[n..];
+                utf8.push_str(decoded);
+            } else {
+                panic!("Error when decoding.");
+            }
+        }
+
+        assert_eq!(&text[..], &utf8[..]);
+    }
+
+    #[test]
+    fn pt_vni_roundtrip(ref text in "[a-zA-Z ăâđêôơưĂÂĐÊÔƠƯàảãáạằẳẵắặầẩẫấậèẻẽéẹềểễếệìỉĩíịòỏõóọồổỗốộờởỡớợùủũúụừửữứựỳỷỹýỵ]*") {
+        let mut buf = [0u8; 32];
+        let mut encoded: Vec<u8> = Vec::new();
+        let mut utf8 = String::new();
+
+        // Encode from utf8 to vni
+        let mut tmp = &text[..];
+        while !tmp.is_empty() {
+            if let Ok((enc, n)) = vni::encode_from_str(tmp, &mut buf) {
+                tmp = &tmp[n..];
+                encoded.extend_from_slice(enc);
+            } else {
+                panic!("Error when encoding.");
+            }
+        }
+
+        // Decode back to utf8
+        let mut tmp = &encoded[..];
+        while !tmp.is_empty() {
+            if let Ok((decoded, n)) = vni::decode_to_str(tmp, &mut buf, true) {
+                tmp = &tmp[n..];
+                utf8.push_str(decoded);
+            } else {
+                panic!("Error when decoding.");
+            }
+        }
+
+        assert_eq!(&text[..], &utf8[..]);
+    }
+
+    #[test]
+    fn pt_vni_decode_random_bytes(ref data in vec(0u8..=255, 0..1000)) {
+        // Attempt to decode, but probably fail.  The important thing is that
+        // it should never panic, only return errors.
+        let mut buf = vec![0u8; 4096];
+        let _ = vni::decode_to_str(data, &mut buf, false);
+        let _ = vni::decode_to_str(data, &mut buf, true);
+    }
+
+    #[test]
+    fn pt_iscii_roundtrip(ref text in "[a-z \n\u{0905}-\u{0914}\u{0915}-\u{0939}\u{093E}-\u{094C}\u{0B85}-\u{0B8A}\u{0B95}\u{0BA4}\u{0C95}-\u{0C99}\u{0A15}-\u{0A28}]*") {
+        let mut buf = [0u8; 32];
+        let mut encoded: Vec<u8> = Vec::new();
+        let mut utf8 = String::new();
+
+        // Encode from utf8 to iscii
+        let mut tmp = &text[..];
+        let mut state = iscii::State::default();
+        while !tmp.is_empty() {
+            if let Ok((enc, n)) = iscii::encode_from_str(tmp, &mut buf, &mut state, true) {
+                tmp = &tmp[n..];
+                encoded.extend_from_slice(enc);
+            } else {
+                panic!("Error when encoding.");
+            }
+        }
+
+        // Decode back to utf8
+        let mut tmp = &encoded[..];
+        let mut state = iscii::State::default();
+        while !tmp.is_empty() {
+            if let Ok((decoded, n)) = iscii::decode_to_str(tmp, &mut buf, &mut state, true) {
+                tmp = &tmp[n..];
+                utf8.push_str(decoded);
+            } else {
+                panic!("Error when decoding.");
+            }
+        }
+
+        assert_eq!(&text[..], &utf8[..]);
+    }
+
+    #[test]
+    fn pt_iscii_decode_random_bytes(ref data in vec(0u8..=255, 0..1000)) {
+        // Attempt to decode, but probably fail.  The important thing is that
+        // it should never panic, only return errors.
+        let mut buf = vec![0u8; 4096];
+        let _ = iscii::decode_to_str(data, &mut buf, &mut iscii::State::default(), false);
+        let _ = iscii::decode_to_str(data, &mut buf, &mut iscii::State::default(), true);
+    }
+
+    #[test]
+    fn pt_iscii_encode_random_text(ref text in "\\PC*\\PC*\\PC*") {
+        // Attempt to encode, but probably fail.  The important thing is that
+        // it should never panic, only return errors.
+        let mut buf = vec![0u8; 4096];
+        let _ = iscii::encode_from_str(text, &mut buf, &mut iscii::State::default(), false);
+        let _ = iscii::encode_from_str(text, &mut buf, &mut iscii::State::default(), true);
+    }
+
+    #[test]
+    fn pt_tscii_roundtrip(ref text in "([கஙசஞடணதநபமயரலவழளறனஜஷஸஹ][ாிீுூெேைொோௌ்]?|[அஆஇஈஉஊஎஏஐஒஓஔ௦௧௨a-z ])*") {
+        let mut buf = [0u8; 32];
+        let mut encoded: Vec<u8> = Vec::new();
+        let mut utf8 = String::new();
+
+        // Encode from utf8 to tscii
+        let mut tmp = &text[..];
+        while !tmp.is_empty() {
+            if let Ok((enc, n)) = tscii::encode_from_str(tmp, &mut buf, true) {
+                tmp = &tmp[n..];
+                encoded.extend_from_slice(enc);
+            } else {
+                panic!("Error when encoding.");
+            }
+        }
+
+        // Decode back to utf8
+        let mut tmp = &encoded[..];
+        while !tmp.is_empty() {
+            if let Ok((decoded, n)) = tscii::decode_to_str(tmp, &mut buf, true) {
+                tmp = &tmp[n..];
+                utf8.push_str(decoded);
+            } else {
+                panic!("Error when decoding.");
+            }
+        }
+
+        assert_eq!(&text[..], &utf8[..]);
+    }
+
+    #[test]
+    fn pt_tscii_decode_random_bytes(ref data in vec(0u8..=255, 0..1000)) {
+        // Attempt to decode, but probably fail.  The important thing is that
+        // it should never panic, only return errors.
+        let mut buf = vec![0u8; 4096];
+        let _ = tscii::decode_to_str(data, &mut buf, false);
+        let _ = tscii::decode_to_str(data, &mut buf, true);
+    }
+
+    #[test]
+    fn pt_tscii_encode_random_text(ref text in "\\PC*\\PC*\\PC*") {
+        // Attempt to encode, but probably fail.  The important thing is that
+        // it should never panic, only return errors.
+        let mut buf = vec![0u8; 4096];
+        let _ = tscii::encode_from_str(text, &mut buf, false);
+        let _ = tscii::encode_from_str(text, &mut buf, true);
+    }
+
+    #[test]
+    fn pt_iso_6937_roundtrip(ref text in "[a-zA-Z0-9 ¡£¥§¤‘“«←↑→↓°±²³×µ¶·÷’”»¼½¾¿ÆÐĦĲĿŁØŒÞŦŊŉĸæđðħıĳŀłøœßþŧŋáàâäãåçéèêëíìîïñóòôöõúùûüýÿčďěľňřšťžőűąęįų´¨]*") {
+        let mut buf = [0u8; 32];
+        let mut encoded: Vec<u8> = Vec::new();
+        let mut utf8 = String::new();
+
+        // Encode from utf8 to iso 6937
+        let mut tmp = &text[..];
+        while !tmp.is_empty() {
+            if let Ok((enc, n)) = iso_6937::encode_from_str(tmp, &mut buf, true) {
+                tmp = &tmp[n..];
+                encoded.extend_from_slice(enc);
+            } else {
+                panic!("Error when encoding.");
+            }
+        }
+
+        // Decode back to utf8
+        let mut tmp = &encoded[..];
+        while !tmp.is_empty() {
+            if let Ok((decoded, n)) = iso_6937::decode_to_str(tmp, &mut buf, true) {
+                tmp = &tmp[n..];
+                utf8.push_str(decoded);
+            } else {
+                panic!("Error when decoding.");
+            }
+        }
+
+        assert_eq!(&text[..], &utf8[..]);
+    }
+
+    #[test]
+    fn pt_iso_6937_decode_random_bytes(ref data in vec(0u8..=255, 0..1000)) {
+        // Attempt to decode, but probably fail.  The important thing is that
+        // it should never panic, only return errors.
+        let mut buf = vec![0u8; 4096];
+        let _ = iso_6937::decode_to_str(data, &mut buf, false);
+        let _ = iso_6937::decode_to_str(data, &mut buf, true);
+    }
+
+    #[test]
+    fn pt_dvb_decode_random_bytes(ref data in vec(0u8..=255, 0..1000)) {
+        // Attempt to decode, but probably fail.  The important thing is that
+        // it should never panic, only return errors.
+        let mut buf = vec![0u8; 4096];
+        let _ = dvb::decode_to_str(data, &mut buf, &mut dvb::State::new(), false);
+        let _ = dvb::decode_to_str(data, &mut buf, &mut dvb::State::new(), true);
+    }
+
+    #[test]
+    fn pt_gsm_0338_roundtrip(ref text in "[a-zA-Z0-9 \n@£$¥èéùìòÇØøÅåΔ_ΦΓΛΩΠΨΣΘΞÆæßÉ!\"#¤%&'()*+,\\-./:;<=>?¡ÄÖÑÜ§¿äöñüà^{}\\[~\\]|€\\\\]*") {
+        let mut buf = [0u8; 32];
+        let mut encoded: Vec<u8> = Vec::new();
+        let mut utf8 = String::new();
+        let tables = gsm_0338::Tables::default();
+
+        // Encode from utf8 to gsm 03.38
+        let mut tmp = &text[..];
+        while !tmp.is_empty() {
+            if let Ok((enc, n)) = gsm_0338::encode_from_str(tmp, &mut buf, &tables) {
+                tmp = &tmp[n..];
+                encoded.extend_from_slice(enc);
+            } else {
+                panic!("Error when encoding.");
+            }
+        }
+
+        // Decode back to utf8
+        let mut tmp = &encoded[..];
+        while !tmp.is_empty() {
+            if let Ok((decoded, n)) = gsm_0338::decode_to_str(tmp, &mut buf, true, &tables) {
+                tmp = &tmp[n..];
+                utf8.push_str(decoded);
+            } else {
+                panic!("Error when decoding.");
+            }
+        }
+
+        assert_eq!(&text[..], &utf8[..]);
+    }
+
+    #[test]
+    fn pt_gsm_0338_packed_roundtrip(ref text in "[a-zA-Z0-9 \n@£$¥èéùìòÇØøÅåΔ_ΦΓΛΩΠΨΣΘΞÆæßÉ!\"#¤%&'()*+,\\-./:;<=>?¡ÄÖÑÜ§¿äöñüà^{}\\[~\\]|€\\\\]*") {
+        let mut buf = [0u8; 32];
+        let mut encoded: Vec<u8> = Vec::new();
+        let mut utf8 = String::new();
+
+        // Encode from utf8 to packed gsm 03.38
+        let mut state = gsm_0338::State::default();
+        let mut tmp = &text[..];
+        loop {
+            if let Ok((enc, n)) = gsm_0338::encode_packed_from_str(tmp, &mut buf, &mut state, true) {
+                tmp = &tmp[n..];
+                encoded.extend_from_slice(enc);
+                if tmp.is_empty() && enc.is_empty() {
+                    break;
+                }
+            } else {
+                panic!("Error when encoding.");
+            }
+        }
+
+        // Decode back to utf8
+        let mut state = gsm_0338::State::default();
+        let mut tmp = &encoded[..];
+        while !tmp.is_empty() {
+            if let Ok((decoded, n)) = gsm_0338::decode_packed_to_str(tmp, &mut buf, &mut state, true) {
+                tmp = &tmp[n..];
+                utf8.push_str(decoded);
+            } else {
+                panic!("Error when decoding.");
+            }
+        }
+
+        assert_eq!(&text[..], &utf8[..]);
+    }
+
+    #[test]
+    fn pt_gsm_0338_decode_random_bytes(ref data in vec(0u8..=255, 0..1000)) {
+        // Attempt to decode, but probably fail.  The important thing is that
+        // it should never panic, only return errors.
+        let mut buf = vec![0u8; 4096];
+        let tables = gsm_0338::Tables::new(gsm_0338::Language::Turkish, gsm_0338::Language::Spanish);
+        let _ = gsm_0338::decode_to_str(data, &mut buf, false, &tables);
+        let _ = gsm_0338::decode_to_str(data, &mut buf, true, &tables);
+        let _ = gsm_0338::decode_packed_to_str(data, &mut buf, &mut gsm_0338::State::new(tables), false);
+        let _ = gsm_0338::decode_packed_to_str(data, &mut buf, &mut gsm_0338::State::new(tables), true);
+    }
+
+    #[test]
+    fn pt_marc8_roundtrip(ref text in "[a-zA-Z0-9 ŁØĐÞÆŒʹ·♭®±ƠƯʼʻłøđþæœʺı£ðơư°ℓ℗©♯¿¡ß€]*") {
+        let mut buf = [0u8; 32];
+        let mut encoded: Vec<u8> = Vec::new();
+        let mut utf8 = String::new();
+
+        // Encode from utf8 to marc-8
+        let mut state = marc8::State::new();
+        let mut tmp = &text[..];
+        while !tmp.is_empty() {
+            if let Ok((enc, n)) = marc8::encode_from_str(tmp, &mut buf, &mut state, true) {
+                tmp = &tmp[n..];
+                encoded.extend_from_slice(enc);
+            } else {
+                panic!("Error when encoding.");
+            }
+        }
+
+        // Decode back to utf8
+        let mut state = marc8::State::new();
+        let mut tmp = &encoded[..];
+        while !tmp.is_empty() {
+            if let Ok((decoded, n)) = marc8::decode_to_str(tmp, &mut buf, &mut state, true) {
+                tmp = &tmp[n..];
+                utf8.push_str(decoded);
+            } else {
+                panic!("Error when decoding.");
+            }
+        }
+
+        assert_eq!(&text[..], &utf8[..]);
+    }
+
+    #[test]
+    fn pt_marc8_decode_random_bytes(ref data in vec(0u8..=255, 0..1000)) {
+        // Attempt to decode, but probably fail.  The important thing is that
+        // it should never panic, only return errors.
+        let mut buf = vec![0u8; 4096];
+        let _ = marc8::decode_to_str(data, &mut buf, &mut marc8::State::new(), false);
+        let _ = marc8::decode_to_str(data, &mut buf, &mut marc8::State::new(), true);
+    }
+
+    #[test]
+    fn pt_marc8_encode_random_text(ref text in "\\PC*\\PC*\\PC*") {
+        // Attempt to encode, but probably fail.  The important thing is that
+        // it should never panic, only return errors.
+        let mut buf = vec![0u8; 4096];
+        let _ = marc8::encode_from_str(text, &mut buf, &mut marc8::State::new(), false);
+        let _ = marc8::encode_from_str(text, &mut buf, &mut marc8::State::new(), true);
+    }
+
+    #[test]
+    fn pt_pdf_text_string_roundtrip(ref text in "(\\PC|[a-zé–“”€ﬁ])*") {
+        let mut buf = [0u8; 32];
+        let mut encoded: Vec<u8> = Vec::new();
+        let mut utf8 = String::new();
+
+        // Encode from utf8 to a pdf text string
+        let mut state = pdf_text_string::State::new();
+        let mut tmp = &text[..];
+        while !tmp.is_empty() {
+            if let Ok((enc, n)) = pdf_text_string::encode_from_str(tmp, &mut buf, &mut state) {
+                tmp = &tmp[n..];
+                encoded.extend_from_slice(enc);
+            } else {
+                panic!("Error when encoding.");
+            }
+        }
+
+        // Decode back to utf8
+        let mut state = pdf_text_string::State::new();
+        let mut tmp = &encoded[..];
+        while !tmp.is_empty() {
+            if let Ok((decoded, n)) = pdf_text_string::decode_to_str(tmp, &mut buf, &mut state, true) {
+                tmp = &tmp[n..];
+                utf8.push_str(decoded);
+            } else {
+                panic!("Error when decoding.");
+            }
+        }
+
+        assert_eq!(&text[..], &utf8[..]);
+    }
+
+    #[test]
+    fn pt_pdf_text_string_decode_random_bytes(ref data in vec(0u8..=255, 0..1000)) {
+        // Attempt to decode, but probably fail.  The important thing is that
+        // it should never panic, only return errors.
+        let mut buf = vec![0u8; 4096];
+        let _ = pdf_text_string::decode_to_str(data, &mut buf, &mut pdf_text_string::State::new(), false);
+        let _ = pdf_text_string::decode_to_str(data, &mut buf, &mut pdf_text_string::State::new(), true);
+    }
+
+    #[test]
+    fn pt_big5_hkscs_decode_random_bytes(ref data in vec(0u8..=255, 0..512)) {
+        // Attempt to decode, but probably fail.  The important thing is that
+        // it should never panic, only return errors.
+        let mut buf = vec![0u8; 4096];
+        let _ = big5_hkscs::decode_to_str(data, &mut buf, false);
+        let _ = big5_hkscs::decode_to_str(data, &mut buf, true);
+    }
+
+    #[test]
+    fn pt_big5_hkscs_encode_random_text(ref text in "\\PC*\\PC*\\PC*") {
+        // Attempt to encode, but probably fail.  The important thing is that
+        // it should never panic, only return errors.
+        let mut buf = vec![0u8; 4096];
+        let _ = big5_hkscs::encode_from_str(text, &mut buf, false);
+        let _ = big5_hkscs::encode_from_str(text, &mut buf, true);
+    }
+
+    #[test]
+    fn pt_big5_uao_decode_random_bytes(ref data in vec(0u8..=255, 0..512)) {
+        // Attempt to decode, but probably fail.  The important thing is that
+        // it should never panic, only return errors.
+        let mut buf = vec![0u8; 4096];
+        let _ = big5_uao::decode_to_str(data, &mut buf, false);
+        let _ = big5_uao::decode_to_str(data, &mut buf, true);
+    }
+
+    #[test]
+    fn pt_big5_uao_encode_random_text(ref text in "\\PC*\\PC*\\PC*") {
+        // Attempt to encode, but probably fail.  The important thing is that
+        // it should never panic, only return errors.
+        let mut buf = vec![0u8; 4096];
+        let _ = big5_uao::encode_from_str(text, &mut buf);
+    }
+
+    #[test]
+    fn pt_euc_jis_2004_decode_random_bytes(ref data in vec(0u8..=255, 0..512)) {
+        // Attempt to decode, but probably fail.  The important thing is that
+        // it should never panic, only return errors.
+        let mut buf = vec![0u8; 4096];
+        let _ = euc_jis_2004::decode_to_str(data, &mut buf, false);
+        let _ = euc_jis_2004::decode_to_str(data, &mut buf, true);
+    }
+
+    #[test]
+    fn pt_euc_jis_2004_encode_random_text(ref text in "\\PC*\\PC*\\PC*") {
+        // Attempt to encode, but probably fail.  The important thing is that
+        // it should never panic, only return errors.
+        let mut buf = vec![0u8; 4096];
+        let _ = euc_jis_2004::encode_from_str(text, &mut buf, false);
+        let _ = euc_jis_2004::encode_from_str(text, &mut buf, true);
+    }
+
+    #[test]
+    fn pt_shift_jis_2004_decode_random_bytes(ref data in vec(0u8..=255, 0..512)) {
+        // Attempt to decode, but probably fail.  The important thing is that
+        // it should never panic, only return errors.
+        let mut buf = vec![0u8; 4096];
+        let _ = shift_jis_2004::decode_to_str(data, &mut buf, false);
+        let _ = shift_jis_2004::decode_to_str(data, &mut buf, true);
+    }
+
+    #[test]
+    fn pt_shift_jis_2004_encode_random_text(ref text in "\\PC*\\PC*\\PC*") {
+        // Attempt to encode, but probably fail.  The important thing is that
+        // it should never panic, only return errors.
+        let mut buf = vec![0u8; 4096];
+        let _ = shift_jis_2004::encode_from_str(text, &mut buf, false);
+        let _ = shift_jis_2004::encode_from_str(text, &mut buf, true);
+    }
+
+    #[test]
+    fn pt_hz_gb_2312_decode_random_bytes(ref data in vec(0u8..=255, 0..512)) {
+        // Attempt to decode, but probably fail.  The important thing is that
+        // it should never panic, only return errors.
+        let mut buf = vec![0u8; 4096];
+        let _ = hz_gb_2312::decode_to_str(data, &mut buf, &mut hz_gb_2312::State::new(), false);
+        let _ = hz_gb_2312::decode_to_str(data, &mut buf, &mut hz_gb_2312::State::new(), true);
+    }
+
+    #[test]
+    fn pt_hz_gb_2312_encode_random_text(ref text in "\\PC*\\PC*\\PC*") {
+        // Attempt to encode, but probably fail.  The important thing is that
+        // it should never panic, only return errors.
+        let mut buf = vec![0u8; 4096];
+        let _ = hz_gb_2312::encode_from_str(text, &mut buf, &mut hz_gb_2312::State::new(), false);
+        let _ = hz_gb_2312::encode_from_str(text, &mut buf, &mut hz_gb_2312::State::new(), true);
+    }
+
+    #[test]
+    fn pt_iso_2022_cn_decode_random_bytes(ref data in vec(0u8..=255, 0..512)) {
+        // Attempt to decode, but probably fail.  The important thing is that
+        // it should never panic, only return errors.
+        let mut buf = vec![0u8; 4096];
+        let _ = iso_2022_cn::decode_to_str(data, &mut buf, &mut iso_2022_cn::State::new(), false);
+        let _ = iso_2022_cn::decode_to_str(data, &mut buf, &mut iso_2022_cn::State::new(), true);
+    }
+
+    #[test]
+    fn pt_iso_2022_cn_encode_random_text(ref text in "\\PC*\\PC*\\PC*") {
+        // Attempt to encode, but probably fail.  The important thing is that
+        // it should never panic, only return errors.
+        let mut buf = vec![0u8; 4096];
+        let _ = iso_2022_cn::encode_from_str(text, &mut buf, &mut iso_2022_cn::State::new(), false);
+        let _ = iso_2022_cn::encode_from_str(text, &mut buf, &mut iso_2022_cn::State::new(), true);
+    }
+
+    #[test]
+    fn pt_iso_2022_kr_decode_random_bytes(ref data in vec(0u8..=255, 0..512)) {
+        // Attempt to decode, but probably fail.  The important thing is that
+        // it should never panic, only return errors.
+        let mut buf = vec![0u8; 4096];
+        let _ = iso_2022_kr::decode_to_str(data, &mut buf, &mut iso_2022_kr::State::new(), false);
+        let _ = iso_2022_kr::decode_to_str(data, &mut buf, &mut iso_2022_kr::State::new(), true);
+    }
+
+    #[test]
+    fn pt_iso_2022_kr_encode_random_text(ref text in "\\PC*\\PC*\\PC*") {
+        // Attempt to encode, but probably fail.  The important thing is that
+        // it should never panic, only return errors.
+        let mut buf = vec![0u8; 4096];
+        let _ = iso_2022_kr::encode_from_str(text, &mut buf, &mut iso_2022_kr::State::new(), false);
+        let _ = iso_2022_kr::encode_from_str(text, &mut buf, &mut iso_2022_kr::State::new(), true);
+    }
+
+    #[test]
+    fn pt_euc_tw_decode_random_bytes(ref data in vec(0u8..=255, 0..512)) {
+        // Attempt to decode, but probably fail.  The important thing is that
+        // it should never panic, only return errors.
+        let mut buf = vec![0u8; 4096];
+        let _ = euc_tw::decode_to_str(data, &mut buf, false);
+        let _ = euc_tw::decode_to_str(data, &mut buf, true);
+    }
+
+    #[test]
+    fn pt_euc_tw_encode_random_text(ref text in "\\PC*\\PC*\\PC*") {
+        // Attempt to encode, but probably fail.  The important thing is that
+        // it should never panic, only return errors.
+        let mut buf = vec![0u8; 4096];
+        let _ = euc_tw::encode_from_str(text, &mut buf);
+    }
+
+    #[test]
+    fn pt_johab_decode_random_bytes(ref data in vec(0u8..=255, 0..512)) {
+        // Attempt to decode, but probably fail.  The important thing is that
+        // it should never panic, only return errors.
+        let mut buf = vec![0u8; 4096];
+        let _ = johab::decode_to_str(data, &mut buf, false);
+        let _ = johab::decode_to_str(data, &mut buf, true);
+    }
+
+    #[test]
+    fn pt_johab_encode_random_text(ref text in "\\PC*\\PC*\\PC*") {
+        // Attempt to encode, but probably fail.  The important thing is that
+        // it should never panic, only return errors.
+        let mut buf = vec![0u8; 4096];
+        let _ = johab::encode_from_str(text, &mut buf);
+    }
+
+    #[test]
+    fn pt_mac_chinese_simp_decode_random_bytes(ref data in vec(0u8..=255, 0..512)) {
+        // Attempt to decode, but probably fail.  The important thing is that
+        // it should never panic, only return errors.
+        let mut buf = vec![0u8; 4096];
+        let _ = mac_chinese_simp::decode_to_str(data, &mut buf, false);
+        let _ = mac_chinese_simp::decode_to_str(data, &mut buf, true);
+    }
+
+    #[test]
+    fn pt_mac_chinese_simp_encode_random_text(ref text in "\\PC*\\PC*\\PC*") {
+        // Attempt to encode, but probably fail.  The important thing is that
+        // it should never panic, only return errors.
+        let mut buf = vec![0u8; 4096];
+        let _ = mac_chinese_simp::encode_from_str(text, &mut buf, false);
+        let _ = mac_chinese_simp::encode_from_str(text, &mut buf, true);
+    }
+
+    #[test]
+    fn pt_mac_chinese_trad_decode_random_bytes(ref data in vec(0u8..=255, 0..512)) {
+        // Attempt to decode, but probably fail.  The important thing is that
+        // it should never panic, only return errors.
+        let mut buf = vec![0u8; 4096];
+        let _ = mac_chinese_trad::decode_to_str(data, &mut buf, false);
+        let _ = mac_chinese_trad::decode_to_str(data, &mut buf, true);
+    }
+
+    #[test]
+    fn pt_mac_chinese_trad_encode_random_text(ref text in "\\PC*\\PC*\\PC*") {
+        // Attempt to encode, but probably fail.  The important thing is that
+        // it should never panic, only return errors.
+        let mut buf = vec![0u8; 4096];
+        let _ = mac_chinese_trad::encode_from_str(text, &mut buf, false);
+        let _ = mac_chinese_trad::encode_from_str(text, &mut buf, true);
+    }
+
+    #[test]
+    fn pt_mac_japanese_decode_random_bytes(ref data in vec(0u8..=255, 0..512)) {
+        // Attempt to decode, but probably fail.  The important thing is that
+        // it should never panic, only return errors.
+        let mut buf = vec![0u8; 4096];
+        let _ = mac_japanese::decode_to_str(data, &mut buf, false);
+        let _ = mac_japanese::decode_to_str(data, &mut buf, true);
+    }
+
+    #[test]
+    fn pt_mac_japanese_encode_random_text(ref text in "\\PC*\\PC*\\PC*") {
+        // Attempt to encode, but probably fail.  The important thing is that
+        // it should never panic, only return errors.
+        let mut buf = vec![0u8; 4096];
+        let _ = mac_japanese::encode_from_str(text, &mut buf, false);
+        let _ = mac_japanese::encode_from_str(text, &mut buf, true);
+    }
+
+    #[test]
+    fn pt_mac_korean_decode_random_bytes(ref data in vec(0u8..=255, 0..512)) {
+        // Attempt to decode, but probably fail.  The important thing is that
+        // it should never panic, only return errors.
+        let mut buf = vec![0u8; 4096];
+        let _ = mac_korean::decode_to_str(data, &mut buf, false);
+        let _ = mac_korean::decode_to_str(data, &mut buf, true);
+    }
+
+    #[test]
+    fn pt_mac_korean_encode_random_text(ref text in "\\PC*\\PC*\\PC*") {
+        // Attempt to encode, but probably fail.  The important thing is that
+        // it should never panic, only return errors.
+        let mut buf = vec![0u8; 4096];
+        let _ = mac_korean::encode_from_str(text, &mut buf, false);
+        let _ = mac_korean::encode_from_str(text, &mut buf, true);
+    }
+
+    #[test]
+    fn pt_arib_b24_decode_random_bytes(ref data in vec(0u8..=255, 0..512)) {
+        // Attempt to decode, but probably fail.  The important thing is that
+        // it should never panic, only return errors.
+        let mut buf = vec![0u8; 4096];
+        let _ = arib_b24::decode_to_str(data, &mut buf, &mut arib_b24::State::new(), false);
+        let _ = arib_b24::decode_to_str(data, &mut buf, &mut arib_b24::State::new(), true);
+    }
+
+    #[test]
+    fn pt_arib_b24_encode_random_text(ref text in "\\PC*\\PC*\\PC*") {
+        // Attempt to encode, but probably fail.  The important thing is that
+        // it should never panic, only return errors.
+        let mut buf = vec![0u8; 4096];
+        let _ = arib_b24::encode_from_str(text, &mut buf, &mut arib_b24::State::new(), false);
+        let _ = arib_b24::encode_from_str(text, &mut buf, &mut arib_b24::State::new(), true);
+    }
+
+    #[test]
+    fn pt_ibm_930_decode_random_bytes(ref data in vec(0u8..=255, 0..512)) {
+        // Attempt to decode, but probably fail.  The important thing is that
+        // it should never panic, only return errors.
+        let mut buf = vec![0u8; 4096];
+        let _ = ibm_930::decode_to_str(data, &mut buf, &mut ebcdic_dbcs::State::new(), false);
+        let _ = ibm_930::decode_to_str(data, &mut buf, &mut ebcdic_dbcs::State::new(), true);
+    }
+
+    #[test]
+    fn pt_ibm_930_encode_random_text(ref text in "\\PC*\\PC*\\PC*") {
+        // Attempt to encode, but probably fail.  The important thing is that
+        // it should never panic, only return errors.
+        let mut buf = vec![0u8; 4096];
+        let _ = ibm_930::encode_from_str(text, &mut buf, &mut ebcdic_dbcs::State::new(), false);
+        let _ = ibm_930::encode_from_str(text, &mut buf, &mut ebcdic_dbcs::State::new(), true);
+    }
+
+    #[test]
+    fn pt_ibm_933_decode_random_bytes(ref data in vec(0u8..=255, 0..512)) {
+        // Attempt to decode, but probably fail.  The important thing is that
+        // it should never panic, only return errors.
+        let mut buf = vec![0u8; 4096];
+        let _ = ibm_933::decode_to_str(data, &mut buf, &mut ebcdic_dbcs::State::new(), false);
+        let _ = ibm_933::decode_to_str(data, &mut buf, &mut ebcdic_dbcs::State::new(), true);
+    }
+
+    #[test]
+    fn pt_ibm_933_encode_random_text(ref text in "\\PC*\\PC*\\PC*") {
+        // Attempt to encode, but probably fail.  The important thing is that
+        // it should never panic, only return errors.
+        let mut buf = vec![0u8; 4096];
+        let _ = ibm_933::encode_from_str(text, &mut buf, &mut ebcdic_dbcs::State::new(), false);
+        let _ = ibm_933::encode_from_str(text, &mut buf, &mut ebcdic_dbcs::State::new(), true);
+    }
+
+    #[test]
+    fn pt_ibm_935_decode_random_bytes(ref data in vec(0u8..=255, 0..512)) {
+        // Attempt to decode, but probably fail.  The important thing is that
+        // it should never panic, only return errors.
+        let mut buf = vec![0u8; 4096];
+        let _ = ibm_935::decode_to_str(data, &mut buf, &mut ebcdic_dbcs::State::new(), false);
+        let _ = ibm_935::decode_to_str(data, &mut buf, &mut ebcdic_dbcs::State::new(), true);
+    }
+
+    #[test]
+    fn pt_ibm_935_encode_random_text(ref text in "\\PC*\\PC*\\PC*") {
+        // Attempt to encode, but probably fail.  The important thing is that
+        // it should never panic, only return errors.
+        let mut buf = vec![0u8; 4096];
+        let _ = ibm_935::encode_from_str(text, &mut buf, &mut ebcdic_dbcs::State::new(), false);
+        let _ = ibm_935::encode_from_str(text, &mut buf, &mut ebcdic_dbcs::State::new(), true);
+    }
+
+    #[test]
+    fn pt_ibm_937_decode_random_bytes(ref data in vec(0u8..=255, 0..512)) {
+        // Attempt to decode, but probably fail.  The important thing is that
+        // it should never panic, only return errors.
+        let mut buf = vec![0u8; 4096];
+        let _ = ibm_937::decode_to_str(data, &mut buf, &mut ebcdic_dbcs::State::new(), false);
+        let _ = ibm_937::decode_to_str(data, &mut buf, &mut ebcdic_dbcs::State::new(), true);
+    }
+
+    #[test]
+    fn pt_ibm_937_encode_random_text(ref text in "\\PC*\\PC*\\PC*") {
+        // Attempt to encode, but probably fail.  The important thing is that
+        // it should never panic, only return errors.
+        let mut buf = vec![0u8; 4096];
+        let _ = ibm_937::encode_from_str(text, &mut buf, &mut ebcdic_dbcs::State::new(), false);
+        let _ = ibm_937::encode_from_str(text, &mut buf, &mut ebcdic_dbcs::State::new(), true);
+    }
+
+    #[test]
+    fn pt_ibm_939_decode_random_bytes(ref data in vec(0u8..=255, 0..512)) {
+        // Attempt to decode, but probably fail.  The important thing is that
+        // it should never panic, only return errors.
+        let mut buf = vec![0u8; 4096];
+        let _ = ibm_939::decode_to_str(data, &mut buf, &mut ebcdic_dbcs::State::new(), false);
+        let _ = ibm_939::decode_to_str(data, &mut buf, &mut ebcdic_dbcs::State::new(), true);
+    }
+
+    #[test]
+    fn pt_ibm_939_encode_random_text(ref text in "\\PC*\\PC*\\PC*") {
+        // Attempt to encode, but probably fail.  The important thing is that
+        // it should never panic, only return errors.
+        let mut buf = vec![0u8; 4096];
+        let _ = ibm_939::encode_from_str(text, &mut buf, &mut ebcdic_dbcs::State::new(), false);
+        let _ = ibm_939::encode_from_str(text, &mut buf, &mut ebcdic_dbcs::State::new(), true);
+    }
+
     #[test]
     fn pt_big5_whatwg_decode_random_bytes(ref data in vec(0u8..=255, 0..512)) {
         // Attempt to decode, but probably fail.  The important thing is that
@@ -419,6 +1349,28 @@ proptest! {
         let _ = big5_whatwg::encode_from_str(text, &mut buf, true);
     }
 
+    #[test]
+    fn pt_shiftjis_decode_random_bytes(ref data in vec(0u8..=255, 0..512)) {
+        // Attempt to decode, but probably fail.  The important thing is that
+        // it should never panic, only return errors.
+        let mut buf = vec![0u8; 4096];
+        for &profile in &[shiftjis::Profile::Whatwg, shiftjis::Profile::Jis, shiftjis::Profile::Cp932, shiftjis::Profile::Apple] {
+            let _ = shiftjis::decode_to_str(data, &mut buf, false, profile);
+            let _ = shiftjis::decode_to_str(data, &mut buf, true, profile);
+        }
+    }
+
+    #[test]
+    fn pt_shiftjis_encode_random_text(ref text in "\\PC*\\PC*\\PC*") {
+        // Attempt to encode, but probably fail.  The important thing is that
+        // it should never panic, only return errors.
+        let mut buf = vec![0u8; 4096];
+        for &profile in &[shiftjis::Profile::Whatwg, shiftjis::Profile::Jis, shiftjis::Profile::Cp932, shiftjis::Profile::Apple] {
+            let _ = shiftjis::encode_from_str(text, &mut buf, false, profile);
+            let _ = shiftjis::encode_from_str(text, &mut buf, true, profile);
+        }
+    }
+
     #[test]
     fn pt_shiftjis_whatwg_decode_random_bytes(ref data in vec(0u8..=255, 0..512)) {
         // Attempt to decode, but probably fail.  The important thing is that
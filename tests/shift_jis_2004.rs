@@ -0,0 +1,33 @@
+extern crate text_encoding;
+
+use text_encoding::shift_jis_2004::{decode_to_str, encode_from_str};
+
+const SJIS_DECODE_SJIS_DATA: &[u8] =
+    include_bytes!("test_data/jis0213/shift_jis_2004_test_decode_in.txt");
+const SJIS_DECODE_UTF8_DATA: &[u8] =
+    include_bytes!("test_data/jis0213/shift_jis_2004_test_decode_out.txt");
+const SJIS_ENCODE_UTF8_DATA: &[u8] =
+    include_bytes!("test_data/jis0213/shift_jis_2004_test_encode_in.txt");
+const SJIS_ENCODE_SJIS_DATA: &[u8] =
+    include_bytes!("test_data/jis0213/shift_jis_2004_test_encode_out.txt");
+
+#[test]
+fn shift_jis_2004_encode() {
+    let mut buf = vec![0u8; SJIS_ENCODE_SJIS_DATA.len()];
+    let (encoded, _) = encode_from_str(
+        std::str::from_utf8(SJIS_ENCODE_UTF8_DATA).unwrap(),
+        &mut buf,
+        true,
+    )
+    .unwrap();
+
+    assert_eq!(SJIS_ENCODE_SJIS_DATA, encoded);
+}
+
+#[test]
+fn shift_jis_2004_decode() {
+    let mut buf = vec![0u8; SJIS_DECODE_UTF8_DATA.len()];
+    let (utf8, _) = decode_to_str(SJIS_DECODE_SJIS_DATA, &mut buf, true).unwrap();
+
+    assert_eq!(SJIS_DECODE_UTF8_DATA, utf8.as_bytes());
+}
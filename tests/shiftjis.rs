@@ -1,16 +1,26 @@
 extern crate text_encoding;
 
+use text_encoding::shiftjis::{self, Profile};
 use text_encoding::shiftjis_whatwg::{decode_to_str, encode_from_str};
 
-const WHATWG_DECODE_SHIFTJIS_DATA: &'static [u8] =
+const WHATWG_DECODE_SHIFTJIS_DATA: &[u8] =
     include_bytes!("test_data/whatwg/shiftjis_whatwg_test_decode_in.txt");
-const WHATWG_DECODE_UTF8_DATA: &'static [u8] =
+const WHATWG_DECODE_UTF8_DATA: &[u8] =
     include_bytes!("test_data/whatwg/shiftjis_whatwg_test_decode_out.txt");
-const WHATWG_ENCODE_UTF8_DATA: &'static [u8] =
+const WHATWG_ENCODE_UTF8_DATA: &[u8] =
     include_bytes!("test_data/whatwg/shiftjis_whatwg_test_encode_in.txt");
-const WHATWG_ENCODE_SHIFTJIS_DATA: &'static [u8] =
+const WHATWG_ENCODE_SHIFTJIS_DATA: &[u8] =
     include_bytes!("test_data/whatwg/shiftjis_whatwg_test_encode_out.txt");
 
+const JIS_DECODE_SHIFTJIS_DATA: &[u8] =
+    include_bytes!("test_data/shiftjis/shiftjis_jis_test_decode_in.txt");
+const JIS_DECODE_UTF8_DATA: &[u8] =
+    include_bytes!("test_data/shiftjis/shiftjis_jis_test_decode_out.txt");
+const JIS_ENCODE_UTF8_DATA: &[u8] =
+    include_bytes!("test_data/shiftjis/shiftjis_jis_test_encode_in.txt");
+const JIS_ENCODE_SHIFTJIS_DATA: &[u8] =
+    include_bytes!("test_data/shiftjis/shiftjis_jis_test_encode_out.txt");
+
 #[test]
 fn shiftjis_whatwg_encode() {
     let mut buf = vec![0u8; WHATWG_ENCODE_SHIFTJIS_DATA.len()];
@@ -30,3 +40,52 @@ fn shiftjis_whatwg_decode() {
 
     assert_eq!(WHATWG_DECODE_UTF8_DATA, utf8.as_bytes());
 }
+
+#[test]
+fn shiftjis_jis_encode() {
+    let mut buf = vec![0u8; JIS_ENCODE_SHIFTJIS_DATA.len()];
+    let (encoded, _) = shiftjis::encode_from_str(
+        std::str::from_utf8(JIS_ENCODE_UTF8_DATA).unwrap(),
+        &mut buf,
+        true,
+        Profile::Jis,
+    )
+    .unwrap();
+
+    assert_eq!(JIS_ENCODE_SHIFTJIS_DATA, encoded);
+}
+
+#[test]
+fn shiftjis_jis_decode() {
+    let mut buf = vec![0u8; JIS_DECODE_UTF8_DATA.len()];
+    let (utf8, _) =
+        shiftjis::decode_to_str(JIS_DECODE_SHIFTJIS_DATA, &mut buf, true, Profile::Jis).unwrap();
+
+    assert_eq!(JIS_DECODE_UTF8_DATA, utf8.as_bytes());
+}
+
+// The CP932 profile only differs from WHATWG in code points that aren't in
+// the WHATWG test data, so it should handle that data identically.
+#[test]
+fn shiftjis_cp932_encode() {
+    let mut buf = vec![0u8; WHATWG_ENCODE_SHIFTJIS_DATA.len()];
+    let (encoded, _) = shiftjis::encode_from_str(
+        std::str::from_utf8(WHATWG_ENCODE_UTF8_DATA).unwrap(),
+        &mut buf,
+        true,
+        Profile::Cp932,
+    )
+    .unwrap();
+
+    assert_eq!(WHATWG_ENCODE_SHIFTJIS_DATA, encoded);
+}
+
+#[test]
+fn shiftjis_cp932_decode() {
+    let mut buf = vec![0u8; WHATWG_DECODE_UTF8_DATA.len()];
+    let (utf8, _) =
+        shiftjis::decode_to_str(WHATWG_DECODE_SHIFTJIS_DATA, &mut buf, true, Profile::Cp932)
+            .unwrap();
+
+    assert_eq!(WHATWG_DECODE_UTF8_DATA, utf8.as_bytes());
+}